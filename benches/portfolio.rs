@@ -5,6 +5,7 @@
 
 use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
 use nanobook::portfolio::{CostModel, EqualWeight, compute_metrics, run_backtest};
+use nanobook::rng::Rng;
 use nanobook::{Price, Symbol};
 
 fn sym(s: &str) -> Symbol {
@@ -13,28 +14,22 @@ fn sym(s: &str) -> Symbol {
 
 /// Generate a synthetic price series with `n_bars` bars and `n_stocks` stocks.
 ///
-/// Prices start at $100 and drift randomly using a simple deterministic RNG.
+/// Prices start at $100 and drift randomly using the seeded crate RNG.
 fn generate_price_series(n_bars: usize, n_stocks: usize) -> Vec<Vec<(Symbol, i64)>> {
     let symbols: Vec<Symbol> = (0..n_stocks).map(|i| sym(&format!("S{i:03}"))).collect();
 
     let mut prices = vec![100_00i64; n_stocks];
     let mut series = Vec::with_capacity(n_bars);
 
-    // Simple deterministic PRNG (xorshift32)
-    let mut rng_state: u32 = 42;
+    let mut rng = Rng::from_seed(42);
 
     for _ in 0..n_bars {
         let bar: Vec<(Symbol, i64)> = symbols
             .iter()
             .zip(prices.iter_mut())
             .map(|(sym, price)| {
-                // xorshift32
-                rng_state ^= rng_state << 13;
-                rng_state ^= rng_state >> 17;
-                rng_state ^= rng_state << 5;
-
                 // Random return between -2% and +2%
-                let ret = (rng_state % 401) as i64 - 200; // -200..200 bps
+                let ret = rng.below(401) as i64 - 200; // -200..200 bps
                 *price = (*price + *price * ret / 10_000).max(1_00); // Floor at $1
                 (*sym, *price)
             })
@@ -174,6 +169,44 @@ fn bench_lob_rebalance(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark: per-symbol vs batched simple-fill rebalance on a dense universe
+fn bench_dense_rebalance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("portfolio/rebalance_dense");
+
+    let bars = generate_price_series(2, 500);
+    let w = 1.0 / 500.0;
+    let targets: Vec<(Symbol, f64)> = bars[1].iter().map(|&(s, _)| (s, w)).collect();
+    let seeded = || {
+        let mut p = nanobook::portfolio::Portfolio::new(100_000_000_00, CostModel::zero());
+        p.rebalance_simple(&targets, &bars[0]);
+        p
+    };
+
+    group.bench_function("simple_500", |b| {
+        b.iter_batched(
+            seeded,
+            |mut p| {
+                p.rebalance_simple(&targets, &bars[1]);
+                black_box(p)
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    group.bench_function("batched_500", |b| {
+        b.iter_batched(
+            seeded,
+            |mut p| {
+                p.rebalance_simple_batched(&targets, &bars[1]);
+                black_box(p)
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+
+    group.finish();
+}
+
 #[cfg(feature = "parallel")]
 criterion_group!(
     benches,
@@ -181,6 +214,7 @@ criterion_group!(
     bench_compute_metrics,
     bench_sweep,
     bench_lob_rebalance,
+    bench_dense_rebalance,
 );
 
 #[cfg(not(feature = "parallel"))]
@@ -189,6 +223,7 @@ criterion_group!(
     bench_single_backtest,
     bench_compute_metrics,
     bench_lob_rebalance,
+    bench_dense_rebalance,
 );
 
 criterion_main!(benches);
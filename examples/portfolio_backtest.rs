@@ -15,11 +15,10 @@ fn main() {
     let goog = Symbol::new("GOOG");
 
     // Start with $1,000,000 and 5 bps round-trip cost
-    let cost_model = CostModel {
-        commission_bps: 3,
-        slippage_bps: 2,
-        min_trade_fee: 1_00, // $1 minimum per trade
-    };
+    let cost_model = CostModel::zero()
+        .with_commission_bps(3)
+        .with_slippage_bps(2)
+        .with_min_trade_fee(1_00); // $1 minimum per trade
     let mut portfolio = Portfolio::new(1_000_000_00, cost_model);
 
     println!("=== Monthly Rebalancing Backtest ===\n");
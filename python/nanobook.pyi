@@ -1,4 +1,4 @@
-from typing import List, Tuple, Optional, Dict, Any, Union, Callable
+from typing import List, Tuple, Optional, Dict, Any, Union, Callable, Iterable
 
 __version__: str
 
@@ -8,7 +8,7 @@ class IbkrBroker:
     def disconnect(self) -> None: ...
     def positions(self) -> List[Dict[str, Any]]: ...
     def account(self) -> Dict[str, Any]: ...
-    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, client_order_id: Optional[str] = None) -> int: ...
+    def submit_order(self, symbol: str, side: SideLike, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, client_order_id: Optional[str] = None) -> int: ...
     def order_status(self, order_id: int) -> Dict[str, Any]: ...
     def cancel_order(self, order_id: int) -> None: ...
     def quote(self, symbol: str) -> Dict[str, Any]: ...
@@ -19,7 +19,7 @@ class BinanceBroker:
     def disconnect(self) -> None: ...
     def positions(self) -> List[Dict[str, Any]]: ...
     def account(self) -> Dict[str, Any]: ...
-    def submit_order(self, symbol: str, side: str, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, client_order_id: Optional[str] = None) -> int: ...
+    def submit_order(self, symbol: str, side: SideLike, quantity: int, order_type: str = "market", limit_price_cents: Optional[int] = None, client_order_id: Optional[str] = None) -> int: ...
     def order_status(self, order_id: int) -> Dict[str, Any]: ...
     def cancel_order(self, order_id: int) -> None: ...
     def quote(self, symbol: str) -> Dict[str, Any]: ...
@@ -36,8 +36,20 @@ class RiskEngine:
         max_order_value_cents: int = 10_000_000,
         max_batch_value_cents: int = 100_000_000,
     ) -> None: ...
-    def check_order(self, symbol: str, side: str, quantity: int, price_cents: int, equity_cents: int, positions: List[Tuple[str, int]]) -> List[Dict[str, Any]]: ...
-    def check_batch(self, orders: List[Tuple[str, str, int, int]], equity_cents: int, positions: List[Tuple[str, int]], target_weights: List[Tuple[str, float]]) -> List[Dict[str, Any]]: ...
+    def check_order(self, symbol: str, side: SideLike, quantity: int, price_cents: int, equity_cents: int, positions: List[Tuple[str, int]]) -> List[Dict[str, Any]]: ...
+    def check_batch(self, orders: List[Tuple[str, SideLike, int, int]], equity_cents: int, positions: List[Tuple[str, int]], target_weights: List[Tuple[str, float]]) -> List[Dict[str, Any]]: ...
+
+class Side:
+    BUY: 'Side'
+    SELL: 'Side'
+
+class TimeInForce:
+    GTC: 'TimeInForce'
+    IOC: 'TimeInForce'
+    FOK: 'TimeInForce'
+
+SideLike = Union[Side, str]
+TifLike = Union[TimeInForce, str]
 
 class Order:
     id: int
@@ -49,6 +61,9 @@ class Order:
     status: str
     time_in_force: str
     timestamp: int
+    sequence: int
+    hidden: bool
+    queued_at: int
 
 class Position:
     symbol: str
@@ -56,6 +71,10 @@ class Position:
     avg_entry_price: int
     total_cost: int
     realized_pnl: int
+    holding_period: float
+    closed_holding_period: Optional[float]
+    cost_basis: str
+    lots: List[Tuple[int, int]]
     def unrealized_pnl(self, price: int) -> int: ...
 
 class Event:
@@ -72,6 +91,10 @@ class Trade:
     passive_order_id: int
     timestamp: int
     @property
+    def maker_order_id(self) -> int: ...
+    @property
+    def taker_order_id(self) -> int: ...
+    @property
     def price_float(self) -> float: ...
 
 class LevelSnapshot:
@@ -82,6 +105,7 @@ class LevelSnapshot:
     def price_float(self) -> float: ...
 
 class BookSnapshot:
+    def __init__(self, bids: List[Tuple[int, int]], asks: List[Tuple[int, int]], timestamp: int = 0) -> None: ...
     @property
     def bids(self) -> List[LevelSnapshot]: ...
     @property
@@ -90,6 +114,7 @@ class BookSnapshot:
     def weighted_mid(self) -> Optional[float]: ...
     def mid_price(self) -> Optional[float]: ...
     def spread(self) -> Optional[int]: ...
+    def diff(self, other: 'BookSnapshot') -> List[Dict[str, Any]]: ...
 
 class SubmitResult:
     order_id: int
@@ -97,6 +122,12 @@ class SubmitResult:
     filled_quantity: int
     resting_quantity: int
     cancelled_quantity: int
+    average_fill_price: Optional[float]
+    filled_notional: int
+    reject_reason: Optional[str]
+    reason_code: Optional[int]
+    max_fillable: Optional[int]
+    fills_by_level: List[Tuple[int, int]]
     @property
     def trades(self) -> List[Trade]: ...
 
@@ -111,9 +142,22 @@ class ModifyResult:
     new_order_id: Optional[int]
     cancelled_quantity: int
     error: Optional[str]
+    priority_retained: bool
     @property
     def trades(self) -> List[Trade]: ...
 
+class BracketResult:
+    entry: SubmitResult
+    stop_order_id: Optional[int]
+    target_order_id: Optional[int]
+
+class SpreadResult:
+    success: bool
+    net_notional: int
+    error: Optional[str]
+    @property
+    def legs(self) -> List[SubmitResult]: ...
+
 class StopSubmitResult:
     order_id: int
     status: str
@@ -134,85 +178,228 @@ class Metrics:
     profit_factor: float
     payoff_ratio: float
     kelly: float
+    avg_win: float
+    avg_loss: float
+    expectancy: float
+    def to_dict(self) -> Dict[str, Any]: ...
+
+class RelativeMetrics:
+    beta: float
+    alpha: float
+    tracking_error: float
+    information_ratio: float
+
+class StreamingMetrics:
+    num_periods: int
+    mean: float
+    total_return: float
+    volatility: float
+    sharpe: float
+    sortino: float
+    max_drawdown: float
+    drawdown: float
+    def __init__(self, periods_per_year: float = 252.0, risk_free: float = 0.0) -> None: ...
+    def update(self, r: float) -> None: ...
 
 class BacktestResult:
     portfolio: 'Portfolio'
     metrics: Optional[Metrics]
+    turnover: float
+    relative_metrics: Optional[RelativeMetrics]
+    warmup_bars: int
+    @property
+    def history(self) -> List[Dict[str, Any]]: ...
 
 class CostModel:
-    def __init__(self, commission_bps: int = 0, slippage_bps: int = 0, min_trade_fee: int = 0) -> None: ...
+    def __init__(self, commission_bps: int = 0, slippage_bps: int = 0, min_trade_fee: int = 0, maker_bps: int = 0, taker_bps: int = 0, max_trade_fee: Optional[int] = None, per_share_fee: int = 0, half_spread: bool = False) -> None: ...
     @staticmethod
     def zero() -> 'CostModel': ...
-    def compute_cost(self, notional: int) -> int: ...
+    def rescaled(self, from_scale: int, to_scale: int) -> 'CostModel': ...
+    def compute_cost(self, notional: int, quantity: int = 0) -> int: ...
+    def compute_fill_cost(self, notional: int, liquidity: str, quantity: int = 0) -> int: ...
+    def compute_spread_cost(self, notional: int, quantity: int, spread: int) -> int: ...
 
 class Portfolio:
-    def __init__(self, initial_cash: int, cost_model: CostModel) -> None: ...
+    def __init__(self, initial_cash: int, cost_model: CostModel, incentive_fee: float = 0.0, hurdle: float = 0.0, cash_yield: float = 0.0, cost_basis: str = "average", normalize_gross: Optional[float] = None, max_gross: Optional[float] = None, duplicates: str = "error", price_scale: int = 100, currencies: Optional[Dict[str, str]] = None, rebalance_order: str = "sells_first", leverage: bool = False) -> None: ...
     @property
     def cash(self) -> int: ...
+    @property
+    def price_scale(self) -> int: ...
+    def to_currency(self, amount: int) -> float: ...
+    @property
+    def incentive_fees(self) -> int: ...
+    @property
+    def high_water_mark(self) -> int: ...
+    @property
+    def cash_interest(self) -> int: ...
+    @property
+    def turnover(self) -> float: ...
+    @property
+    def average_holding_period(self) -> Optional[float]: ...
+    @property
+    def realized_pnl(self) -> int: ...
+    def unrealized_pnl(self, prices: List[Tuple[str, int]]) -> int: ...
     def position(self, symbol: str) -> Optional[Position]: ...
     def positions(self) -> Dict[str, Position]: ...
     def total_equity(self, prices: List[Tuple[str, int]]) -> int: ...
     def current_weights(self, prices: List[Tuple[str, int]]) -> List[Tuple[str, float]]: ...
+    def currency(self, symbol: str) -> Optional[str]: ...
+    def total_equity_fx(self, prices: List[Tuple[str, int]], fx_rates: List[Tuple[str, float]]) -> int: ...
+    def current_weights_fx(self, prices: List[Tuple[str, int]], fx_rates: List[Tuple[str, float]]) -> List[Tuple[str, float]]: ...
+    def gross_exposure(self, prices: List[Tuple[str, int]]) -> float: ...
+    def net_exposure(self, prices: List[Tuple[str, int]]) -> float: ...
     def returns(self) -> List[float]: ...
     def equity_curve(self) -> List[int]: ...
-    def rebalance_simple(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]]) -> None: ...
-    def rebalance_lob(self, targets: List[Tuple[str, float]], exchanges: 'MultiExchange') -> None: ...
+    def rebalance_simple(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]], fill_prices: Optional[List[Tuple[str, int]]] = None) -> List[Tuple[str, int, int, int]]: ...
+    def rebalance_simple_fx(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]], fx_rates: List[Tuple[str, float]]) -> List[Tuple[str, int, int, int]]: ...
+    def rebalance_cost_aware(self, targets: List[Tuple[str, float]], prices: List[Tuple[str, int]], aversion: float) -> List[Tuple[str, int, int, int]]: ...
+    def apply_trade(self, symbol: str, trade: Trade, order_id: int) -> Optional[int]: ...
+    def rebalance_lob(self, targets: List[Tuple[str, float]], exchanges: 'MultiExchange', fill_ratio: float = 1.0) -> None: ...
+    def estimate_rebalance_cost(self, targets: List[Tuple[str, float]], exchanges: 'MultiExchange') -> Dict[str, Any]: ...
     def record_return(self, prices: List[Tuple[str, int]]) -> None: ...
+    def record_return_fx(self, prices: List[Tuple[str, int]], fx_rates: List[Tuple[str, float]]) -> None: ...
     def snapshot(self, prices: List[Tuple[str, int]]) -> Dict[str, Any]: ...
-    def compute_metrics(self, periods_per_year: float, risk_free: float) -> Optional[Metrics]: ...
+    def compute_metrics(self, periods_per_year: float = 252.0, risk_free: float = 0.0, minimum_acceptable_return: Optional[float] = None, calmar_lookback: Optional[int] = None) -> Optional[Metrics]: ...
     def save_json(self, path: str) -> None: ...
     @staticmethod
     def load_json(path: str) -> 'Portfolio': ...
 
 class Exchange:
-    def __init__(self) -> None: ...
+    def __init__(self, order_id_start: int = 1, trade_id_start: int = 1, price_scale: int = 100, position_limit_mode: str = "reject") -> None: ...
+    @property
+    def price_scale(self) -> int: ...
+    @property
+    def clock(self) -> Optional[int]: ...
+    def set_clock(self, now: int) -> None: ...
+    @property
+    def session(self) -> str: ...
+    def set_session(self, state: str) -> None: ...
+    def set_session_schedule(self, schedule: List[Tuple[int, str]]) -> None: ...
     @staticmethod
     def replay(events: List[Event]) -> 'Exchange': ...
-    def submit_limit(self, side: str, price: int, quantity: int, tif: str = "gtc") -> SubmitResult: ...
-    def submit_market(self, side: str, quantity: int) -> SubmitResult: ...
+    def submit_limit(self, side: SideLike, price: int, quantity: int, tif: TifLike = "gtc", owner: Optional[int] = None) -> SubmitResult: ...
+    def submit_hidden_limit(self, side: SideLike, price: int, quantity: int, tif: TifLike = "gtc") -> SubmitResult: ...
+    def submit_market(self, side: SideLike, quantity: int) -> SubmitResult: ...
     def cancel(self, order_id: int) -> CancelResult: ...
     def modify(self, order_id: int, new_price: int, new_quantity: int) -> ModifyResult: ...
-    def submit_stop_market(self, side: str, stop_price: int, quantity: int) -> StopSubmitResult: ...
-    def submit_stop_limit(self, side: str, stop_price: int, limit_price: int, quantity: int, tif: str = "gtc") -> StopSubmitResult: ...
-    def submit_trailing_stop_market(self, side: str, initial_stop_price: int, quantity: int, trail_type: str, trail_value: float, atr_period: Optional[int] = None) -> StopSubmitResult: ...
-    def submit_trailing_stop_limit(self, side: str, initial_stop_price: int, limit_price: int, quantity: int, trail_type: str, trail_value: float, tif: str = "gtc", atr_period: Optional[int] = None) -> StopSubmitResult: ...
+    def submit_bracket(self, side: SideLike, entry_price: int, quantity: int, stop_price: int, target_price: int, tif: TifLike = "gtc") -> BracketResult: ...
+    def bracket(self, entry_order_id: int) -> Optional[Dict[str, Any]]: ...
+    def link_oco(self, order_a: int, order_b: int) -> None: ...
+    def oco_partner(self, order_id: int) -> Optional[int]: ...
+    def submit_stop_market(self, side: SideLike, stop_price: int, quantity: int) -> StopSubmitResult: ...
+    def submit_stop_limit(self, side: SideLike, stop_price: int, limit_price: int, quantity: int, tif: TifLike = "gtc") -> StopSubmitResult: ...
+    def submit_trailing_stop_market(self, side: SideLike, initial_stop_price: int, quantity: int, trail_type: str, trail_value: float, atr_period: Optional[int] = None) -> StopSubmitResult: ...
+    def submit_trailing_stop_limit(self, side: SideLike, initial_stop_price: int, limit_price: int, quantity: int, trail_type: str, trail_value: float, tif: TifLike = "gtc", atr_period: Optional[int] = None) -> StopSubmitResult: ...
     def get_order(self, order_id: int) -> Optional[Order]: ...
+    def time_in_queue(self, order_id: int) -> Optional[int]: ...
     def get_stop_order(self, order_id: int) -> Optional[Dict[str, Any]]: ...
     def best_bid_ask(self) -> Tuple[Optional[int], Optional[int]]: ...
+    def top_of_book(self) -> Tuple[Optional[Tuple[int, int]], Optional[Tuple[int, int]]]: ...
+    def resting_order_count(self) -> int: ...
+    def total_resting_quantity(self, side: SideLike) -> int: ...
     def best_bid(self) -> Optional[int]: ...
     def best_ask(self) -> Optional[int]: ...
     def spread(self) -> Optional[int]: ...
+    def is_crossed(self) -> bool: ...
+    def is_locked(self) -> bool: ...
+    def flow_stats(self, owner: int) -> Dict[str, int]: ...
+    def halt(self, all: bool = False) -> None: ...
+    def resume(self) -> None: ...
+    @property
+    def is_halted(self) -> bool: ...
+    def set_position_limit(self, owner: int, max_position: int) -> None: ...
+    def remove_position_limit(self, owner: int) -> Optional[int]: ...
+    def position_limit(self, owner: int) -> Optional[int]: ...
+    def net_position(self, owner: int) -> int: ...
+    def trade_stats(self, from_ts: int = 0, to_ts: int = ...) -> Dict[str, Any]: ...
+    def order_flow_imbalance(self) -> int: ...
+    def reset_flow(self) -> None: ...
+    def sweep_vwap(self, side: SideLike, quantity: int) -> Dict[str, Optional[int]]: ...
+    def preview(self, side: SideLike, price: int, quantity: int, tif: TifLike = "gtc") -> SubmitResult: ...
     def last_trade_price(self) -> Optional[int]: ...
     def trades(self) -> List[Trade]: ...
     def events(self) -> List[Event]: ...
     def depth(self, levels: int = 10) -> BookSnapshot: ...
+    def levels(self, side: SideLike, depth: Optional[int] = None) -> Tuple[Any, Any]: ...
+    def orders_at(self, side: SideLike, price: int) -> List[Order]: ...
+    def matching_policy(self) -> Dict[str, str]: ...
     def full_book(self) -> BookSnapshot: ...
     def pending_stop_count(self) -> int: ...
     def clear_trades(self) -> None: ...
     def clear_order_history(self) -> int: ...
     def compact(self) -> None: ...
+    def clear(self) -> None: ...
 
 class MultiExchange:
-    def __init__(self) -> None: ...
+    def __init__(self, price_scale: int = 100) -> None: ...
+    @property
+    def price_scale(self) -> int: ...
+    def checkpoint(self) -> bytes: ...
+    def state_hash(self) -> int: ...
+    @staticmethod
+    def restore(data: bytes) -> 'MultiExchange': ...
+    def save_json(self, path: str) -> None: ...
+    @staticmethod
+    def load_json(path: str) -> 'MultiExchange': ...
     def get_or_create(self, symbol: str) -> Exchange: ...
     def symbols(self) -> List[str]: ...
-    def best_prices(self) -> List[Tuple[str, Optional[int], Optional[int]]]: ...
-    def submit_limit(self, symbol: str, side: str, price: int, quantity: int, tif: str = "gtc") -> SubmitResult: ...
-    def submit_market(self, symbol: str, side: str, quantity: int) -> SubmitResult: ...
+    def best_prices(self, include_sizes: bool = False) -> List[Tuple[Any, ...]]: ...
+    def snapshot_all(self, depth: int = 10) -> Dict[str, BookSnapshot]: ...
+    def submit_limit(self, symbol: str, side: SideLike, price: int, quantity: int, tif: TifLike = "gtc") -> SubmitResult: ...
+    def submit_market(self, symbol: str, side: SideLike, quantity: int) -> SubmitResult: ...
     def cancel(self, symbol: str, order_id: int) -> CancelResult: ...
     def modify(self, symbol: str, order_id: int, new_price: int, new_quantity: int) -> ModifyResult: ...
+    def submit_spread(self, legs: List[Tuple[str, SideLike, int]], net_limit: int, quantity: int) -> SpreadResult: ...
+    def resting_order_count(self, symbol: str) -> int: ...
+    def total_resting_quantity(self, symbol: str, side: SideLike) -> int: ...
+    def halt(self, symbol: str, all: bool = False) -> None: ...
+    def resume(self, symbol: str) -> None: ...
+    def is_halted(self, symbol: str) -> bool: ...
+    def clear(self, symbol: str) -> bool: ...
+    def clear_all(self) -> None: ...
     def len(self) -> int: ...
+    def replay(self, messages: Iterable[Tuple[Any, ...]]) -> MultiReplay: ...
 
-def compute_metrics(returns: List[float], periods_per_year: float = 252.0, risk_free: float = 0.0) -> Optional[Metrics]: ...
+class MultiReplay:
+    def __iter__(self) -> MultiReplay: ...
+    def __next__(self) -> Union[SubmitResult, CancelResult, ModifyResult]: ...
+
+def compute_metrics(returns: List[float], periods_per_year: float = 252.0, risk_free: float = 0.0, minimum_acceptable_return: Optional[float] = None, calmar_lookback: Optional[int] = None) -> Optional[Metrics]: ...
+def compute_metrics_batch(series: List[List[float]], periods_per_year: float = 252.0, risk_free: float = 0.0) -> List[Optional[Metrics]]: ...
+def argmax_by(results: List[Optional[Metrics]], key: Union[str, Callable[[Metrics], float]]) -> Optional[int]: ...
+def summarize(results: List[Optional[Metrics]], key: Union[str, Callable[[Metrics], float]]) -> Optional[Dict[str, Any]]: ...
+def metrics_to_json(metrics: Union[Metrics, List[Optional[Metrics]]], pretty: bool = False) -> str: ...
 def py_drawdown_series(equity: List[float]) -> List[Dict[str, Any]]: ...
 def py_rolling_max_drawdown(equity: List[float], window: int) -> List[float]: ...
 def sweep_equal_weight(price_series: List[List[Tuple[str, int]]], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0) -> BacktestResult: ...
-def run_backtest(strategy: Callable[[int, Dict[str, int], Portfolio], List[Tuple[str, float]]], price_series: List[Dict[str, int]], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0) -> BacktestResult: ...
+def sweep_top_k(scores_per_bar: List[List[Tuple[str, float]]], price_series: List[List[Tuple[str, int]]], ks: List[int], initial_cash: int, periods_per_year: float = 12.0, risk_free: float = 0.0) -> List[Optional[Metrics]]: ...
+class PriceSeries:
+    @staticmethod
+    def from_matrix(prices: Any, symbols: List[str], missing: str = "ffill") -> 'PriceSeries': ...
+    def bar(self, index: int) -> Dict[str, int]: ...
+    def __len__(self) -> int: ...
+
+def run_backtest(strategy: Callable[[int, Dict[str, int], Portfolio], List[Tuple[str, float]]], price_series: Union[List[Dict[str, int]], PriceSeries], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0, record_history: bool = False, benchmark: Optional[List[float]] = None, warmup: int = 0, fill_delay: int = 0, cash_yield: float = 0.0) -> BacktestResult: ...
+def run_backtest_matrix(weights: Any, prices: Any, symbols: List[str], initial_cash: int, cost_model: CostModel, periods_per_year: float = 252.0, risk_free: float = 0.0, missing: str = "ffill", fill_delay: int = 0) -> BacktestResult: ...
+def weights_to_shares(weights: List[Tuple[str, float]], equity: int, prices: List[Tuple[str, int]], lot_size: int = 1) -> List[Tuple[str, int]]: ...
+def shares_to_weights(positions: List[Tuple[str, int]], prices: List[Tuple[str, int]]) -> List[Tuple[str, float]]: ...
+def run_long_short(scores_per_bar: List[List[Tuple[str, float]]], price_series: List[List[Tuple[str, int]]], n_quantiles: int, initial_cash: int, cost_model: Optional[CostModel] = None, periods_per_year: float = 12.0, risk_free: float = 0.0) -> BacktestResult: ...
 def parse_itch(path: str) -> List[Tuple[str, Event]]: ...
 def py_backtest_weights(weight_schedule: List[List[Tuple[str, float]]], price_schedule: List[List[Tuple[str, int]]], initial_cash: int, cost_bps: int, periods_per_year: float = 252.0, risk_free: float = 0.0, stop_cfg: Optional[Dict[str, Any]] = None) -> Dict[str, Any]: ...
 def py_decompose_backtest(weight_schedule: List[List[Tuple[str, float]]], return_schedule: List[List[Tuple[str, float]]]) -> Dict[str, Any]: ...
 def py_tear_sheet(backtest_result: Dict[str, Any], rolling_window: int = 63, periods_per_year: int = 252) -> Dict[str, Any]: ...
 
+def hashed_symbol(name: str, hash_long: bool = True) -> str: ...
+def best_consolidated(exchanges: List[Exchange]) -> Tuple[Optional[int], Optional[int]]: ...
+def route_order(venues: List[Exchange], side: SideLike, quantity: int) -> Dict[str, Any]: ...
+def implementation_shortfall(
+    arrival_price: float,
+    fills: List[Trade],
+    side: SideLike,
+    target_quantity: Optional[int] = None,
+    final_price: Optional[float] = None,
+) -> float: ...
+
 # v0.8 — Technical indicators (ta-lib replacements)
 def py_sma(close: List[float], period: int) -> List[float]: ...
 def py_ema(close: List[float], period: int) -> List[float]: ...
@@ -225,7 +412,13 @@ def py_wilder_atr(high: List[float], low: List[float], close: List[float], perio
 
 # v0.8 — Statistics (scipy replacements)
 def py_spearman(x: List[float], y: List[float]) -> Tuple[float, float]: ...
+def py_permutation_test(scores: List[float], returns: List[float], n_perms: int = 1000, seed: int = 0) -> Tuple[float, float]: ...
 def py_quintile_spread(scores: List[float], returns: List[float], n_quantiles: int) -> float: ...
+def py_demean(values: List[float]) -> List[float]: ...
+def py_zscore(values: List[float]) -> List[float]: ...
+def py_winsorize(values: List[float], lower_pct: float, upper_pct: float) -> List[float]: ...
+def py_quantile_means(scores: List[float], returns: List[float], n_quantiles: int = 5) -> List[float]: ...
+def py_quintile_spread_weighted(scores: List[float], returns: List[float], weights: List[float], n_quantiles: int = 5) -> float: ...
 
 # v0.8 — Cross-validation (sklearn replacement)
 def py_time_series_split(n_samples: int, n_splits: int) -> List[Tuple[List[int], List[int]]]: ...
@@ -268,3 +461,9 @@ def optimize_risk_parity(returns_matrix: List[List[float]], symbols: List[str])
 def inverse_cvar_weights(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
 def inverse_cdar_weights(returns_matrix: List[List[float]], symbols: List[str], alpha: float = 0.95) -> Dict[str, float]: ...
 def optimize_hrp(returns_matrix: List[List[float]], symbols: List[str]) -> Dict[str, float]: ...
+def rolling_corr_matrix(series: List[List[float]], window: int) -> List[List[List[float]]]: ...
+def correlation_clusters(series: List[List[float]], threshold: float) -> List[List[int]]: ...
+def cov_shrinkage(series: List[List[float]]) -> List[List[float]]: ...
+def min_variance_weights(cov: List[List[float]], symbols: List[str], long_only: bool = True) -> Dict[str, float]: ...
+def mean_variance_weights(cov: List[List[float]], mu: List[float], symbols: List[str], risk_aversion: float = 1.0, long_only: bool = True) -> Dict[str, float]: ...
+def risk_parity_weights(cov: List[List[float]], symbols: List[str]) -> Dict[str, Any]: ...
@@ -5,7 +5,7 @@ use nanobook_broker::ibkr::IbkrBroker as RustIbkrBroker;
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 
-use crate::types::parse_symbol;
+use crate::types::{SideArg, parse_symbol};
 
 /// Interactive Brokers connection.
 ///
@@ -107,7 +107,7 @@ impl PyIbkrBroker {
     fn submit_order(
         &self,
         symbol: &str,
-        side: &str,
+        side: SideArg,
         quantity: u64,
         order_type: &str,
         limit_price_cents: Option<i64>,
@@ -115,15 +115,7 @@ impl PyIbkrBroker {
     ) -> PyResult<u64> {
         let sym = parse_symbol(symbol)?;
 
-        let broker_side = match side.to_ascii_lowercase().as_str() {
-            "buy" | "b" => nanobook_broker::BrokerSide::Buy,
-            "sell" | "s" => nanobook_broker::BrokerSide::Sell,
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                    "Invalid side '{side}'. Use 'buy' or 'sell'."
-                )));
-            }
-        };
+        let broker_side = nanobook_broker::BrokerSide::from(side);
 
         let broker_order_type = match order_type.to_ascii_lowercase().as_str() {
             "market" => nanobook_broker::BrokerOrderType::Market,
@@ -309,7 +301,7 @@ mod binance_binding {
         fn submit_order(
             &self,
             symbol: &str,
-            side: &str,
+            side: SideArg,
             quantity: u64,
             order_type: &str,
             limit_price_cents: Option<i64>,
@@ -317,15 +309,7 @@ mod binance_binding {
         ) -> PyResult<u64> {
             let sym = parse_symbol(symbol)?;
 
-            let broker_side = match side.to_ascii_lowercase().as_str() {
-                "buy" | "b" => nanobook_broker::BrokerSide::Buy,
-                "sell" | "s" => nanobook_broker::BrokerSide::Sell,
-                _ => {
-                    return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                        "Invalid side '{side}'. Use 'buy' or 'sell'."
-                    )));
-                }
-            };
+            let broker_side = nanobook_broker::BrokerSide::from(side);
 
             let broker_order_type = match order_type.to_ascii_lowercase().as_str() {
                 "market" => nanobook_broker::BrokerOrderType::Market,
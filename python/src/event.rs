@@ -14,6 +14,7 @@ impl PyEvent {
     fn kind(&self) -> String {
         match &self.inner {
             Event::SubmitLimit { .. } => "submit_limit".to_string(),
+            Event::SubmitHiddenLimit { .. } => "submit_hidden_limit".to_string(),
             Event::SubmitMarket { .. } => "submit_market".to_string(),
             Event::Cancel { .. } => "cancel".to_string(),
             Event::Modify { .. } => "modify".to_string(),
@@ -21,6 +22,14 @@ impl PyEvent {
             Event::SubmitStopLimit { .. } => "submit_stop_limit".to_string(),
             Event::SubmitTrailingStopMarket { .. } => "submit_trailing_stop_market".to_string(),
             Event::SubmitTrailingStopLimit { .. } => "submit_trailing_stop_limit".to_string(),
+            Event::SubmitBracket { .. } => "submit_bracket".to_string(),
+            Event::LinkOco { .. } => "link_oco".to_string(),
+            Event::SetClock { .. } => "set_clock".to_string(),
+            Event::Halt { .. } => "halt".to_string(),
+            Event::Resume => "resume".to_string(),
+            Event::SetSession { .. } => "set_session".to_string(),
+            Event::SetPositionLimit { .. } => "set_position_limit".to_string(),
+            Event::RemovePositionLimit { .. } => "remove_position_limit".to_string(),
         }
     }
 
@@ -1,4 +1,8 @@
-use nanobook::{Event, Exchange, OrderId, Price, TrailMethod};
+use nanobook::{
+    Event, Exchange, LevelChangeKind, LevelPriority, OrderId, OrderOwner, PositionLimitMode, Price,
+    SessionState, StpPolicy, TieBreak, TimeInForce, Trade, TradeId, TrailMethod, best_consolidated,
+    execution, route_order,
+};
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
@@ -6,7 +10,7 @@ use pyo3::types::PyDict;
 use crate::event::PyEvent;
 use crate::order::PyOrder;
 use crate::results::*;
-use crate::types::{parse_side, parse_tif, price_to_float, side_str};
+use crate::types::{SideArg, TifArg, format_price, parse_side, side_str};
 
 /// A limit order book exchange.
 ///
@@ -33,11 +37,129 @@ impl PyExchange {
 
 #[pymethods]
 impl PyExchange {
+    /// Args:
+    ///     order_id_start: First order ID to assign (must be >= 1)
+    ///     trade_id_start: First trade ID to assign
+    ///     price_scale: Price units per whole currency unit, used for
+    ///         float prices and display (100 = cents, 10000 = 1/100 cent)
+    ///     position_limit_mode: What to do with an order that would breach
+    ///         its owner's position limit: "reject" it or "clip" it to the
+    ///         allowed quantity
     #[new]
-    fn new() -> Self {
-        Self {
-            inner: Exchange::new(),
+    #[pyo3(signature = (order_id_start=1, trade_id_start=1, price_scale=100, position_limit_mode="reject"))]
+    fn new(
+        order_id_start: u64,
+        trade_id_start: u64,
+        price_scale: i64,
+        position_limit_mode: &str,
+    ) -> PyResult<Self> {
+        if order_id_start == 0 {
+            return Err(PyValueError::new_err("order_id_start must be >= 1"));
+        }
+        if price_scale <= 0 {
+            return Err(PyValueError::new_err("price_scale must be > 0"));
+        }
+        let mode = match position_limit_mode {
+            "reject" => PositionLimitMode::Reject,
+            "clip" => PositionLimitMode::Clip,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown position_limit_mode '{other}' (expected 'reject' or 'clip')"
+                )));
+            }
+        };
+        Ok(Self {
+            inner: Exchange::with_id_start(order_id_start, trade_id_start)
+                .with_price_scale(price_scale)
+                .with_position_limit_mode(mode),
+        })
+    }
+
+    /// Price units per whole currency unit.
+    #[getter]
+    fn price_scale(&self) -> i64 {
+        self.inner.price_scale()
+    }
+
+    /// The matching rules this exchange runs, as a dict.
+    ///
+    /// Keys are ``level_priority`` ("time" or "size_time"), ``tie_break``
+    /// ("sequence" or "order_id"), and ``stp_policy`` ("off",
+    /// "cancel_newest", "cancel_oldest", or "decrement_and_cancel"). The
+    /// remaining rules are fixed: better prices first, displayed before
+    /// hidden at one price, each resting order filled in full before the
+    /// next (no pro-rata), and no all-or-none resting orders.
+    fn matching_policy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let policy = self.inner.matching_policy();
+        let dict = PyDict::new(py);
+        let level_priority = match policy.level_priority {
+            LevelPriority::Time => "time",
+            LevelPriority::SizeTime => "size_time",
+        };
+        let tie_break = match policy.tie_break {
+            TieBreak::Sequence => "sequence",
+            TieBreak::OrderId => "order_id",
+        };
+        let stp_policy = match policy.stp_policy {
+            StpPolicy::Off => "off",
+            StpPolicy::CancelNewest => "cancel_newest",
+            StpPolicy::CancelOldest => "cancel_oldest",
+            StpPolicy::DecrementAndCancel => "decrement_and_cancel",
+        };
+        dict.set_item("level_priority", level_priority)?;
+        dict.set_item("tie_break", tie_break)?;
+        dict.set_item("stp_policy", stp_policy)?;
+        Ok(dict)
+    }
+
+    /// Advance the logical clock that stamps trades.
+    ///
+    /// Once set, every trade carries the current clock value until the next
+    /// call, so all fills of one order share a timestamp. Raises ValueError
+    /// if ``now`` is earlier than the current clock or, on the first call,
+    /// than the last counter-stamped order or trade.
+    fn set_clock(&mut self, now: u64) -> PyResult<()> {
+        if now < self.inner.min_clock() {
+            return Err(PyValueError::new_err("logical clock cannot move backwards"));
         }
+        self.inner.set_clock(now);
+        Ok(())
+    }
+
+    /// Current logical clock value, or None if it was never set.
+    #[getter]
+    fn clock(&self) -> Option<u64> {
+        self.inner.clock()
+    }
+
+    /// Current session state: "pre_open", "open", or "closed".
+    #[getter]
+    fn session(&self) -> &'static str {
+        self.inner.session().as_str()
+    }
+
+    /// Move to a session state: "pre_open", "open", or "closed".
+    ///
+    /// Pre-open, GTC limit orders rest without matching and immediate
+    /// orders are rejected; opening matches them in arrival order. Closed,
+    /// new orders are rejected with ``reject_reason == "session"``.
+    fn set_session(&mut self, state: &str) -> PyResult<()> {
+        self.inner.set_session(parse_session(state)?);
+        Ok(())
+    }
+
+    /// Drive session transitions from the logical clock.
+    ///
+    /// Args:
+    ///     schedule: ``(timestamp, state)`` pairs; each state takes effect
+    ///         once ``set_clock`` reaches its timestamp
+    fn set_session_schedule(&mut self, schedule: Vec<(u64, String)>) -> PyResult<()> {
+        let schedule = schedule
+            .into_iter()
+            .map(|(ts, state)| Ok((ts, parse_session(&state)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.inner.set_session_schedule(schedule);
+        Ok(())
     }
 
     /// Replay events to reconstruct exchange state.
@@ -58,23 +180,60 @@ impl PyExchange {
     ///     price: Price in cents (e.g., 10050 = $100.50)
     ///     quantity: Number of shares
     ///     tif: Time-in-force: "gtc", "ioc", or "fok"
+    ///     owner: Optional owner tag, used by `flow_stats` and position
+    ///         limits
     ///
     /// Returns:
     ///     SubmitResult with order_id, status, trades, and fill details
-    #[pyo3(signature = (side, price, quantity, tif="gtc"))]
+    #[pyo3(signature = (side, price, quantity, tif=TifArg(TimeInForce::GTC), owner=None))]
     fn submit_limit(
         &mut self,
-        side: &str,
+        side: SideArg,
         price: i64,
         quantity: u64,
-        tif: &str,
+        tif: TifArg,
+        owner: Option<u32>,
     ) -> PyResult<PySubmitResult> {
-        let side = parse_side(side)?;
-        let tif = parse_tif(tif)?;
-        Ok(self
+        let side = side.0;
+        let tif = tif.0;
+        let result = match owner {
+            Some(owner) => self.inner.submit_limit_with_owner(
+                side,
+                Price(price),
+                quantity,
+                tif,
+                OrderOwner(owner),
+            ),
+            None => self.inner.submit_limit(side, Price(price), quantity, tif),
+        };
+        Ok(PySubmitResult::from(result).with_price_scale(self.inner.price_scale()))
+    }
+
+    /// Submit a hidden (non-displayed) limit order.
+    ///
+    /// It matches like ``submit_limit`` but rests out of ``best_bid_ask``
+    /// and depth snapshots. Incoming orders fill it ahead of displayed
+    /// orders at worse prices and behind displayed orders at its price.
+    ///
+    /// Args:
+    ///     side: "buy" or "sell"
+    ///     price: Price in cents
+    ///     quantity: Number of shares
+    ///     tif: Time-in-force: "gtc", "ioc", or "fok"
+    #[pyo3(signature = (side, price, quantity, tif=TifArg(TimeInForce::GTC)))]
+    fn submit_hidden_limit(
+        &mut self,
+        side: SideArg,
+        price: i64,
+        quantity: u64,
+        tif: TifArg,
+    ) -> PyResult<PySubmitResult> {
+        let side = side.0;
+        let tif = tif.0;
+        let result = self
             .inner
-            .submit_limit(side, Price(price), quantity, tif)
-            .into())
+            .submit_hidden_limit(side, Price(price), quantity, tif);
+        Ok(PySubmitResult::from(result).with_price_scale(self.inner.price_scale()))
     }
 
     /// Submit a market order.
@@ -85,9 +244,81 @@ impl PyExchange {
     ///
     /// Returns:
     ///     SubmitResult with fill details
-    fn submit_market(&mut self, side: &str, quantity: u64) -> PyResult<PySubmitResult> {
-        let side = parse_side(side)?;
-        Ok(self.inner.submit_market(side, quantity).into())
+    fn submit_market(&mut self, side: SideArg, quantity: u64) -> PyResult<PySubmitResult> {
+        let side = side.0;
+        let result = self.inner.submit_market(side, quantity);
+        Ok(PySubmitResult::from(result).with_price_scale(self.inner.price_scale()))
+    }
+
+    /// Submit a bracket order: a limit entry with an attached stop-loss and
+    /// take-profit on the opposite side.
+    ///
+    /// The exits are armed for the filled quantity once the entry is done
+    /// (fully filled, or cancelled after a partial fill). They are
+    /// one-cancels-other: a fill of either reduces the other by the same
+    /// quantity, and a triggered stop-loss cancels the take-profit.
+    ///
+    /// Args:
+    ///     side: Entry side, "buy" or "sell"
+    ///     entry_price: Entry limit price in cents
+    ///     quantity: Entry quantity
+    ///     stop_price: Stop-loss trigger price in cents
+    ///     target_price: Take-profit limit price in cents
+    ///     tif: Entry time-in-force
+    #[pyo3(signature = (side, entry_price, quantity, stop_price, target_price, tif=TifArg(TimeInForce::GTC)))]
+    fn submit_bracket(
+        &mut self,
+        side: SideArg,
+        entry_price: i64,
+        quantity: u64,
+        stop_price: i64,
+        target_price: i64,
+        tif: TifArg,
+    ) -> PyResult<PyBracketResult> {
+        let side = side.0;
+        let tif = tif.0;
+        let result = self.inner.submit_bracket(
+            side,
+            Price(entry_price),
+            quantity,
+            tif,
+            Price(stop_price),
+            Price(target_price),
+        );
+        Ok(PyBracketResult::from(result).with_price_scale(self.inner.price_scale()))
+    }
+
+    /// Get the bracket entered by an order, as a dict (``None`` if none).
+    ///
+    /// ``stop_order_id`` and ``target_order_id`` are ``None`` until armed.
+    fn bracket(&self, py: Python<'_>, entry_order_id: u64) -> PyResult<Option<Py<PyAny>>> {
+        let Some(bracket) = self.inner.bracket(OrderId(entry_order_id)) else {
+            return Ok(None);
+        };
+        let dict = PyDict::new(py);
+        dict.set_item("side", side_str(bracket.side))?;
+        dict.set_item("stop_price", bracket.stop_price.0)?;
+        dict.set_item("target_price", bracket.target_price.0)?;
+        dict.set_item("filled_quantity", bracket.filled_quantity)?;
+        dict.set_item("stop_order_id", bracket.stop_order_id.map(|id| id.0))?;
+        dict.set_item("target_order_id", bracket.target_order_id.map(|id| id.0))?;
+        Ok(Some(dict.into_any().unbind()))
+    }
+
+    /// Link two live orders (resting or pending stops) as one-cancels-other.
+    ///
+    /// A fill of either reduces the other by the same quantity; once the
+    /// other has nothing left it is cancelled. Raises ``ValueError`` if the
+    /// IDs are equal, an order is not live, or either is already linked.
+    fn link_oco(&mut self, order_a: u64, order_b: u64) -> PyResult<()> {
+        self.inner
+            .link_oco(OrderId(order_a), OrderId(order_b))
+            .map_err(|e| PyValueError::new_err(format!("cannot link orders: {e:?}")))
+    }
+
+    /// The order linked to ``order_id`` as one-cancels-other, if any.
+    fn oco_partner(&self, order_id: u64) -> Option<u64> {
+        self.inner.oco_partner(OrderId(order_id)).map(|id| id.0)
     }
 
     // === Order Management ===
@@ -97,13 +328,19 @@ impl PyExchange {
         self.inner.cancel(OrderId(order_id)).into()
     }
 
-    /// Modify an order (cancel and replace).
+    /// Modify an order's price and/or open quantity.
     ///
-    /// The new order gets a new ID and loses time priority.
+    /// Same price with a smaller or equal quantity amends in place: the
+    /// order keeps its ID and queue position (``priority_retained=True``).
+    /// Under size-time priority a reduction that moves the order in the
+    /// queue keeps its ID but reports ``priority_retained=False``.
+    /// Otherwise the order is cancelled and replaced with a new ID that
+    /// loses time priority.
     fn modify(&mut self, order_id: u64, new_price: i64, new_quantity: u64) -> PyModifyResult {
-        self.inner
-            .modify(OrderId(order_id), Price(new_price), new_quantity)
-            .into()
+        let result = self
+            .inner
+            .modify(OrderId(order_id), Price(new_price), new_quantity);
+        PyModifyResult::from(result).with_price_scale(self.inner.price_scale())
     }
 
     // === Stop Orders ===
@@ -113,11 +350,11 @@ impl PyExchange {
     /// Triggers when trade price reaches stop_price, then becomes a market order.
     fn submit_stop_market(
         &mut self,
-        side: &str,
+        side: SideArg,
         stop_price: i64,
         quantity: u64,
     ) -> PyResult<PyStopSubmitResult> {
-        let side = parse_side(side)?;
+        let side = side.0;
         Ok(self
             .inner
             .submit_stop_market(side, Price(stop_price), quantity)
@@ -125,17 +362,17 @@ impl PyExchange {
     }
 
     /// Submit a stop-limit order.
-    #[pyo3(signature = (side, stop_price, limit_price, quantity, tif="gtc"))]
+    #[pyo3(signature = (side, stop_price, limit_price, quantity, tif=TifArg(TimeInForce::GTC)))]
     fn submit_stop_limit(
         &mut self,
-        side: &str,
+        side: SideArg,
         stop_price: i64,
         limit_price: i64,
         quantity: u64,
-        tif: &str,
+        tif: TifArg,
     ) -> PyResult<PyStopSubmitResult> {
-        let side = parse_side(side)?;
-        let tif = parse_tif(tif)?;
+        let side = side.0;
+        let tif = tif.0;
         Ok(self
             .inner
             .submit_stop_limit(side, Price(stop_price), Price(limit_price), quantity, tif)
@@ -158,14 +395,14 @@ impl PyExchange {
     #[pyo3(signature = (side, initial_stop_price, quantity, trail_type, trail_value, atr_period=None))]
     fn submit_trailing_stop_market(
         &mut self,
-        side: &str,
+        side: SideArg,
         initial_stop_price: i64,
         quantity: u64,
         trail_type: &str,
         trail_value: f64,
         atr_period: Option<usize>,
     ) -> PyResult<PyStopSubmitResult> {
-        let side = parse_side(side)?;
+        let side = side.0;
         let method = parse_trail_method(trail_type, trail_value, atr_period)?;
         Ok(self
             .inner
@@ -175,20 +412,20 @@ impl PyExchange {
 
     /// Submit a trailing stop-limit order.
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (side, initial_stop_price, limit_price, quantity, trail_type, trail_value, tif="gtc", atr_period=None))]
+    #[pyo3(signature = (side, initial_stop_price, limit_price, quantity, trail_type, trail_value, tif=TifArg(TimeInForce::GTC), atr_period=None))]
     fn submit_trailing_stop_limit(
         &mut self,
-        side: &str,
+        side: SideArg,
         initial_stop_price: i64,
         limit_price: i64,
         quantity: u64,
         trail_type: &str,
         trail_value: f64,
-        tif: &str,
+        tif: TifArg,
         atr_period: Option<usize>,
     ) -> PyResult<PyStopSubmitResult> {
-        let side = parse_side(side)?;
-        let tif = parse_tif(tif)?;
+        let side = side.0;
+        let tif = tif.0;
         let method = parse_trail_method(trail_type, trail_value, atr_period)?;
         Ok(self
             .inner
@@ -212,6 +449,15 @@ impl PyExchange {
             .map(|o| PyOrder { inner: o.clone() })
     }
 
+    /// Logical time an order has held its current queue priority.
+    ///
+    /// Restarts when the order loses priority (e.g. a modify that moves or
+    /// grows it). Returns None if the order is not resting or the clock
+    /// has not been set.
+    fn time_in_queue(&self, order_id: u64) -> Option<u64> {
+        self.inner.time_in_queue(OrderId(order_id))
+    }
+
     /// Get a stop order by ID.
     fn get_stop_order(&self, py: Python<'_>, order_id: u64) -> PyResult<Option<Py<PyAny>>> {
         if let Some(stop) = self.inner.get_stop_order(OrderId(order_id)) {
@@ -236,6 +482,24 @@ impl PyExchange {
         (bid.map(|p| p.0), ask.map(|p| p.0))
     }
 
+    /// Get the best bid and ask with sizes as ((bid, bid_qty), (ask, ask_qty)).
+    /// Returns None for sides with no orders.
+    #[allow(clippy::type_complexity)]
+    fn top_of_book(&self) -> (Option<(i64, u64)>, Option<(i64, u64)>) {
+        let (bid, ask) = self.inner.top_of_book();
+        (bid.map(|(p, q)| (p.0, q)), ask.map(|(p, q)| (p.0, q)))
+    }
+
+    /// Number of orders resting on both sides of the book.
+    fn resting_order_count(&self) -> usize {
+        self.inner.resting_order_count()
+    }
+
+    /// Total quantity resting on one side ("buy" or "sell").
+    fn total_resting_quantity(&self, side: SideArg) -> PyResult<u64> {
+        Ok(self.inner.total_resting_quantity(side.0))
+    }
+
     /// Get the best bid price, or None.
     fn best_bid(&self) -> Option<i64> {
         self.inner.best_bid().map(|p| p.0)
@@ -251,6 +515,146 @@ impl PyExchange {
         self.inner.spread()
     }
 
+    /// True if the book is crossed (best bid >= best ask).
+    fn is_crossed(&self) -> bool {
+        self.inner.is_crossed()
+    }
+
+    /// True if the book is locked (best bid == best ask).
+    fn is_locked(&self) -> bool {
+        self.inner.is_locked()
+    }
+
+    /// Volume and notional an owner provided (maker) and took (taker).
+    ///
+    /// Returns a dict with ``maker_volume``, ``taker_volume``,
+    /// ``maker_notional``, and ``taker_notional``; all zero if the owner
+    /// never traded.
+    fn flow_stats(&self, py: Python<'_>, owner: u32) -> PyResult<Py<PyAny>> {
+        let stats = self.inner.flow_stats(OrderOwner(owner));
+        let dict = PyDict::new(py);
+        dict.set_item("maker_volume", stats.maker_volume)?;
+        dict.set_item("taker_volume", stats.taker_volume)?;
+        dict.set_item("maker_notional", stats.maker_notional)?;
+        dict.set_item("taker_notional", stats.taker_notional)?;
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Halt trading.
+    ///
+    /// New orders that would match on arrival (every new order if ``all``
+    /// is true) are rejected with ``reject_reason == "halted"``. Resting
+    /// orders stay and can still be cancelled.
+    #[pyo3(signature = (all=false))]
+    fn halt(&mut self, all: bool) {
+        if all {
+            self.inner.halt_all();
+        } else {
+            self.inner.halt();
+        }
+    }
+
+    /// Lift a halt.
+    fn resume(&mut self) {
+        self.inner.resume();
+    }
+
+    /// True while the exchange is halted.
+    #[getter]
+    fn is_halted(&self) -> bool {
+        self.inner.is_halted()
+    }
+
+    /// Cap an owner's absolute net position.
+    ///
+    /// Orders submitted with this ``owner`` are checked at submit time: the
+    /// filled position plus all open quantity on the order's side plus the
+    /// order itself must stay within ``±max_position``. Breaching orders
+    /// are rejected or clipped per ``position_limit_mode``, with
+    /// ``reject_reason == "position_limit"``.
+    fn set_position_limit(&mut self, owner: u32, max_position: u64) {
+        self.inner
+            .set_position_limit(OrderOwner(owner), max_position);
+    }
+
+    /// Remove an owner's position limit; returns the old limit or None.
+    fn remove_position_limit(&mut self, owner: u32) -> Option<u64> {
+        self.inner.remove_position_limit(OrderOwner(owner))
+    }
+
+    /// The owner's position limit, or None if it has none.
+    fn position_limit(&self, owner: u32) -> Option<u64> {
+        self.inner.position_limit(OrderOwner(owner))
+    }
+
+    /// Net filled position of an owner (bought minus sold).
+    fn net_position(&self, owner: u32) -> i64 {
+        self.inner.net_position(OrderOwner(owner))
+    }
+
+    /// Buy/sell volume, trade count, and VWAP over a timestamp window.
+    ///
+    /// Counts trades with ``from_ts <= timestamp <= to_ts``; volume is
+    /// split by the aggressor side. Returns a dict with ``buy_volume``,
+    /// ``sell_volume``, ``trade_count``, and ``vwap`` (cents, or None).
+    #[pyo3(signature = (from_ts=0, to_ts=u64::MAX))]
+    fn trade_stats(&self, py: Python<'_>, from_ts: u64, to_ts: u64) -> PyResult<Py<PyAny>> {
+        let stats = self.inner.trade_stats(from_ts, to_ts);
+        let dict = PyDict::new(py);
+        dict.set_item("buy_volume", stats.buy_volume)?;
+        dict.set_item("sell_volume", stats.sell_volume)?;
+        dict.set_item("trade_count", stats.trade_count)?;
+        dict.set_item("vwap", stats.vwap.map(|p| p.0))?;
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Running signed aggressor volume since the last ``reset_flow``.
+    ///
+    /// Buy-initiated volume minus sell-initiated volume, updated as trades
+    /// occur, so reading it is O(1).
+    fn order_flow_imbalance(&self) -> i64 {
+        self.inner.order_flow_imbalance()
+    }
+
+    /// Zero the running order-flow imbalance.
+    fn reset_flow(&mut self) {
+        self.inner.reset_flow();
+    }
+
+    /// Estimate a market order without submitting it.
+    ///
+    /// Walks the book, hidden orders included, as ``submit_market`` would
+    /// and returns a dict with ``filled_quantity``, ``notional``, ``vwap``,
+    /// and ``worst_price`` (cents, or None if nothing fills). The book is
+    /// not modified.
+    fn sweep_vwap(&self, py: Python<'_>, side: SideArg, quantity: u64) -> PyResult<Py<PyAny>> {
+        let est = self.inner.sweep_vwap(side.0, quantity);
+        let dict = PyDict::new(py);
+        dict.set_item("filled_quantity", est.filled_quantity)?;
+        dict.set_item("notional", est.notional)?;
+        dict.set_item("vwap", est.vwap.map(|p| p.0))?;
+        dict.set_item("worst_price", est.worst_price.map(|p| p.0))?;
+        Ok(dict.into_any().unbind())
+    }
+
+    /// Preview a limit order without submitting it.
+    ///
+    /// Returns the SubmitResult ``submit_limit`` would return now, with
+    /// full per-trade detail and the same time-in-force handling, while
+    /// leaving the book unchanged. Stops the fills would trigger are not
+    /// simulated.
+    #[pyo3(signature = (side, price, quantity, tif=TifArg(TimeInForce::GTC)))]
+    fn preview(
+        &self,
+        side: SideArg,
+        price: i64,
+        quantity: u64,
+        tif: TifArg,
+    ) -> PyResult<PySubmitResult> {
+        let result = self.inner.preview(side.0, Price(price), quantity, tif.0);
+        Ok(PySubmitResult::from(result).with_price_scale(self.inner.price_scale()))
+    }
+
     /// Get the last trade price, or None.
     fn last_trade_price(&self) -> Option<i64> {
         self.inner.last_trade_price().map(|p| p.0)
@@ -262,7 +666,10 @@ impl PyExchange {
             .trades()
             .iter()
             .cloned()
-            .map(PyTrade::from)
+            .map(|t| PyTrade {
+                price_scale: self.inner.price_scale(),
+                ..PyTrade::from(t)
+            })
             .collect()
     }
 
@@ -280,13 +687,49 @@ impl PyExchange {
     #[pyo3(signature = (levels=10))]
     fn depth(&self, levels: usize) -> PyBookSnapshot {
         let snap = self.inner.depth(levels);
-        PyBookSnapshot::from_snapshot(&snap)
+        PyBookSnapshot::from_snapshot(&snap, self.inner.price_scale())
+    }
+
+    /// Visible levels of one side as two numpy arrays, best to worst.
+    ///
+    /// Returns ``(prices, sizes)``: ``int64`` prices in cents and
+    /// ``uint64`` resting quantities for the top ``depth`` levels (all
+    /// levels if None). Hidden orders are not included. Requires numpy.
+    #[pyo3(signature = (side, depth=None))]
+    fn levels<'py>(
+        &self,
+        py: Python<'py>,
+        side: SideArg,
+        depth: Option<usize>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        let (prices, sizes): (Vec<i64>, Vec<u64>) = self
+            .inner
+            .levels(side.0, depth.unwrap_or(usize::MAX))
+            .into_iter()
+            .unzip();
+        let np = py.import("numpy")?;
+        Ok((
+            np.call_method1("array", (prices, "int64"))?,
+            np.call_method1("array", (sizes, "uint64"))?,
+        ))
+    }
+
+    /// Orders resting at one price, in the order they fill.
+    ///
+    /// Displayed orders come first in queue priority, then hidden ones.
+    /// Returns an empty list if nothing rests at ``price`` on ``side``.
+    fn orders_at(&self, side: SideArg, price: i64) -> Vec<PyOrder> {
+        self.inner
+            .orders_at(side.0, Price(price))
+            .into_iter()
+            .map(|inner| PyOrder { inner })
+            .collect()
     }
 
     /// Get a full snapshot of the book.
     fn full_book(&self) -> PyBookSnapshot {
         let snap = self.inner.full_book();
-        PyBookSnapshot::from_snapshot(&snap)
+        PyBookSnapshot::from_snapshot(&snap, self.inner.price_scale())
     }
 
     /// Number of pending stop orders.
@@ -311,9 +754,16 @@ impl PyExchange {
         self.inner.compact();
     }
 
+    /// Remove all orders, trades, and events, keeping configuration.
+    ///
+    /// Order and trade IDs restart from the constructor's starting values.
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
     fn __repr__(&self) -> String {
         let (bid, ask) = self.inner.best_bid_ask();
-        let fmt_price = |p: nanobook::Price| format!("${:.2}", price_to_float(p));
+        let fmt_price = |p: nanobook::Price| format_price(p, self.inner.price_scale());
         let bid_str = bid.map(fmt_price).unwrap_or_else(|| "None".to_string());
         let ask_str = ask.map(fmt_price).unwrap_or_else(|| "None".to_string());
         format!(
@@ -336,6 +786,30 @@ pub struct PyBookSnapshot {
 
 #[pymethods]
 impl PyBookSnapshot {
+    /// Build a snapshot from crafted levels (e.g. for testing analytics).
+    ///
+    /// Args:
+    ///     bids: List of (price_cents, quantity) tuples, highest price first
+    ///     asks: List of (price_cents, quantity) tuples, lowest price first
+    ///     timestamp: Snapshot timestamp
+    ///
+    /// Each level is recorded with an order count of 1. Raises ValueError
+    /// if either side is not strictly sorted best-to-worst.
+    #[new]
+    #[pyo3(signature = (bids, asks, timestamp=0))]
+    fn new(bids: Vec<(i64, u64)>, asks: Vec<(i64, u64)>, timestamp: u64) -> PyResult<Self> {
+        fn to_levels(levels: Vec<(i64, u64)>) -> Vec<nanobook::LevelSnapshot> {
+            levels
+                .into_iter()
+                .map(|(p, q)| nanobook::LevelSnapshot::new(nanobook::Price(p), q, 1))
+                .collect()
+        }
+
+        let snap = nanobook::BookSnapshot::new(to_levels(bids), to_levels(asks), timestamp)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        Ok(Self::from_snapshot(&snap, nanobook::Price::DEFAULT_SCALE))
+    }
+
     #[getter]
     fn bids(&self) -> Vec<PyLevelSnapshot> {
         self.bids.clone()
@@ -366,6 +840,33 @@ impl PyBookSnapshot {
         self.inner.spread()
     }
 
+    /// Level changes that turn this snapshot into `other`.
+    ///
+    /// Returns a list of dicts with ``side`` ("buy"/"sell"), ``price``,
+    /// ``kind`` ("added", "removed", or "changed"), ``quantity``, and
+    /// ``order_count``. Removed levels report zero quantity and count.
+    /// Bids come first, then asks, each best to worst.
+    fn diff(&self, py: Python<'_>, other: &PyBookSnapshot) -> PyResult<Vec<Py<PyAny>>> {
+        self.inner
+            .diff(&other.inner)
+            .into_iter()
+            .map(|c| {
+                let dict = PyDict::new(py);
+                dict.set_item("side", side_str(c.side))?;
+                dict.set_item("price", c.price.0)?;
+                let kind = match c.kind {
+                    LevelChangeKind::Added => "added",
+                    LevelChangeKind::Removed => "removed",
+                    LevelChangeKind::Changed => "changed",
+                };
+                dict.set_item("kind", kind)?;
+                dict.set_item("quantity", c.quantity)?;
+                dict.set_item("order_count", c.order_count)?;
+                Ok(dict.into_any().unbind())
+            })
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "BookSnapshot(bids={}, asks={})",
@@ -376,17 +877,18 @@ impl PyBookSnapshot {
 }
 
 impl PyBookSnapshot {
-    pub fn from_snapshot(snap: &nanobook::BookSnapshot) -> Self {
-        fn convert_levels(levels: &[nanobook::LevelSnapshot]) -> Vec<PyLevelSnapshot> {
+    pub fn from_snapshot(snap: &nanobook::BookSnapshot, price_scale: i64) -> Self {
+        let convert_levels = |levels: &[nanobook::LevelSnapshot]| -> Vec<PyLevelSnapshot> {
             levels
                 .iter()
                 .map(|l| PyLevelSnapshot {
                     price: l.price.0,
                     quantity: l.quantity,
                     order_count: l.order_count,
+                    price_scale,
                 })
                 .collect()
-        }
+        };
 
         Self {
             inner: snap.clone(),
@@ -396,6 +898,119 @@ impl PyBookSnapshot {
     }
 }
 
+/// Consolidated best bid and ask (NBBO) across several exchanges for the
+/// same instrument.
+///
+/// Returns ``(bid, ask)``: the highest bid and lowest ask over all
+/// exchanges, each None if no exchange has orders on that side.
+///
+/// Example::
+///
+///     bid, ask = best_consolidated([venue_a, venue_b])
+///
+#[pyfunction]
+#[pyo3(name = "best_consolidated")]
+pub fn py_best_consolidated(exchanges: Vec<PyRef<PyExchange>>) -> (Option<i64>, Option<i64>) {
+    let refs: Vec<&Exchange> = exchanges.iter().map(|ex| &ex.inner).collect();
+    let (bid, ask) = best_consolidated(&refs);
+    (bid.map(|p| p.0), ask.map(|p| p.0))
+}
+
+/// Route an order across several exchanges for the same instrument,
+/// taking the best-priced liquidity first regardless of venue.
+///
+/// Equal prices fill in the order the exchanges are given. Returns a dict
+/// with ``filled_quantity``, ``filled_notional``, and ``fills``: a list of
+/// ``(venue_index, SubmitResult)`` for each exchange that was sent an IOC
+/// child order.
+///
+/// Example::
+///
+///     routed = route_order([venue_a, venue_b], "buy", 500)
+///
+#[pyfunction]
+#[pyo3(name = "route_order")]
+pub fn py_route_order(
+    py: Python<'_>,
+    mut venues: Vec<PyRefMut<PyExchange>>,
+    side: SideArg,
+    quantity: u64,
+) -> PyResult<Py<PyAny>> {
+    let side = side.0;
+    let mut refs: Vec<&mut Exchange> = venues.iter_mut().map(|ex| &mut ex.inner).collect();
+    let routed = route_order(&mut refs, side, quantity);
+    let fills: Vec<(usize, PySubmitResult)> = routed
+        .fills
+        .into_iter()
+        .map(|f| (f.venue, PySubmitResult::from(f.result)))
+        .collect();
+    let dict = PyDict::new(py);
+    dict.set_item("filled_quantity", routed.filled_quantity)?;
+    dict.set_item("filled_notional", routed.filled_notional)?;
+    dict.set_item("fills", fills)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Implementation shortfall of ``fills`` against the arrival (decision)
+/// price, in basis points. Positive values are a cost.
+///
+/// Prices are in cents like the trades themselves. Without
+/// ``target_quantity`` the cost is per unit filled; with it, the unfilled
+/// remainder is charged at ``final_price`` and the total is divided by the
+/// arrival value of the whole target. Returns NaN when the result is
+/// undefined (nothing filled and no target, or a non-positive price).
+///
+/// Example::
+///
+///     res = ex.submit_market("buy", 500)
+///     bps = implementation_shortfall(10000, res.trades, "buy")
+///
+#[pyfunction]
+#[pyo3(
+    name = "implementation_shortfall",
+    signature = (arrival_price, fills, side, target_quantity=None, final_price=None)
+)]
+pub fn py_implementation_shortfall(
+    arrival_price: f64,
+    fills: Vec<PyRef<PyTrade>>,
+    side: SideArg,
+    target_quantity: Option<u64>,
+    final_price: Option<f64>,
+) -> PyResult<f64> {
+    let side = side.0;
+    let trades = fills
+        .iter()
+        .map(|t| {
+            Ok(Trade::new(
+                TradeId(t.trade_id),
+                Price(t.price),
+                t.quantity,
+                OrderId(t.aggressor_order_id),
+                OrderId(t.passive_order_id),
+                parse_side(&t.aggressor_side)?,
+                t.timestamp,
+            ))
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    match (target_quantity, final_price) {
+        (None, _) => Ok(execution::implementation_shortfall(
+            arrival_price,
+            &trades,
+            side,
+        )),
+        (Some(target), Some(final_price)) => Ok(execution::implementation_shortfall_with_target(
+            arrival_price,
+            &trades,
+            side,
+            target,
+            final_price,
+        )),
+        (Some(_), None) => Err(PyValueError::new_err(
+            "final_price is required when target_quantity is given",
+        )),
+    }
+}
+
 /// Parse trail method from Python arguments.
 ///
 /// Accepted `trail_type` values:
@@ -438,3 +1053,15 @@ fn parse_trail_method(
         ))),
     }
 }
+
+/// Parse a session state name ("pre_open", "open", or "closed").
+fn parse_session(state: &str) -> PyResult<SessionState> {
+    match state.to_ascii_lowercase().as_str() {
+        "pre_open" => Ok(SessionState::PreOpen),
+        "open" => Ok(SessionState::Open),
+        "closed" => Ok(SessionState::Closed),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid session state '{state}'. Use 'pre_open', 'open', or 'closed'."
+        ))),
+    }
+}
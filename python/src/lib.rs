@@ -57,8 +57,14 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<risk::PyRiskEngine>()?;
 
     // Core exchange types
+    m.add_class::<types::PySide>()?;
+    m.add_class::<types::PyTimeInForce>()?;
     m.add_class::<exchange::PyExchange>()?;
     m.add_class::<multi::PyMultiExchange>()?;
+    m.add_class::<multi::PyMultiReplay>()?;
+    m.add_function(wrap_pyfunction!(exchange::py_best_consolidated, m)?)?;
+    m.add_function(wrap_pyfunction!(exchange::py_route_order, m)?)?;
+    m.add_function(wrap_pyfunction!(exchange::py_implementation_shortfall, m)?)?;
     m.add_class::<order::PyOrder>()?;
     m.add_class::<event::PyEvent>()?;
 
@@ -66,6 +72,8 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<results::PySubmitResult>()?;
     m.add_class::<results::PyCancelResult>()?;
     m.add_class::<results::PyModifyResult>()?;
+    m.add_class::<results::PySpreadResult>()?;
+    m.add_class::<results::PyBracketResult>()?;
     m.add_class::<results::PyStopSubmitResult>()?;
     m.add_class::<results::PyTrade>()?;
     m.add_class::<results::PyLevelSnapshot>()?;
@@ -75,15 +83,27 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Portfolio types
     m.add_class::<portfolio::PyCostModel>()?;
     m.add_class::<portfolio::PyPortfolio>()?;
+    m.add_function(wrap_pyfunction!(portfolio::py_weights_to_shares, m)?)?;
+    m.add_function(wrap_pyfunction!(portfolio::py_shares_to_weights, m)?)?;
     m.add_class::<position::PyPosition>()?;
     m.add_class::<metrics::PyMetrics>()?;
+    m.add_class::<metrics::PyStreamingMetrics>()?;
+    m.add_class::<metrics::PyRelativeMetrics>()?;
 
     // v0.7 functions
     m.add_function(wrap_pyfunction!(metrics::py_compute_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::py_compute_metrics_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::py_metrics_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::py_argmax_by, m)?)?;
+    m.add_function(wrap_pyfunction!(metrics::py_summarize, m)?)?;
     m.add_function(wrap_pyfunction!(metrics::py_drawdown_series, m)?)?;
     m.add_function(wrap_pyfunction!(metrics::py_rolling_max_drawdown, m)?)?;
     m.add_function(wrap_pyfunction!(sweep::py_sweep_equal_weight, m)?)?;
+    m.add_function(wrap_pyfunction!(sweep::py_sweep_top_k, m)?)?;
+    m.add_class::<strategy::PyPriceSeries>()?;
     m.add_function(wrap_pyfunction!(strategy::py_run_backtest, m)?)?;
+    m.add_function(wrap_pyfunction!(strategy::py_run_backtest_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(strategy::py_run_long_short, m)?)?;
     m.add_function(wrap_pyfunction!(backtest_bridge::backtest_weights, m)?)?;
     m.add_function(wrap_pyfunction!(backtest_bridge::py_backtest_weights, m)?)?;
     m.add_function(wrap_pyfunction!(backtest_bridge::py_decompose_backtest, m)?)?;
@@ -91,6 +111,8 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     #[cfg(feature = "itch")]
     m.add_function(wrap_pyfunction!(itch::parse_itch, m)?)?;
 
+    m.add_function(wrap_pyfunction!(types::py_hashed_symbol, m)?)?;
+
     // v0.8 — Technical indicators (ta-lib replacements)
     m.add_function(wrap_pyfunction!(indicators::py_sma, m)?)?;
     m.add_function(wrap_pyfunction!(indicators::py_ema, m)?)?;
@@ -103,7 +125,13 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // v0.8 — Statistics (scipy replacements)
     m.add_function(wrap_pyfunction!(stats::py_spearman, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_permutation_test, m)?)?;
     m.add_function(wrap_pyfunction!(stats::py_quintile_spread, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_quintile_spread_weighted, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_quantile_means, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_demean, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_zscore, m)?)?;
+    m.add_function(wrap_pyfunction!(stats::py_winsorize, m)?)?;
     m.add_function(wrap_pyfunction!(stats::py_deflated_sharpe, m)?)?;
 
     // v0.8 — Cross-validation (sklearn replacement)
@@ -132,6 +160,12 @@ fn nanobook(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(optimize::py_inverse_cdar_weights, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::optimize_hrp, m)?)?;
     m.add_function(wrap_pyfunction!(optimize::py_optimize_hrp, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_rolling_corr_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_correlation_clusters, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_cov_shrinkage, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_min_variance_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_mean_variance_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(optimize::py_risk_parity_weights, m)?)?;
 
     Ok(())
 }
@@ -1,7 +1,9 @@
 use nanobook::portfolio::metrics::{
-    Metrics, compute_metrics, drawdown_series, rolling_max_drawdown, rolling_sharpe,
-    rolling_volatility,
+    Metrics, MetricsOptions, RelativeMetrics, StreamingMetrics, argmax_by, compute_metrics_batch,
+    compute_metrics_ex, drawdown_series, rolling_max_drawdown, rolling_sharpe, rolling_volatility,
+    summarize,
 };
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 
@@ -41,10 +43,30 @@ pub struct PyMetrics {
     pub payoff_ratio: f64,
     #[pyo3(get)]
     pub kelly: f64,
+    #[pyo3(get)]
+    pub avg_win: f64,
+    #[pyo3(get)]
+    pub avg_loss: f64,
+    #[pyo3(get)]
+    pub expectancy: f64,
+    pub inner: Metrics,
 }
 
 #[pymethods]
 impl PyMetrics {
+    /// All fields as a dict keyed by field name.
+    ///
+    /// The keys are the fields of the Rust ``Metrics`` struct, so new
+    /// metrics appear here without listing them. Values keep NaN and
+    /// infinities as floats.
+    fn to_dict(slf: &Bound<'_, Self>) -> PyResult<Py<PyDict>> {
+        let dict = PyDict::new(slf.py());
+        for name in metric_names(&slf.borrow().inner)? {
+            dict.set_item(&name, slf.getattr(name.as_str())?)?;
+        }
+        Ok(dict.unbind())
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Metrics(total_return={:.2}%, sharpe={:.2}, max_drawdown={:.2}%, win_rate={:.1}%)",
@@ -74,7 +96,182 @@ impl From<Metrics> for PyMetrics {
             profit_factor: m.profit_factor,
             payoff_ratio: m.payoff_ratio,
             kelly: m.kelly,
+            avg_win: m.avg_win,
+            avg_loss: m.avg_loss,
+            expectancy: m.expectancy,
+            inner: m,
+        }
+    }
+}
+
+/// Field names of [`Metrics`], read from its serialized form.
+fn metric_names(m: &Metrics) -> PyResult<Vec<String>> {
+    match serde_json::to_value(m) {
+        Ok(serde_json::Value::Object(fields)) => Ok(fields.into_iter().map(|(k, _)| k).collect()),
+        _ => Err(PyValueError::new_err(
+            "metrics did not serialize to an object",
+        )),
+    }
+}
+
+/// Serialize metrics to a JSON string.
+///
+/// Accepts a single ``Metrics`` or a list of ``Metrics`` / ``None`` (as
+/// returned by sweeps), giving a JSON object or array. NaN and infinite
+/// values, which JSON cannot represent, are written as ``null``.
+///
+/// Args:
+///     metrics: A Metrics object or a list of Metrics / None
+///     pretty: Indent the output
+///
+/// Example::
+///
+///     with open("metrics.json", "w") as f:
+///         f.write(nanobook.metrics_to_json(result.metrics, pretty=True))
+///
+#[pyfunction]
+#[pyo3(name = "metrics_to_json", signature = (metrics, pretty=false))]
+pub fn py_metrics_to_json(metrics: &Bound<'_, PyAny>, pretty: bool) -> PyResult<String> {
+    let value = if let Ok(m) = metrics.extract::<PyRef<'_, PyMetrics>>() {
+        serde_json::to_value(&m.inner)
+    } else {
+        let list: Vec<Option<PyRef<'_, PyMetrics>>> = metrics.extract()?;
+        let inner: Vec<Option<&Metrics>> =
+            list.iter().map(|m| m.as_ref().map(|m| &m.inner)).collect();
+        serde_json::to_value(inner)
+    }
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let json = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    };
+    json.map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Beta, alpha, tracking error, and information ratio against a benchmark.
+#[pyclass(name = "RelativeMetrics")]
+#[derive(Clone)]
+pub struct PyRelativeMetrics {
+    #[pyo3(get)]
+    pub beta: f64,
+    #[pyo3(get)]
+    pub alpha: f64,
+    #[pyo3(get)]
+    pub tracking_error: f64,
+    #[pyo3(get)]
+    pub information_ratio: f64,
+}
+
+#[pymethods]
+impl PyRelativeMetrics {
+    fn __repr__(&self) -> String {
+        format!(
+            "RelativeMetrics(beta={:.2}, alpha={:.2}%, tracking_error={:.2}%, information_ratio={:.2})",
+            self.beta,
+            self.alpha * 100.0,
+            self.tracking_error * 100.0,
+            self.information_ratio,
+        )
+    }
+}
+
+impl From<RelativeMetrics> for PyRelativeMetrics {
+    fn from(m: RelativeMetrics) -> Self {
+        Self {
+            beta: m.beta,
+            alpha: m.alpha,
+            tracking_error: m.tracking_error,
+            information_ratio: m.information_ratio,
+        }
+    }
+}
+
+/// Running Sharpe, Sortino, and drawdown updated one return at a time.
+///
+/// Each ``update`` is O(1); on the same series the values agree with
+/// ``py_compute_metrics``.
+///
+/// Example::
+///
+///     live = nanobook.StreamingMetrics(252.0, 0.0)
+///     for r in daily_returns:
+///         live.update(r)
+///         print(live.sharpe, live.max_drawdown)
+///
+#[pyclass(name = "StreamingMetrics")]
+#[derive(Clone)]
+pub struct PyStreamingMetrics {
+    inner: StreamingMetrics,
+}
+
+#[pymethods]
+impl PyStreamingMetrics {
+    #[new]
+    #[pyo3(signature = (periods_per_year=252.0, risk_free=0.0))]
+    fn new(periods_per_year: f64, risk_free: f64) -> PyResult<Self> {
+        if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+            return Err(PyValueError::new_err(
+                "periods_per_year must be positive and finite",
+            ));
         }
+        Ok(Self {
+            inner: StreamingMetrics::new(periods_per_year, risk_free),
+        })
+    }
+
+    /// Fold one periodic return into the running statistics.
+    fn update(&mut self, r: f64) {
+        self.inner.update(r);
+    }
+
+    #[getter]
+    fn num_periods(&self) -> usize {
+        self.inner.num_periods()
+    }
+
+    #[getter]
+    fn mean(&self) -> f64 {
+        self.inner.mean()
+    }
+
+    #[getter]
+    fn total_return(&self) -> f64 {
+        self.inner.total_return()
+    }
+
+    #[getter]
+    fn volatility(&self) -> f64 {
+        self.inner.volatility()
+    }
+
+    #[getter]
+    fn sharpe(&self) -> f64 {
+        self.inner.sharpe()
+    }
+
+    #[getter]
+    fn sortino(&self) -> f64 {
+        self.inner.sortino()
+    }
+
+    #[getter]
+    fn max_drawdown(&self) -> f64 {
+        self.inner.max_drawdown()
+    }
+
+    #[getter]
+    fn drawdown(&self) -> f64 {
+        self.inner.drawdown()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StreamingMetrics(num_periods={}, sharpe={:.2}, max_drawdown={:.2}%)",
+            self.inner.num_periods(),
+            self.inner.sharpe(),
+            self.inner.max_drawdown() * 100.0,
+        )
     }
 }
 
@@ -84,6 +281,9 @@ impl From<Metrics> for PyMetrics {
 ///     returns: List of periodic returns (e.g., [0.01, -0.005, 0.02])
 ///     periods_per_year: Annualization factor (252 for daily, 12 for monthly)
 ///     risk_free: Risk-free rate per period
+///     minimum_acceptable_return: Sortino target per period (default: risk_free)
+///     calmar_lookback: Trailing periods for Calmar's CAGR and drawdown
+///         (default: full sample; also used when the series is shorter)
 ///
 /// Returns:
 ///     Metrics object, or None if returns is empty
@@ -94,13 +294,147 @@ impl From<Metrics> for PyMetrics {
 ///     print(m.sharpe, m.cvar_95, m.kelly)
 ///
 #[pyfunction]
-#[pyo3(signature = (returns, periods_per_year=252.0, risk_free=0.0))]
+#[pyo3(signature = (
+    returns,
+    periods_per_year=252.0,
+    risk_free=0.0,
+    minimum_acceptable_return=None,
+    calmar_lookback=None,
+))]
 pub fn py_compute_metrics(
     returns: Vec<f64>,
     periods_per_year: f64,
     risk_free: f64,
+    minimum_acceptable_return: Option<f64>,
+    calmar_lookback: Option<usize>,
+) -> Option<PyMetrics> {
+    metrics_for(
+        &returns,
+        periods_per_year,
+        risk_free,
+        minimum_acceptable_return,
+        calmar_lookback,
+    )
+}
+
+/// Compute performance metrics for many return series in one call.
+///
+/// Series are processed in parallel with the GIL released. Result ``i``
+/// is identical to ``py_compute_metrics(series[i], periods_per_year,
+/// risk_free)``.
+///
+/// Args:
+///     series: List of return series, or a 2D array with one series per row
+///     periods_per_year: Annualization factor (252 for daily, 12 for monthly)
+///     risk_free: Risk-free rate per period
+///
+/// Returns:
+///     List of Metrics, with None for each empty series
+///
+/// Example::
+///
+///     results = nanobook.compute_metrics_batch(candidate_returns, 252.0)
+///     best = nanobook.argmax_by(results, "sharpe")
+///
+#[pyfunction]
+#[pyo3(name = "compute_metrics_batch", signature = (series, periods_per_year=252.0, risk_free=0.0))]
+pub fn py_compute_metrics_batch(
+    py: Python<'_>,
+    series: Vec<Vec<f64>>,
+    periods_per_year: f64,
+    risk_free: f64,
+) -> Vec<Option<PyMetrics>> {
+    let refs: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+    py.detach(|| compute_metrics_batch(&refs, periods_per_year, risk_free))
+        .into_iter()
+        .map(|m| m.map(PyMetrics::from))
+        .collect()
+}
+
+/// Shared body of the module-level and `Portfolio` `compute_metrics`, so
+/// both treat defaults and empty series the same way.
+pub fn metrics_for(
+    returns: &[f64],
+    periods_per_year: f64,
+    risk_free: f64,
+    minimum_acceptable_return: Option<f64>,
+    calmar_lookback: Option<usize>,
 ) -> Option<PyMetrics> {
-    compute_metrics(&returns, periods_per_year, risk_free).map(PyMetrics::from)
+    let options = MetricsOptions {
+        minimum_acceptable_return,
+        calmar_lookback,
+    };
+    compute_metrics_ex(returns, periods_per_year, risk_free, options).map(PyMetrics::from)
+}
+
+/// Evaluate `key` (a field name or a callable) on each non-None result.
+fn metric_keys(
+    results: &[Option<Bound<'_, PyMetrics>>],
+    key: &Bound<'_, PyAny>,
+) -> PyResult<Vec<Option<f64>>> {
+    let name = key.extract::<String>().ok();
+    results
+        .iter()
+        .map(|m| {
+            m.as_ref()
+                .map(|m| match &name {
+                    Some(name) => m.getattr(name.as_str())?.extract::<f64>(),
+                    None => key.call1((m,))?.extract::<f64>(),
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+/// Index of the best result in a sweep.
+///
+/// Args:
+///     results: List of Metrics or None (as returned by sweeps)
+///     key: Field name such as ``"sharpe"``, or a callable taking a Metrics
+///         and returning a float
+///
+/// Returns:
+///     Index of the highest key, or None if every entry is None or NaN.
+///     None entries and NaN keys are skipped.
+#[pyfunction]
+#[pyo3(name = "argmax_by")]
+pub fn py_argmax_by(
+    results: Vec<Option<Bound<'_, PyMetrics>>>,
+    key: &Bound<'_, PyAny>,
+) -> PyResult<Option<usize>> {
+    Ok(argmax_by(&metric_keys(&results, key)?, |&v| v))
+}
+
+/// Summarize one metric across sweep results.
+///
+/// Args:
+///     results: List of Metrics or None (as returned by sweeps)
+///     key: Field name such as ``"sharpe"``, or a callable taking a Metrics
+///         and returning a float
+///
+/// Returns:
+///     Dict with ``count``, ``mean``, ``std`` (sample), ``best``, ``worst``,
+///     ``best_index``, and ``worst_index``, or None if every entry is None
+///     or NaN.
+#[pyfunction]
+#[pyo3(name = "summarize")]
+pub fn py_summarize(
+    py: Python<'_>,
+    results: Vec<Option<Bound<'_, PyMetrics>>>,
+    key: &Bound<'_, PyAny>,
+) -> PyResult<Option<Py<PyDict>>> {
+    let Some(s) = summarize(&metric_keys(&results, key)?, |&v| v) else {
+        return Ok(None);
+    };
+    let dict = PyDict::new(py);
+    dict.set_item("count", s.count)?;
+    dict.set_item("mean", s.mean)?;
+    dict.set_item("std", s.std)?;
+    dict.set_item("best", s.best)?;
+    dict.set_item("worst", s.worst)?;
+    dict.set_item("best_index", s.best_index)?;
+    dict.set_item("worst_index", s.worst_index)?;
+    Ok(Some(dict.unbind()))
 }
 
 /// Detect drawdown events from an equity curve.
@@ -1,12 +1,19 @@
-use nanobook::{MultiExchange, OrderId, Price};
+use nanobook::{MultiExchange, OrderId, Price, Symbol, TimeInForce};
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyIterator};
 
-use crate::exchange::PyExchange;
+use crate::exchange::{PyBookSnapshot, PyExchange};
 use crate::results::*;
-use crate::types::{parse_side, parse_symbol, parse_tif};
+use crate::types::{SideArg, TifArg};
 
 /// Multi-symbol exchange wrapping one Exchange per symbol.
 ///
+/// Symbol names of any length are accepted: names longer than 8 bytes are
+/// interned to compact internal symbols, and ``symbols()``/``best_prices()``
+/// report the original names, sorted by name.
+///
 /// Example::
 ///
 ///     multi = MultiExchange()
@@ -17,13 +24,83 @@ pub struct PyMultiExchange {
     pub inner: MultiExchange,
 }
 
+impl PyMultiExchange {
+    /// Every (name, symbol) pair, sorted by name so the order is
+    /// deterministic even when long names map to generated symbols.
+    fn sorted_symbols(&self) -> Vec<(&str, Symbol)> {
+        let mut syms: Vec<(&str, Symbol)> = self
+            .inner
+            .symbols()
+            .map(|sym| (self.inner.name(sym), *sym))
+            .collect();
+        syms.sort_by(|a, b| a.0.cmp(b.0));
+        syms
+    }
+}
+
 #[pymethods]
 impl PyMultiExchange {
+    /// Args:
+    ///     price_scale: Price units per whole currency unit for every
+    ///         symbol's exchange (100 = cents)
     #[new]
-    fn new() -> Self {
-        Self {
-            inner: MultiExchange::new(),
+    #[pyo3(signature = (price_scale=100))]
+    fn new(price_scale: i64) -> PyResult<Self> {
+        if price_scale <= 0 {
+            return Err(PyValueError::new_err("price_scale must be > 0"));
         }
+        Ok(Self {
+            inner: MultiExchange::new().with_price_scale(price_scale),
+        })
+    }
+
+    /// Price units per whole currency unit.
+    #[getter]
+    fn price_scale(&self) -> i64 {
+        self.inner.price_scale()
+    }
+
+    /// Encode the full state (every book, stop, counter, and clock) as
+    /// compact binary checkpoint bytes.
+    fn checkpoint<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.checkpoint())
+    }
+
+    /// Stable 64-bit hash of the full state.
+    ///
+    /// Two runs driven through the same operations hash equal on any
+    /// platform; any divergence changes the hash. Useful for asserting
+    /// that replays are deterministic.
+    fn state_hash(&self) -> u64 {
+        self.inner.state_hash()
+    }
+
+    /// Rebuild a MultiExchange from ``checkpoint()`` bytes.
+    ///
+    /// The restored exchange behaves exactly like the original from the
+    /// checkpoint on. Raises ``ValueError`` on bytes that are not a
+    /// checkpoint of this version.
+    #[staticmethod]
+    fn restore(data: &[u8]) -> PyResult<Self> {
+        let inner =
+            MultiExchange::restore(data).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { inner })
+    }
+
+    /// Save the full state (books, queue order, stops, counters) to a JSON
+    /// file.
+    fn save_json(&self, path: &str) -> PyResult<()> {
+        self.inner
+            .save_json(std::path::Path::new(path))
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))
+    }
+
+    /// Load a MultiExchange saved with ``save_json``.
+    #[staticmethod]
+    fn load_json(path: &str) -> PyResult<Self> {
+        let inner = MultiExchange::load_json(std::path::Path::new(path))
+            .map_err(|e| pyo3::exceptions::PyIOError::new_err(e.to_string()))?;
+        Ok(Self { inner })
     }
 
     /// Get or create an Exchange for the given symbol.
@@ -32,67 +109,106 @@ impl PyMultiExchange {
     /// to the returned ``PyExchange`` do NOT flow back to the ``MultiExchange``.
     /// For mutations, use the ``submit_*`` methods directly on ``MultiExchange``.
     fn get_or_create(&mut self, symbol: &str) -> PyResult<PyExchange> {
-        let sym = parse_symbol(symbol)?;
+        let sym = self.inner.intern(symbol);
         let ex = self.inner.get_or_create(&sym);
         Ok(PyExchange::from_exchange(ex.clone()))
     }
 
-    /// List all symbols that have exchanges.
+    /// List all symbols that have exchanges, sorted by name.
     fn symbols(&self) -> Vec<String> {
-        self.inner
-            .symbols()
-            .map(|s| s.as_str().to_string())
+        self.sorted_symbols()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
             .collect()
     }
 
-    /// Get best bid/ask prices for all symbols.
-    /// Returns list of (symbol, bid, ask) tuples.
-    fn best_prices(&self) -> Vec<(String, Option<i64>, Option<i64>)> {
-        self.inner
-            .symbols()
-            .map(|sym| {
+    /// Get best bid/ask prices for all symbols, sorted by name.
+    ///
+    /// Returns list of (symbol, bid, ask) tuples, or with
+    /// ``include_sizes=True`` (symbol, bid, bid_qty, ask, ask_qty) tuples.
+    /// Sizes are the total resting quantity at the best level (0 if empty).
+    #[pyo3(signature = (include_sizes=false))]
+    fn best_prices(&self, py: Python<'_>, include_sizes: bool) -> PyResult<Vec<Py<PyAny>>> {
+        self.sorted_symbols()
+            .into_iter()
+            .map(|(name, sym)| {
                 let (bid, ask) = self
                     .inner
-                    .get(sym)
-                    .map(|ex| ex.best_bid_ask())
+                    .get(&sym)
+                    .map(|ex| ex.top_of_book())
                     .unwrap_or((None, None));
-                (sym.to_string(), bid.map(|p| p.0), ask.map(|p| p.0))
+                let name = name.to_string();
+                let bid_px = bid.map(|(p, _)| p.0);
+                let ask_px = ask.map(|(p, _)| p.0);
+                if include_sizes {
+                    let bid_qty = bid.map_or(0, |(_, q)| q);
+                    let ask_qty = ask.map_or(0, |(_, q)| q);
+                    (name, bid_px, bid_qty, ask_px, ask_qty).into_py_any(py)
+                } else {
+                    (name, bid_px, ask_px).into_py_any(py)
+                }
             })
             .collect()
     }
 
+    /// Depth-limited book snapshot for every symbol.
+    ///
+    /// Returns a dict of symbol name -> BookSnapshot, ordered by name.
+    #[pyo3(signature = (depth=10))]
+    fn snapshot_all(&self, py: Python<'_>, depth: usize) -> PyResult<Py<PyDict>> {
+        let scale = self.inner.price_scale();
+        let snapshots = self.inner.snapshot_all(depth);
+        let mut books: Vec<(&str, PyBookSnapshot)> = snapshots
+            .iter()
+            .map(|(sym, snap)| {
+                (
+                    self.inner.name(sym),
+                    PyBookSnapshot::from_snapshot(snap, scale),
+                )
+            })
+            .collect();
+        books.sort_by(|a, b| a.0.cmp(b.0));
+        let dict = PyDict::new(py);
+        for (name, snap) in books {
+            dict.set_item(name, snap)?;
+        }
+        Ok(dict.unbind())
+    }
+
     // === Method Forwarding (Option 3) ===
 
-    #[pyo3(signature = (symbol, side, price, quantity, tif="gtc"))]
+    #[pyo3(signature = (symbol, side, price, quantity, tif=TifArg(TimeInForce::GTC)))]
     fn submit_limit(
         &mut self,
         symbol: &str,
-        side: &str,
+        side: SideArg,
         price: i64,
         quantity: u64,
-        tif: &str,
+        tif: TifArg,
     ) -> PyResult<PySubmitResult> {
-        let sym = parse_symbol(symbol)?;
-        let side = parse_side(side)?;
-        let tif = parse_tif(tif)?;
+        let sym = self.inner.intern(symbol);
+        let side = side.0;
+        let tif = tif.0;
         let ex = self.inner.get_or_create(&sym);
-        Ok(ex.submit_limit(side, Price(price), quantity, tif).into())
+        let result = ex.submit_limit(side, Price(price), quantity, tif);
+        Ok(PySubmitResult::from(result).with_price_scale(ex.price_scale()))
     }
 
     fn submit_market(
         &mut self,
         symbol: &str,
-        side: &str,
+        side: SideArg,
         quantity: u64,
     ) -> PyResult<PySubmitResult> {
-        let sym = parse_symbol(symbol)?;
-        let side = parse_side(side)?;
+        let sym = self.inner.intern(symbol);
+        let side = side.0;
         let ex = self.inner.get_or_create(&sym);
-        Ok(ex.submit_market(side, quantity).into())
+        let result = ex.submit_market(side, quantity);
+        Ok(PySubmitResult::from(result).with_price_scale(ex.price_scale()))
     }
 
     fn cancel(&mut self, symbol: &str, order_id: u64) -> PyResult<PyCancelResult> {
-        let sym = parse_symbol(symbol)?;
+        let sym = self.inner.intern(symbol);
         let ex = self.inner.get_or_create(&sym);
         Ok(ex.cancel(OrderId(order_id)).into())
     }
@@ -104,11 +220,103 @@ impl PyMultiExchange {
         new_price: i64,
         new_quantity: u64,
     ) -> PyResult<PyModifyResult> {
-        let sym = parse_symbol(symbol)?;
+        let sym = self.inner.intern(symbol);
+        let ex = self.inner.get_or_create(&sym);
+        let result = ex.modify(OrderId(order_id), Price(new_price), new_quantity);
+        Ok(PyModifyResult::from(result).with_price_scale(ex.price_scale()))
+    }
+
+    /// Submit an all-or-nothing spread order across symbols.
+    ///
+    /// Each leg is a ``(symbol, side, ratio)`` tuple trading
+    /// ``ratio * quantity``. The spread fills only if every leg can fill
+    /// in full at a net price (buy legs minus sell legs, per spread unit)
+    /// of at most ``net_limit``; otherwise nothing trades.
+    fn submit_spread(
+        &mut self,
+        legs: Vec<(String, SideArg, u64)>,
+        net_limit: i64,
+        quantity: u64,
+    ) -> PyResult<PySpreadResult> {
+        let legs = legs
+            .iter()
+            .map(|(symbol, side, ratio)| (self.inner.intern(symbol), side.0, *ratio))
+            .collect::<Vec<_>>();
+        let result = self.inner.submit_spread(&legs, Price(net_limit), quantity);
+        Ok(PySpreadResult::from(result).with_price_scale(self.inner.price_scale()))
+    }
+
+    /// Halt trading in ``symbol``.
+    ///
+    /// New orders that would match on arrival (every new order if
+    /// ``all`` is true) are rejected with ``reject_reason == "halted"``.
+    /// Resting orders stay and can still be cancelled.
+    #[pyo3(signature = (symbol, all=false))]
+    fn halt(&mut self, symbol: &str, all: bool) {
+        let sym = self.inner.intern(symbol);
         let ex = self.inner.get_or_create(&sym);
-        Ok(ex
-            .modify(OrderId(order_id), Price(new_price), new_quantity)
-            .into())
+        if all {
+            ex.halt_all();
+        } else {
+            ex.halt();
+        }
+    }
+
+    /// Lift a halt on ``symbol``.
+    fn resume(&mut self, symbol: &str) {
+        let sym = self.inner.intern(symbol);
+        self.inner.get_or_create(&sym).resume();
+    }
+
+    /// True if ``symbol`` is halted (False if unknown).
+    fn is_halted(&self, symbol: &str) -> bool {
+        self.inner
+            .symbol_table()
+            .get(symbol)
+            .and_then(|sym| self.inner.get(&sym))
+            .is_some_and(|ex| ex.is_halted())
+    }
+
+    /// Remove all orders and trades for one symbol, keeping the symbol.
+    ///
+    /// Returns ``False`` if the symbol has no exchange.
+    fn clear(&mut self, symbol: &str) -> bool {
+        let Some(sym) = self.inner.symbol_table().get(symbol) else {
+            return false;
+        };
+        match self.inner.get_mut(&sym) {
+            Some(ex) => {
+                ex.clear();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Number of orders resting in ``symbol``'s book (0 if unknown).
+    fn resting_order_count(&self, symbol: &str) -> usize {
+        self.inner
+            .symbol_table()
+            .get(symbol)
+            .and_then(|sym| self.inner.get(&sym))
+            .map_or(0, |ex| ex.resting_order_count())
+    }
+
+    /// Total quantity resting on one side of ``symbol``'s book (0 if
+    /// unknown).
+    fn total_resting_quantity(&self, symbol: &str, side: SideArg) -> PyResult<u64> {
+        let side = side.0;
+        Ok(self
+            .inner
+            .symbol_table()
+            .get(symbol)
+            .and_then(|sym| self.inner.get(&sym))
+            .map_or(0, |ex| ex.total_resting_quantity(side)))
+    }
+
+    /// Remove all orders and trades for every symbol, keeping the symbols.
+    fn clear_all(&mut self) {
+        self.inner.clear_all();
     }
 
     /// Number of symbols.
@@ -116,7 +324,116 @@ impl PyMultiExchange {
         self.inner.len()
     }
 
+    /// Apply a stream of messages lazily, yielding one result per message.
+    ///
+    /// ``messages`` is any iterable (including a generator) of tuples:
+    ///
+    /// - ``("limit", symbol, side, price, quantity[, tif])`` yields a
+    ///   ``SubmitResult``
+    /// - ``("market", symbol, side, quantity)`` yields a ``SubmitResult``
+    /// - ``("cancel", symbol, order_id)`` yields a ``CancelResult``
+    /// - ``("modify", symbol, order_id, new_price, new_quantity)`` yields a
+    ///   ``ModifyResult``
+    ///
+    /// Each message is read and applied only when the next result is
+    /// requested, so memory stays flat for arbitrarily long streams.
+    /// A malformed message raises ValueError when it is reached.
+    ///
+    /// Example::
+    ///
+    ///     for result in multi.replay(read_messages()):
+    ///         handle(result)
+    ///
+    fn replay(slf: Py<Self>, messages: &Bound<'_, PyAny>) -> PyResult<PyMultiReplay> {
+        Ok(PyMultiReplay {
+            multi: slf,
+            messages: messages.try_iter()?.unbind(),
+        })
+    }
+
     fn __repr__(&self) -> String {
         format!("MultiExchange(symbols={})", self.inner.len())
     }
 }
+
+/// Iterator returned by ``MultiExchange.replay``.
+#[pyclass(name = "MultiReplay")]
+pub struct PyMultiReplay {
+    multi: Py<PyMultiExchange>,
+    messages: Py<PyIterator>,
+}
+
+impl PyMultiReplay {
+    /// Apply one message tuple to the multi-exchange.
+    fn apply(&self, py: Python<'_>, message: &Bound<'_, PyAny>) -> PyResult<Py<PyAny>> {
+        let kind: String = message.get_item(0)?.extract()?;
+        let mut multi = self.multi.borrow_mut(py);
+        let malformed = || PyValueError::new_err(format!("malformed {kind:?} message"));
+        match (kind.as_str(), message.len()?) {
+            ("limit", 5) => {
+                let (_, symbol, side, price, quantity): (String, String, SideArg, i64, u64) =
+                    message.extract().map_err(|_| malformed())?;
+                let tif = TifArg(TimeInForce::GTC);
+                multi
+                    .submit_limit(&symbol, side, price, quantity, tif)?
+                    .into_py_any(py)
+            }
+            ("limit", 6) => {
+                let (_, symbol, side, price, quantity, tif): (
+                    String,
+                    String,
+                    SideArg,
+                    i64,
+                    u64,
+                    TifArg,
+                ) = message.extract().map_err(|_| malformed())?;
+                multi
+                    .submit_limit(&symbol, side, price, quantity, tif)?
+                    .into_py_any(py)
+            }
+            ("market", 4) => {
+                let (_, symbol, side, quantity): (String, String, SideArg, u64) =
+                    message.extract().map_err(|_| malformed())?;
+                multi
+                    .submit_market(&symbol, side, quantity)?
+                    .into_py_any(py)
+            }
+            ("cancel", 3) => {
+                let (_, symbol, order_id): (String, String, u64) =
+                    message.extract().map_err(|_| malformed())?;
+                multi.cancel(&symbol, order_id)?.into_py_any(py)
+            }
+            ("modify", 5) => {
+                let (_, symbol, order_id, new_price, new_quantity): (
+                    String,
+                    String,
+                    u64,
+                    i64,
+                    u64,
+                ) = message.extract().map_err(|_| malformed())?;
+                multi
+                    .modify(&symbol, order_id, new_price, new_quantity)?
+                    .into_py_any(py)
+            }
+            ("limit" | "market" | "cancel" | "modify", _) => Err(malformed()),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown message kind: {kind:?} (expected limit, market, cancel, or modify)"
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl PyMultiReplay {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<Py<PyAny>>> {
+        let mut messages = self.messages.bind(py).clone();
+        match messages.next() {
+            Some(message) => self.apply(py, &message?).map(Some),
+            None => Ok(None),
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use nanobook::optimize;
+use nanobook::optimize::{self, OptimizeError, WeightConstraint};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
@@ -158,3 +159,196 @@ pub fn py_optimize_hrp(
 ) -> PyResult<Py<PyAny>> {
     optimize_hrp(py, returns_matrix, symbols)
 }
+
+/// Correlation matrix of every trailing window across per-asset series.
+///
+/// Args:
+///     series: One return series per asset, all the same length.
+///     window: Window length in periods (at least 2).
+///
+/// Returns:
+///     ``len - window + 1`` matrices; matrix ``k`` covers periods
+///     ``k..k + window``. Empty on invalid input.
+///
+/// Example::
+///
+///     mats = nanobook.rolling_corr_matrix([spy, tlt, gld], 63)
+///     print(mats[-1][0][1])  # latest SPY/TLT correlation
+///
+#[pyfunction]
+#[pyo3(name = "rolling_corr_matrix")]
+pub fn py_rolling_corr_matrix(
+    py: Python<'_>,
+    series: Vec<Vec<f64>>,
+    window: usize,
+) -> Vec<Vec<Vec<f64>>> {
+    let refs: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+    py.detach(|| optimize::rolling_corr_matrix(&refs, window))
+}
+
+/// Group assets into clusters of correlated return series.
+///
+/// Single linkage cut at ``threshold``: two assets share a cluster if a
+/// chain of pairs, each correlated at least ``threshold``, joins them.
+/// Picking one asset per cluster gives names whose pairwise correlations
+/// all stay below ``threshold``.
+///
+/// Args:
+///     series: One return series per asset, all the same length.
+///     threshold: Minimum correlation that links two assets.
+///
+/// Returns:
+///     List of clusters, each a sorted list of asset indices, ordered by
+///     lowest index. Empty on invalid input.
+///
+/// Example::
+///
+///     groups = nanobook.correlation_clusters([spy, qqq, tlt], 0.8)
+///     # [[0, 1], [2]]
+///
+#[pyfunction]
+#[pyo3(name = "correlation_clusters")]
+pub fn py_correlation_clusters(
+    py: Python<'_>,
+    series: Vec<Vec<f64>>,
+    threshold: f64,
+) -> Vec<Vec<usize>> {
+    let refs: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+    py.detach(|| optimize::correlation_clusters(&refs, threshold))
+}
+
+/// Ledoit-Wolf shrunk covariance matrix of per-asset return series.
+///
+/// Args:
+///     series: One return series per asset, all the same length.
+///
+/// Returns:
+///     Covariance matrix shrunk toward a scaled identity. Empty on invalid
+///     input.
+///
+#[pyfunction]
+#[pyo3(name = "cov_shrinkage")]
+pub fn py_cov_shrinkage(py: Python<'_>, series: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let refs: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+    py.detach(|| optimize::cov_shrinkage(&refs))
+}
+
+fn constraint(long_only: bool) -> WeightConstraint {
+    if long_only {
+        WeightConstraint::LongOnly
+    } else {
+        WeightConstraint::Unconstrained
+    }
+}
+
+fn keyed_weights(
+    py: Python<'_>,
+    symbols: &[String],
+    weights: Result<Vec<f64>, OptimizeError>,
+) -> PyResult<Py<PyAny>> {
+    let weights = weights.map_err(|e| PyValueError::new_err(e.to_string()))?;
+    if symbols.len() != weights.len() {
+        return Err(PyValueError::new_err(format!(
+            "expected {} symbols, got {}",
+            weights.len(),
+            symbols.len()
+        )));
+    }
+    Ok(to_weights_dict(py, symbols, weights)?.into_any().unbind())
+}
+
+/// Minimum-variance weights for a covariance matrix.
+///
+/// Args:
+///     cov: Square covariance matrix (e.g. from ``cov_shrinkage``).
+///     symbols: One name per covariance row.
+///     long_only: Forbid negative weights (default True).
+///
+/// Returns:
+///     Dict of symbol -> weight, summing to 1.
+///
+/// Raises:
+///     ValueError: On invalid input, or a singular covariance when
+///         ``long_only=False``.
+///
+#[pyfunction]
+#[pyo3(name = "min_variance_weights")]
+#[pyo3(signature = (cov, symbols, long_only=true))]
+pub fn py_min_variance_weights(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+    long_only: bool,
+) -> PyResult<Py<PyAny>> {
+    let w = py.detach(|| optimize::min_variance_weights(&cov, constraint(long_only)));
+    keyed_weights(py, &symbols, w)
+}
+
+/// Mean-variance weights maximizing ``mu·w - risk_aversion/2 · wᵀΣw``.
+///
+/// Args:
+///     cov: Square covariance matrix.
+///     mu: Expected return per asset.
+///     symbols: One name per covariance row.
+///     risk_aversion: Positive penalty on variance (default 1.0).
+///     long_only: Forbid negative weights (default True).
+///
+/// Returns:
+///     Dict of symbol -> weight, summing to 1.
+///
+/// Raises:
+///     ValueError: On invalid input, or a singular covariance when
+///         ``long_only=False``.
+///
+#[pyfunction]
+#[pyo3(name = "mean_variance_weights")]
+#[pyo3(signature = (cov, mu, symbols, risk_aversion=1.0, long_only=true))]
+pub fn py_mean_variance_weights(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    mu: Vec<f64>,
+    symbols: Vec<String>,
+    risk_aversion: f64,
+    long_only: bool,
+) -> PyResult<Py<PyAny>> {
+    let w = py.detach(|| {
+        optimize::mean_variance_weights(&cov, &mu, risk_aversion, constraint(long_only))
+    });
+    keyed_weights(py, &symbols, w)
+}
+
+/// Risk-parity (equal risk contribution) weights for a covariance matrix.
+///
+/// Stops once every asset's share of portfolio variance is within 1e-10 of
+/// ``1/n``, or after 10,000 coordinate-descent sweeps.
+///
+/// Args:
+///     cov: Square covariance matrix.
+///     symbols: One name per covariance row.
+///
+/// Returns:
+///     Dict with ``weights`` (symbol -> weight), ``dispersion`` (max
+///     deviation of a risk share from ``1/n``), ``iterations``, and
+///     ``converged``.
+///
+/// Raises:
+///     ValueError: On invalid input or a non-positive variance.
+///
+#[pyfunction]
+#[pyo3(name = "risk_parity_weights")]
+pub fn py_risk_parity_weights(
+    py: Python<'_>,
+    cov: Vec<Vec<f64>>,
+    symbols: Vec<String>,
+) -> PyResult<Py<PyAny>> {
+    let result = py
+        .detach(|| optimize::risk_parity_weights(&cov))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let weights = keyed_weights(py, &symbols, Ok(result.weights))?;
+    let out = PyDict::new(py);
+    out.set_item("weights", weights)?;
+    out.set_item("dispersion", result.dispersion)?;
+    out.set_item("iterations", result.iters)?;
+    out.set_item("converged", result.converged)?;
+    Ok(out.into_any().unbind())
+}
@@ -55,6 +55,24 @@ impl PyOrder {
         self.inner.timestamp
     }
 
+    /// Arrival sequence number; breaks ties between equal timestamps.
+    #[getter]
+    fn sequence(&self) -> u64 {
+        self.inner.sequence
+    }
+
+    /// Logical clock value when the order took its current queue priority.
+    #[getter]
+    fn queued_at(&self) -> u64 {
+        self.inner.queued_at
+    }
+
+    /// True for a hidden (non-displayed) order.
+    #[getter]
+    fn hidden(&self) -> bool {
+        self.inner.hidden
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Order(id={}, side={}, price={}, qty={}/{}, status={})",
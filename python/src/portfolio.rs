@@ -1,11 +1,17 @@
-use nanobook::portfolio::{CostModel, Portfolio};
+use nanobook::portfolio::{
+    CostBasis, CostModel, DuplicatePolicy, Portfolio, PortfolioSnapshot, RebalanceOrder,
+    WeightPolicy, merge_duplicates, shares_to_weights, weights_to_shares,
+};
+use nanobook::{Liquidity, OrderId, Price, Symbol, Trade, TradeId};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-use crate::metrics::PyMetrics;
+use crate::metrics::{PyMetrics, metrics_for};
 use crate::multi::PyMultiExchange;
 use crate::position::PyPosition;
-use crate::types::parse_symbol;
+use crate::results::PyTrade;
+use crate::types::{format_price, parse_side, parse_symbol};
 
 /// Transaction cost model.
 ///
@@ -13,6 +19,13 @@ use crate::types::parse_symbol;
 ///     commission_bps: Commission in basis points (1 bps = 0.01%)
 ///     slippage_bps: Slippage estimate in basis points
 ///     min_trade_fee: Minimum fee per trade in cents
+///     maker_bps: Extra fee in basis points for passive (maker) fills;
+///         negative for a rebate
+///     taker_bps: Extra fee in basis points for aggressive (taker) fills
+///     max_trade_fee: Maximum fee per trade in cents (None for no cap)
+///     per_share_fee: Commission per share in cents, added to the bps cost
+///     half_spread: Charge half the quoted spread per share on
+///         ``Portfolio.rebalance_lob`` fills instead of ``slippage_bps``
 ///
 /// Example::
 ///
@@ -28,14 +41,28 @@ pub struct PyCostModel {
 #[pymethods]
 impl PyCostModel {
     #[new]
-    #[pyo3(signature = (commission_bps=0, slippage_bps=0, min_trade_fee=0))]
-    fn new(commission_bps: u32, slippage_bps: u32, min_trade_fee: i64) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (commission_bps=0, slippage_bps=0, min_trade_fee=0, maker_bps=0, taker_bps=0, max_trade_fee=None, per_share_fee=0, half_spread=false))]
+    fn new(
+        commission_bps: u32,
+        slippage_bps: u32,
+        min_trade_fee: i64,
+        maker_bps: i32,
+        taker_bps: u32,
+        max_trade_fee: Option<i64>,
+        per_share_fee: i64,
+        half_spread: bool,
+    ) -> Self {
         Self {
-            inner: CostModel {
-                commission_bps,
-                slippage_bps,
-                min_trade_fee,
-            },
+            inner: CostModel::zero()
+                .with_commission_bps(commission_bps)
+                .with_slippage_bps(slippage_bps)
+                .with_min_trade_fee(min_trade_fee)
+                .with_max_trade_fee(max_trade_fee.unwrap_or(i64::MAX))
+                .with_maker_bps(maker_bps)
+                .with_taker_bps(taker_bps)
+                .with_per_share_fee(per_share_fee)
+                .with_half_spread(half_spread),
         }
     }
 
@@ -47,24 +74,115 @@ impl PyCostModel {
         }
     }
 
+    /// Copy with the fixed fees converted between price scales.
+    ///
+    /// ``min_trade_fee``, ``max_trade_fee``, and ``per_share_fee`` are
+    /// rescaled from ``from_scale`` to ``to_scale`` price units per
+    /// currency unit (e.g. 100 to 10000), rounded to the nearest unit.
+    fn rescaled(&self, from_scale: i64, to_scale: i64) -> PyResult<Self> {
+        if from_scale <= 0 || to_scale <= 0 {
+            return Err(PyValueError::new_err("price scales must be > 0"));
+        }
+        Ok(Self {
+            inner: self.inner.rescaled(from_scale, to_scale),
+        })
+    }
+
     /// Compute cost for a trade with the given notional value (cents).
-    fn compute_cost(&self, notional: i64) -> i64 {
-        self.inner.compute_cost(notional)
+    ///
+    /// Pass the share ``quantity`` to include ``per_share_fee``.
+    #[pyo3(signature = (notional, quantity=0))]
+    fn compute_cost(&self, notional: i64, quantity: i64) -> i64 {
+        self.inner.compute_cost_with_quantity(notional, quantity)
+    }
+
+    /// Compute cost for a fill with the given notional value (cents).
+    ///
+    /// Args:
+    ///     notional: Trade notional in cents
+    ///     liquidity: "maker" (passive fill) or "taker" (aggressive fill)
+    ///     quantity: Shares traded, for ``per_share_fee``
+    #[pyo3(signature = (notional, liquidity, quantity=0))]
+    fn compute_fill_cost(&self, notional: i64, liquidity: &str, quantity: i64) -> PyResult<i64> {
+        Ok(self.inner.compute_fill_cost_with_quantity(
+            notional,
+            quantity,
+            parse_liquidity(liquidity)?,
+        ))
+    }
+
+    /// Compute the cost of a taker fill that pays half the quoted
+    /// ``spread`` (cents) per share in place of ``slippage_bps``.
+    fn compute_spread_cost(&self, notional: i64, quantity: i64, spread: i64) -> i64 {
+        self.inner.compute_spread_cost(notional, quantity, spread)
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "CostModel(commission_bps={}, slippage_bps={}, min_trade_fee={})",
-            self.inner.commission_bps, self.inner.slippage_bps, self.inner.min_trade_fee
+            "CostModel(commission_bps={}, slippage_bps={}, min_trade_fee={}, max_trade_fee={}, maker_bps={}, taker_bps={}, per_share_fee={}, half_spread={})",
+            self.inner.commission_bps,
+            self.inner.slippage_bps,
+            self.inner.min_trade_fee,
+            self.inner.max_trade_fee,
+            self.inner.maker_bps,
+            self.inner.taker_bps,
+            self.inner.per_share_fee,
+            if self.inner.half_spread {
+                "True"
+            } else {
+                "False"
+            }
         )
     }
 }
 
+fn parse_liquidity(s: &str) -> PyResult<Liquidity> {
+    match s.to_ascii_lowercase().as_str() {
+        "maker" => Ok(Liquidity::Maker),
+        "taker" => Ok(Liquidity::Taker),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid liquidity '{s}'. Use 'maker' or 'taker'."
+        ))),
+    }
+}
+
+fn parse_cost_basis(s: &str) -> PyResult<CostBasis> {
+    match s.to_ascii_lowercase().as_str() {
+        "average" => Ok(CostBasis::AverageCost),
+        "fifo" => Ok(CostBasis::Fifo),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid cost_basis '{s}'. Use 'average' or 'fifo'."
+        ))),
+    }
+}
+
 /// Portfolio: tracks cash, positions, and returns.
 ///
 /// Args:
 ///     initial_cash: Starting cash in cents (e.g., 1_000_000_00 = $1M)
 ///     cost_model: A CostModel instance
+///     incentive_fee: Fee rate on equity above the high-water mark and
+///         hurdle, charged at each record_return (0.2 = 20%)
+///     hurdle: Return over the high-water mark required before the
+///         incentive fee accrues (0.05 = 5%)
+///     cash_yield: Per-period interest credited on idle cash at each
+///         record_return (0.0001 = 1bp)
+///     cost_basis: "average" (average-cost) or "fifo" (realize PnL
+///         against the oldest lots first)
+///     normalize_gross: Rescale rebalance targets so their gross exposure
+///         (sum of absolute weights) equals this value
+///     max_gross: Raise ValueError from rebalances whose targets' gross
+///         exposure exceeds this leverage cap
+///     duplicates: "error" (raise ValueError on a symbol listed twice in
+///         rebalance targets) or "sum" (add its weights up). Duplicate
+///         prices always raise.
+///     price_scale: Price units per whole currency unit (100 = cents)
+///     currencies: Dict of symbol -> quote currency, used by the ``_fx``
+///         methods; unlisted symbols are in the base currency
+///     rebalance_order: "sells_first" (rebalance_simple sells before it
+///         buys) or "as_listed" (closes, then targets in the given order)
+///     leverage: Let rebalance_simple buy past the cash on hand instead of
+///         capping buys and reporting the shortfall
 ///
 /// Example::
 ///
@@ -81,15 +199,80 @@ impl PyPortfolio {
     pub fn from_portfolio(inner: Portfolio) -> Self {
         Self { inner }
     }
+
+    /// Parse rebalance targets, raising ValueError where the duplicate or
+    /// weight policy rejects them.
+    fn parse_targets(&self, targets: &[(String, f64)]) -> PyResult<Vec<(Symbol, f64)>> {
+        let targets = parse_pairs(targets)?;
+        self.inner
+            .check_targets(&targets)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(targets)
+    }
 }
 
 #[pymethods]
 impl PyPortfolio {
     #[new]
-    fn new(initial_cash: i64, cost_model: &PyCostModel) -> Self {
-        Self {
-            inner: Portfolio::new(initial_cash, cost_model.inner),
+    #[pyo3(signature = (initial_cash, cost_model, incentive_fee=0.0, hurdle=0.0, cash_yield=0.0, cost_basis="average", normalize_gross=None, max_gross=None, duplicates="error", price_scale=100, currencies=None, rebalance_order="sells_first", leverage=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        initial_cash: i64,
+        cost_model: &PyCostModel,
+        incentive_fee: f64,
+        hurdle: f64,
+        cash_yield: f64,
+        cost_basis: &str,
+        normalize_gross: Option<f64>,
+        max_gross: Option<f64>,
+        duplicates: &str,
+        price_scale: i64,
+        currencies: Option<std::collections::HashMap<String, String>>,
+        rebalance_order: &str,
+        leverage: bool,
+    ) -> PyResult<Self> {
+        if price_scale <= 0 {
+            return Err(PyValueError::new_err("price_scale must be > 0"));
+        }
+        let cost_basis = parse_cost_basis(cost_basis)?;
+        let duplicate_policy = parse_duplicate_policy(duplicates)?;
+        let rebalance_order = parse_rebalance_order(rebalance_order)?;
+        let weight_policy = match (normalize_gross, max_gross) {
+            (None, None) => WeightPolicy::AsGiven,
+            (Some(gross), None) => WeightPolicy::Normalize { gross },
+            (None, Some(max_gross)) => WeightPolicy::Strict { max_gross },
+            (Some(_), Some(_)) => {
+                return Err(PyValueError::new_err(
+                    "pass at most one of normalize_gross and max_gross",
+                ));
+            }
+        };
+        if !(0.0..=1.0).contains(&incentive_fee) {
+            return Err(PyValueError::new_err("incentive_fee must be in [0, 1]"));
+        }
+        if hurdle.is_nan() || hurdle < 0.0 {
+            return Err(PyValueError::new_err("hurdle must be non-negative"));
         }
+        if !cash_yield.is_finite() || cash_yield < 0.0 {
+            return Err(PyValueError::new_err("cash_yield must be non-negative"));
+        }
+        let currencies = currencies
+            .unwrap_or_default()
+            .iter()
+            .map(|(sym, ccy)| Ok((parse_symbol(sym)?, parse_symbol(ccy)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Self {
+            inner: Portfolio::new(initial_cash, cost_model.inner)
+                .with_incentive_fee(incentive_fee, hurdle)
+                .with_cash_yield(cash_yield)
+                .with_cost_basis(cost_basis)
+                .with_weight_policy(weight_policy)
+                .with_duplicate_policy(duplicate_policy)
+                .with_price_scale(price_scale)
+                .with_currencies(currencies)
+                .with_rebalance_order(rebalance_order)
+                .with_leverage(leverage),
+        })
     }
 
     /// Current cash balance in cents.
@@ -98,6 +281,68 @@ impl PyPortfolio {
         self.inner.cash()
     }
 
+    /// Price units per whole currency unit (100 = cents).
+    #[getter]
+    fn price_scale(&self) -> i64 {
+        self.inner.price_scale()
+    }
+
+    /// Convert an amount in price units (cash, equity, a cost) to whole
+    /// currency units, e.g. ``to_currency(p.cash)``.
+    fn to_currency(&self, amount: i64) -> f64 {
+        self.inner.to_currency(amount)
+    }
+
+    /// Total incentive fees debited so far in cents.
+    #[getter]
+    fn incentive_fees(&self) -> i64 {
+        self.inner.incentive_fees()
+    }
+
+    /// High-water mark for incentive fees in cents.
+    #[getter]
+    fn high_water_mark(&self) -> i64 {
+        self.inner.high_water_mark()
+    }
+
+    /// Total interest credited on idle cash in cents.
+    #[getter]
+    fn cash_interest(&self) -> i64 {
+        self.inner.cash_interest()
+    }
+
+    /// Cumulative one-way turnover from rebalances, in units of equity.
+    #[getter]
+    fn turnover(&self) -> f64 {
+        self.inner.turnover()
+    }
+
+    /// Average periods closed quantity was held across all positions, or
+    /// None if nothing has been closed. Periods are counted by
+    /// ``record_return``.
+    #[getter]
+    fn average_holding_period(&self) -> Option<f64> {
+        self.inner.average_holding_period()
+    }
+
+    /// Realized PnL summed over every position, closed ones included
+    /// (cents).
+    #[getter]
+    fn realized_pnl(&self) -> i64 {
+        self.inner.realized_pnl()
+    }
+
+    /// Unrealized PnL of the open positions, summed (cents).
+    ///
+    /// Held symbols missing from ``prices`` contribute zero.
+    ///
+    /// Args:
+    ///     prices: List of (symbol, price_in_cents) tuples
+    fn unrealized_pnl(&self, prices: Vec<(String, i64)>) -> PyResult<i64> {
+        let prices = parse_price_list(&prices)?;
+        Ok(self.inner.unrealized_pnl(&prices))
+    }
+
     /// Get a position by symbol.
     fn position(&self, symbol: &str) -> PyResult<Option<PyPosition>> {
         let sym = parse_symbol(symbol)?;
@@ -125,6 +370,48 @@ impl PyPortfolio {
         Ok(self.inner.total_equity(&prices))
     }
 
+    /// Quote currency of ``symbol`` (None for the base currency).
+    fn currency(&self, symbol: &str) -> PyResult<Option<String>> {
+        let symbol = parse_symbol(symbol)?;
+        Ok(self.inner.currency(&symbol).map(|c| c.as_str().to_string()))
+    }
+
+    /// Total equity in the base currency.
+    ///
+    /// Each position's value is converted at the rate of its symbol's
+    /// currency (see ``currencies``); missing rates count as 1.0.
+    ///
+    /// Args:
+    ///     prices: List of (symbol, price_in_cents) tuples
+    ///     fx_rates: List of (currency, base_per_unit) tuples
+    fn total_equity_fx(
+        &self,
+        prices: Vec<(String, i64)>,
+        fx_rates: Vec<(String, f64)>,
+    ) -> PyResult<i64> {
+        let prices = parse_price_list(&prices)?;
+        let fx_rates = reject_duplicates(parse_pairs(&fx_rates)?, "fx_rates")?;
+        Ok(self.inner.total_equity_fx(&prices, &fx_rates))
+    }
+
+    /// Current weights from base-currency values.
+    ///
+    /// Returns list of (symbol, weight) tuples.
+    fn current_weights_fx(
+        &self,
+        prices: Vec<(String, i64)>,
+        fx_rates: Vec<(String, f64)>,
+    ) -> PyResult<Vec<(String, f64)>> {
+        let prices = parse_price_list(&prices)?;
+        let fx_rates = reject_duplicates(parse_pairs(&fx_rates)?, "fx_rates")?;
+        Ok(self
+            .inner
+            .current_weights_fx(&prices, &fx_rates)
+            .into_iter()
+            .map(|(sym, w)| (sym.as_str().to_string(), w))
+            .collect())
+    }
+
     /// Current portfolio weights.
     ///
     /// Returns list of (symbol, weight) tuples.
@@ -138,6 +425,22 @@ impl PyPortfolio {
             .collect())
     }
 
+    /// Gross exposure: sum of absolute position values / equity.
+    ///
+    /// Returns 0.0 when there are no positions or equity is zero.
+    fn gross_exposure(&self, prices: Vec<(String, i64)>) -> PyResult<f64> {
+        let prices = parse_price_list(&prices)?;
+        Ok(self.inner.gross_exposure(&prices))
+    }
+
+    /// Net exposure: sum of signed position values / equity.
+    ///
+    /// Returns 0.0 when there are no positions or equity is zero.
+    fn net_exposure(&self, prices: Vec<(String, i64)>) -> PyResult<f64> {
+        let prices = parse_price_list(&prices)?;
+        Ok(self.inner.net_exposure(&prices))
+    }
+
     /// Get the return series.
     fn returns(&self) -> Vec<f64> {
         self.inner.returns().to_vec()
@@ -152,29 +455,185 @@ impl PyPortfolio {
     ///
     /// Args:
     ///     targets: List of (symbol, weight) tuples. Weights should sum to <= 1.0.
-    ///     prices: List of (symbol, price_in_cents) tuples.
+    ///     prices: List of (symbol, price_in_cents) tuples used to value the
+    ///         portfolio.
+    ///     fill_prices: Optional (symbol, price_in_cents) tuples to execute at
+    ///         (e.g. next bar's open). Symbols not listed fill at ``prices``.
+    ///
+    /// Returns:
+    ///     List of (symbol, requested, filled, price) tuples, one per buy
+    ///     cut short because cash ran out. Empty with ``leverage=True``.
+    #[pyo3(signature = (targets, prices, fill_prices=None))]
     fn rebalance_simple(
         &mut self,
         targets: Vec<(String, f64)>,
         prices: Vec<(String, i64)>,
-    ) -> PyResult<()> {
-        let targets = parse_target_list(&targets)?;
+        fill_prices: Option<Vec<(String, i64)>>,
+    ) -> PyResult<Vec<(String, i64, i64, i64)>> {
+        let targets = self.parse_targets(&targets)?;
         let prices = parse_price_list(&prices)?;
-        self.inner.rebalance_simple(&targets, &prices);
-        Ok(())
+        let shortfalls = match fill_prices {
+            Some(fill_prices) => {
+                let fill_prices = parse_price_list(&fill_prices)?;
+                self.inner
+                    .rebalance_simple_with_fill_prices(&targets, &prices, &fill_prices)
+            }
+            None => self.inner.rebalance_simple(&targets, &prices),
+        };
+        Ok(shortfalls
+            .into_iter()
+            .map(|s| (s.symbol.to_string(), s.requested, s.filled, s.price))
+            .collect())
+    }
+
+    /// Rebalance with ``prices`` in each symbol's own currency.
+    ///
+    /// Each price is converted to the base currency at its currency's rate
+    /// before sizing and filling, so cash is spent in the base currency.
+    /// Other trading methods take base-currency prices.
+    ///
+    /// Args:
+    ///     targets: List of (symbol, weight) tuples
+    ///     prices: List of (symbol, local_price) tuples
+    ///     fx_rates: List of (currency, base_per_unit) tuples
+    ///
+    /// Returns:
+    ///     Shortfalls as in ``rebalance_simple``.
+    fn rebalance_simple_fx(
+        &mut self,
+        targets: Vec<(String, f64)>,
+        prices: Vec<(String, i64)>,
+        fx_rates: Vec<(String, f64)>,
+    ) -> PyResult<Vec<(String, i64, i64, i64)>> {
+        let targets = self.parse_targets(&targets)?;
+        let prices = parse_price_list(&prices)?;
+        let fx_rates = reject_duplicates(parse_pairs(&fx_rates)?, "fx_rates")?;
+        Ok(self
+            .inner
+            .rebalance_simple_fx(&targets, &prices, &fx_rates)
+            .into_iter()
+            .map(|s| (s.symbol.to_string(), s.requested, s.filled, s.price))
+            .collect())
+    }
+
+    /// Rebalance toward targets, trading only where the tracking-error
+    /// reduction justifies the cost.
+    ///
+    /// Uses a no-trade band of half-width `(commission_bps + slippage_bps)
+    /// / 10_000 / aversion` around each target and trades only to the band
+    /// edge. Trades whose penalty reduction is below the charged cost
+    /// (including `min_trade_fee`) are skipped. Costs come from the
+    /// portfolio's own cost model, which also charges the fills; sells run
+    /// before buys unless the rebalance order is "as_listed".
+    ///
+    /// Args:
+    ///     targets: List of (symbol, weight) tuples. Missing held symbols target 0.
+    ///     prices: List of (symbol, price_in_cents) tuples.
+    ///     aversion: Tracking-error aversion; larger trades more.
+    ///
+    /// Returns:
+    ///     List of (symbol, quantity, price, cost) for the fills made.
+    fn rebalance_cost_aware(
+        &mut self,
+        targets: Vec<(String, f64)>,
+        prices: Vec<(String, i64)>,
+        aversion: f64,
+    ) -> PyResult<Vec<(String, i64, i64, i64)>> {
+        let targets = self.parse_targets(&targets)?;
+        let prices = parse_price_list(&prices)?;
+        Ok(self
+            .inner
+            .rebalance_cost_aware(&targets, &prices, aversion)
+            .into_iter()
+            .map(|f| (f.symbol.to_string(), f.quantity, f.price, f.cost))
+            .collect())
+    }
+
+    /// Apply one exchange trade in which ``order_id`` took part.
+    ///
+    /// Charges the maker rate if ``order_id`` was the resting order and the
+    /// taker rate if it was the aggressor.
+    ///
+    /// Returns:
+    ///     The cost charged in cents, or None if ``order_id`` is not in the trade.
+    fn apply_trade(
+        &mut self,
+        symbol: &str,
+        trade: &PyTrade,
+        order_id: u64,
+    ) -> PyResult<Option<i64>> {
+        let sym = parse_symbol(symbol)?;
+        let trade = Trade::new(
+            TradeId(trade.trade_id),
+            Price(trade.price),
+            trade.quantity,
+            OrderId(trade.aggressor_order_id),
+            OrderId(trade.passive_order_id),
+            parse_side(&trade.aggressor_side)?,
+            trade.timestamp,
+        );
+        Ok(self.inner.apply_trade(sym, &trade, OrderId(order_id)))
     }
 
     /// Rebalance through LOB matching engines.
+    ///
+    /// Args:
+    ///     targets: List of (symbol, weight) pairs
+    ///     exchanges: MultiExchange holding the books
+    ///     fill_ratio: Fraction of each order's marketable quantity to fill,
+    ///         in (0, 1]; the rest is not traded
+    #[pyo3(signature = (targets, exchanges, fill_ratio=1.0))]
     fn rebalance_lob(
         &mut self,
         targets: Vec<(String, f64)>,
         exchanges: &mut PyMultiExchange,
+        fill_ratio: f64,
     ) -> PyResult<()> {
-        let targets = parse_target_list(&targets)?;
-        self.inner.rebalance_lob(&targets, &mut exchanges.inner);
+        if !(fill_ratio > 0.0 && fill_ratio <= 1.0) {
+            return Err(PyValueError::new_err("fill_ratio must be in (0, 1]"));
+        }
+        let targets = self.parse_targets(&targets)?;
+        self.inner
+            .rebalance_lob_with_fill_ratio(&targets, &mut exchanges.inner, fill_ratio);
         Ok(())
     }
 
+    /// Estimate the cost of ``rebalance_lob`` without trading.
+    ///
+    /// Returns a dict with ``slippage``, ``commission``, and ``total``
+    /// (cents) plus ``symbols``: symbol -> dict of ``quantity``,
+    /// ``filled_quantity``, ``reference_price``, ``vwap``, ``slippage``,
+    /// and ``commission``. Neither the books nor the portfolio change.
+    fn estimate_rebalance_cost(
+        &self,
+        py: Python<'_>,
+        targets: Vec<(String, f64)>,
+        exchanges: &PyMultiExchange,
+    ) -> PyResult<Py<PyAny>> {
+        let targets = self.parse_targets(&targets)?;
+        let est = self
+            .inner
+            .estimate_rebalance_cost(&targets, &exchanges.inner);
+
+        let dict = PyDict::new(py);
+        dict.set_item("slippage", est.slippage)?;
+        dict.set_item("commission", est.commission)?;
+        dict.set_item("total", est.total())?;
+        let symbols = PyDict::new(py);
+        for s in &est.symbols {
+            let entry = PyDict::new(py);
+            entry.set_item("quantity", s.quantity)?;
+            entry.set_item("filled_quantity", s.filled_quantity)?;
+            entry.set_item("reference_price", s.reference_price)?;
+            entry.set_item("vwap", s.vwap)?;
+            entry.set_item("slippage", s.slippage)?;
+            entry.set_item("commission", s.commission)?;
+            symbols.set_item(s.symbol.to_string(), entry)?;
+        }
+        dict.set_item("symbols", symbols)?;
+        Ok(dict.into_any().unbind())
+    }
+
     /// Record a return for the current period.
     fn record_return(&mut self, prices: Vec<(String, i64)>) -> PyResult<()> {
         let prices = parse_price_list(&prices)?;
@@ -182,34 +641,64 @@ impl PyPortfolio {
         Ok(())
     }
 
+    /// Record a return for the current period with positions valued in
+    /// the base currency at ``fx_rates`` (list of (currency, rate)).
+    fn record_return_fx(
+        &mut self,
+        prices: Vec<(String, i64)>,
+        fx_rates: Vec<(String, f64)>,
+    ) -> PyResult<()> {
+        let prices = parse_price_list(&prices)?;
+        let fx_rates = reject_duplicates(parse_pairs(&fx_rates)?, "fx_rates")?;
+        self.inner.record_return_fx(&prices, &fx_rates);
+        Ok(())
+    }
+
     /// Take a portfolio snapshot.
+    ///
+    /// Returns a dict with ``cash``, ``equity``, ``num_positions``,
+    /// ``total_realized_pnl``, ``average_holding_period``, ``weights``
+    /// (symbol -> weight), and ``positions`` (symbol -> dict of
+    /// ``quantity``, ``avg_entry_price``, ``unrealized_pnl``, ``weight``,
+    /// and ``holding_period``).
     fn snapshot(&self, py: Python<'_>, prices: Vec<(String, i64)>) -> PyResult<Py<PyAny>> {
         let prices = parse_price_list(&prices)?;
-        let snap = self.inner.snapshot(&prices);
-
-        let dict = PyDict::new(py);
-        dict.set_item("cash", snap.cash)?;
-        dict.set_item("equity", snap.equity)?;
-        dict.set_item("num_positions", snap.num_positions)?;
-        dict.set_item("total_realized_pnl", snap.total_realized_pnl)?;
-
-        let weights = PyDict::new(py);
-        for (sym, w) in snap.weights {
-            weights.set_item(sym.to_string(), w)?;
-        }
-        dict.set_item("weights", weights)?;
-
-        Ok(dict.into_any().unbind())
+        snapshot_to_dict(py, &self.inner.snapshot(&prices))
     }
 
     /// Compute metrics from the recorded return series.
     ///
+    /// Same arguments and defaults as the module-level ``py_compute_metrics``,
+    /// which it matches exactly on ``returns()``.
+    ///
     /// Args:
     ///     periods_per_year: Annualization factor (252 for daily, 12 for monthly)
     ///     risk_free: Risk-free rate per period
-    fn compute_metrics(&self, periods_per_year: f64, risk_free: f64) -> Option<PyMetrics> {
-        nanobook::portfolio::compute_metrics(self.inner.returns(), periods_per_year, risk_free)
-            .map(PyMetrics::from)
+    ///     minimum_acceptable_return: Sortino target per period (default: risk_free)
+    ///     calmar_lookback: Trailing periods for Calmar's CAGR and drawdown
+    ///
+    /// Returns:
+    ///     Metrics object, or None if no returns have been recorded
+    #[pyo3(signature = (
+        periods_per_year=252.0,
+        risk_free=0.0,
+        minimum_acceptable_return=None,
+        calmar_lookback=None,
+    ))]
+    fn compute_metrics(
+        &self,
+        periods_per_year: f64,
+        risk_free: f64,
+        minimum_acceptable_return: Option<f64>,
+        calmar_lookback: Option<usize>,
+    ) -> Option<PyMetrics> {
+        metrics_for(
+            self.inner.returns(),
+            periods_per_year,
+            risk_free,
+            minimum_acceptable_return,
+            calmar_lookback,
+        )
     }
 
     /// Save portfolio state to a JSON file.
@@ -229,25 +718,137 @@ impl PyPortfolio {
 
     fn __repr__(&self) -> String {
         format!(
-            "Portfolio(cash=${:.2}, returns={})",
-            self.inner.cash() as f64 / 100.0,
+            "Portfolio(cash={}, returns={})",
+            format_price(Price(self.inner.cash()), self.inner.price_scale()),
             self.inner.returns().len()
         )
     }
 }
 
-/// Parse Python list of (str, i64) into Vec<(Symbol, i64)>.
-fn parse_price_list(prices: &[(String, i64)]) -> PyResult<Vec<(nanobook::Symbol, i64)>> {
-    prices
+/// Convert a [`PortfolioSnapshot`] into the dict returned by
+/// `Portfolio.snapshot` and `BacktestResult.history`.
+pub(crate) fn snapshot_to_dict(py: Python<'_>, snap: &PortfolioSnapshot) -> PyResult<Py<PyAny>> {
+    let dict = PyDict::new(py);
+    dict.set_item("cash", snap.cash)?;
+    dict.set_item("equity", snap.equity)?;
+    dict.set_item("num_positions", snap.num_positions)?;
+    dict.set_item("total_realized_pnl", snap.total_realized_pnl)?;
+    dict.set_item("average_holding_period", snap.average_holding_period)?;
+
+    let weights = PyDict::new(py);
+    for (sym, w) in &snap.weights {
+        weights.set_item(sym.to_string(), w)?;
+    }
+    dict.set_item("weights", weights)?;
+
+    let positions = PyDict::new(py);
+    for pos in &snap.positions {
+        let entry = PyDict::new(py);
+        entry.set_item("quantity", pos.quantity)?;
+        entry.set_item("avg_entry_price", pos.avg_entry_price)?;
+        entry.set_item("unrealized_pnl", pos.unrealized_pnl)?;
+        entry.set_item("weight", pos.weight)?;
+        entry.set_item("holding_period", pos.holding_period)?;
+        positions.set_item(pos.symbol.to_string(), entry)?;
+    }
+    dict.set_item("positions", positions)?;
+
+    Ok(dict.into_any().unbind())
+}
+
+/// Parse Python list of (str, value) pairs into Vec<(Symbol, value)>.
+fn parse_pairs<T: Copy>(pairs: &[(String, T)]) -> PyResult<Vec<(Symbol, T)>> {
+    pairs
         .iter()
-        .map(|(s, p)| Ok((parse_symbol(s)?, *p)))
+        .map(|(s, v)| Ok((parse_symbol(s)?, *v)))
         .collect()
 }
 
+/// Raise ValueError if a symbol appears twice in `pairs`.
+fn reject_duplicates<T: Copy + std::ops::AddAssign>(
+    pairs: Vec<(Symbol, T)>,
+    what: &str,
+) -> PyResult<Vec<(Symbol, T)>> {
+    match merge_duplicates(&pairs, DuplicatePolicy::Reject) {
+        Ok(_) => Ok(pairs),
+        Err(sym) => Err(PyValueError::new_err(format!(
+            "duplicate symbol {sym} in {what}"
+        ))),
+    }
+}
+
+/// Parse Python list of (str, i64) into Vec<(Symbol, i64)>.
+fn parse_price_list(prices: &[(String, i64)]) -> PyResult<Vec<(Symbol, i64)>> {
+    reject_duplicates(parse_pairs(prices)?, "prices")
+}
+
 /// Parse Python list of (str, f64) into Vec<(Symbol, f64)>.
-fn parse_target_list(targets: &[(String, f64)]) -> PyResult<Vec<(nanobook::Symbol, f64)>> {
-    targets
-        .iter()
-        .map(|(s, w)| Ok((parse_symbol(s)?, *w)))
-        .collect()
+fn parse_target_list(targets: &[(String, f64)]) -> PyResult<Vec<(Symbol, f64)>> {
+    reject_duplicates(parse_pairs(targets)?, "targets")
+}
+
+/// Parse a duplicate policy name.
+fn parse_duplicate_policy(duplicates: &str) -> PyResult<DuplicatePolicy> {
+    match duplicates {
+        "error" => Ok(DuplicatePolicy::Reject),
+        "sum" => Ok(DuplicatePolicy::Sum),
+        other => Err(PyValueError::new_err(format!(
+            "unknown duplicates policy: {other:?} (expected \"error\" or \"sum\")"
+        ))),
+    }
+}
+
+/// Parse a rebalance order name.
+fn parse_rebalance_order(order: &str) -> PyResult<RebalanceOrder> {
+    match order {
+        "sells_first" => Ok(RebalanceOrder::SellsFirst),
+        "as_listed" => Ok(RebalanceOrder::AsListed),
+        other => Err(PyValueError::new_err(format!(
+            "unknown rebalance order: {other:?} (expected \"sells_first\" or \"as_listed\")"
+        ))),
+    }
+}
+
+/// Convert target weights into share counts.
+///
+/// Share counts are ``equity * weight / price`` truncated toward zero to a
+/// multiple of ``lot_size``; with ``lot_size=1`` they match what
+/// ``Portfolio.rebalance_simple`` buys from cash. Symbols without a
+/// positive price are skipped.
+#[pyfunction]
+#[pyo3(name = "weights_to_shares")]
+#[pyo3(signature = (weights, equity, prices, lot_size=1))]
+pub fn py_weights_to_shares(
+    weights: Vec<(String, f64)>,
+    equity: i64,
+    prices: Vec<(String, i64)>,
+    lot_size: u64,
+) -> PyResult<Vec<(String, i64)>> {
+    if lot_size == 0 {
+        return Err(PyValueError::new_err("lot_size must be positive"));
+    }
+    let weights = parse_target_list(&weights)?;
+    let prices = parse_price_list(&prices)?;
+    Ok(weights_to_shares(&weights, equity, &prices, lot_size)
+        .into_iter()
+        .map(|(sym, qty)| (sym.to_string(), qty))
+        .collect())
+}
+
+/// Convert share counts into weights of the positions' net market value.
+///
+/// Symbols without a price are skipped; returns an empty list if the net
+/// value is not positive.
+#[pyfunction]
+#[pyo3(name = "shares_to_weights")]
+pub fn py_shares_to_weights(
+    positions: Vec<(String, i64)>,
+    prices: Vec<(String, i64)>,
+) -> PyResult<Vec<(String, f64)>> {
+    let positions = parse_price_list(&positions)?;
+    let prices = parse_price_list(&prices)?;
+    Ok(shares_to_weights(&positions, &prices)
+        .into_iter()
+        .map(|(sym, w)| (sym.to_string(), w))
+        .collect())
 }
@@ -1,4 +1,4 @@
-use nanobook::portfolio::Position;
+use nanobook::portfolio::{CostBasis, Position};
 use pyo3::prelude::*;
 
 #[pyclass(name = "Position")]
@@ -34,6 +34,38 @@ impl PyPosition {
         self.inner.realized_pnl
     }
 
+    /// Average number of periods the open quantity has been held.
+    #[getter]
+    fn holding_period(&self) -> f64 {
+        self.inner.holding_period()
+    }
+
+    /// Average periods closed quantity was held, or None if none closed.
+    #[getter]
+    fn closed_holding_period(&self) -> Option<f64> {
+        self.inner.closed_holding_period()
+    }
+
+    /// Accounting method: "average" or "fifo".
+    #[getter]
+    fn cost_basis(&self) -> &'static str {
+        match self.inner.cost_basis {
+            CostBasis::AverageCost => "average",
+            CostBasis::Fifo => "fifo",
+        }
+    }
+
+    /// Open FIFO lots, oldest first, as (signed quantity, price) tuples.
+    /// Empty under average-cost accounting.
+    #[getter]
+    fn lots(&self) -> Vec<(i64, i64)> {
+        self.inner
+            .lots
+            .iter()
+            .map(|l| (l.quantity, l.price))
+            .collect()
+    }
+
     fn unrealized_pnl(&self, price: i64) -> i64 {
         self.inner.unrealized_pnl(price)
     }
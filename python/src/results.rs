@@ -1,6 +1,6 @@
-use crate::metrics::PyMetrics;
-use crate::portfolio::PyPortfolio;
-use crate::types::{price_to_float, side_str};
+use crate::metrics::{PyMetrics, PyRelativeMetrics};
+use crate::portfolio::{PyPortfolio, snapshot_to_dict};
+use crate::types::{format_price, price_to_float, side_str};
 use pyo3::prelude::*;
 
 /// Result of a backtest run.
@@ -11,10 +11,28 @@ pub struct PyBacktestResult {
     pub portfolio: PyPortfolio,
     #[pyo3(get)]
     pub metrics: Option<PyMetrics>,
+    #[pyo3(get)]
+    pub turnover: f64,
+    #[pyo3(get)]
+    pub relative_metrics: Option<PyRelativeMetrics>,
+    #[pyo3(get)]
+    pub warmup_bars: usize,
+    pub history: Vec<nanobook::portfolio::PortfolioSnapshot>,
 }
 
 #[pymethods]
 impl PyBacktestResult {
+    /// Per-bar snapshot dicts (same keys as ``Portfolio.snapshot``).
+    ///
+    /// Empty unless the backtest ran with ``record_history=True``.
+    #[getter]
+    fn history(&self, py: Python<'_>) -> PyResult<Vec<Py<PyAny>>> {
+        self.history
+            .iter()
+            .map(|snap| snapshot_to_dict(py, snap))
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "BacktestResult(returns={}, sharpe={:?})",
@@ -29,6 +47,10 @@ impl From<nanobook::portfolio::BacktestResult> for PyBacktestResult {
         Self {
             portfolio: PyPortfolio::from_portfolio(r.portfolio),
             metrics: r.metrics.map(PyMetrics::from),
+            turnover: r.turnover,
+            relative_metrics: r.relative_metrics.map(PyRelativeMetrics::from),
+            warmup_bars: r.warmup_bars,
+            history: r.history,
         }
     }
 }
@@ -47,6 +69,22 @@ pub struct PySubmitResult {
     pub resting_quantity: u64,
     #[pyo3(get)]
     pub cancelled_quantity: u64,
+    #[pyo3(get)]
+    pub average_fill_price: Option<f64>,
+    #[pyo3(get)]
+    pub filled_notional: i64,
+    /// Stable rejection reason name, e.g. `"self_trade"` (`None` if accepted)
+    #[pyo3(get)]
+    pub reject_reason: Option<String>,
+    /// Numeric code matching `reject_reason`
+    #[pyo3(get)]
+    pub reason_code: Option<u8>,
+    /// For a rejected FOK, the quantity the book could have filled
+    #[pyo3(get)]
+    pub max_fillable: Option<u64>,
+    /// `(price, quantity)` filled at each level, in sweep order
+    #[pyo3(get)]
+    pub fills_by_level: Vec<(i64, u64)>,
     pub trades: Vec<PyTrade>,
 }
 
@@ -70,6 +108,16 @@ impl PySubmitResult {
     }
 }
 
+impl PySubmitResult {
+    /// Use `price_scale` for the trades' float prices and repr.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        for trade in &mut self.trades {
+            trade.price_scale = price_scale;
+        }
+        self
+    }
+}
+
 impl From<nanobook::SubmitResult> for PySubmitResult {
     fn from(r: nanobook::SubmitResult) -> Self {
         Self {
@@ -78,6 +126,16 @@ impl From<nanobook::SubmitResult> for PySubmitResult {
             filled_quantity: r.filled_quantity,
             resting_quantity: r.resting_quantity,
             cancelled_quantity: r.cancelled_quantity,
+            average_fill_price: r.average_fill_price,
+            filled_notional: r.filled_notional,
+            reject_reason: r.reject_reason.map(|reason| reason.as_str().to_string()),
+            reason_code: r.reject_reason.map(|reason| reason.code()),
+            max_fillable: r.max_fillable,
+            fills_by_level: r
+                .fills_by_level
+                .into_iter()
+                .map(|(price, quantity)| (price.0, quantity))
+                .collect(),
             trades: r.trades.into_iter().map(PyTrade::from).collect(),
         }
     }
@@ -137,6 +195,8 @@ pub struct PyModifyResult {
     pub trades: Vec<PyTrade>,
     #[pyo3(get)]
     pub error: Option<String>,
+    #[pyo3(get)]
+    pub priority_retained: bool,
 }
 
 #[pymethods]
@@ -163,6 +223,16 @@ impl PyModifyResult {
     }
 }
 
+impl PyModifyResult {
+    /// Use `price_scale` for the trades' float prices and repr.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        for trade in &mut self.trades {
+            trade.price_scale = price_scale;
+        }
+        self
+    }
+}
+
 impl From<nanobook::ModifyResult> for PyModifyResult {
     fn from(r: nanobook::ModifyResult) -> Self {
         Self {
@@ -172,6 +242,107 @@ impl From<nanobook::ModifyResult> for PyModifyResult {
             cancelled_quantity: r.cancelled_quantity,
             trades: r.trades.into_iter().map(PyTrade::from).collect(),
             error: r.error.map(|e| format!("{e:?}")),
+            priority_retained: r.priority_retained,
+        }
+    }
+}
+
+/// Result of submitting a bracket order.
+#[pyclass(name = "BracketResult")]
+#[derive(Clone)]
+pub struct PyBracketResult {
+    #[pyo3(get)]
+    pub entry: PySubmitResult,
+    #[pyo3(get)]
+    pub stop_order_id: Option<u64>,
+    #[pyo3(get)]
+    pub target_order_id: Option<u64>,
+}
+
+#[pymethods]
+impl PyBracketResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "BracketResult(entry={}, stop={:?}, target={:?})",
+            self.entry.order_id, self.stop_order_id, self.target_order_id,
+        )
+    }
+}
+
+impl PyBracketResult {
+    /// Use `price_scale` for the entry's trade prices and repr.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        self.entry = self.entry.with_price_scale(price_scale);
+        self
+    }
+}
+
+impl From<nanobook::BracketResult> for PyBracketResult {
+    fn from(r: nanobook::BracketResult) -> Self {
+        Self {
+            entry: r.entry.into(),
+            stop_order_id: r.stop_order_id.map(|id| id.0),
+            target_order_id: r.target_order_id.map(|id| id.0),
+        }
+    }
+}
+
+/// Result of submitting an all-or-nothing spread order.
+#[pyclass(name = "SpreadResult")]
+#[derive(Clone)]
+pub struct PySpreadResult {
+    #[pyo3(get)]
+    pub success: bool,
+    pub legs: Vec<PySubmitResult>,
+    #[pyo3(get)]
+    pub net_notional: i64,
+    #[pyo3(get)]
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl PySpreadResult {
+    /// Per-leg submit results, in the order the legs were given.
+    #[getter]
+    fn legs(&self) -> Vec<PySubmitResult> {
+        self.legs.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        if self.success {
+            format!(
+                "SpreadResult(success=True, legs={}, net_notional={})",
+                self.legs.len(),
+                self.net_notional,
+            )
+        } else {
+            format!(
+                "SpreadResult(success=False, error='{}')",
+                self.error.as_deref().unwrap_or("unknown")
+            )
+        }
+    }
+}
+
+impl PySpreadResult {
+    /// Use `price_scale` for the legs' trade prices and repr.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        self.legs = self
+            .legs
+            .into_iter()
+            .map(|leg| leg.with_price_scale(price_scale))
+            .collect();
+        self
+    }
+}
+
+impl From<nanobook::SpreadResult> for PySpreadResult {
+    fn from(r: nanobook::SpreadResult) -> Self {
+        Self {
+            success: r.success,
+            legs: r.legs.into_iter().map(PySubmitResult::from).collect(),
+            net_notional: r.net_notional,
+            error: r.error.map(|e| format!("{e:?}")),
         }
     }
 }
@@ -223,21 +394,35 @@ pub struct PyTrade {
     pub passive_order_id: u64,
     #[pyo3(get)]
     pub timestamp: u64,
+    pub price_scale: i64,
 }
 
 #[pymethods]
 impl PyTrade {
-    /// Price as a float (dollars, not cents).
+    /// ID of the resting (maker) order; same as ``passive_order_id``.
+    #[getter]
+    fn maker_order_id(&self) -> u64 {
+        self.passive_order_id
+    }
+
+    /// ID of the incoming (taker) order; same as ``aggressor_order_id``.
+    #[getter]
+    fn taker_order_id(&self) -> u64 {
+        self.aggressor_order_id
+    }
+
+    /// Price as a float (e.g. dollars, not cents), using the exchange's
+    /// price scale.
     #[getter]
     fn price_float(&self) -> f64 {
-        price_to_float(nanobook::Price(self.price))
+        price_to_float(nanobook::Price(self.price), self.price_scale)
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "Trade(id={}, price=${:.2}, qty={}, side='{}')",
+            "Trade(id={}, price={}, qty={}, side='{}')",
             self.trade_id,
-            price_to_float(nanobook::Price(self.price)),
+            format_price(nanobook::Price(self.price), self.price_scale),
             self.quantity,
             self.aggressor_side,
         )
@@ -254,6 +439,7 @@ impl From<nanobook::Trade> for PyTrade {
             aggressor_order_id: t.aggressor_order_id.0,
             passive_order_id: t.passive_order_id.0,
             timestamp: t.timestamp,
+            price_scale: nanobook::Price::DEFAULT_SCALE,
         }
     }
 }
@@ -268,19 +454,20 @@ pub struct PyLevelSnapshot {
     pub quantity: u64,
     #[pyo3(get)]
     pub order_count: usize,
+    pub price_scale: i64,
 }
 
 #[pymethods]
 impl PyLevelSnapshot {
     #[getter]
     fn price_float(&self) -> f64 {
-        price_to_float(nanobook::Price(self.price))
+        price_to_float(nanobook::Price(self.price), self.price_scale)
     }
 
     fn __repr__(&self) -> String {
         format!(
-            "Level(price=${:.2}, qty={}, orders={})",
-            price_to_float(nanobook::Price(self.price)),
+            "Level(price={}, qty={}, orders={})",
+            format_price(nanobook::Price(self.price), self.price_scale),
             self.quantity,
             self.order_count,
         )
@@ -5,7 +5,7 @@ use nanobook_risk::{RiskConfig, RiskEngine as RustRiskEngine};
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 
-use crate::types::parse_symbol;
+use crate::types::{SideArg, parse_symbol};
 
 /// Pre-trade risk engine.
 ///
@@ -86,14 +86,14 @@ impl PyRiskEngine {
         &self,
         py: Python<'_>,
         symbol: &str,
-        side: &str,
+        side: SideArg,
         quantity: u64,
         price_cents: i64,
         equity_cents: i64,
         positions: Vec<(String, i64)>,
     ) -> PyResult<Py<PyAny>> {
         let sym = parse_symbol(symbol)?;
-        let broker_side = parse_side(side)?;
+        let broker_side = BrokerSide::from(side);
 
         let account = Account {
             equity_cents,
@@ -126,7 +126,7 @@ impl PyRiskEngine {
     fn check_batch(
         &self,
         py: Python<'_>,
-        orders: Vec<(String, String, u64, i64)>,
+        orders: Vec<(String, SideArg, u64, i64)>,
         equity_cents: i64,
         positions: Vec<(String, i64)>,
         target_weights: Vec<(String, f64)>,
@@ -140,7 +140,9 @@ impl PyRiskEngine {
 
         let broker_orders: Vec<_> = orders
             .iter()
-            .map(|(s, side, qty, price)| Ok((parse_symbol(s)?, parse_side(side)?, *qty, *price)))
+            .map(|(s, side, qty, price)| {
+                Ok((parse_symbol(s)?, BrokerSide::from(*side), *qty, *price))
+            })
             .collect::<PyResult<Vec<_>>>()?;
 
         let pos: Vec<_> = positions
@@ -169,16 +171,6 @@ impl PyRiskEngine {
     }
 }
 
-fn parse_side(s: &str) -> PyResult<BrokerSide> {
-    match s.to_ascii_lowercase().as_str() {
-        "buy" | "b" => Ok(BrokerSide::Buy),
-        "sell" | "s" => Ok(BrokerSide::Sell),
-        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
-            "Invalid side '{s}'. Use 'buy' or 'sell'."
-        ))),
-    }
-}
-
 fn report_to_py(py: Python<'_>, report: &nanobook_risk::RiskReport) -> PyResult<Py<PyAny>> {
     let list = PyList::empty(py);
     for check in &report.checks {
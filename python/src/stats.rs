@@ -45,6 +45,145 @@ pub fn py_quintile_spread(scores: Vec<f64>, returns: Vec<f64>, n_quantiles: usiz
     stats::quintile_spread(&scores, &returns, n_quantiles)
 }
 
+/// Compute the mean return of every score quantile.
+///
+/// Every observation is assigned a bucket by its score rank (bucket sizes
+/// differ by at most one), so the result shows whether a factor is
+/// monotone across buckets, not just at the extremes.
+///
+/// Args:
+///     scores: Factor scores (list of floats).
+///     returns: Realized returns (list of floats, same length as scores).
+///     n_quantiles: Number of buckets (default 5).
+///
+/// Returns:
+///     List of ``n_quantiles`` mean returns in ascending score order. All
+///     NaN if inputs are invalid.
+///
+/// Example::
+///
+///     means = nanobook.py_quantile_means(scores, returns, 10)
+///
+#[pyfunction]
+#[pyo3(signature = (scores, returns, n_quantiles=5))]
+pub fn py_quantile_means(scores: Vec<f64>, returns: Vec<f64>, n_quantiles: usize) -> Vec<f64> {
+    stats::quantile_means(&scores, &returns, n_quantiles)
+}
+
+/// Compute a weighted quintile spread.
+///
+/// Groups are formed as in ``py_quintile_spread``; the top and bottom
+/// group means are weighted by ``weights`` (e.g. market caps) instead of
+/// equal-weighted.
+///
+/// Args:
+///     scores: Factor scores (list of floats).
+///     returns: Realized returns (list of floats, same length as scores).
+///     weights: Non-negative weights (list of floats, same length as scores).
+///     n_quantiles: Number of groups (default 5).
+///
+/// Returns:
+///     Float: weighted top_mean - bottom_mean. NaN if inputs are invalid
+///     or the top or bottom group's weights sum to zero.
+///
+/// Example::
+///
+///     spread = nanobook.py_quintile_spread_weighted(scores, returns, caps, 5)
+///
+#[pyfunction]
+#[pyo3(signature = (scores, returns, weights, n_quantiles=5))]
+pub fn py_quintile_spread_weighted(
+    scores: Vec<f64>,
+    returns: Vec<f64>,
+    weights: Vec<f64>,
+    n_quantiles: usize,
+) -> f64 {
+    stats::quintile_spread_weighted(&scores, &returns, &weights, n_quantiles)
+}
+
+/// Subtract the mean from every value.
+///
+/// Returns all NaN if any value is NaN.
+///
+/// Example::
+///
+///     neutral = nanobook.py_demean(scores)
+///
+#[pyfunction]
+pub fn py_demean(values: Vec<f64>) -> Vec<f64> {
+    stats::demean(&values)
+}
+
+/// Standardize values to zero mean and unit standard deviation.
+///
+/// Drop-in replacement for ``scipy.stats.zscore(values)`` (population
+/// standard deviation). Returns all NaN if any value is NaN or all values
+/// are equal.
+///
+/// Example::
+///
+///     z = nanobook.py_zscore(scores)
+///
+#[pyfunction]
+pub fn py_zscore(values: Vec<f64>) -> Vec<f64> {
+    stats::zscore(&values)
+}
+
+/// Clip values to their ``lower_pct`` and ``upper_pct`` percentiles.
+///
+/// Equivalent to ``np.clip(values, np.percentile(values, lower_pct),
+/// np.percentile(values, upper_pct))``.
+///
+/// Args:
+///     values: Scores to clip (list of floats).
+///     lower_pct: Lower percentile in [0, 100] (e.g. 1.0).
+///     upper_pct: Upper percentile in [lower_pct, 100] (e.g. 99.0).
+///
+/// Returns:
+///     List of clipped values. All NaN if any value is NaN or the
+///     percentiles are out of range.
+///
+/// Example::
+///
+///     clipped = nanobook.py_winsorize(scores, 1.0, 99.0)
+///
+#[pyfunction]
+pub fn py_winsorize(values: Vec<f64>, lower_pct: f64, upper_pct: f64) -> Vec<f64> {
+    stats::winsorize(&values, lower_pct, upper_pct)
+}
+
+/// Permutation test for a factor's information coefficient.
+///
+/// Computes the Spearman IC of ``scores`` and ``returns``, then shuffles
+/// ``returns`` ``n_perms`` times with a generator seeded by ``seed`` and
+/// counts how often the permuted IC is at least the observed one.
+///
+/// Args:
+///     scores: Factor scores (list of floats).
+///     returns: Realized returns (list of floats, same length as scores).
+///     n_perms: Number of permutations (default 1000).
+///     seed: RNG seed; the same seed gives the same p-value.
+///
+/// Returns:
+///     Tuple of (observed_ic, p_value), with the one-sided p-value
+///     ``(1 + hits) / (1 + n_perms)``. (NaN, NaN) if inputs are invalid.
+///
+/// Example::
+///
+///     ic, p = nanobook.py_permutation_test(scores, returns, 1000, seed=7)
+///
+#[pyfunction]
+#[pyo3(signature = (scores, returns, n_perms=1000, seed=0))]
+pub fn py_permutation_test(
+    py: Python<'_>,
+    scores: Vec<f64>,
+    returns: Vec<f64>,
+    n_perms: usize,
+    seed: u64,
+) -> (f64, f64) {
+    py.detach(|| stats::permutation_test(&scores, &returns, n_perms, seed))
+}
+
 /// Compute the Deflated Sharpe Ratio.
 ///
 /// Lopez de Prado's Deflated Sharpe Ratio adjusts an observed Sharpe ratio for
@@ -1,5 +1,10 @@
 use nanobook::Symbol;
-use nanobook::portfolio::{Portfolio, Strategy, run_backtest};
+use nanobook::portfolio::{
+    BacktestOptions, CostModel, LongShortQuantile, MissingPrice, Portfolio, Strategy,
+    TargetWeights, price_series_from_matrix, run_backtest, run_backtest_ex,
+};
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::HashMap;
 
@@ -47,36 +52,361 @@ impl Strategy for PyStrategy {
     }
 }
 
+/// Per-bar prices built in Rust from a dense matrix.
+///
+/// Pass it to ``run_backtest`` in place of a list of dicts to skip building
+/// the bars in Python.
+///
+/// Example::
+///
+///     series = PriceSeries.from_matrix(prices_cents, ["AAPL", "MSFT"])
+///     result = run_backtest(strategy, series, 1_000_000_00, CostModel.zero())
+///
+#[pyclass(name = "PriceSeries")]
+#[derive(Clone)]
+pub struct PyPriceSeries {
+    pub bars: Vec<Vec<(Symbol, i64)>>,
+}
+
+#[pymethods]
+impl PyPriceSeries {
+    /// Build from a 2D float64 array of shape ``(bars, len(symbols))``.
+    ///
+    /// Prices are in cents (rounded to the nearest cent). NaN or infinite
+    /// entries are missing: ``missing="ffill"`` repeats the symbol's last
+    /// price, ``missing="skip"`` leaves the symbol out of that bar.
+    #[staticmethod]
+    #[pyo3(signature = (prices, symbols, missing="ffill"))]
+    fn from_matrix(
+        py: Python<'_>,
+        prices: PyBuffer<f64>,
+        symbols: Vec<String>,
+        missing: &str,
+    ) -> PyResult<Self> {
+        let missing = parse_missing(missing)?;
+        let symbols = parse_symbols(&symbols)?;
+        let values = matrix_values(py, "prices", &prices, symbols.len())?;
+        let bars = py.detach(|| price_series_from_matrix(&values, &symbols, missing));
+        Ok(Self { bars })
+    }
+
+    /// Prices of one bar as ``{symbol: price_cents}``.
+    fn bar(&self, index: usize) -> PyResult<HashMap<String, i64>> {
+        let bar = self
+            .bars
+            .get(index)
+            .ok_or_else(|| PyValueError::new_err(format!("bar {index} out of range")))?;
+        Ok(bar.iter().map(|(s, p)| (s.to_string(), *p)).collect())
+    }
+
+    fn __len__(&self) -> usize {
+        self.bars.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PriceSeries(bars={})", self.bars.len())
+    }
+}
+
+fn parse_missing(missing: &str) -> PyResult<MissingPrice> {
+    match missing {
+        "ffill" => Ok(MissingPrice::CarryForward),
+        "skip" => Ok(MissingPrice::Skip),
+        _ => Err(PyValueError::new_err(format!(
+            "Invalid missing '{missing}'. Use 'ffill' or 'skip'."
+        ))),
+    }
+}
+
+fn parse_symbols(symbols: &[String]) -> PyResult<Vec<Symbol>> {
+    if symbols.is_empty() {
+        return Err(PyValueError::new_err("symbols must not be empty"));
+    }
+    symbols.iter().map(|s| parse_symbol(s)).collect()
+}
+
+/// Row-major values of a 2D ``(bars, n_symbols)`` float64 buffer.
+fn matrix_values(
+    py: Python<'_>,
+    name: &str,
+    matrix: &PyBuffer<f64>,
+    n_symbols: usize,
+) -> PyResult<Vec<f64>> {
+    let shape = matrix.shape();
+    if shape.len() != 2 || shape[1] != n_symbols {
+        return Err(PyValueError::new_err(format!(
+            "{name} must have shape (bars, {n_symbols}), got {shape:?}"
+        )));
+    }
+    matrix.to_vec(py)
+}
+
+/// Accept either a ``PriceSeries`` or a list of ``{symbol: price}`` dicts.
+fn extract_price_series(price_series: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<(Symbol, i64)>>> {
+    if let Ok(series) = price_series.extract::<PyRef<PyPriceSeries>>() {
+        return Ok(series.bars.clone());
+    }
+    let bars: Vec<HashMap<String, i64>> = price_series.extract()?;
+    let mut rust_series = Vec::with_capacity(bars.len());
+    for bar in bars {
+        let mut rust_bar = Vec::with_capacity(bar.len());
+        for (s, p) in bar {
+            rust_bar.push((parse_symbol(&s)?, p));
+        }
+        rust_series.push(rust_bar);
+    }
+    Ok(rust_series)
+}
+
+/// Run a backtest driven by a Python strategy callback.
+///
+/// ``strategy(bar_index, prices, portfolio)`` is called once per bar with
+/// the bar's ``{symbol: price_cents}`` dict and a snapshot of the
+/// portfolio, and returns the target weights as ``[(symbol, weight), ...]``.
+/// The portfolio is rebalanced to those weights and the bar's return
+/// recorded. If the callback raises or returns something that is not a
+/// list of ``(str, float)`` pairs, the error is printed and the bar gets
+/// no target weights.
+///
+/// Args:
+///     strategy: Callable ``(int, dict, Portfolio) -> list[(str, float)]``
+///     price_series: List of ``{symbol: price_cents}`` dicts, or a
+///         ``PriceSeries``
+///     initial_cash: Starting cash in cents
+///     cost_model: Transaction cost model
+///     periods_per_year: Annualization factor
+///     risk_free: Risk-free rate per period
+///     record_history: Keep a per-bar portfolio snapshot
+///     benchmark: Optional per-bar benchmark returns (same length)
+///     warmup: Leading bars kept in cash (the callback is still called)
+///     fill_delay: Bars between a decision and its fill
+///     cash_yield: Per-period interest rate on idle cash
+///
+/// Returns:
+///     BacktestResult with the final portfolio and metrics
 #[pyfunction]
 #[pyo3(name = "run_backtest")]
-#[pyo3(signature = (strategy, price_series, initial_cash, cost_model, periods_per_year=252.0, risk_free=0.0))]
+#[pyo3(signature = (
+    strategy,
+    price_series,
+    initial_cash,
+    cost_model,
+    periods_per_year=252.0,
+    risk_free=0.0,
+    record_history=false,
+    benchmark=None,
+    warmup=0,
+    fill_delay=0,
+    cash_yield=0.0,
+))]
+#[allow(clippy::too_many_arguments)]
 pub fn py_run_backtest(
     strategy: Py<PyAny>,
-    price_series: Vec<HashMap<String, i64>>,
+    price_series: &Bound<'_, PyAny>,
     initial_cash: i64,
     cost_model: PyCostModel,
     periods_per_year: f64,
     risk_free: f64,
+    record_history: bool,
+    benchmark: Option<Vec<f64>>,
+    warmup: usize,
+    fill_delay: usize,
+    cash_yield: f64,
 ) -> PyResult<PyBacktestResult> {
-    let strat = PyStrategy { callback: strategy };
+    let rust_series = extract_price_series(price_series)?;
+    if let Some(b) = &benchmark
+        && b.len() != rust_series.len()
+    {
+        return Err(PyValueError::new_err(format!(
+            "benchmark has {} returns but price_series has {} bars",
+            b.len(),
+            rust_series.len()
+        )));
+    }
 
-    let mut rust_series = Vec::with_capacity(price_series.len());
-    for bar in price_series {
-        let mut rust_bar = Vec::with_capacity(bar.len());
-        for (s, p) in bar {
-            rust_bar.push((parse_symbol(&s)?, p));
-        }
-        rust_series.push(rust_bar);
+    if !cash_yield.is_finite() || cash_yield < 0.0 {
+        return Err(PyValueError::new_err("cash_yield must be non-negative"));
     }
 
-    let result = run_backtest(
+    let strat = PyStrategy { callback: strategy };
+
+    let options = BacktestOptions {
+        record_history,
+        benchmark: benchmark.as_deref(),
+        warmup: Some(warmup),
+        fill_delay,
+        cash_yield,
+    };
+    let result = run_backtest_ex(
         &strat,
         &rust_series,
         initial_cash,
         cost_model.inner,
         periods_per_year,
         risk_free,
+        options,
     );
 
     Ok(result.into())
 }
+
+/// Backtest precomputed target weights without a per-bar Python callback.
+///
+/// Row ``i`` of ``weights`` is the target allocation at bar ``i``, in the
+/// column order of ``symbols``; NaN weights count as 0. The rebalance and
+/// return loop runs entirely in Rust and matches ``run_backtest`` with a
+/// callback returning the same weights.
+///
+/// Args:
+///     weights: 2D float64 array of shape ``(bars, len(symbols))``
+///     prices: 2D float64 array of prices in cents, same shape
+///     symbols: Column symbols
+///     initial_cash: Starting cash in cents
+///     cost_model: Transaction cost model
+///     periods_per_year: Annualization factor
+///     risk_free: Risk-free rate per period
+///     missing: ``"ffill"`` or ``"skip"`` for NaN prices (see
+///         ``PriceSeries.from_matrix``)
+///     fill_delay: Bars between a decision and its fill
+///
+/// Returns:
+///     BacktestResult; ``result.portfolio.equity_curve()`` is the equity
+///     curve
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(name = "run_backtest_matrix")]
+#[pyo3(signature = (
+    weights,
+    prices,
+    symbols,
+    initial_cash,
+    cost_model,
+    periods_per_year=252.0,
+    risk_free=0.0,
+    missing="ffill",
+    fill_delay=0,
+))]
+pub fn py_run_backtest_matrix(
+    py: Python<'_>,
+    weights: PyBuffer<f64>,
+    prices: PyBuffer<f64>,
+    symbols: Vec<String>,
+    initial_cash: i64,
+    cost_model: PyCostModel,
+    periods_per_year: f64,
+    risk_free: f64,
+    missing: &str,
+    fill_delay: usize,
+) -> PyResult<PyBacktestResult> {
+    let missing = parse_missing(missing)?;
+    let symbols = parse_symbols(&symbols)?;
+    let weight_values = matrix_values(py, "weights", &weights, symbols.len())?;
+    let price_values = matrix_values(py, "prices", &prices, symbols.len())?;
+    if weight_values.len() != price_values.len() {
+        return Err(PyValueError::new_err(format!(
+            "weights has {} bars but prices has {}",
+            weight_values.len() / symbols.len(),
+            price_values.len() / symbols.len()
+        )));
+    }
+
+    let result = py.detach(|| {
+        let series = price_series_from_matrix(&price_values, &symbols, missing);
+        let schedule: Vec<Vec<(Symbol, f64)>> = weight_values
+            .chunks_exact(symbols.len())
+            .map(|row| {
+                symbols
+                    .iter()
+                    .zip(row)
+                    .map(|(&sym, &w)| (sym, if w.is_finite() { w } else { 0.0 }))
+                    .collect()
+            })
+            .collect();
+        let options = BacktestOptions {
+            fill_delay,
+            ..BacktestOptions::default()
+        };
+        run_backtest_ex(
+            &TargetWeights::new(&schedule),
+            &series,
+            initial_cash,
+            cost_model.inner,
+            periods_per_year,
+            risk_free,
+            options,
+        )
+    });
+
+    Ok(result.into())
+}
+
+/// Backtest a dollar-neutral long-short quantile factor portfolio.
+///
+/// Each bar, symbols are ranked by score; the top quantile is held long and
+/// the bottom quantile short, each leg at 50% of equity. Ranking matches
+/// `quintile_spread` (group size `n // n_quantiles`), and ties break by symbol.
+///
+/// Args:
+///     scores_per_bar: List of bars, each bar is [(symbol, score), ...]
+///     price_series: List of bars, each bar is [(symbol, price_cents), ...]
+///     n_quantiles: Number of quantile buckets (e.g. 5 for quintiles)
+///     initial_cash: Starting cash in cents
+///     cost_model: Optional transaction cost model (zero cost if omitted)
+///     periods_per_year: Annualization factor
+///     risk_free: Risk-free rate per period
+///
+/// Returns:
+///     BacktestResult with the final portfolio and metrics
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(name = "run_long_short")]
+#[pyo3(signature = (scores_per_bar, price_series, n_quantiles, initial_cash, cost_model=None, periods_per_year=12.0, risk_free=0.0))]
+pub fn py_run_long_short(
+    py: Python<'_>,
+    scores_per_bar: Vec<Vec<(String, f64)>>,
+    price_series: Vec<Vec<(String, i64)>>,
+    n_quantiles: usize,
+    initial_cash: i64,
+    cost_model: Option<PyCostModel>,
+    periods_per_year: f64,
+    risk_free: f64,
+) -> PyResult<PyBacktestResult> {
+    if scores_per_bar.len() != price_series.len() {
+        return Err(PyValueError::new_err(format!(
+            "scores_per_bar has {} bars but price_series has {}",
+            scores_per_bar.len(),
+            price_series.len()
+        )));
+    }
+
+    let scores: Vec<Vec<(Symbol, f64)>> = scores_per_bar
+        .into_iter()
+        .map(|bar| {
+            bar.into_iter()
+                .map(|(s, v)| Ok((parse_symbol(&s)?, v)))
+                .collect::<PyResult<Vec<_>>>()
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let rust_series: Vec<Vec<(Symbol, i64)>> = price_series
+        .into_iter()
+        .map(|bar| {
+            bar.into_iter()
+                .map(|(s, p)| Ok((parse_symbol(&s)?, p)))
+                .collect::<PyResult<Vec<_>>>()
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let cost_model = cost_model.map_or_else(CostModel::zero, |c| c.inner);
+
+    let result = py.detach(|| {
+        run_backtest(
+            &LongShortQuantile::new(n_quantiles, &scores),
+            &rust_series,
+            initial_cash,
+            cost_model,
+            periods_per_year,
+            risk_free,
+        )
+    });
+
+    Ok(result.into())
+}
@@ -1,5 +1,5 @@
 use nanobook::portfolio::sweep::sweep_strategy;
-use nanobook::portfolio::{CostModel, EqualWeight};
+use nanobook::portfolio::{CostModel, EqualWeight, TopK};
 use pyo3::prelude::*;
 
 use crate::metrics::PyMetrics;
@@ -71,3 +71,79 @@ pub fn py_sweep_equal_weight(
         .map(|r| r.metrics.map(PyMetrics::from))
         .collect())
 }
+
+/// Run a parallel sweep of the cross-sectional top-k strategy.
+///
+/// Each bar, symbols are ranked by their score and the `k` highest are
+/// equal-weighted. Ties in score break by symbol (ascending), so results
+/// are deterministic. NaN scores and unpriced symbols are skipped.
+///
+/// Args:
+///     scores_per_bar: List of bars, each bar is [(symbol, score), ...]
+///     price_series: List of bars, each bar is [(symbol, price_cents), ...]
+///     ks: Values of k to sweep (one backtest per value)
+///     initial_cash: Starting cash in cents
+///     periods_per_year: Annualization factor
+///     risk_free: Risk-free rate per period
+///
+/// Returns:
+///     List of Metrics (one per k)
+///
+/// Example::
+///
+///     results = sweep_top_k(scores, prices, [5, 10, 20], 1_000_000_00)
+///
+#[pyfunction]
+#[pyo3(name = "sweep_top_k")]
+#[pyo3(signature = (scores_per_bar, price_series, ks, initial_cash, periods_per_year=12.0, risk_free=0.0))]
+pub fn py_sweep_top_k(
+    py: Python<'_>,
+    scores_per_bar: Vec<Vec<(String, f64)>>,
+    price_series: Vec<Vec<(String, i64)>>,
+    ks: Vec<usize>,
+    initial_cash: i64,
+    periods_per_year: f64,
+    risk_free: f64,
+) -> PyResult<Vec<Option<PyMetrics>>> {
+    if scores_per_bar.len() != price_series.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "scores_per_bar has {} bars but price_series has {}",
+            scores_per_bar.len(),
+            price_series.len()
+        )));
+    }
+
+    let scores: Vec<Vec<(nanobook::Symbol, f64)>> = scores_per_bar
+        .into_iter()
+        .map(|bar| {
+            bar.into_iter()
+                .map(|(s, v)| Ok((parse_symbol(&s)?, v)))
+                .collect::<PyResult<Vec<_>>>()
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+    let price_series: Vec<Vec<(nanobook::Symbol, i64)>> = price_series
+        .into_iter()
+        .map(|bar| {
+            bar.into_iter()
+                .map(|(s, p)| Ok((parse_symbol(&s)?, p)))
+                .collect::<PyResult<Vec<_>>>()
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let results = py.detach(|| {
+        sweep_strategy(
+            &ks,
+            &price_series,
+            initial_cash,
+            CostModel::zero(),
+            periods_per_year,
+            risk_free,
+            |&k| TopK::new(k, &scores),
+        )
+    });
+
+    Ok(results
+        .into_iter()
+        .map(|r| r.metrics.map(PyMetrics::from))
+        .collect())
+}
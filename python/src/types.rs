@@ -1,7 +1,117 @@
 use nanobook::{Price, Side, TimeInForce};
-use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 
+/// Order side. Accepted anywhere a side string (``"buy"``/``"sell"``) is.
+///
+/// Example::
+///
+///     ex.submit_limit(nanobook.Side.BUY, 100_00, 10, nanobook.TimeInForce.GTC)
+///
+#[pyclass(name = "Side", eq, eq_int, frozen)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PySide {
+    #[pyo3(name = "BUY")]
+    Buy,
+    #[pyo3(name = "SELL")]
+    Sell,
+}
+
+#[pymethods]
+impl PySide {
+    fn __str__(&self) -> &'static str {
+        side_str(Side::from(*self))
+    }
+}
+
+impl From<PySide> for Side {
+    fn from(side: PySide) -> Self {
+        match side {
+            PySide::Buy => Side::Buy,
+            PySide::Sell => Side::Sell,
+        }
+    }
+}
+
+/// Time in force. Accepted anywhere a string (``"gtc"``/``"ioc"``/``"fok"``)
+/// is.
+#[pyclass(name = "TimeInForce", eq, eq_int, frozen)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PyTimeInForce {
+    #[pyo3(name = "GTC")]
+    Gtc,
+    #[pyo3(name = "IOC")]
+    Ioc,
+    #[pyo3(name = "FOK")]
+    Fok,
+}
+
+#[pymethods]
+impl PyTimeInForce {
+    fn __str__(&self) -> &'static str {
+        match self {
+            PyTimeInForce::Gtc => "gtc",
+            PyTimeInForce::Ioc => "ioc",
+            PyTimeInForce::Fok => "fok",
+        }
+    }
+}
+
+impl From<PyTimeInForce> for TimeInForce {
+    fn from(tif: PyTimeInForce) -> Self {
+        match tif {
+            PyTimeInForce::Gtc => TimeInForce::GTC,
+            PyTimeInForce::Ioc => TimeInForce::IOC,
+            PyTimeInForce::Fok => TimeInForce::FOK,
+        }
+    }
+}
+
+/// A side argument: a ``Side`` member or a side string.
+#[derive(Clone, Copy)]
+pub struct SideArg(pub Side);
+
+impl<'py> FromPyObject<'_, 'py> for SideArg {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(side) = obj.extract::<PySide>() {
+            return Ok(Self(side.into()));
+        }
+        let s: String = obj
+            .extract()
+            .map_err(|_| PyTypeError::new_err("side must be a Side or a string"))?;
+        parse_side(&s).map(Self)
+    }
+}
+
+impl From<SideArg> for nanobook_broker::BrokerSide {
+    fn from(side: SideArg) -> Self {
+        match side.0 {
+            Side::Buy => Self::Buy,
+            Side::Sell => Self::Sell,
+        }
+    }
+}
+
+/// A time-in-force argument: a ``TimeInForce`` member or a string.
+#[derive(Clone, Copy)]
+pub struct TifArg(pub TimeInForce);
+
+impl<'py> FromPyObject<'_, 'py> for TifArg {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'_, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(tif) = obj.extract::<PyTimeInForce>() {
+            return Ok(Self(tif.into()));
+        }
+        let s: String = obj
+            .extract()
+            .map_err(|_| PyTypeError::new_err("time_in_force must be a TimeInForce or a string"))?;
+        parse_tif(&s).map(Self)
+    }
+}
+
 /// Parse a side string ("buy"/"sell") into a Side enum.
 pub fn parse_side(s: &str) -> PyResult<Side> {
     match s.to_ascii_lowercase().as_str() {
@@ -33,16 +143,49 @@ pub fn side_str(side: Side) -> &'static str {
     }
 }
 
-/// Format a Price as a dollars float for Python.
-pub fn price_to_float(price: Price) -> f64 {
-    price.0 as f64 / 100.0
+/// Convert a Price to a float for Python, given the price units per whole
+/// currency unit (100 for cents).
+pub fn price_to_float(price: Price, scale: i64) -> f64 {
+    price.to_f64(scale)
+}
+
+/// Format a Price as `$123.45`, with as many decimals as `scale` implies.
+pub fn format_price(price: Price, scale: i64) -> String {
+    let decimals = (scale as f64).log10().ceil().max(0.0) as usize;
+    format!("${:.*}", decimals, price.to_f64(scale))
 }
 
 /// Parse a symbol string, returning an error if > 8 bytes.
 pub fn parse_symbol(s: &str) -> PyResult<nanobook::Symbol> {
+    parse_symbol_with(s, false)
+}
+
+/// Parse a symbol string. With `hash_long`, names over 8 bytes are hashed
+/// via `Symbol::from_hashed` instead of rejected.
+pub fn parse_symbol_with(s: &str, hash_long: bool) -> PyResult<nanobook::Symbol> {
+    if hash_long {
+        return Ok(nanobook::Symbol::from_hashed(s));
+    }
     nanobook::Symbol::try_new(s).ok_or_else(|| {
         PyValueError::new_err(format!(
-            "Symbol '{s}' exceeds 8 bytes. Use a shorter symbol."
+            "Symbol '{s}' exceeds 8 bytes. Use a shorter symbol, or hashed_symbol() to map it."
         ))
     })
 }
+
+/// Map a name of any length to a stable symbol string.
+///
+/// Names up to 8 bytes are returned unchanged. Longer names become ``#``
+/// plus 7 characters of a stable hash, usable anywhere a symbol is
+/// accepted. Distinct long names may collide (≈50% chance of any collision
+/// at ~2.2 million names); keep your own mapping back to the full name.
+///
+/// Args:
+///     name: Instrument name
+///     hash_long: If False, raise ValueError for names over 8 bytes instead
+#[pyfunction]
+#[pyo3(name = "hashed_symbol")]
+#[pyo3(signature = (name, hash_long=true))]
+pub fn py_hashed_symbol(name: &str, hash_long: bool) -> PyResult<String> {
+    Ok(parse_symbol_with(name, hash_long)?.to_string())
+}
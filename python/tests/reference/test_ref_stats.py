@@ -87,6 +87,33 @@ class TestQuintileSpreadReference:
         got = nanobook.py_quintile_spread(scores, returns, 5)
         assert got < 0.0  # inverse → negative spread
 
+    def test_quantile_means(self):
+        rng = np.random.default_rng(7)
+        scores = rng.normal(size=103)
+        returns = rng.normal(size=103) * 0.01
+        got = nanobook.py_quantile_means(scores.tolist(), returns.tolist(), 10)
+
+        order = np.argsort(scores, kind="stable")
+        buckets = np.arange(103) * 10 // 103
+        expected = [returns[order][buckets == b].mean() for b in range(10)]
+        np.testing.assert_allclose(got, expected, rtol=0, atol=1e-12)
+
+    def test_weighted_spread(self):
+        scores = [float(i) for i in range(100)]
+        returns = [float(i) * 0.001 for i in range(100)]
+        caps = [float(1 + i % 7) for i in range(100)]
+        got = nanobook.py_quintile_spread_weighted(scores, returns, caps, 5)
+
+        bottom = np.average(returns[:20], weights=caps[:20])
+        top = np.average(returns[80:], weights=caps[80:])
+        assert abs(got - (top - bottom)) < 1e-12
+
+    def test_weighted_zero_group_weight(self):
+        scores = [float(i) for i in range(10)]
+        returns = [0.01] * 10
+        weights = [1.0] * 8 + [0.0, 0.0]
+        assert np.isnan(nanobook.py_quintile_spread_weighted(scores, returns, weights, 5))
+
 
 class TestDeflatedSharpe:
     """Basic coverage for Deflated Sharpe Ratio binding."""
@@ -103,3 +130,41 @@ class TestDeflatedSharpe:
     def test_invalid_inputs(self):
         got = nanobook.py_deflated_sharpe(1.0, 0, 0.0, 3.0)
         assert np.isnan(got)
+
+
+class TestPreprocessingReference:
+    """Validate demean/zscore/winsorize against scipy/numpy."""
+
+    VALUES = [0.3, -1.2, 4.5, 0.0, 2.2, -0.7, 9.9, 1.1, -3.4, 0.8]
+
+    def test_demean(self):
+        expected = np.array(self.VALUES) - np.mean(self.VALUES)
+        np.testing.assert_allclose(nanobook.py_demean(self.VALUES), expected, atol=1e-12)
+
+    def test_zscore(self):
+        expected = stats.zscore(self.VALUES)
+        np.testing.assert_allclose(nanobook.py_zscore(self.VALUES), expected, atol=1e-12)
+        assert all(np.isnan(nanobook.py_zscore([2.0, 2.0, 2.0])))
+
+    def test_winsorize(self):
+        lo, hi = np.percentile(self.VALUES, [5.0, 90.0])
+        expected = np.clip(self.VALUES, lo, hi)
+        np.testing.assert_allclose(nanobook.py_winsorize(self.VALUES, 5.0, 90.0), expected, atol=1e-12)
+        assert all(np.isnan(nanobook.py_winsorize(self.VALUES, 60.0, 40.0)))
+
+
+class TestPermutationTest:
+    """Seeded permutation test for factor IC."""
+
+    def test_matches_spearman_and_is_reproducible(self):
+        rng = np.random.default_rng(3)
+        scores = rng.normal(size=60)
+        returns = 0.5 * scores + rng.normal(size=60)
+        ic, p = nanobook.py_permutation_test(scores.tolist(), returns.tolist(), 500, seed=11)
+        assert abs(ic - stats.spearmanr(scores, returns)[0]) < 1e-12
+        assert 1 / 501 <= p < 0.01
+        assert nanobook.py_permutation_test(scores.tolist(), returns.tolist(), 500, seed=11) == (ic, p)
+
+    def test_invalid(self):
+        ic, p = nanobook.py_permutation_test([1.0, 2.0], [1.0, 2.0], 10)
+        assert np.isnan(ic) and np.isnan(p)
@@ -1,5 +1,7 @@
 """Tests for the Exchange Python bindings."""
 
+import pytest
+
 import nanobook
 
 
@@ -33,6 +35,30 @@ def test_submit_limit_full_fill():
     assert result.trades[0].quantity == 100
 
 
+def test_side_and_tif_enums():
+    Side, Tif = nanobook.Side, nanobook.TimeInForce
+    ex = nanobook.Exchange()
+    ex.submit_limit(Side.SELL, 10000, 100, Tif.GTC)
+    r = ex.submit_limit(Side.BUY, 10000, 30, tif=Tif.IOC)
+    assert r.filled_quantity == 30
+    # Strings and enum members mix freely
+    r = ex.submit_limit("buy", 10000, 200, Tif.FOK)
+    assert r.filled_quantity == 0
+    assert ex.submit_market(Side.BUY, 10).filled_quantity == 10
+    assert ex.total_resting_quantity(Side.SELL) == 60
+    assert str(Side.BUY) == "buy" and str(Tif.GTC) == "gtc"
+    assert Side.BUY == Side.BUY and Side.BUY != Side.SELL
+
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", Side.SELL, 10000, 5, Tif.GTC)
+    assert multi.submit_market("AAPL", Side.BUY, 5).filled_quantity == 5
+
+    with pytest.raises(TypeError):
+        ex.submit_market(1, 10)
+    with pytest.raises(ValueError):
+        ex.submit_market("long", 10)
+
+
 def test_submit_limit_ioc():
     ex = nanobook.Exchange()
     ex.submit_limit("sell", 10000, 30, "gtc")
@@ -49,6 +75,17 @@ def test_submit_limit_fok_reject():
     assert result.filled_quantity == 0
     assert result.cancelled_quantity == 100
     assert len(result.trades) == 0
+    assert result.reject_reason == "insufficient_liquidity"
+    assert result.reason_code == 1
+    assert result.max_fillable == 50
+
+
+def test_accepted_order_has_no_reject_reason():
+    ex = nanobook.Exchange()
+    result = ex.submit_limit("buy", 10000, 100, "gtc")
+    assert result.reject_reason is None
+    assert result.reason_code is None
+    assert result.max_fillable is None
 
 
 def test_submit_market():
@@ -59,6 +96,192 @@ def test_submit_market():
     assert result.status == "Filled"
 
 
+def test_submit_average_fill_price():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 50, "gtc")
+    ex.submit_limit("sell", 10100, 50, "gtc")
+    result = ex.submit_market("buy", 100)
+    assert result.filled_notional == 50 * 10000 + 50 * 10100
+    assert result.average_fill_price == 10050.0
+
+    empty = ex.submit_market("buy", 10)
+    assert empty.average_fill_price is None
+    assert empty.filled_notional == 0
+
+
+def test_submit_fills_by_level():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 30, "gtc")
+    ex.submit_limit("sell", 10000, 20, "gtc")
+    ex.submit_limit("sell", 10100, 50, "gtc")
+    result = ex.submit_market("buy", 70)
+    assert result.fills_by_level == [(10000, 50), (10100, 20)]
+    assert ex.submit_limit("buy", 9000, 10, "gtc").fills_by_level == []
+
+
+def test_is_crossed_and_locked():
+    ex = nanobook.Exchange()
+    assert not ex.is_crossed()
+    assert not ex.is_locked()
+    ex.submit_limit("buy", 10000, 100, "gtc")
+    ex.submit_limit("sell", 10000, 40, "gtc")  # matches instead of locking
+    ex.submit_limit("sell", 10100, 100, "gtc")
+    assert not ex.is_crossed()
+    assert not ex.is_locked()
+
+
+def test_flow_stats():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc", owner=1)
+    ex.submit_limit("buy", 10000, 60, "gtc")
+    ex.submit_limit("buy", 9900, 30, "gtc")
+    ex.submit_limit("sell", 9900, 20, "ioc", owner=1)
+    assert ex.flow_stats(1) == {
+        "maker_volume": 60,
+        "taker_volume": 20,
+        "maker_notional": 600000,
+        "taker_notional": 198000,
+    }
+    assert ex.flow_stats(2) == {
+        "maker_volume": 0,
+        "taker_volume": 0,
+        "maker_notional": 0,
+        "taker_notional": 0,
+    }
+
+
+def test_position_limit_reject_and_clip():
+    ex = nanobook.Exchange()
+    ex.set_position_limit(1, 100)
+    ex.submit_limit("sell", 10000, 200, "gtc")
+    ex.submit_limit("buy", 10000, 70, "gtc", owner=1)
+    assert ex.net_position(1) == 70
+    result = ex.submit_limit("buy", 10000, 40, "gtc", owner=1)
+    assert result.reject_reason == "position_limit"
+    assert result.reason_code == 3
+    assert result.filled_quantity == 0
+    assert ex.position_limit(1) == 100
+
+    clip = nanobook.Exchange(position_limit_mode="clip")
+    clip.set_position_limit(1, 100)
+    clip.submit_limit("sell", 10000, 200, "gtc")
+    result = clip.submit_limit("buy", 10000, 150, "gtc", owner=1)
+    assert result.filled_quantity == 100
+    assert result.cancelled_quantity == 50
+    assert result.reject_reason == "position_limit"
+    assert clip.net_position(1) == 100
+    assert clip.remove_position_limit(1) == 100
+    assert clip.position_limit(1) is None
+
+    with pytest.raises(ValueError):
+        nanobook.Exchange(position_limit_mode="warn")
+
+
+def test_session_schedule_queues_pre_open_orders():
+    ex = nanobook.Exchange()
+    ex.set_session_schedule([(0, "pre_open"), (100, "open"), (200, "closed")])
+    ex.set_clock(0)
+    assert ex.session == "pre_open"
+    ex.submit_limit("sell", 10000, 10, "gtc")
+    ex.submit_limit("buy", 10100, 10, "gtc")
+    assert ex.submit_market("buy", 5).reject_reason == "session"
+    assert ex.trades() == []
+
+    ex.set_clock(100)
+    assert ex.session == "open"
+    assert len(ex.trades()) == 1
+    assert ex.trades()[0].price == 10000
+
+    ex.set_clock(200)
+    result = ex.submit_limit("buy", 9900, 10, "gtc")
+    assert result.reject_reason == "session"
+    assert result.reason_code == 5
+
+    ex.set_session("open")
+    assert ex.submit_limit("buy", 9900, 10, "gtc").reject_reason is None
+    with pytest.raises(ValueError):
+        ex.set_session("lunch")
+
+
+def test_orders_at_returns_level_queue():
+    ex = nanobook.Exchange()
+    a = ex.submit_limit("buy", 9900, 10, "gtc")
+    b = ex.submit_limit("buy", 9900, 20, "gtc")
+    ex.submit_limit("buy", 9800, 30, "gtc")
+    queue = ex.orders_at("buy", 9900)
+    assert [o.id for o in queue] == [a.order_id, b.order_id]
+    assert [o.remaining_quantity for o in queue] == [10, 20]
+    assert ex.orders_at("sell", 9900) == []
+
+
+def test_time_in_queue():
+    ex = nanobook.Exchange()
+    ex.set_clock(100)
+    bid = ex.submit_limit("buy", 9900, 10, "gtc")
+    ex.set_clock(130)
+    assert ex.time_in_queue(bid.order_id) == 30
+    assert ex.get_order(bid.order_id).queued_at == 100
+    moved = ex.modify(bid.order_id, 9800, 10)
+    assert ex.time_in_queue(moved.new_order_id) == 0
+    assert ex.time_in_queue(bid.order_id) is None
+
+
+def test_preview_leaves_book_unchanged():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 30, "gtc")
+    ex.submit_limit("sell", 10100, 30, "gtc")
+
+    preview = ex.preview("buy", 10100, 40, "ioc")
+    assert preview.filled_quantity == 40
+    assert [t.price for t in preview.trades] == [10000, 10100]
+    assert ex.trades() == []
+    assert ex.total_resting_quantity("sell") == 60
+
+    assert ex.preview("buy", 10100, 100, "fok").reject_reason == "insufficient_liquidity"
+    result = ex.submit_limit("buy", 10100, 40, "ioc")
+    assert result.order_id == preview.order_id
+
+
+def test_trade_stats():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    ex.submit_limit("buy", 9900, 100, "gtc")
+    ex.submit_market("buy", 30)
+    ex.submit_market("sell", 10)
+    stats = ex.trade_stats()
+    assert stats["buy_volume"] == 30
+    assert stats["sell_volume"] == 10
+    assert stats["trade_count"] == 2
+    assert stats["vwap"] == (30 * 10000 + 10 * 9900) // 40
+    last = ex.trades()[-1].timestamp
+    assert ex.trade_stats(last + 1)["vwap"] is None
+
+
+def test_order_flow_imbalance():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    ex.submit_limit("buy", 9900, 100, "gtc")
+    ex.submit_market("buy", 30)
+    ex.submit_market("sell", 10)
+    assert ex.order_flow_imbalance() == 20
+    ex.reset_flow()
+    assert ex.order_flow_imbalance() == 0
+    ex.submit_market("sell", 5)
+    assert ex.order_flow_imbalance() == -5
+
+
+def test_cancel_after_head_fill():
+    ex = nanobook.Exchange()
+    ex.submit_limit("buy", 10000, 100, "gtc")
+    b = ex.submit_limit("buy", 10000, 100, "gtc")
+    c = ex.submit_limit("buy", 10000, 100, "gtc")
+    ex.submit_market("sell", 100)
+    assert ex.cancel(c.order_id).success
+    assert ex.get_order(b.order_id).sequence < ex.get_order(c.order_id).sequence
+    result = ex.submit_market("sell", 300)
+    assert [t.passive_order_id for t in result.trades] == [b.order_id]
+
+
 def test_cancel():
     ex = nanobook.Exchange()
     submit = ex.submit_limit("buy", 10000, 100, "gtc")
@@ -82,6 +305,34 @@ def test_modify():
     assert result.new_order_id is not None
     assert result.cancelled_quantity == 100
 
+    assert not result.priority_retained
+
+
+def test_modify_stale_order():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 100, "gtc")
+    filled = ex.submit_limit("buy", 10000, 100, "gtc")
+    result = ex.modify(filled.order_id, 9900, 50)
+    assert not result.success
+    assert result.error == "OrderNotActive"
+    assert result.new_order_id is None
+    missing = ex.modify(999, 9900, 0)
+    assert missing.error == "OrderNotFound"
+    assert ex.cancel(999).error == "OrderNotFound"
+
+
+def test_modify_reduce_keeps_priority():
+    ex = nanobook.Exchange()
+    first = ex.submit_limit("buy", 10000, 100, "gtc")
+    ex.submit_limit("buy", 10000, 100, "gtc")
+    result = ex.modify(first.order_id, 10000, 40)
+    assert result.success
+    assert result.priority_retained
+    assert result.new_order_id == first.order_id
+    assert result.cancelled_quantity == 60
+    fill = ex.submit_market("sell", 40)
+    assert fill.trades[0].passive_order_id == first.order_id
+
 
 def test_best_bid_ask():
     ex = nanobook.Exchange()
@@ -127,6 +378,155 @@ def test_trade_repr():
     assert trade.price_float == 100.50
 
 
+def test_price_scale():
+    ex = nanobook.Exchange(price_scale=10000)
+    assert ex.price_scale == 10000
+    ex.submit_limit("sell", 1234567, 100, "gtc")
+    result = ex.submit_limit("buy", 1234567, 100, "gtc")
+    trade = result.trades[0]
+    assert trade.price_float == 123.4567
+    assert "$123.4567" in repr(trade)
+    assert ex.trades()[0].price_float == 123.4567
+
+    ex.submit_limit("buy", 1234500, 10, "gtc")
+    assert ex.depth(1).bids[0].price_float == 123.45
+
+    multi = nanobook.MultiExchange(price_scale=10000)
+    multi.submit_limit("AAPL", "sell", 1500050, 10, "gtc")
+    trade = multi.submit_market("AAPL", "buy", 10).trades[0]
+    assert trade.price_float == 150.005
+
+    with pytest.raises(ValueError):
+        nanobook.Exchange(price_scale=0)
+
+
+def test_bracket_oco_cancel():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 100_00, 10)
+    result = ex.submit_bracket("buy", 100_00, 10, 95_00, 110_00)
+    assert result.entry.filled_quantity == 10
+    assert ex.bracket(result.entry.order_id)["target_order_id"] == result.target_order_id
+
+    # Partial take-profit shrinks the stop, the rest cancels it.
+    ex.submit_limit("buy", 110_00, 4)
+    assert ex.get_stop_order(result.stop_order_id)["quantity"] == 6
+    ex.submit_limit("buy", 110_00, 6)
+    assert ex.get_stop_order(result.stop_order_id)["status"] == "cancelled"
+    assert ex.pending_stop_count() == 0
+
+
+def test_oco_partial_fill_reduces_partner():
+    ex = nanobook.Exchange()
+    a = ex.submit_limit("buy", 99_00, 100).order_id
+    b = ex.submit_limit("sell", 101_00, 60).order_id
+    ex.link_oco(a, b)
+    assert ex.oco_partner(a) == b
+
+    ex.submit_limit("sell", 99_00, 30)
+    assert ex.get_order(b).remaining_quantity == 30
+
+    ex.submit_market("buy", 30)
+    assert ex.get_order(a).remaining_quantity == 40
+    assert ex.oco_partner(a) is None
+
+    with pytest.raises(ValueError):
+        ex.link_oco(a, a)
+
+
+def test_multi_exchange_state_hash():
+    def run(extra):
+        multi = nanobook.MultiExchange()
+        multi.submit_limit("AAPL", "sell", 150_00, 30)
+        multi.submit_market("AAPL", "buy", extra)
+        return multi
+
+    assert run(10).state_hash() == run(10).state_hash()
+    assert run(10).state_hash() != run(11).state_hash()
+    restored = nanobook.MultiExchange.restore(run(10).checkpoint())
+    assert restored.state_hash() == run(10).state_hash()
+
+
+def test_multi_exchange_checkpoint_restore():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "sell", 150_00, 30)
+    multi.submit_limit("AAPL", "sell", 150_00, 20)
+    multi.submit_limit("BERKSHIRE_HATHAWAY_B", "buy", 400_00, 5)
+
+    data = multi.checkpoint()
+    assert isinstance(data, bytes)
+    restored = nanobook.MultiExchange.restore(data)
+    assert sorted(restored.symbols()) == sorted(multi.symbols())
+
+    a = multi.submit_market("AAPL", "buy", 40)
+    b = restored.submit_market("AAPL", "buy", 40)
+    assert a.order_id == b.order_id
+    assert [(t.passive_order_id, t.quantity) for t in a.trades] == [
+        (t.passive_order_id, t.quantity) for t in b.trades
+    ]
+
+    with pytest.raises(ValueError):
+        nanobook.MultiExchange.restore(b"garbage")
+
+
+def test_multi_exchange_save_load_json(tmp_path):
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "sell", 150_00, 10)
+    multi.submit_limit("AAPL", "sell", 150_00, 20)
+    path = str(tmp_path / "multi.json")
+
+    multi.save_json(path)
+    loaded = nanobook.MultiExchange.load_json(path)
+
+    result = loaded.submit_market("AAPL", "buy", 15)
+    assert [(t.passive_order_id, t.quantity) for t in result.trades] == [(1, 10), (2, 5)]
+    assert result.order_id == 3
+
+
+def test_sweep_vwap_does_not_trade():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 100_00, 50)
+    ex.submit_limit("sell", 102_00, 150)
+
+    est = ex.sweep_vwap("buy", 100)
+    assert est == {"filled_quantity": 100, "notional": 101_00 * 100, "vwap": 101_00, "worst_price": 102_00}
+    assert ex.sweep_vwap("buy", 500)["filled_quantity"] == 200
+    assert ex.sweep_vwap("sell", 10)["vwap"] is None
+    assert ex.best_ask() == 100_00
+
+
+def test_multi_exchange_snapshot_all():
+    multi = nanobook.MultiExchange()
+    for name in ["MSFT", "AAPL", "BERKSHIRE_HATHAWAY"]:
+        for i in range(3):
+            multi.submit_limit(name, "buy", 100_00 - i, 10)
+            multi.submit_limit(name, "sell", 101_00 + i, 10)
+
+    books = multi.snapshot_all(depth=2)
+    assert list(books) == ["AAPL", "BERKSHIRE_HATHAWAY", "MSFT"]
+    assert [lvl.price for lvl in books["MSFT"].asks] == [101_00, 101_01]
+    assert books["AAPL"].spread() == 1_00
+
+
+def test_submit_spread_all_or_nothing():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("CLZ5", "sell", 70_00, 10)
+    multi.submit_limit("CLF6", "buy", 71_00, 10)
+    legs = [("CLZ5", "buy", 1), ("CLF6", "sell", 1)]
+
+    rejected = multi.submit_spread(legs, -2_00, 10)
+    assert not rejected.success
+    assert rejected.error == "LimitExceeded"
+    assert rejected.legs == []
+
+    thin = multi.submit_spread(legs, -1_00, 11)
+    assert thin.error == "InsufficientLiquidity"
+
+    result = multi.submit_spread(legs, -1_00, 10)
+    assert result.success
+    assert result.net_notional == -10_00
+    assert [leg.filled_quantity for leg in result.legs] == [10, 10]
+
+
 def test_stop_market():
     ex = nanobook.Exchange()
     result = ex.submit_stop_market("buy", 10500, 100)
@@ -190,6 +590,23 @@ def test_clear_trades():
     assert len(ex.trades()) == 0
 
 
+def test_id_start():
+    ex = nanobook.Exchange(order_id_start=1000, trade_id_start=500)
+    ask = ex.submit_limit("sell", 10000, 100, "gtc")
+    bid = ex.submit_limit("buy", 10000, 100, "gtc")
+    assert ask.order_id == 1000
+    assert bid.order_id == 1001
+    assert bid.trades[0].trade_id == 500
+
+
+def test_id_start_zero_rejected():
+    try:
+        nanobook.Exchange(order_id_start=0)
+        assert False, "Should have raised ValueError"
+    except ValueError:
+        pass
+
+
 def test_invalid_side():
     ex = nanobook.Exchange()
     try:
@@ -206,3 +623,159 @@ def test_invalid_tif():
         assert False, "Should have raised ValueError"
     except ValueError:
         pass
+
+
+def test_best_consolidated():
+    a, b = nanobook.Exchange(), nanobook.Exchange()
+    a.submit_limit("buy", 9900, 100, "gtc")
+    a.submit_limit("sell", 10200, 100, "gtc")
+    b.submit_limit("buy", 9950, 100, "gtc")
+    b.submit_limit("sell", 10100, 100, "gtc")
+    assert nanobook.best_consolidated([a, b]) == (9950, 10100)
+    assert nanobook.best_consolidated([]) == (None, None)
+
+
+def test_route_order():
+    a, b = nanobook.Exchange(), nanobook.Exchange()
+    a.submit_limit("sell", 10100, 100, "gtc")
+    b.submit_limit("sell", 10000, 50, "gtc")
+    routed = nanobook.route_order([a, b], "buy", 120)
+    assert routed["filled_quantity"] == 120
+    assert routed["filled_notional"] == 50 * 10000 + 70 * 10100
+    assert [(venue, res.filled_quantity) for venue, res in routed["fills"]] == [(0, 70), (1, 50)]
+    assert b.best_ask() is None
+
+
+def test_logical_clock_stamps_trades():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 10, "gtc")
+    ex.submit_limit("sell", 10100, 10, "gtc")
+    assert ex.clock is None
+    ex.set_clock(500)
+    res = ex.submit_market("buy", 15)
+    assert [t.timestamp for t in res.trades] == [500, 500]
+    assert ex.events()[-2].kind == "set_clock"
+    with pytest.raises(ValueError):
+        ex.set_clock(499)
+
+
+def test_logical_clock_cannot_start_behind_trades():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10000, 10, "gtc")
+    ex.submit_market("buy", 5)
+    with pytest.raises(ValueError):
+        ex.set_clock(0)
+    assert ex.clock is None
+
+
+def test_implementation_shortfall():
+    ex = nanobook.Exchange()
+    ex.submit_limit("sell", 10050, 50, "gtc")
+    res = ex.submit_market("buy", 100)
+    assert nanobook.implementation_shortfall(10000, res.trades, "buy") == pytest.approx(50.0)
+    # Half filled at +50 bps, the other half charged at a 2% move.
+    bps = nanobook.implementation_shortfall(
+        10000, res.trades, "buy", target_quantity=100, final_price=10200
+    )
+    assert bps == pytest.approx(125.0)
+    with pytest.raises(ValueError):
+        nanobook.implementation_shortfall(10000, res.trades, "buy", target_quantity=100)
+
+
+def test_hidden_limit_priority():
+    ex = nanobook.Exchange()
+    hidden = ex.submit_hidden_limit("sell", 10000, 30)
+    shown = ex.submit_limit("sell", 10000, 20, "gtc")
+    ex.submit_limit("sell", 10100, 50, "gtc")
+    assert ex.best_ask() == 10000
+    assert ex.depth(5).asks[0].quantity == 20
+    assert ex.get_order(hidden.order_id).hidden
+
+    # Displayed first at the shared price, then hidden, then the worse level
+    res = ex.submit_market("buy", 60)
+    assert [(t.passive_order_id, t.quantity) for t in res.trades] == [
+        (shown.order_id, 20),
+        (hidden.order_id, 30),
+        (3, 10),
+    ]
+
+
+def test_levels_as_arrays():
+    np = pytest.importorskip("numpy")
+    ex = nanobook.Exchange()
+    for price, qty in [(9900, 10), (10000, 20), (9800, 30), (10000, 5)]:
+        ex.submit_limit("buy", price, qty, "gtc")
+    ex.submit_hidden_limit("buy", 10000, 40)
+
+    prices, sizes = ex.levels(nanobook.Side.BUY)
+    assert prices.dtype == np.int64 and sizes.dtype == np.uint64
+    assert prices.tolist() == [10000, 9900, 9800]
+    assert sizes.tolist() == [25, 10, 30]
+
+    prices, sizes = ex.levels("sell", depth=3)
+    assert len(prices) == 0 and len(sizes) == 0
+    assert ex.levels("buy", 1)[0].tolist() == [10000]
+
+
+def test_matching_policy():
+    ex = nanobook.Exchange()
+    assert ex.matching_policy() == {
+        "level_priority": "time",
+        "tie_break": "sequence",
+        "stp_policy": "off",
+    }
+
+
+def test_multi_exchange_halt_and_resume():
+    multi = nanobook.MultiExchange()
+    ask = multi.submit_limit("AAPL", "sell", 15000, 100)
+    multi.halt("AAPL")
+    assert multi.is_halted("AAPL")
+    assert not multi.is_halted("MSFT")
+
+    result = multi.submit_market("AAPL", "buy", 10)
+    assert result.reject_reason == "halted"
+    assert result.filled_quantity == 0
+    assert multi.submit_limit("AAPL", "buy", 14900, 10).reject_reason is None
+    assert multi.cancel("AAPL", ask.order_id).success
+
+    multi.halt("AAPL", all=True)
+    assert multi.submit_limit("AAPL", "sell", 15100, 10).reject_reason == "halted"
+
+    multi.resume("AAPL")
+    assert not multi.is_halted("AAPL")
+    assert multi.submit_limit("AAPL", "sell", 15100, 10).reject_reason is None
+
+
+def test_multi_replay_yields_per_message():
+    multi = nanobook.MultiExchange()
+    consumed = []
+
+    def messages():
+        for msg in [
+            ("limit", "AAPL", "sell", 15000, 100),
+            ("limit", "MSFT", nanobook.Side.SELL, 30000, 50, "gtc"),
+            ("market", "AAPL", "buy", 40),
+            ("modify", "MSFT", 1, 30100, 40),
+            ("cancel", "AAPL", 1),
+        ]:
+            consumed.append(msg[0])
+            yield msg
+
+    replay = multi.replay(messages())
+    assert consumed == []
+    first = next(replay)
+    assert consumed == ["limit"]
+    assert first.order_id == 1 and first.resting_quantity == 100
+
+    results = list(replay)
+    assert len(results) == 4
+    assert results[1].filled_quantity == 40
+    assert results[2].success
+    assert results[3].cancelled_quantity == 60
+    assert multi.symbols() == ["AAPL", "MSFT"]
+
+    with pytest.raises(ValueError, match="unknown message kind"):
+        next(multi.replay([("stop", "AAPL")]))
+    with pytest.raises(ValueError, match="malformed"):
+        next(multi.replay([("market", "AAPL", "buy")]))
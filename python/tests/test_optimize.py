@@ -0,0 +1,79 @@
+"""Tests for covariance, correlation, and allocation helpers."""
+
+import nanobook
+import pytest
+
+
+def _sample_series():
+    # One return series per asset.
+    return [
+        [0.010, -0.003, 0.007, 0.004, -0.002, 0.006, 0.003, -0.001],
+        [0.004, 0.006, -0.001, 0.003, 0.005, -0.002, 0.004, 0.002],
+        [-0.002, 0.001, 0.002, -0.004, 0.003, 0.001, -0.001, 0.002],
+    ]
+
+
+def test_rolling_corr_matrix_shape():
+    series = _sample_series()
+    mats = nanobook.rolling_corr_matrix(series, 5)
+    assert len(mats) == len(series[0]) - 5 + 1
+    for m in mats:
+        assert len(m) == 3
+        for i in range(3):
+            assert m[i][i] == 1.0
+            for j in range(3):
+                assert m[i][j] == m[j][i]
+                assert -1.0 <= m[i][j] <= 1.0
+    assert nanobook.rolling_corr_matrix(series, 20) == []
+
+
+def test_correlation_clusters():
+    a = [0.01, -0.02, 0.015, 0.005]
+    b = [0.02, -0.04, 0.03, 0.01]  # perfectly correlated with a
+    c = [0.01, 0.01, -0.01, -0.01]
+    assert nanobook.correlation_clusters([c, a, b], 0.9) == [[0], [1, 2]]
+    assert nanobook.correlation_clusters([c, a, b], -1.0) == [[0, 1, 2]]
+    assert nanobook.correlation_clusters([a, b[:3]], 0.9) == []
+
+
+def test_cov_shrinkage_symmetric_and_regularized():
+    a = [0.01, -0.02, 0.015, 0.005]
+    b = [0.02, -0.04, 0.03, 0.01]  # perfectly correlated with a
+    cov = nanobook.cov_shrinkage([a, b])
+    assert cov[0][1] == cov[1][0]
+    assert cov[0][0] * cov[1][1] - cov[0][1] ** 2 > 0
+    assert nanobook.cov_shrinkage([]) == []
+
+
+def test_min_variance_weights():
+    cov = [[1.0, 0.0], [0.0, 4.0]]
+    w = nanobook.min_variance_weights(cov, ["A", "B"])
+    assert abs(w["A"] - 0.8) < 1e-9
+    assert abs(w["B"] - 0.2) < 1e-9
+
+
+def test_mean_variance_weights_unconstrained_shorts():
+    cov = [[1.0, 0.9], [0.9, 1.0]]
+    free = nanobook.mean_variance_weights(cov, [0.5, 0.0], ["A", "B"], risk_aversion=2.0, long_only=False)
+    assert abs(free["A"] - 1.75) < 1e-12
+    assert abs(free["B"] + 0.75) < 1e-12
+    long = nanobook.mean_variance_weights(cov, [0.5, 0.0], ["A", "B"], risk_aversion=2.0)
+    assert abs(long["A"] - 1.0) < 1e-9
+
+
+def test_min_variance_weights_singular_raises():
+    cov = [[1.0, 1.0], [1.0, 1.0]]
+    with pytest.raises(ValueError):
+        nanobook.min_variance_weights(cov, ["A", "B"], long_only=False)
+    with pytest.raises(ValueError):
+        nanobook.min_variance_weights([[1.0]], ["A", "B"])
+
+
+def test_risk_parity_weights():
+    cov = [[1.0, 0.0], [0.0, 4.0]]
+    rp = nanobook.risk_parity_weights(cov, ["A", "B"])
+    assert rp["converged"]
+    assert rp["dispersion"] < 1e-10
+    assert abs(rp["weights"]["A"] - 2.0 / 3.0) < 1e-9
+    with pytest.raises(ValueError):
+        nanobook.risk_parity_weights([[1.0, 0.0], [0.0, 0.0]], ["A", "B"])
@@ -1,6 +1,8 @@
 """Tests for the Portfolio Python bindings."""
 
+import math
 import nanobook
+import pytest
 import tempfile
 import os
 
@@ -16,6 +18,147 @@ def test_cost_model_custom():
     assert cost == 1500  # 15 bps on $10,000
 
 
+def test_cost_model_maker_taker():
+    model = nanobook.CostModel(commission_bps=1, slippage_bps=5, maker_bps=2, taker_bps=4)
+    assert model.compute_fill_cost(1_000_000, "maker") == 300
+    assert model.compute_fill_cost(1_000_000, "taker") == 1000
+
+
+def test_cost_model_fee_cap():
+    model = nanobook.CostModel(commission_bps=10, slippage_bps=5, min_trade_fee=100, max_trade_fee=5000)
+    assert model.compute_cost(100_000_000) == 5000
+    assert model.compute_cost(10_000) == 100
+    assert nanobook.CostModel(commission_bps=10).compute_cost(100_000_000) == 100_000
+
+
+def test_cost_model_per_share_fee():
+    model = nanobook.CostModel(commission_bps=10, per_share_fee=1)
+    assert model.compute_cost(1_000_000) == 1000
+    assert model.compute_cost(1_000_000, 100) == 1100
+    assert model.compute_fill_cost(1_000_000, "taker", quantity=100) == 1100
+
+    p = nanobook.Portfolio(1_000_000, model)
+    p.rebalance_simple([("AAPL", 0.5)], [("AAPL", 10_000)])
+    # 50 shares: 10 bps on $5,000 plus 50¢
+    assert p.cash == 1_000_000 - 500_000 - 500 - 50
+
+
+def test_portfolio_price_scale():
+    cents = nanobook.CostModel(commission_bps=10, min_trade_fee=100)
+    fine = cents.rescaled(100, 10_000)
+    # $1 minimum fee on a $10 trade, in each scale
+    assert cents.compute_cost(1_000) == 100
+    assert fine.compute_cost(100_000) == 10_000
+
+    p = nanobook.Portfolio(1_000_0000, fine, price_scale=10_000)
+    assert p.price_scale == 10_000
+    assert p.to_currency(p.cash) == 1_000.0
+    p.rebalance_simple([("AAPL", 0.5)], [("AAPL", 100_0000)])
+    # 5 shares at $100 and the $1 minimum fee
+    assert p.to_currency(p.total_equity([("AAPL", 100_0000)])) == 999.0
+    assert "$499.0000" in repr(p)
+
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(1_000, cents, price_scale=0)
+
+
+def test_portfolio_rebalance_order():
+    prices = [("AAPL", 100_00), ("MSFT", 50_00)]
+    for order in ("sells_first", "as_listed"):
+        p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), rebalance_order=order)
+        p.rebalance_simple([("AAPL", 1.0)], prices)
+        p.rebalance_simple([("MSFT", 1.0)], prices)
+        assert p.cash == 0
+        assert p.position("MSFT").quantity == 20_000
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(1_000, nanobook.CostModel.zero(), rebalance_order="random")
+
+
+def test_portfolio_rebalance_shortfall():
+    cost = nanobook.CostModel(commission_bps=10)
+    prices = [("AAPL", 100_00)]
+    p = nanobook.Portfolio(1_000_000_00, cost)
+    assert p.rebalance_simple([("AAPL", 1.0)], prices) == [("AAPL", 10_000, 9_990, 100_00)]
+    assert p.cash >= 0
+
+    levered = nanobook.Portfolio(1_000_000_00, cost, leverage=True)
+    assert levered.rebalance_simple([("AAPL", 1.0)], prices) == []
+    assert levered.cash < 0
+
+
+def test_portfolio_pnl_totals():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    p.rebalance_simple([("AAPL", 0.2), ("MSFT", 0.2)], [("AAPL", 100_00), ("MSFT", 200_00)])
+    p.rebalance_simple([("AAPL", 0.2)], [("AAPL", 100_00), ("MSFT", 210_00)])
+    assert p.realized_pnl == 10_000_00
+    assert p.unrealized_pnl([("AAPL", 95_00)]) == -10_100_00
+    assert p.unrealized_pnl([]) == 0
+
+
+def test_portfolio_record_return_fx():
+    p = nanobook.Portfolio(1_000_00, nanobook.CostModel.zero(), currencies={"SAP": "EUR"})
+    assert p.currency("SAP") == "EUR"
+    assert p.currency("AAPL") is None
+    prices = [("AAPL", 10_00), ("SAP", 10_00)]
+    p.rebalance_simple([("AAPL", 0.4), ("SAP", 0.4)], prices)
+
+    p.record_return_fx(prices, [("EUR", 1.0)])
+    assert p.equity_curve()[-1] == 1_000_00
+    p.record_return_fx(prices, [("EUR", 1.1)])
+    assert p.equity_curve()[-1] == 1_040_00
+    assert p.total_equity_fx(prices, [("EUR", 1.1)]) == 1_040_00
+    weights = dict(p.current_weights_fx(prices, [("EUR", 1.1)]))
+    assert weights["SAP"] == pytest.approx(440 / 1040)
+
+
+def test_portfolio_rebalance_simple_fx():
+    p = nanobook.Portfolio(1_000_00, nanobook.CostModel.zero(), currencies={"SAP": "EUR"})
+    prices = [("SAP", 100_00)]
+    assert p.rebalance_simple_fx([("SAP", 0.5)], prices, [("EUR", 1.25)]) == []
+    assert p.cash == 500_00
+    assert p.total_equity_fx(prices, [("EUR", 1.25)]) == 1_000_00
+
+
+def test_cost_model_half_spread():
+    model = nanobook.CostModel(commission_bps=1, slippage_bps=10, half_spread=True)
+    assert model.compute_spread_cost(1_000_000, 100, 10) == 100 + 500
+
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 99_90, 1000)
+    multi.submit_limit("AAPL", "sell", 100_10, 1000)
+    p = nanobook.Portfolio(100_000_00, model)
+    p.rebalance_lob([("AAPL", 0.5)], multi)
+    # 500 shares at $100.10: 1 bp ($5) plus a 10¢ half-spread per share
+    assert p.cash == 100_000_00 - 50_050_00 - 500 - 5000
+
+
+def test_portfolio_apply_trade_maker():
+    ex = nanobook.Exchange()
+    resting = ex.submit_limit("sell", 10000, 1000, "gtc")
+    taker = ex.submit_limit("buy", 10000, 1000, "gtc")
+    trade = taker.trades[0]
+    assert trade.maker_order_id == resting.order_id
+    assert trade.taker_order_id == taker.order_id
+
+    model = nanobook.CostModel(maker_bps=1, taker_bps=5)
+    p = nanobook.Portfolio(1_000_000_00, model)
+    assert p.apply_trade("AAPL", trade, resting.order_id) == 1000
+    assert p.position("AAPL").quantity == -1000
+    assert p.apply_trade("AAPL", trade, 999) is None
+
+
+def test_portfolio_maker_rebate():
+    ex = nanobook.Exchange()
+    resting = ex.submit_limit("buy", 10000, 1000, "gtc")
+    trade = ex.submit_limit("sell", 10000, 1000, "gtc").trades[0]
+
+    model = nanobook.CostModel(min_trade_fee=100, maker_bps=-2)
+    assert model.compute_fill_cost(1_000_000, "maker") == -200
+    p = nanobook.Portfolio(1_000_000_00, model)
+    assert p.apply_trade("AAPL", trade, resting.order_id) == -2000
+    assert p.cash == 1_000_000_00 - 10_000_000 + 2000
+
+
 def test_cost_model_repr():
     model = nanobook.CostModel.zero()
     assert "CostModel" in repr(model)
@@ -34,6 +177,55 @@ def test_portfolio_rebalance_simple():
     assert p.cash < 1_000_000_00  # Some cash spent buying
 
 
+def test_portfolio_exposure():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    prices = [("AAPL", 100_00), ("MSFT", 100_00)]
+    assert p.gross_exposure(prices) == 0.0
+    assert p.net_exposure(prices) == 0.0
+    p.rebalance_simple([("AAPL", 1.3), ("MSFT", -0.3)], prices)
+    assert abs(p.gross_exposure(prices) - 1.6) < 1e-9
+    assert abs(p.net_exposure(prices) - 1.0) < 1e-9
+
+
+def test_portfolio_snapshot_positions():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    p.rebalance_simple([("AAPL", 0.1)], [("AAPL", 100_00)])
+    snap = p.snapshot([("AAPL", 110_00)])
+    assert snap["num_positions"] == 1
+    pos = snap["positions"]["AAPL"]
+    assert pos["quantity"] == 1000
+    assert pos["avg_entry_price"] == 100_00
+    assert pos["unrealized_pnl"] == 10_000_00
+    assert pos["weight"] == snap["weights"]["AAPL"]
+
+
+def test_portfolio_incentive_fee():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), incentive_fee=0.2, hurdle=0.05)
+    p.rebalance_simple([("AAPL", 1.0)], [("AAPL", 100_00)])
+    p.record_return([("AAPL", 104_00)])
+    assert p.incentive_fees == 0
+    p.record_return([("AAPL", 110_00)])
+    assert p.incentive_fees == 10_000_00
+    assert p.high_water_mark == 1_090_000_00
+    assert p.equity_curve()[-1] == 1_090_000_00
+
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(100, nanobook.CostModel.zero(), incentive_fee=1.5)
+
+
+def test_portfolio_cash_yield():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), cash_yield=0.001)
+    p.rebalance_simple([("AAPL", 0.6)], [("AAPL", 100_00)])
+    p.record_return([("AAPL", 100_00)])
+    assert p.cash_interest == 0
+    p.record_return([("AAPL", 100_00)])
+    assert p.cash_interest == 400_00
+    assert p.cash == 400_400_00
+
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(100, nanobook.CostModel.zero(), cash_yield=-0.01)
+
+
 def test_portfolio_equity():
     p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
     equity = p.total_equity([("AAPL", 150_00)])
@@ -67,6 +259,109 @@ def test_compute_metrics():
     assert "Metrics" in repr(m)
 
 
+def test_compute_metrics_batch_matches_single():
+    series = [[0.01, -0.005, 0.02], [], [0.03, 0.01, -0.02, 0.005]]
+    results = nanobook.compute_metrics_batch(series, 12.0, 0.001)
+    assert len(results) == 3
+    assert results[1] is None
+    for returns, got in zip([series[0], series[2]], [results[0], results[2]]):
+        want = nanobook.py_compute_metrics(returns, 12.0, 0.001)
+        assert got.sharpe == want.sharpe
+        assert got.max_drawdown == want.max_drawdown
+        assert got.num_periods == want.num_periods
+
+
+def test_portfolio_compute_metrics_defaults():
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    assert p.compute_metrics() is None
+    assert nanobook.py_compute_metrics(p.returns()) is None
+
+    p.rebalance_simple([("AAPL", 1.0)], [("AAPL", 100_00)])
+    for price in [101_00, 99_00, 104_00]:
+        p.record_return([("AAPL", price)])
+    m = p.compute_metrics()
+    assert m.to_dict() == nanobook.py_compute_metrics(p.returns()).to_dict()
+    assert m.to_dict() == p.compute_metrics(252.0, 0.0).to_dict()
+    assert p.compute_metrics(minimum_acceptable_return=0.01).sortino < m.sortino
+
+
+def test_streaming_metrics_matches_batch():
+    returns = [0.01, -0.02, 0.015, 0.003, -0.004, 0.007]
+    live = nanobook.StreamingMetrics(252.0, 0.0)
+    for r in returns:
+        live.update(r)
+    m = nanobook.py_compute_metrics(returns, 252.0, 0.0)
+    assert live.num_periods == 6
+    assert abs(live.sharpe - m.sharpe) < 1e-9
+    assert abs(live.sortino - m.sortino) < 1e-9
+    assert abs(live.max_drawdown - m.max_drawdown) < 1e-12
+
+
+def test_compute_metrics_sortino_mar():
+    returns = [0.02, -0.01, 0.015, 0.004, -0.003, 0.008]
+    base = nanobook.py_compute_metrics(returns, 252.0, 0.0)
+    same = nanobook.py_compute_metrics(returns, 252.0, 0.0, minimum_acceptable_return=0.0)
+    target = nanobook.py_compute_metrics(returns, 252.0, 0.0, minimum_acceptable_return=0.005)
+    assert same.sortino == base.sortino
+    assert target.sortino < base.sortino
+    assert target.sharpe == base.sharpe
+
+
+def test_compute_metrics_calmar_lookback():
+    returns = [0.05, -0.30, 0.10, 0.02, -0.01, 0.03, -0.02, 0.01, 0.02]
+    full = nanobook.py_compute_metrics(returns, 12.0, 0.0)
+    trailing = nanobook.py_compute_metrics(returns, 12.0, 0.0, calmar_lookback=6)
+    short = nanobook.py_compute_metrics(returns, 12.0, 0.0, calmar_lookback=36)
+    assert trailing.calmar > full.calmar
+    assert trailing.max_drawdown == full.max_drawdown
+    assert short.calmar == full.calmar
+
+
+def test_compute_metrics_avg_win_loss():
+    m = nanobook.py_compute_metrics([0.03, -0.01, 0.0, 0.01, -0.02], 252.0, 0.0)
+    assert abs(m.avg_win - 0.02) < 1e-12
+    assert abs(m.avg_loss + 0.015) < 1e-12
+    assert abs(m.expectancy - 0.002) < 1e-12
+    wins = nanobook.py_compute_metrics([0.01, 0.02], 252.0, 0.0)
+    assert math.isnan(wins.avg_loss)
+
+
+def test_metrics_to_dict_and_json():
+    import json
+
+    m = nanobook.py_compute_metrics([0.01, 0.02, 0.03], 252.0, 0.0)
+    d = m.to_dict()
+    assert d["sharpe"] == m.sharpe
+    assert d["num_periods"] == 3
+    assert {"total_return", "cvar_95", "expectancy"} <= d.keys()
+    # No losing periods: avg_loss stays NaN in the dict but is null in JSON
+    assert math.isnan(d["avg_loss"])
+    parsed = json.loads(nanobook.metrics_to_json(m))
+    assert parsed.keys() == d.keys()
+    assert parsed["avg_loss"] is None
+    assert parsed["num_periods"] == 3
+
+    many = json.loads(nanobook.metrics_to_json([m, None], pretty=True))
+    assert many[1] is None and many[0]["sharpe"] == parsed["sharpe"]
+
+
+def test_argmax_by_and_summarize():
+    results = [
+        nanobook.py_compute_metrics([0.01, 0.02, -0.01], 12.0, 0.0),
+        None,
+        nanobook.py_compute_metrics([0.02, 0.03, 0.01], 12.0, 0.0),
+    ]
+    assert nanobook.argmax_by(results, "sharpe") == 2
+    assert nanobook.argmax_by(results, lambda m: -m.max_drawdown) == 2
+
+    s = nanobook.summarize(results, "total_return")
+    assert s["count"] == 2
+    assert s["best_index"] == 2 and s["worst_index"] == 0
+    assert s["mean"] == pytest.approx((results[0].total_return + results[2].total_return) / 2)
+    assert nanobook.summarize([None], "sharpe") is None
+    assert nanobook.argmax_by([], "sharpe") is None
+
+
 def test_compute_metrics_empty():
     m = nanobook.py_compute_metrics([], 252.0, 0.0)
     assert m is None
@@ -102,3 +397,218 @@ def test_portfolio_save_load():
 def test_portfolio_repr():
     p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
     assert "Portfolio" in repr(p)
+
+
+def test_run_long_short_dollar_neutral():
+    prices = [
+        [("A", 100_00), ("B", 100_00)],
+        [("A", 110_00), ("B", 90_00)],
+        [("A", 121_00), ("B", 81_00)],
+    ]
+    scores = [[("A", 1.0), ("B", -1.0)]] * 3
+    result = nanobook.run_long_short(scores, prices, 2, 1_000_000_00)
+    assert result.metrics is not None
+    assert result.metrics.total_return > 0
+    assert result.portfolio.position("A").quantity > 0
+    assert result.portfolio.position("B").quantity < 0
+
+
+def test_rebalance_cost_aware_band():
+    cm = nanobook.CostModel(commission_bps=10)
+    p = nanobook.Portfolio(1_000_000_00, cm)
+    fills = p.rebalance_cost_aware([("AAPL", 0.5)], [("AAPL", 100_00)], 0.01)
+    assert fills == [("AAPL", 4_000, 100_00, 400_00)]
+    # Now inside the band: no trade.
+    assert p.rebalance_cost_aware([("AAPL", 0.45)], [("AAPL", 100_00)], 0.01) == []
+
+
+def test_backtest_turnover():
+    def rotate(bar_index, prices, portfolio):
+        return [("A" if bar_index % 2 == 0 else "B", 1.0)]
+
+    price_series = [{"A": 100_00, "B": 100_00}] * 4
+    res = nanobook.run_backtest(rotate, price_series, 1_000_000_00, nanobook.CostModel.zero(), periods_per_year=12.0)
+    assert abs(res.portfolio.turnover - 3.0) < 1e-12
+    assert abs(res.turnover - 9.0) < 1e-12
+
+    hold = nanobook.run_backtest(lambda i, p, pf: [("A", 1.0)], price_series, 1_000_000_00, nanobook.CostModel.zero())
+    assert hold.turnover == 0.0
+
+
+def test_fifo_cost_basis():
+    def run(cost_basis):
+        p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), cost_basis=cost_basis)
+        p.rebalance_simple([("AAPL", 0.1)], [("AAPL", 100_00)])
+        p.rebalance_simple([("AAPL", 0.2)], [("AAPL", 110_00)])
+        p.rebalance_simple([("AAPL", 0.1)], [("AAPL", 120_00)])
+        return p.position("AAPL")
+
+    avg, fifo = run("average"), run("fifo")
+    assert fifo.cost_basis == "fifo" and avg.lots == []
+    assert fifo.quantity == avg.quantity
+    # FIFO sells the $100 lot first, so it realizes more on the way up
+    assert fifo.realized_pnl > avg.realized_pnl
+    assert fifo.realized_pnl == 979 * 20_00
+    assert fifo.lots == [(21, 100_00), (836, 110_00)]
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(100_00, nanobook.CostModel.zero(), cost_basis="lifo")
+
+
+def test_portfolio_weight_policy():
+    prices = [("AAPL", 100_00), ("MSFT", 200_00)]
+    over = [("AAPL", 1.2), ("MSFT", -0.4)]
+
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), normalize_gross=1.0)
+    p.rebalance_simple(over, prices)
+    assert p.position("AAPL").quantity == 7_500
+    assert p.position("MSFT").quantity == -1_250
+
+    strict = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), max_gross=1.5)
+    with pytest.raises(ValueError, match="exceeds"):
+        strict.rebalance_simple(over, prices)
+    assert strict.cash == 1_000_000_00
+    strict.rebalance_simple([("AAPL", 1.0), ("MSFT", -0.5)], prices)
+
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(100_00, nanobook.CostModel.zero(), normalize_gross=1.0, max_gross=1.0)
+
+
+def test_portfolio_duplicate_symbols():
+    prices = [("AAPL", 100_00)]
+    twice = [("AAPL", 0.25), ("AAPL", 0.25)]
+
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    with pytest.raises(ValueError, match="duplicate"):
+        p.rebalance_simple(twice, prices)
+    with pytest.raises(ValueError, match="duplicate symbol AAPL in prices"):
+        p.rebalance_simple([("AAPL", 0.5)], [("AAPL", 100_00), ("AAPL", 101_00)])
+    assert p.cash == 1_000_000_00
+
+    summed = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero(), duplicates="sum")
+    summed.rebalance_simple(twice, prices)
+    assert summed.position("AAPL").quantity == 5_000
+
+    with pytest.raises(ValueError):
+        nanobook.Portfolio(100_00, nanobook.CostModel.zero(), duplicates="last")
+
+
+def test_holding_period():
+    def rotate(bar_index, prices, portfolio):
+        return [("A" if bar_index % 2 == 0 else "B", 1.0)]
+
+    price_series = [{"A": 100_00, "B": 100_00}] * 4
+    res = nanobook.run_backtest(rotate, price_series, 1_000_000_00, nanobook.CostModel.zero())
+    # Every rotation closes a position held for one bar
+    assert res.portfolio.average_holding_period == 1.0
+    assert res.portfolio.position("B").holding_period == 1.0
+    assert res.portfolio.position("A").closed_holding_period == 1.0
+    snap = res.portfolio.snapshot([("A", 100_00), ("B", 100_00)])
+    assert snap["average_holding_period"] == 1.0
+    assert snap["positions"]["B"]["holding_period"] == 1.0
+    assert nanobook.Portfolio(100_00, nanobook.CostModel.zero()).average_holding_period is None
+
+
+def test_backtest_record_history():
+    price_series = [{"A": 100_00}, {"A": 110_00}, {"A": 99_00}]
+    res = nanobook.run_backtest(
+        lambda i, p, pf: [("A", 1.0)], price_series, 1_000_000_00, nanobook.CostModel.zero(), record_history=True
+    )
+    assert len(res.history) == 3
+    assert [snap["equity"] for snap in res.history] == res.portfolio.equity_curve()[1:]
+    assert set(res.history[-1]["weights"]) == {"A"}
+    assert res.history[0]["positions"]["A"]["quantity"] == 10_000
+
+    plain = nanobook.run_backtest(lambda i, p, pf: [("A", 1.0)], price_series, 1_000_000_00, nanobook.CostModel.zero())
+    assert plain.history == []
+
+
+def test_backtest_benchmark_relative_metrics():
+    price_series = [{"A": 100_00}, {"A": 110_00}, {"A": 99_00}, {"A": 104_00}]
+    hold = lambda i, p, pf: [("A", 1.0)]
+    plain = nanobook.run_backtest(hold, price_series, 1_000_000_00, nanobook.CostModel.zero())
+    assert plain.relative_metrics is None
+
+    own = plain.portfolio.returns()
+    res = nanobook.run_backtest(hold, price_series, 1_000_000_00, nanobook.CostModel.zero(), benchmark=own)
+    rel = res.relative_metrics
+    assert abs(rel.beta - 1.0) < 1e-12
+    assert rel.tracking_error == 0.0
+
+    with pytest.raises(ValueError):
+        nanobook.run_backtest(hold, price_series, 1_000_000_00, nanobook.CostModel.zero(), benchmark=own[:2])
+
+
+def test_backtest_warmup():
+    price_series = [{"A": 100_00}, {"A": 120_00}, {"A": 80_00}, {"A": 88_00}]
+    seen = []
+
+    def strategy(i, prices, pf):
+        seen.append(i)
+        return [("A", 1.0)]
+
+    res = nanobook.run_backtest(strategy, price_series, 1_000_000_00, nanobook.CostModel.zero(), warmup=2)
+    assert seen == [0, 1, 2, 3]
+    assert res.warmup_bars == 2
+    returns = res.portfolio.returns()
+    assert returns[:3] == [0.0, 0.0, 0.0]
+    assert abs(returns[3] - 0.1) < 1e-10
+
+
+def test_rebalance_simple_fill_prices():
+    p = nanobook.Portfolio(100_000_00, nanobook.CostModel.zero())
+    p.rebalance_simple([("AAPL", 1.0)], [("AAPL", 100_00)], fill_prices=[("AAPL", 125_00)])
+    pos = p.position("AAPL")
+    assert pos.quantity == 800
+    assert pos.avg_entry_price == 125_00
+
+
+def test_backtest_fill_delay():
+    price_series = [{"A": 200_00}, {"A": 100_00}, {"A": 125_00}, {"A": 150_00}]
+
+    def below_150(i, prices, pf):
+        return [("A", 1.0)] if prices["A"] < 150_00 else []
+
+    res = nanobook.run_backtest(below_150, price_series, 100_000_00, nanobook.CostModel.zero(), fill_delay=1)
+    pos = res.portfolio.position("A")
+    assert pos.avg_entry_price == 125_00
+    assert pos.quantity == 800
+
+
+def test_backtest_cash_yield():
+    price_series = [{"A": 100_00}] * 3
+
+    def half(i, prices, pf):
+        return [("A", 0.5)]
+
+    res = nanobook.run_backtest(half, price_series, 100_000_00, nanobook.CostModel.zero(), cash_yield=0.01)
+    returns = res.portfolio.returns()
+    assert returns[0] == 0.0
+    assert abs(returns[1] - 0.005) < 1e-9
+
+
+def test_estimate_rebalance_cost():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 99_00, 1_000)
+    multi.submit_limit("AAPL", "sell", 101_00, 500)
+    multi.submit_limit("AAPL", "sell", 103_00, 500)
+
+    p = nanobook.Portfolio(100_000_00, nanobook.CostModel.zero())
+    est = p.estimate_rebalance_cost([("AAPL", 1.0)], multi)
+    assert est["slippage"] == 2_000_00
+    assert est["total"] == 2_000_00
+    aapl = est["symbols"]["AAPL"]
+    assert aapl["filled_quantity"] == 1_000
+    assert aapl["vwap"] == 102_00
+    # Books untouched
+    assert multi.get_or_create("AAPL").best_ask() == 101_00
+
+
+def test_weights_shares_conversion():
+    shares = nanobook.weights_to_shares([("AAPL", 0.5)], 100_000_00, [("AAPL", 150_00)], lot_size=100)
+    assert shares == [("AAPL", 300)]
+    assert nanobook.weights_to_shares([("AAPL", 0.5)], 100_000_00, [("AAPL", 150_00)]) == [("AAPL", 333)]
+    with pytest.raises(ValueError):
+        nanobook.weights_to_shares([("AAPL", 0.5)], 100_000_00, [("AAPL", 150_00)], lot_size=0)
+
+    weights = nanobook.shares_to_weights([("AAPL", 300), ("MSFT", 100)], [("AAPL", 100_00), ("MSFT", 100_00)])
+    assert weights == [("AAPL", 0.75), ("MSFT", 0.25)]
@@ -49,3 +49,29 @@ def test_sweep_multi_stock():
     )
     assert len(results) == 10
     assert all(r is not None for r in results)
+
+
+def test_sweep_top_k_picks_best_scored():
+    prices = [
+        [("AAPL", 100_00), ("MSFT", 100_00)],
+        [("AAPL", 110_00), ("MSFT", 90_00)],
+        [("AAPL", 120_00), ("MSFT", 80_00)],
+    ]
+    scores = [[("AAPL", 1.0), ("MSFT", 0.0)]] * 3
+    results = nanobook.sweep_top_k(scores, prices, [1, 2], 1_000_000_00)
+    assert len(results) == 2
+    top1, top2 = results
+    assert top1.total_return > 0
+    assert top1.total_return > top2.total_return
+
+
+def test_sweep_top_k_ties_are_deterministic():
+    prices = [
+        [("AAPL", 100_00), ("MSFT", 100_00)],
+        [("AAPL", 110_00), ("MSFT", 90_00)],
+    ]
+    forward = [[("AAPL", 1.0), ("MSFT", 1.0)]] * 2
+    reverse = [[("MSFT", 1.0), ("AAPL", 1.0)]] * 2
+    a = nanobook.sweep_top_k(forward, prices, [1], 1_000_000_00)[0]
+    b = nanobook.sweep_top_k(reverse, prices, [1], 1_000_000_00)[0]
+    assert a.total_return == b.total_return
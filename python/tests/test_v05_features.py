@@ -49,6 +49,32 @@ def test_book_snapshot_empty_analytics():
     assert snap.imbalance() is None
     assert snap.weighted_mid() is None
 
+def test_book_snapshot_constructor():
+    snap = nanobook.BookSnapshot([(10000, 300)], [(10200, 100)])
+    assert snap.weighted_mid() == 10150.0
+    assert abs(snap.imbalance() - 0.5) < 1e-6
+    assert snap.bids[0].order_count == 1
+
+def test_book_snapshot_constructor_rejects_unsorted():
+    with pytest.raises(ValueError):
+        nanobook.BookSnapshot([(9900, 100), (10000, 100)], [])
+    with pytest.raises(ValueError):
+        nanobook.BookSnapshot([], [(10200, 100), (10100, 100)])
+
+def test_book_snapshot_diff():
+    ex = nanobook.Exchange()
+    ex.submit_limit("buy", 10000, 100)
+    ex.submit_limit("sell", 10100, 50)
+    before = ex.full_book()
+    ex.submit_limit("buy", 10000, 20)
+    ex.submit_limit("sell", 10100, 50)  # takes the whole ask level
+    changes = before.diff(ex.full_book())
+    assert changes == [
+        {"side": "buy", "price": 10000, "kind": "changed", "quantity": 120, "order_count": 2},
+        {"side": "sell", "price": 10100, "kind": "removed", "quantity": 0, "order_count": 0},
+    ]
+    assert ex.full_book().diff(ex.full_book()) == []
+
 def test_exchange_events_and_replay():
     ex = nanobook.Exchange()
     ex.submit_limit("buy", 10000, 100)
@@ -171,6 +197,61 @@ def test_multiexchange_forwarding():
     assert price_dict["AAPL"] == (10000, None)
     assert price_dict["MSFT"] == (None, 20000)
 
+def test_multiexchange_best_prices_with_sizes():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 10000, 100)
+    multi.submit_limit("AAPL", "buy", 10000, 50)
+    multi.submit_limit("AAPL", "sell", 10100, 30)
+    assert multi.best_prices(include_sizes=True) == [("AAPL", 10000, 150, 10100, 30)]
+    assert multi.best_prices() == [("AAPL", 10000, 10100)]
+    ex = multi.get_or_create("AAPL")
+    assert ex.top_of_book() == ((10000, 150), (10100, 30))
+
+def test_multiexchange_long_symbol_names():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("BERKSHIRE_HATHAWAY_B", "sell", 40000, 10)
+    multi.submit_limit("AAPL", "buy", 10000, 100)
+    assert sorted(multi.symbols()) == ["AAPL", "BERKSHIRE_HATHAWAY_B"]
+    price_dict = {p[0]: (p[1], p[2]) for p in multi.best_prices()}
+    assert price_dict["BERKSHIRE_HATHAWAY_B"] == (None, 40000)
+
+def test_resting_order_count_and_quantity():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 10000, 100)
+    multi.submit_limit("AAPL", "buy", 9900, 50)
+    res = multi.submit_limit("AAPL", "sell", 10100, 30)
+    assert multi.resting_order_count("AAPL") == 3
+    assert multi.total_resting_quantity("AAPL", "buy") == 150
+    assert multi.resting_order_count("GOOG") == 0
+    multi.cancel("AAPL", res.order_id)
+    assert multi.total_resting_quantity("AAPL", "sell") == 0
+
+    ex = multi.get_or_create("AAPL")
+    assert ex.resting_order_count() == 2
+    assert ex.total_resting_quantity("buy") == 150
+
+def test_multiexchange_symbol_order_is_sorted():
+    multi = nanobook.MultiExchange()
+    for name in ["NVDA", "ZZZZ_LONG_NAME_CO", "AAPL", "MSFT", "AAAA_LONG_NAME_CO"]:
+        multi.submit_limit(name, "buy", 10000, 1)
+    expected = ["AAAA_LONG_NAME_CO", "AAPL", "MSFT", "NVDA", "ZZZZ_LONG_NAME_CO"]
+    assert multi.symbols() == expected
+    assert [p[0] for p in multi.best_prices()] == expected
+
+def test_multiexchange_clear():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 10000, 100)
+    multi.submit_limit("MSFT", "sell", 20000, 50)
+    assert multi.clear("AAPL")
+    assert not multi.clear("GOOG")
+    price_dict = {p[0]: (p[1], p[2]) for p in multi.best_prices()}
+    assert price_dict["AAPL"] == (None, None)
+    assert price_dict["MSFT"] == (None, 20000)
+    assert multi.submit_limit("AAPL", "buy", 10000, 10).order_id == 1
+    multi.clear_all()
+    assert sorted(multi.symbols()) == ["AAPL", "MSFT"]
+    assert all(p[1] is None and p[2] is None for p in multi.best_prices())
+
 def test_multiexchange_complex_forwarding():
     multi = nanobook.MultiExchange()
     multi.submit_limit("AAPL", "buy", 10000, 100)
@@ -210,6 +291,65 @@ def test_run_backtest_callback():
     assert res.metrics.total_return > 0
     assert "BacktestResult" in repr(res)
 
+def test_run_backtest_callback_arguments():
+    seen = []
+
+    def strat(bar_index, prices, portfolio):
+        seen.append((bar_index, dict(prices), portfolio.cash))
+        return [("AAPL", 0.5)]
+
+    res = nanobook.run_backtest(
+        strat, [{"AAPL": 100_00}, {"AAPL": 105_00}], 10_000_00, nanobook.CostModel.zero()
+    )
+    assert [(i, p) for i, p, _ in seen] == [(0, {"AAPL": 100_00}), (1, {"AAPL": 105_00})]
+    # The second call sees the portfolio after the first rebalance.
+    assert seen[0][2] == 10_000_00
+    assert seen[1][2] == 5_000_00
+    assert res.portfolio.position("AAPL").quantity == 50
+    assert res.metrics is not None
+
+def test_price_series_from_matrix():
+    np = pytest.importorskip("numpy")
+    prices = np.array([[100_00.0, np.nan], [110_00.0, 50_00.0], [np.nan, 55_00.0]])
+
+    series = nanobook.PriceSeries.from_matrix(prices, ["AAPL", "MSFT"])
+    assert len(series) == 3
+    assert series.bar(0) == {"AAPL": 100_00}
+    assert series.bar(2) == {"AAPL": 110_00, "MSFT": 55_00}
+    assert nanobook.PriceSeries.from_matrix(prices, ["AAPL", "MSFT"], missing="skip").bar(2) == {"MSFT": 55_00}
+
+    with pytest.raises(ValueError):
+        nanobook.PriceSeries.from_matrix(prices, ["AAPL"])
+    with pytest.raises(ValueError):
+        nanobook.PriceSeries.from_matrix(prices, ["AAPL", "MSFT"], missing="zero")
+
+    res = nanobook.run_backtest(
+        lambda i, p, port: [("AAPL", 1.0)], series, 100_000_00, nanobook.CostModel.zero()
+    )
+    assert len(res.portfolio.returns()) == 3
+
+def test_run_backtest_matrix_matches_callback():
+    np = pytest.importorskip("numpy")
+    symbols = ["AAPL", "MSFT"]
+    prices = np.array([[100_00.0, 200_00.0], [110_00.0, 190_00.0], [105_00.0, 210_00.0]])
+    weights = np.array([[0.5, 0.5], [1.0, np.nan], [0.2, 0.7]])
+
+    def strat(i, p, port):
+        return [(s, 0.0 if np.isnan(w) else float(w)) for s, w in zip(symbols, weights[i])]
+
+    fast = nanobook.run_backtest_matrix(weights, prices, symbols, 1_000_000_00, nanobook.CostModel(10))
+    slow = nanobook.run_backtest(
+        strat,
+        [dict(zip(symbols, map(int, row))) for row in prices],
+        1_000_000_00,
+        nanobook.CostModel(10),
+    )
+    assert fast.portfolio.equity_curve() == slow.portfolio.equity_curve()
+    assert fast.metrics.sharpe == slow.metrics.sharpe
+
+    with pytest.raises(ValueError):
+        nanobook.run_backtest_matrix(weights[:2], prices, symbols, 100_00, nanobook.CostModel.zero())
+
 def test_strategy_exception_handling():
     def breaking_strat(bar_index, prices, portfolio):
         if bar_index == 1:
@@ -241,6 +381,18 @@ def test_portfolio_rebalance_lob():
     assert portfolio.position("AAPL").quantity > 0
     assert multi.get_or_create("AAPL").best_ask() is None # Swept the book
 
+def test_portfolio_rebalance_lob_fill_ratio():
+    multi = nanobook.MultiExchange()
+    multi.submit_limit("AAPL", "buy", 99_00, 1000)
+    multi.submit_limit("AAPL", "sell", 101_00, 300)
+
+    portfolio = nanobook.Portfolio(100_000_00, nanobook.CostModel.zero())
+    portfolio.rebalance_lob([("AAPL", 0.5)], multi, fill_ratio=0.5)
+    # 500 wanted, 300 marketable, half of that filled
+    assert portfolio.position("AAPL").quantity == 150
+    with pytest.raises(ValueError):
+        portfolio.rebalance_lob([("AAPL", 0.5)], multi, fill_ratio=0.0)
+
 def test_clear_order_history():
     ex = nanobook.Exchange()
     ex.submit_limit("buy", 10000, 100)
@@ -340,3 +492,14 @@ def test_cost_model_min_fee():
     model = nanobook.CostModel(commission_bps=0, slippage_bps=0, min_trade_fee=100)
     assert model.compute_cost(1000) == 100
     assert model.compute_cost(1000000) == 100
+
+def test_hashed_symbol_for_long_names():
+    assert nanobook.hashed_symbol("AAPL") == "AAPL"
+    h = nanobook.hashed_symbol("BERKSHIRE_HATHAWAY_B")
+    assert h.startswith("#") and len(h) == 8
+    assert h == nanobook.hashed_symbol("BERKSHIRE_HATHAWAY_B")
+    p = nanobook.Portfolio(1_000_000_00, nanobook.CostModel.zero())
+    p.rebalance_simple([(h, 0.5)], [(h, 100_00)])
+    assert p.position(h).quantity == 5_000
+    with pytest.raises(ValueError):
+        nanobook.hashed_symbol("BERKSHIRE_HATHAWAY_B", hash_long=False)
@@ -168,8 +168,7 @@ pub fn backtest_weights_with_options(
 
     let cost_model = CostModel {
         commission_bps: cost_bps,
-        slippage_bps: 0,
-        min_trade_fee: 0,
+        ..CostModel::zero()
     };
 
     let mut portfolio = Portfolio::new(initial_cash_cents, cost_model);
@@ -7,7 +7,10 @@
 
 use rustc_hash::FxHashMap;
 
-use crate::{Order, OrderId, Price, PriceLevels, Quantity, Side, TimeInForce, Timestamp, TradeId};
+use crate::{
+    LevelPriority, Order, OrderId, OrderOwner, Price, PriceLevels, Quantity, Side, TieBreak,
+    TimeInForce, Timestamp, TradeId, ValidationError, level::PriorityKey,
+};
 
 // Re-import for tests only
 #[cfg(test)]
@@ -18,6 +21,10 @@ use crate::OrderStatus;
 /// Maintains both sides of the book plus a central index of all orders
 /// (active and historical) for O(1) lookup.
 ///
+/// [Hidden](Order::hidden) orders rest in a separate pair of queues that
+/// only the matcher reads, so best prices, spreads, and snapshots describe
+/// the displayed book alone.
+///
 /// ```
 /// use nanobook::{Order, OrderBook, Price, Side, TimeInForce};
 ///
@@ -40,12 +47,23 @@ use crate::OrderStatus;
 /// assert_eq!(book.active_order_count(), 0);
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderBook {
     /// Buy orders, sorted by price descending (best = highest)
     bids: PriceLevels,
     /// Sell orders, sorted by price ascending (best = lowest)
     asks: PriceLevels,
+    /// Hidden buy orders, matched but not displayed
+    #[cfg_attr(feature = "serde", serde(default = "hidden_bids"))]
+    hidden_bids: PriceLevels,
+    /// Hidden sell orders, matched but not displayed
+    #[cfg_attr(feature = "serde", serde(default = "hidden_asks"))]
+    hidden_asks: PriceLevels,
     /// All orders indexed by ID (includes filled/cancelled for history)
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
     pub(crate) orders: FxHashMap<OrderId, Order>,
     /// Next order ID to assign
     next_order_id: u64,
@@ -53,6 +71,35 @@ pub struct OrderBook {
     next_trade_id: u64,
     /// Next timestamp to assign (monotonic counter)
     next_timestamp: u64,
+    /// First order ID, restored by [`Self::clear`]
+    order_id_start: u64,
+    /// First trade ID, restored by [`Self::clear`]
+    trade_id_start: u64,
+    /// Next arrival sequence number to assign
+    next_sequence: u64,
+    /// Queue order among equal timestamps at one price
+    tie_break: TieBreak,
+    /// Ranking of resting orders within a price level
+    level_priority: LevelPriority,
+    /// Logical clock that stamps trades, if set (see [`Self::set_clock`])
+    #[cfg_attr(feature = "serde", serde(default))]
+    clock: Option<Timestamp>,
+    /// Resting (bid, ask) quantity per owner
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "crate::sorted_map::serialize")
+    )]
+    owner_open: FxHashMap<OrderOwner, (Quantity, Quantity)>,
+}
+
+#[cfg(feature = "serde")]
+fn hidden_bids() -> PriceLevels {
+    PriceLevels::new(Side::Buy)
+}
+
+#[cfg(feature = "serde")]
+fn hidden_asks() -> PriceLevels {
+    PriceLevels::new(Side::Sell)
 }
 
 impl OrderBook {
@@ -61,10 +108,64 @@ impl OrderBook {
         Self {
             bids: PriceLevels::new(Side::Buy),
             asks: PriceLevels::new(Side::Sell),
+            hidden_bids: PriceLevels::new(Side::Buy),
+            hidden_asks: PriceLevels::new(Side::Sell),
             orders: FxHashMap::default(),
             next_order_id: 1,
             next_trade_id: 1,
             next_timestamp: 1,
+            order_id_start: 1,
+            trade_id_start: 1,
+            next_sequence: 1,
+            tie_break: TieBreak::Sequence,
+            level_priority: LevelPriority::Time,
+            clock: None,
+            owner_open: FxHashMap::default(),
+        }
+    }
+
+    /// Set how orders with equal timestamps at the same price are queued.
+    ///
+    /// Builder form; call it before adding orders, since orders already
+    /// queued keep their position.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Returns the configured tie-break policy.
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// Set how resting orders within a price level are ranked.
+    ///
+    /// Builder form; call it before adding orders, since orders already
+    /// queued keep their position. See [`LevelPriority`].
+    pub fn with_level_priority(mut self, level_priority: LevelPriority) -> Self {
+        self.level_priority = level_priority;
+        self
+    }
+
+    /// Returns the configured level priority.
+    pub fn level_priority(&self) -> LevelPriority {
+        self.level_priority
+    }
+
+    /// Create an empty order book whose order and trade IDs start at the
+    /// given values instead of 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order_id_start` is 0 (reserved for level tombstones).
+    pub fn with_id_start(order_id_start: u64, trade_id_start: u64) -> Self {
+        assert!(order_id_start != 0, "OrderId(0) is reserved");
+        Self {
+            next_order_id: order_id_start,
+            next_trade_id: trade_id_start,
+            order_id_start,
+            trade_id_start,
+            ..Self::new()
         }
     }
 
@@ -91,6 +192,49 @@ impl OrderBook {
         ts
     }
 
+    /// Set the logical clock that stamps trades.
+    ///
+    /// Until the clock is first set, each trade takes the next value of
+    /// the internal timestamp counter, so every trade has a distinct
+    /// timestamp. Once set, every trade is stamped with the current clock
+    /// value: all trades between two clock updates, including several
+    /// fills of one aggressor, share a timestamp. Order timestamps (and so
+    /// queue priority) still come from the counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `now` is earlier than [`Self::min_clock`]: the current
+    /// clock value, or the last counter value if the clock is unset, so
+    /// trade timestamps never decrease.
+    pub fn set_clock(&mut self, now: Timestamp) {
+        assert!(
+            now >= self.min_clock(),
+            "logical clock cannot move backwards"
+        );
+        self.clock = Some(now);
+    }
+
+    /// Current logical clock value (`None` if trades are stamped from the
+    /// counter).
+    pub fn clock(&self) -> Option<Timestamp> {
+        self.clock
+    }
+
+    /// Earliest value [`Self::set_clock`] accepts: the current clock, or
+    /// until it is set, the last timestamp taken from the counter.
+    pub fn min_clock(&self) -> Timestamp {
+        self.clock.unwrap_or(self.next_timestamp - 1)
+    }
+
+    /// Timestamp for a new trade: the logical clock if set, else the next
+    /// counter value.
+    pub(crate) fn trade_timestamp(&mut self) -> Timestamp {
+        match self.clock {
+            Some(now) => now,
+            None => self.next_timestamp(),
+        }
+    }
+
     /// Peek at what the next order ID would be (without consuming it).
     pub fn peek_next_order_id(&self) -> OrderId {
         OrderId(self.next_order_id)
@@ -171,6 +315,24 @@ impl OrderBook {
         self.side_mut(side.opposite())
     }
 
+    /// Get the hidden orders on one side.
+    pub fn hidden_side(&self, side: Side) -> &PriceLevels {
+        match side {
+            Side::Buy => &self.hidden_bids,
+            Side::Sell => &self.hidden_asks,
+        }
+    }
+
+    /// The displayed or hidden queues of one side.
+    pub(crate) fn queues_mut(&mut self, side: Side, hidden: bool) -> &mut PriceLevels {
+        match (side, hidden) {
+            (Side::Buy, false) => &mut self.bids,
+            (Side::Sell, false) => &mut self.asks,
+            (Side::Buy, true) => &mut self.hidden_bids,
+            (Side::Sell, true) => &mut self.hidden_asks,
+        }
+    }
+
     // === Best prices ===
 
     /// Get the best bid price (highest buy price).
@@ -205,8 +367,70 @@ impl OrderBook {
         }
     }
 
+    /// Check if the book is locked (best bid == best ask).
+    ///
+    /// A locked book is also crossed under [`Self::is_crossed`].
+    pub fn is_locked(&self) -> bool {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => bid == ask,
+            _ => false,
+        }
+    }
+
+    /// Quantity resting on the book for `owner` on `side`, hidden orders
+    /// included.
+    pub(crate) fn owner_open_quantity(&self, owner: OrderOwner, side: Side) -> Quantity {
+        self.owner_open
+            .get(&owner)
+            .map_or(0, |&(bid, ask)| match side {
+                Side::Buy => bid,
+                Side::Sell => ask,
+            })
+    }
+
+    /// Add `quantity` to (or with `added == false`, take it from) the
+    /// resting quantity of `owner` on `side`.
+    pub(crate) fn track_owner_open(
+        &mut self,
+        owner: Option<OrderOwner>,
+        side: Side,
+        quantity: Quantity,
+        added: bool,
+    ) {
+        let Some(owner) = owner else {
+            return;
+        };
+        let open = self.owner_open.entry(owner).or_default();
+        let slot = match side {
+            Side::Buy => &mut open.0,
+            Side::Sell => &mut open.1,
+        };
+        if added {
+            *slot += quantity;
+        } else {
+            *slot = slot.saturating_sub(quantity);
+        }
+        if *open == (0, 0) {
+            self.owner_open.remove(&owner);
+        }
+    }
+
     // === Order management ===
 
+    /// Queue priority of `order`: its size rank (if any), its timestamp,
+    /// then the tie-break.
+    fn priority_key(&self, order: &Order) -> PriorityKey {
+        let rank = match self.level_priority {
+            LevelPriority::Time => 0,
+            LevelPriority::SizeTime => u64::MAX - order.queue_size,
+        };
+        let tie = match self.tie_break {
+            TieBreak::Sequence => order.sequence,
+            TieBreak::OrderId => order.id.0,
+        };
+        (rank, order.timestamp, tie)
+    }
+
     /// Add a new order to the book.
     ///
     /// The order must have a unique ID (typically from `next_order_id()`).
@@ -223,23 +447,56 @@ impl OrderBook {
             order.id
         );
 
+        order.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        order.queue_size = order.remaining_quantity;
+        order.queued_at = self.clock.unwrap_or(0);
+
         let side = order.side;
         let price = order.price;
         let quantity = order.remaining_quantity;
         let order_id = order.id;
+        let hidden = order.hidden;
+        let key = self.priority_key(&order);
+        self.track_owner_open(order.owner, side, quantity, true);
 
-        // Add to appropriate price level and get its index
-        let index = self.side_mut(side).insert_order(price, order_id, quantity);
-        order.position_in_level = index;
+        // Queue at the price level by rank, timestamp, then tie-break
+        order.position_in_level = self
+            .queues_mut(side, hidden)
+            .insert_order_with_key(price, order_id, key, quantity);
 
         // Store in central index
         self.orders.insert(order_id, order);
     }
 
+    /// Add an order, rejecting it if it would lock or cross the book.
+    ///
+    /// Strict form of [`Self::add_order`] for building books from external
+    /// data: a buy priced at or above the best ask (or a sell at or below
+    /// the best bid) returns [`ValidationError::CrossesBook`] and leaves the
+    /// book unchanged.
+    pub fn try_add_order(&mut self, order: Order) -> Result<(), ValidationError> {
+        let opposite = match order.side {
+            Side::Buy => self.best_ask().filter(|&ask| order.price >= ask),
+            Side::Sell => self.best_bid().filter(|&bid| order.price <= bid),
+        };
+        if opposite.is_some() {
+            return Err(ValidationError::CrossesBook {
+                price: order.price.0,
+            });
+        }
+        self.add_order(order);
+        Ok(())
+    }
+
     /// Remove an order from the book (for cancellation).
     ///
     /// Updates the order's status to Cancelled and marks it as a tombstone
-    /// in the price level queue for O(1) performance.
+    /// in the price level queue. The order is found in O(1) at the position
+    /// it was queued at, which stays current while orders join the back of
+    /// its level (always under the default time priority with sequence
+    /// tie-break, barring back-dated [`Self::add_order`] timestamps), and
+    /// otherwise by its priority key in O(log n).
     pub fn cancel_order(&mut self, order_id: OrderId) -> Option<Quantity> {
         let order = self.orders.get_mut(&order_id)?;
 
@@ -250,17 +507,78 @@ impl OrderBook {
         let side = order.side;
         let price = order.price;
         let remaining = order.remaining_quantity;
-        let index = order.position_in_level;
+        let hidden = order.hidden;
+        let owner = order.owner;
 
         // Cancel the order (updates status)
         order.cancel();
+        self.track_owner_open(owner, side, remaining, false);
 
-        // Mark as tombstone in price level (O(1))
-        self.side_mut(side).mark_tombstone(price, index, remaining);
+        // Mark as tombstone in price level (O(1) by position, else by key)
+        let order = &self.orders[&order_id];
+        let key = self.priority_key(order);
+        let position = order.position_in_level;
+        self.queues_mut(side, hidden)
+            .tombstone_order(price, order_id, key, position, remaining);
 
         Some(remaining)
     }
 
+    /// Reduce a resting order's remaining quantity in place.
+    ///
+    /// The order keeps its ID and queue position, except under
+    /// [`LevelPriority::SizeTime`], where it is re-ranked by its new size
+    /// (keeping its timestamp). Returns `None` if the order is not active
+    /// or `quantity` is not strictly less than its remaining quantity (use
+    /// [`cancel_order`](Self::cancel_order) to remove it entirely).
+    pub fn reduce_order(&mut self, order_id: OrderId, quantity: Quantity) -> Option<Quantity> {
+        let order = self.orders.get(&order_id)?;
+        if !order.is_active() || quantity >= order.remaining_quantity {
+            return None;
+        }
+
+        let side = order.side;
+        let price = order.price;
+        let before = order.remaining_quantity;
+        let hidden = order.hidden;
+        let owner = order.owner;
+        let old_key = self.priority_key(order);
+        let old_position = order.position_in_level;
+        self.track_owner_open(owner, side, quantity, false);
+
+        let order = self.orders.get_mut(&order_id)?;
+        order.stp_decrement(quantity);
+        if self.level_priority == LevelPriority::SizeTime {
+            // Re-rank by the new size
+            order.queue_size = order.remaining_quantity;
+            order.queued_at = self.clock.unwrap_or(0);
+            let new_key = self.priority_key(&self.orders[&order_id]);
+            let levels = self.queues_mut(side, hidden);
+            levels.tombstone_order(price, order_id, old_key, old_position, before);
+            let position =
+                levels.insert_order_with_key(price, order_id, new_key, before - quantity);
+            if let Some(order) = self.orders.get_mut(&order_id) {
+                order.position_in_level = position;
+            }
+        } else if let Some(level) = self.queues_mut(side, hidden).get_level_mut(price) {
+            level.decrease_quantity(quantity);
+        }
+        Some(quantity)
+    }
+
+    /// Number of active orders ahead of `order_id` in its price level's
+    /// queue, or `None` if it is not resting.
+    pub(crate) fn queue_index(&self, order_id: OrderId) -> Option<usize> {
+        let order = self.orders.get(&order_id).filter(|o| o.is_active())?;
+        let side = match order.hidden {
+            false => self.side(order.side),
+            true => self.hidden_side(order.side),
+        };
+        side.get_level(order.price)?
+            .iter()
+            .position(|id| id == order_id)
+    }
+
     /// Create a new order with auto-generated ID and timestamp.
     ///
     /// This is a convenience method that:
@@ -294,10 +612,61 @@ impl OrderBook {
         before - self.orders.len()
     }
 
+    /// Copy of the book with its resting orders but none of the filled or
+    /// cancelled history, for dry runs whose cost should not grow with it.
+    pub(crate) fn clone_resting(&self) -> Self {
+        let orders = [&self.bids, &self.asks, &self.hidden_bids, &self.hidden_asks]
+            .into_iter()
+            .flat_map(|levels| levels.iter_best_to_worst())
+            .flat_map(|(_, level)| level.iter())
+            .filter_map(|id| Some((id, self.orders.get(&id)?.clone())))
+            .collect();
+        Self {
+            bids: self.bids.clone(),
+            asks: self.asks.clone(),
+            hidden_bids: self.hidden_bids.clone(),
+            hidden_asks: self.hidden_asks.clone(),
+            orders,
+            next_order_id: self.next_order_id,
+            next_trade_id: self.next_trade_id,
+            next_timestamp: self.next_timestamp,
+            order_id_start: self.order_id_start,
+            trade_id_start: self.trade_id_start,
+            next_sequence: self.next_sequence,
+            tie_break: self.tie_break,
+            level_priority: self.level_priority,
+            clock: self.clock,
+            owner_open: self.owner_open.clone(),
+        }
+    }
+
     /// Remove all tombstones from the book.
     pub fn compact(&mut self) {
         self.bids.compact();
         self.asks.compact();
+        self.hidden_bids.compact();
+        self.hidden_asks.compact();
+
+        // Entries moved up, so re-record queue positions for O(1) cancel
+        for levels in [&self.bids, &self.asks, &self.hidden_bids, &self.hidden_asks] {
+            for (_, level) in levels.iter_best_to_worst() {
+                for (position, id) in level.positions() {
+                    if let Some(order) = self.orders.get_mut(&id) {
+                        order.position_in_level = position;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove every order, active or not, restart the ID, timestamp,
+    /// and sequence counters from their configured starting values, and
+    /// unset the logical clock. The tie-break and level priority policies
+    /// are kept.
+    pub fn clear(&mut self) {
+        *self = Self::with_id_start(self.order_id_start, self.trade_id_start)
+            .with_tie_break(self.tie_break)
+            .with_level_priority(self.level_priority);
     }
 }
 
@@ -348,6 +717,79 @@ mod tests {
         assert_eq!(book.peek_next_order_id(), OrderId(2));
     }
 
+    #[test]
+    fn clear_restores_id_start() {
+        let mut book = OrderBook::with_id_start(10, 20);
+        let order = book.create_order(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        book.add_order(order);
+        book.next_trade_id();
+
+        book.clear();
+
+        assert_eq!(book.order_count(), 0);
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.next_order_id(), OrderId(10));
+        assert_eq!(book.next_trade_id(), TradeId(20));
+        assert_eq!(book.next_timestamp(), 1);
+    }
+
+    #[test]
+    fn sequence_is_assigned_on_add() {
+        let mut book = OrderBook::new();
+        let first = book.create_order(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        let second = book.create_order(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        let (first_id, second_id) = (first.id, second.id);
+        book.add_order(first);
+        book.add_order(second);
+
+        assert_eq!(book.get_order(first_id).unwrap().sequence, 1);
+        assert_eq!(book.get_order(second_id).unwrap().sequence, 2);
+    }
+
+    #[test]
+    fn earlier_timestamp_queues_first() {
+        let mut book = OrderBook::new().with_tie_break(TieBreak::OrderId);
+        assert_eq!(book.tie_break(), TieBreak::OrderId);
+
+        let late = Order::new(
+            OrderId(1),
+            Side::Buy,
+            Price(100_00),
+            10,
+            20,
+            TimeInForce::GTC,
+        );
+        let early = Order::new(
+            OrderId(2),
+            Side::Buy,
+            Price(100_00),
+            10,
+            10,
+            TimeInForce::GTC,
+        );
+        book.add_order(late);
+        book.add_order(early);
+
+        let level = book.bids().get_level(Price(100_00)).unwrap();
+        assert_eq!(
+            level.iter().collect::<Vec<_>>(),
+            vec![OrderId(2), OrderId(1)]
+        );
+
+        // Cancelling by key still finds the right entry after reordering
+        assert_eq!(book.cancel_order(OrderId(1)), Some(10));
+        let level = book.bids().get_level(Price(100_00)).unwrap();
+        assert_eq!(level.iter().collect::<Vec<_>>(), vec![OrderId(2)]);
+    }
+
+    #[test]
+    fn id_start_is_configurable() {
+        let mut book = OrderBook::with_id_start(1_000, 50);
+        assert_eq!(book.next_order_id(), OrderId(1_000));
+        assert_eq!(book.next_order_id(), OrderId(1_001));
+        assert_eq!(book.next_trade_id(), TradeId(50));
+    }
+
     #[test]
     fn add_and_get_order() {
         let mut book = OrderBook::new();
@@ -426,6 +868,49 @@ mod tests {
         assert_eq!(book.best_bid(), None);
     }
 
+    #[test]
+    fn compact_rerecords_queue_positions() {
+        let mut book = OrderBook::new();
+        let ids: Vec<_> = (0..4)
+            .map(|_| {
+                let order = book.create_order(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+                let id = order.id;
+                book.add_order(order);
+                id
+            })
+            .collect();
+
+        book.cancel_order(ids[0]);
+        book.cancel_order(ids[1]);
+        book.compact();
+
+        for &id in &ids[2..] {
+            let position = book.get_order(id).unwrap().position_in_level;
+            let level = book.asks.get_level_mut(Price(100_00)).unwrap();
+            assert!(level.tombstone_at(position, id, 0));
+        }
+    }
+
+    #[test]
+    fn clone_resting_drops_history() {
+        let mut book = OrderBook::new();
+        let mut ids = Vec::new();
+        for hidden in [false, false, true] {
+            let mut order = book.create_order(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+            order.hidden = hidden;
+            ids.push(order.id);
+            book.add_order(order);
+        }
+        book.cancel_order(ids[0]);
+
+        let copy = book.clone_resting();
+        assert_eq!(copy.order_count(), 2);
+        assert!(copy.get_order(ids[0]).is_none());
+        assert_eq!(copy.best_ask(), book.best_ask());
+        assert_eq!(copy.peek_next_order_id(), book.peek_next_order_id());
+        assert_eq!(copy.hidden_side(Side::Sell).total_quantity(), 10);
+    }
+
     #[test]
     fn cancel_nonexistent_order() {
         let mut book = OrderBook::new();
@@ -499,6 +984,46 @@ mod tests {
         let high_bid = book.create_order(Side::Buy, Price(102_00), 100, TimeInForce::GTC);
         book.add_order(high_bid);
         assert!(book.is_crossed());
+        assert!(!book.is_locked());
+    }
+
+    #[test]
+    fn is_locked() {
+        let mut book = OrderBook::new();
+
+        let bid = book.create_order(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let ask = book.create_order(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        book.add_order(bid);
+        assert!(!book.is_locked());
+        book.add_order(ask);
+        assert!(book.is_locked());
+        assert!(book.is_crossed());
+    }
+
+    #[test]
+    fn try_add_order_rejects_locking_and_crossing() {
+        let mut book = OrderBook::new();
+
+        let bid = book.create_order(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let ask = book.create_order(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        assert_eq!(book.try_add_order(bid), Ok(()));
+        assert_eq!(book.try_add_order(ask), Ok(()));
+
+        let locking = book.create_order(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let locking_id = locking.id;
+        assert_eq!(
+            book.try_add_order(locking),
+            Err(ValidationError::CrossesBook { price: 100_00 })
+        );
+        assert!(book.get_order(locking_id).is_none());
+
+        let crossing = book.create_order(Side::Buy, Price(102_00), 10, TimeInForce::GTC);
+        assert!(book.try_add_order(crossing).is_err());
+        assert!(!book.is_crossed());
+        assert_eq!(
+            book.best_bid_ask(),
+            (Some(Price(100_00)), Some(Price(101_00)))
+        );
     }
 
     #[test]
@@ -522,6 +1047,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn owner_open_quantity_follows_resting_orders() {
+        let mut book = OrderBook::new();
+        let fund = OrderOwner(1);
+        let add = |book: &mut OrderBook, side, qty| {
+            let mut order = book.create_order(side, Price(100_00), qty, TimeInForce::GTC);
+            order.owner = Some(fund);
+            let id = order.id;
+            book.add_order(order);
+            id
+        };
+        let first = add(&mut book, Side::Sell, 30);
+        let second = add(&mut book, Side::Sell, 50);
+        assert_eq!(book.owner_open_quantity(fund, Side::Sell), 80);
+        assert_eq!(book.owner_open_quantity(fund, Side::Buy), 0);
+
+        let mut taker = book.create_order(Side::Buy, Price(100_00), 40, TimeInForce::IOC);
+        book.match_order(&mut taker);
+        assert_eq!(book.owner_open_quantity(fund, Side::Sell), 40);
+
+        book.reduce_order(second, 15);
+        assert_eq!(book.owner_open_quantity(fund, Side::Sell), 25);
+        assert_eq!(book.cancel_order(first), None);
+        book.cancel_order(second);
+        assert_eq!(book.owner_open_quantity(fund, Side::Sell), 0);
+        assert!(book.owner_open.is_empty());
+    }
+
     #[test]
     #[should_panic(expected = "already exists")]
     fn add_duplicate_order_panics() {
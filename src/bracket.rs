@@ -0,0 +1,560 @@
+//! Bracket orders and one-cancels-other (OCO) linkage.
+//!
+//! A bracket is an entry limit order with two attached exits on the
+//! opposite side: a stop-loss (stop-market) and a take-profit (GTC limit).
+//! The exits are armed once the entry is done, and are OCO-linked: a fill
+//! of one reduces the other by the same quantity, so a full fill of either
+//! exit cancels the other.
+
+#[cfg(feature = "event-log")]
+use crate::event::Event;
+use crate::{
+    Exchange, OrderId, Price, Quantity, Side, TimeInForce, Trade,
+    result::{BracketResult, OcoError},
+    stop::{StopOrder, StopStatus},
+};
+
+/// A bracket order: an entry with an attached stop-loss and take-profit.
+///
+/// Returned by [`Exchange::bracket`]. The exit order IDs are `None` until
+/// the bracket is armed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bracket {
+    /// Side of the entry order (the exits trade the opposite side)
+    pub side: Side,
+    /// Trigger price of the stop-loss exit
+    pub stop_price: Price,
+    /// Limit price of the take-profit exit
+    pub target_price: Price,
+    /// Entry quantity filled so far; the size of each exit once armed
+    pub filled_quantity: Quantity,
+    /// Stop-loss order ID (a stop-market order), once armed
+    pub stop_order_id: Option<OrderId>,
+    /// Take-profit order ID (a GTC limit order), once armed
+    pub target_order_id: Option<OrderId>,
+}
+
+impl Bracket {
+    /// Returns true once the exits have been placed.
+    pub fn is_armed(&self) -> bool {
+        self.stop_order_id.is_some()
+    }
+}
+
+impl Exchange {
+    /// Submit a bracket order: a limit entry with an attached stop-loss at
+    /// `stop_price` and take-profit at `target_price`.
+    ///
+    /// The exits are armed when the entry is done: fully filled, or
+    /// cancelled (including an IOC remainder) after a partial fill. They
+    /// trade the opposite side for the entry's filled quantity; an entry
+    /// that is cancelled without any fill drops the bracket. The
+    /// stop-loss is a stop-market order evaluated from the next trade on,
+    /// and the take-profit is a GTC limit order.
+    ///
+    /// The exits are one-cancels-other: a fill of either reduces the other
+    /// by the same quantity, and a stop-loss that triggers cancels the
+    /// take-profit outright.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+    ///
+    /// let result = exchange.submit_bracket(
+    ///     Side::Buy, Price(100_00), 10, TimeInForce::GTC, Price(95_00), Price(110_00),
+    /// );
+    /// let target = result.target_order_id.unwrap();
+    /// assert_eq!(exchange.best_ask(), Some(Price(110_00)));
+    ///
+    /// // The stop-loss triggers and cancels the take-profit.
+    /// exchange.submit_limit(Side::Buy, Price(94_00), 20, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Sell, Price(94_00), 1, TimeInForce::GTC);
+    /// assert!(!exchange.get_order(target).unwrap().is_active());
+    /// ```
+    pub fn submit_bracket(
+        &mut self,
+        side: Side,
+        entry_price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        stop_price: Price,
+        target_price: Price,
+    ) -> BracketResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitBracket {
+            side,
+            entry_price,
+            quantity,
+            time_in_force: tif,
+            stop_price,
+            target_price,
+        });
+
+        let result = self.submit_bracket_internal(
+            side,
+            entry_price,
+            quantity,
+            tif,
+            stop_price,
+            target_price,
+        );
+        if !result.entry.trades.is_empty() {
+            let last_price = result.entry.trades.last().unwrap().price;
+            self.last_trade_price = Some(last_price);
+            self.process_trade_triggers();
+        }
+        result
+    }
+
+    /// Internal: submit bracket order without recording event.
+    pub(crate) fn submit_bracket_internal(
+        &mut self,
+        side: Side,
+        entry_price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        stop_price: Price,
+        target_price: Price,
+    ) -> BracketResult {
+        let entry = self.submit_limit_internal(side, entry_price, quantity, tif);
+        self.brackets.insert(
+            entry.order_id,
+            Bracket {
+                side,
+                stop_price,
+                target_price,
+                filled_quantity: entry.filled_quantity,
+                stop_order_id: None,
+                target_order_id: None,
+            },
+        );
+        self.arm_bracket_if_done(entry.order_id);
+
+        let bracket = self.brackets.get(&entry.order_id);
+        BracketResult {
+            stop_order_id: bracket.and_then(|b| b.stop_order_id),
+            target_order_id: bracket.and_then(|b| b.target_order_id),
+            entry,
+        }
+    }
+
+    /// The bracket whose entry is `entry_order_id`, if any.
+    ///
+    /// A cancel-and-replace [`modify`](Self::modify) of a pending entry
+    /// moves its bracket to the replacement order's ID.
+    pub fn bracket(&self, entry_order_id: OrderId) -> Option<&Bracket> {
+        self.brackets.get(&entry_order_id)
+    }
+
+    /// Link two live orders as one-cancels-other.
+    ///
+    /// Either order may be a resting limit order or a pending stop. From
+    /// then on a fill of one reduces the other by the same quantity: a
+    /// partial fill of 30 takes 30 off the partner's open quantity, and
+    /// once the partner has nothing left it is cancelled. A stop counts as
+    /// filled for its whole quantity when it triggers. The pair is
+    /// unlinked as soon as either order is done; cancelling one by hand
+    /// leaves the other untouched, and a cancel-and-replace
+    /// [`modify`](Self::modify) carries the link to the new order ID.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// let a = exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC).order_id;
+    /// let b = exchange.submit_limit(Side::Sell, Price(102_00), 100, TimeInForce::GTC).order_id;
+    /// exchange.link_oco(a, b).unwrap();
+    ///
+    /// exchange.submit_market(Side::Buy, 30);
+    /// assert_eq!(exchange.get_order(b).unwrap().remaining_quantity, 70);
+    /// ```
+    pub fn link_oco(&mut self, order_a: OrderId, order_b: OrderId) -> Result<(), OcoError> {
+        if order_a == order_b {
+            return Err(OcoError::SameOrder);
+        }
+        if !self.is_live(order_a) || !self.is_live(order_b) {
+            return Err(OcoError::OrderNotActive);
+        }
+        if self.oco_links.contains_key(&order_a) || self.oco_links.contains_key(&order_b) {
+            return Err(OcoError::AlreadyLinked);
+        }
+
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::LinkOco { order_a, order_b });
+
+        self.link_oco_internal(order_a, order_b);
+        Ok(())
+    }
+
+    /// Internal: link without validating or recording an event.
+    pub(crate) fn link_oco_internal(&mut self, order_a: OrderId, order_b: OrderId) {
+        self.oco_links.insert(order_a, order_b);
+        self.oco_links.insert(order_b, order_a);
+    }
+
+    /// The order linked to `order_id` as one-cancels-other, if any.
+    pub fn oco_partner(&self, order_id: OrderId) -> Option<OrderId> {
+        self.oco_links.get(&order_id).copied()
+    }
+
+    /// Update brackets and OCO partners for orders filled by `trades`.
+    pub(crate) fn process_linked_fills(&mut self, trades: &[Trade]) {
+        if self.brackets.is_empty() && self.oco_links.is_empty() {
+            return;
+        }
+        for trade in trades {
+            for order_id in [trade.aggressor_order_id, trade.passive_order_id] {
+                if let Some(bracket) = self.brackets.get_mut(&order_id) {
+                    if !bracket.is_armed() {
+                        bracket.filled_quantity += trade.quantity;
+                    }
+                }
+                self.reduce_oco_partner(order_id, trade.quantity);
+            }
+        }
+        for trade in trades {
+            self.arm_bracket_if_done(trade.aggressor_order_id);
+            self.arm_bracket_if_done(trade.passive_order_id);
+        }
+    }
+
+    /// Update links after `order_id` was cancelled by the user.
+    pub(crate) fn process_linked_cancel(&mut self, order_id: OrderId) {
+        if let Some(partner) = self.oco_links.remove(&order_id) {
+            self.oco_links.remove(&partner);
+        }
+        self.arm_bracket_if_done(order_id);
+    }
+
+    /// Move links from a replaced order to its replacement.
+    pub(crate) fn move_links(&mut self, old_id: OrderId, new_id: OrderId) {
+        if let Some(bracket) = self.brackets.remove(&old_id) {
+            self.brackets.insert(new_id, bracket);
+        }
+        if let Some(partner) = self.oco_links.remove(&old_id) {
+            self.oco_links.insert(new_id, partner);
+            self.oco_links.insert(partner, new_id);
+        }
+    }
+
+    /// Reduce the OCO partner of `order_id` by `quantity`, cancelling it if
+    /// nothing would remain. Unlinks the pair once either side is done.
+    pub(crate) fn reduce_oco_partner(&mut self, order_id: OrderId, quantity: Quantity) {
+        let Some(&partner) = self.oco_links.get(&order_id) else {
+            return;
+        };
+
+        if self.stop_book.contains_pending(partner) {
+            self.stop_book.reduce(partner, quantity);
+        } else if let Some(remaining) = self
+            .book
+            .get_order(partner)
+            .filter(|o| o.is_active())
+            .map(|o| o.remaining_quantity)
+        {
+            if quantity < remaining {
+                self.book.reduce_order(partner, quantity);
+            } else {
+                self.book.cancel_order(partner);
+            }
+        }
+
+        if !self.is_live(order_id) || !self.is_live(partner) {
+            self.oco_links.remove(&order_id);
+            self.oco_links.remove(&partner);
+        }
+    }
+
+    /// Returns true if `order_id` is a pending stop or an active order.
+    fn is_live(&self, order_id: OrderId) -> bool {
+        self.stop_book.contains_pending(order_id)
+            || self.book.get_order(order_id).is_some_and(|o| o.is_active())
+    }
+
+    /// Arm the exits of the bracket entered by `entry_id` if the entry is
+    /// done, or drop the bracket if the entry never filled.
+    fn arm_bracket_if_done(&mut self, entry_id: OrderId) {
+        let Some(bracket) = self.brackets.get(&entry_id) else {
+            return;
+        };
+        if bracket.is_armed() || self.is_live(entry_id) {
+            return;
+        }
+        if bracket.filled_quantity == 0 {
+            self.brackets.remove(&entry_id);
+            return;
+        }
+
+        let exit_side = bracket.side.opposite();
+        let (stop_price, target_price) = (bracket.stop_price, bracket.target_price);
+        let quantity = bracket.filled_quantity;
+
+        let stop_id = self.book.next_order_id();
+        let timestamp = self.book.next_timestamp();
+        self.stop_book.insert(StopOrder {
+            id: stop_id,
+            side: exit_side,
+            stop_price,
+            limit_price: None,
+            quantity,
+            time_in_force: TimeInForce::GTC,
+            timestamp,
+            status: StopStatus::Pending,
+            trail_method: None,
+            watermark: None,
+        });
+
+        // Link before submitting so a take-profit that fills on arrival
+        // already reduces the stop.
+        let target_id = self.book.peek_next_order_id();
+        self.link_oco_internal(stop_id, target_id);
+        if let Some(bracket) = self.brackets.get_mut(&entry_id) {
+            bracket.stop_order_id = Some(stop_id);
+            bracket.target_order_id = Some(target_id);
+        }
+
+        let result =
+            self.submit_limit_internal(exit_side, target_price, quantity, TimeInForce::GTC);
+        debug_assert_eq!(result.order_id, target_id);
+        if let Some(trade) = result.trades.last() {
+            self.last_trade_price = Some(trade.price);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use crate::{Exchange, OcoError, OrderId, Price, Side, StopStatus, TimeInForce};
+
+    fn bracket_long(exchange: &mut Exchange, quantity: u64) -> (OrderId, OrderId) {
+        exchange.submit_limit(Side::Sell, Price(100_00), quantity, TimeInForce::GTC);
+        let result = exchange.submit_bracket(
+            Side::Buy,
+            Price(100_00),
+            quantity,
+            TimeInForce::GTC,
+            Price(95_00),
+            Price(110_00),
+        );
+        assert_eq!(result.entry.filled_quantity, quantity);
+        (
+            result.stop_order_id.unwrap(),
+            result.target_order_id.unwrap(),
+        )
+    }
+
+    #[test]
+    fn resting_entry_arms_on_fill() {
+        let mut exchange = Exchange::new();
+        let result = exchange.submit_bracket(
+            Side::Buy,
+            Price(100_00),
+            10,
+            TimeInForce::GTC,
+            Price(95_00),
+            Price(110_00),
+        );
+        let entry = result.entry.order_id;
+        assert_eq!(result.stop_order_id, None);
+        assert!(!exchange.bracket(entry).unwrap().is_armed());
+        assert_eq!(exchange.pending_stop_count(), 0);
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 4, TimeInForce::GTC);
+        assert!(!exchange.bracket(entry).unwrap().is_armed());
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 6, TimeInForce::GTC);
+        let bracket = exchange.bracket(entry).unwrap();
+        assert!(bracket.is_armed());
+        assert_eq!(bracket.filled_quantity, 10);
+        let stop = exchange
+            .get_stop_order(bracket.stop_order_id.unwrap())
+            .unwrap();
+        assert_eq!((stop.side, stop.quantity), (Side::Sell, 10));
+        assert_eq!(exchange.best_ask(), Some(Price(110_00)));
+    }
+
+    #[test]
+    fn target_fill_cancels_stop_once() {
+        let mut exchange = Exchange::new();
+        let (stop, target) = bracket_long(&mut exchange, 10);
+
+        // Partial take-profit shrinks the stop by the same quantity.
+        exchange.submit_limit(Side::Buy, Price(110_00), 3, TimeInForce::GTC);
+        assert_eq!(exchange.get_stop_order(stop).unwrap().quantity, 7);
+
+        exchange.submit_limit(Side::Buy, Price(110_00), 7, TimeInForce::GTC);
+        assert_eq!(
+            exchange.get_stop_order(stop).unwrap().status,
+            StopStatus::Cancelled
+        );
+        assert_eq!(exchange.pending_stop_count(), 0);
+        assert!(exchange.oco_links.is_empty());
+
+        // Nothing left to cancel: later trades at the stop do nothing.
+        exchange.submit_limit(Side::Buy, Price(90_00), 5, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(90_00), 5, TimeInForce::GTC);
+        assert_eq!(exchange.get_order(target).unwrap().remaining_quantity, 0);
+        assert_eq!(exchange.trades().len(), 4);
+    }
+
+    #[test]
+    fn stop_trigger_cancels_target() {
+        let mut exchange = Exchange::new();
+        let (stop, target) = bracket_long(&mut exchange, 10);
+
+        exchange.submit_limit(Side::Buy, Price(94_00), 20, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(94_00), 1, TimeInForce::GTC);
+
+        assert_eq!(
+            exchange.get_stop_order(stop).unwrap().status,
+            StopStatus::Triggered
+        );
+        let target = exchange.get_order(target).unwrap();
+        assert!(!target.is_active());
+        assert_eq!(target.filled_quantity, 0);
+        // The stop sold its 10 into the bid.
+        assert_eq!(exchange.best_bid(), Some(Price(94_00)));
+        assert_eq!(exchange.book().bids().total_quantity(), 9);
+        assert!(exchange.oco_links.is_empty());
+    }
+
+    #[test]
+    fn partial_entry_arms_on_cancel_and_unfilled_drops() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 4, TimeInForce::GTC);
+        let result = exchange.submit_bracket(
+            Side::Buy,
+            Price(100_00),
+            10,
+            TimeInForce::GTC,
+            Price(95_00),
+            Price(110_00),
+        );
+        let entry = result.entry.order_id;
+        assert!(!exchange.bracket(entry).unwrap().is_armed());
+
+        exchange.cancel(entry);
+        let bracket = exchange.bracket(entry).unwrap();
+        assert!(bracket.is_armed());
+        assert_eq!(bracket.filled_quantity, 4);
+
+        let unfilled = exchange.submit_bracket(
+            Side::Buy,
+            Price(90_00),
+            10,
+            TimeInForce::IOC,
+            Price(85_00),
+            Price(99_00),
+        );
+        assert!(exchange.bracket(unfilled.entry.order_id).is_none());
+    }
+
+    #[test]
+    fn modify_moves_pending_bracket() {
+        let mut exchange = Exchange::new();
+        let result = exchange.submit_bracket(
+            Side::Buy,
+            Price(99_00),
+            10,
+            TimeInForce::GTC,
+            Price(95_00),
+            Price(110_00),
+        );
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+
+        let modified = exchange.modify(result.entry.order_id, Price(100_00), 10);
+        let new_id = modified.new_order_id.unwrap();
+        assert!(exchange.bracket(result.entry.order_id).is_none());
+        assert!(exchange.bracket(new_id).unwrap().is_armed());
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn replay_rebuilds_brackets() {
+        let mut exchange = Exchange::new();
+        let (stop, target) = bracket_long(&mut exchange, 10);
+        exchange.submit_limit(Side::Buy, Price(110_00), 3, TimeInForce::GTC);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.get_stop_order(stop).unwrap().quantity, 7);
+        assert_eq!(replayed.get_order(target).unwrap().remaining_quantity, 7);
+    }
+
+    #[test]
+    fn oco_partial_fill_reduces_partner() {
+        let mut exchange = Exchange::new();
+        let a = exchange
+            .submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC)
+            .order_id;
+        let b = exchange
+            .submit_limit(Side::Sell, Price(101_00), 60, TimeInForce::GTC)
+            .order_id;
+        exchange.link_oco(a, b).unwrap();
+        assert_eq!(exchange.oco_partner(b), Some(a));
+
+        // 30 off the bid takes 30 off the ask.
+        exchange.submit_limit(Side::Sell, Price(99_00), 30, TimeInForce::GTC);
+        assert_eq!(exchange.get_order(b).unwrap().remaining_quantity, 30);
+        assert_eq!(exchange.get_order(a).unwrap().remaining_quantity, 70);
+
+        // Filling the rest of the ask leaves the bid at 40 and unlinks.
+        exchange.submit_market(Side::Buy, 30);
+        assert_eq!(exchange.get_order(a).unwrap().remaining_quantity, 40);
+        assert!(exchange.get_order(a).unwrap().is_active());
+        assert_eq!(exchange.oco_partner(a), None);
+    }
+
+    #[test]
+    fn oco_fill_past_partner_cancels_it() {
+        let mut exchange = Exchange::new();
+        let a = exchange
+            .submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC)
+            .order_id;
+        let stop = exchange
+            .submit_stop_market(Side::Buy, Price(105_00), 50)
+            .order_id;
+        exchange.link_oco(a, stop).unwrap();
+
+        exchange.submit_market(Side::Buy, 20);
+        assert_eq!(exchange.get_stop_order(stop).unwrap().quantity, 30);
+        exchange.submit_market(Side::Buy, 40);
+        assert_eq!(
+            exchange.get_stop_order(stop).unwrap().status,
+            StopStatus::Cancelled
+        );
+        assert_eq!(exchange.get_order(a).unwrap().remaining_quantity, 40);
+        assert_eq!(exchange.oco_partner(a), None);
+    }
+
+    #[test]
+    fn link_oco_validates() {
+        let mut exchange = Exchange::new();
+        let a = exchange
+            .submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC)
+            .order_id;
+        let b = exchange
+            .submit_limit(Side::Buy, Price(98_00), 10, TimeInForce::GTC)
+            .order_id;
+        let c = exchange
+            .submit_limit(Side::Buy, Price(97_00), 10, TimeInForce::GTC)
+            .order_id;
+
+        assert_eq!(exchange.link_oco(a, a), Err(OcoError::SameOrder));
+        assert_eq!(
+            exchange.link_oco(a, OrderId(99)),
+            Err(OcoError::OrderNotActive)
+        );
+        exchange.link_oco(a, b).unwrap();
+        assert_eq!(exchange.link_oco(b, c), Err(OcoError::AlreadyLinked));
+
+        // Cancelling one by hand leaves the other resting, unlinked.
+        exchange.cancel(a);
+        assert!(exchange.get_order(b).unwrap().is_active());
+        assert_eq!(exchange.oco_partner(b), None);
+        exchange.link_oco(b, c).unwrap();
+    }
+}
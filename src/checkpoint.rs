@@ -0,0 +1,259 @@
+//! Compact binary checkpoints of full engine state.
+//!
+//! Unlike [`persistence`](crate::persistence), which stores the event log
+//! and rebuilds state by replaying it, a checkpoint captures the state
+//! itself: every book with its queue order, order history, pending stops,
+//! brackets, trade tape, ID counters, and clocks. Restoring is a single
+//! decode, and the restored engine behaves exactly like the original from
+//! that point on.
+//!
+//! The encoding is postcard behind a short header (magic bytes and a format
+//! version), so a checkpoint from an incompatible version is rejected
+//! instead of misread.
+//!
+//! ```
+//! use nanobook::{MultiExchange, Price, Side, Symbol, TimeInForce};
+//!
+//! let mut multi = MultiExchange::new();
+//! let aapl = Symbol::new("AAPL");
+//! multi.get_or_create(&aapl).submit_limit(Side::Sell, Price(150_00), 100, TimeInForce::GTC);
+//!
+//! let bytes = multi.checkpoint();
+//! let mut restored = MultiExchange::restore(&bytes).unwrap();
+//! let result = restored.get_or_create(&aapl).submit_market(Side::Buy, 40);
+//! assert_eq!(result.filled_quantity, 40);
+//! ```
+
+use std::io;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{Exchange, MultiExchange};
+
+/// Leading bytes of every checkpoint.
+const MAGIC: &[u8; 4] = b"NBCK";
+
+/// Checkpoint format version; bump when the engine state layout or the
+/// encoding changes.
+const VERSION: u8 = 12;
+
+fn encode<T: Serialize>(state: &T) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(1024);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    postcard::to_extend(state, bytes).expect("in-memory checkpoint serialization cannot fail")
+}
+
+/// 64-bit FNV-1a over the encoded state: simple, and stable across Rust
+/// versions and platforms, unlike `std`'s `DefaultHasher`.
+fn hash<T: Serialize>(state: &T) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    encode(state)
+        .iter()
+        .fold(OFFSET, |h, &b| (h ^ u64::from(b)).wrapping_mul(PRIME))
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8]) -> io::Result<T> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let body = bytes
+        .strip_prefix(MAGIC.as_slice())
+        .ok_or_else(|| invalid("not a nanobook checkpoint".to_string()))?;
+    let (&version, body) = body
+        .split_first()
+        .ok_or_else(|| invalid("truncated checkpoint header".to_string()))?;
+    if version != VERSION {
+        return Err(invalid(format!(
+            "checkpoint version {version} is not supported (expected {VERSION})"
+        )));
+    }
+    postcard::from_bytes(body).map_err(|e| invalid(format!("corrupt checkpoint: {e}")))
+}
+
+impl Exchange {
+    /// Encode the full exchange state as a compact binary checkpoint.
+    ///
+    /// Requires the `checkpoint` feature.
+    pub fn checkpoint(&self) -> Vec<u8> {
+        encode(self)
+    }
+
+    /// Rebuild an exchange from [`checkpoint`](Self::checkpoint) bytes.
+    ///
+    /// Returns an `InvalidData` error if the bytes are not a checkpoint of
+    /// this format version.
+    pub fn restore(bytes: &[u8]) -> io::Result<Self> {
+        decode(bytes)
+    }
+
+    /// Stable 64-bit hash of the full exchange state; see
+    /// [`MultiExchange::state_hash`].
+    pub fn state_hash(&self) -> u64 {
+        hash(self)
+    }
+}
+
+impl MultiExchange {
+    /// Encode every exchange and the symbol table as a compact binary
+    /// checkpoint.
+    ///
+    /// Requires the `checkpoint` feature.
+    pub fn checkpoint(&self) -> Vec<u8> {
+        encode(self)
+    }
+
+    /// Rebuild a multi-exchange from [`checkpoint`](Self::checkpoint)
+    /// bytes.
+    ///
+    /// Returns an `InvalidData` error if the bytes are not a checkpoint of
+    /// this format version.
+    pub fn restore(bytes: &[u8]) -> io::Result<Self> {
+        decode(bytes)
+    }
+
+    /// Stable 64-bit hash of the full engine state: every book, order
+    /// history, stops, trade tape, ID counters, clocks, and (with
+    /// `event-log`) the event log.
+    ///
+    /// It hashes the [`checkpoint`](Self::checkpoint) encoding, so two
+    /// engines driven through the same operations hash equal, on any
+    /// platform, and any divergence changes the hash. Use it to assert
+    /// that replays are deterministic without diffing full dumps. Hashes
+    /// are only comparable within one checkpoint format version.
+    ///
+    /// Requires the `checkpoint` feature.
+    ///
+    /// ```
+    /// use nanobook::{MultiExchange, Price, Side, Symbol, TimeInForce};
+    ///
+    /// let run = || {
+    ///     let mut multi = MultiExchange::new();
+    ///     let ex = multi.get_or_create(&Symbol::new("AAPL"));
+    ///     ex.submit_limit(Side::Sell, Price(150_00), 100, TimeInForce::GTC);
+    ///     ex.submit_market(Side::Buy, 40);
+    ///     multi
+    /// };
+    /// assert_eq!(run().state_hash(), run().state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        hash(self)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+    use super::*;
+    use crate::{LevelPriority, OrderId, Price, Side, Symbol, TimeInForce};
+
+    fn warmed_up() -> MultiExchange {
+        let mut multi = MultiExchange::new().with_price_scale(10_000);
+        let ex = multi.get_or_create_named("BERKSHIRE_HATHAWAY_B");
+        for i in 0..5 {
+            ex.submit_limit(Side::Sell, Price(101_00 + i), 10, TimeInForce::GTC);
+            ex.submit_limit(Side::Buy, Price(99_00 - i), 10, TimeInForce::GTC);
+        }
+        ex.submit_limit(Side::Buy, Price(99_00), 7, TimeInForce::GTC);
+        ex.cancel(OrderId(2));
+        ex.submit_market(Side::Buy, 15);
+        ex.submit_stop_market(Side::Sell, Price(98_50), 20);
+        ex.submit_bracket(
+            Side::Buy,
+            Price(101_02),
+            5,
+            TimeInForce::GTC,
+            Price(97_00),
+            Price(105_00),
+        );
+
+        let aapl = multi.get_or_create(&Symbol::new("AAPL"));
+        *aapl = Exchange::new().with_level_priority(LevelPriority::SizeTime);
+        aapl.submit_limit(Side::Sell, Price(150_00), 30, TimeInForce::GTC);
+        aapl.submit_limit(Side::Sell, Price(150_00), 80, TimeInForce::GTC);
+        multi
+    }
+
+    fn drive(multi: &mut MultiExchange) -> Vec<crate::SubmitResult> {
+        let brk = multi.intern("BERKSHIRE_HATHAWAY_B");
+        let ex = multi.get_or_create(&brk);
+        let mut results = vec![
+            ex.submit_market(Side::Sell, 25),
+            ex.submit_limit(Side::Buy, Price(101_03), 12, TimeInForce::IOC),
+        ];
+        let aapl = multi.get_or_create(&Symbol::new("AAPL"));
+        results.push(aapl.submit_market(Side::Buy, 50));
+        results
+    }
+
+    #[test]
+    fn restore_reproduces_subsequent_behavior() {
+        let mut original = warmed_up();
+        let mut restored = MultiExchange::restore(&original.checkpoint()).unwrap();
+
+        let a = drive(&mut original);
+        let b = drive(&mut restored);
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.order_id, y.order_id);
+            assert_eq!(x.status, y.status);
+            assert_eq!(x.trades, y.trades);
+        }
+
+        assert_eq!(restored.price_scale(), 10_000);
+        let brk = restored.intern("BERKSHIRE_HATHAWAY_B");
+        assert_eq!(brk, original.intern("BERKSHIRE_HATHAWAY_B"));
+        let (x, y) = (original.get(&brk).unwrap(), restored.get(&brk).unwrap());
+        assert_eq!(x.trades(), y.trades());
+        assert!(x.full_book().diff(&y.full_book()).is_empty());
+        assert_eq!(x.pending_stop_count(), y.pending_stop_count());
+        #[cfg(feature = "event-log")]
+        assert_eq!(x.events(), y.events());
+        // SizeTime priority survives: the 80-lot was ahead of the 30-lot.
+        let aapl = restored.get(&Symbol::new("AAPL")).unwrap();
+        assert_eq!(aapl.book().level_priority(), LevelPriority::SizeTime);
+        assert_eq!(aapl.book().asks().total_quantity(), 60);
+    }
+
+    #[test]
+    fn exchange_round_trip() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_00), 20, TimeInForce::GTC);
+
+        let mut restored = Exchange::restore(&exchange.checkpoint()).unwrap();
+        let result = restored.submit_market(Side::Buy, 15);
+        assert_eq!(result.trades[0].passive_order_id, OrderId(1));
+        assert_eq!(result.order_id, OrderId(3));
+    }
+
+    #[test]
+    fn state_hash_tracks_divergence() {
+        let (mut a, mut b) = (warmed_up(), warmed_up());
+        assert_eq!(a.state_hash(), b.state_hash());
+        drive(&mut a);
+        assert_ne!(a.state_hash(), b.state_hash());
+        drive(&mut b);
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        // Restoring preserves the state exactly.
+        let restored = MultiExchange::restore(&a.checkpoint()).unwrap();
+        assert_eq!(restored.state_hash(), a.state_hash());
+
+        b.get_or_create(&Symbol::new("AAPL")).set_clock(1_000);
+        assert_ne!(a.state_hash(), b.state_hash());
+
+        let single = Exchange::new();
+        assert_eq!(single.state_hash(), Exchange::new().state_hash());
+    }
+
+    #[test]
+    fn rejects_foreign_bytes() {
+        let err = MultiExchange::restore(b"{\"not\": \"a checkpoint\"}").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let mut bytes = MultiExchange::new().checkpoint();
+        bytes[MAGIC.len()] = VERSION + 1;
+        assert!(MultiExchange::restore(&bytes).is_err());
+        assert!(MultiExchange::restore(&bytes[..MAGIC.len()]).is_err());
+    }
+}
@@ -16,6 +16,9 @@ pub enum ValidationError {
     /// product that wraps to negative) from propagating to P&L
     /// accounting, risk checks, or log output.
     NotionalOverflow { price: i64, quantity: u64 },
+    /// Adding the order at `price` would lock or cross the book (best bid
+    /// ≥ best ask). Returned by strict book-building methods.
+    CrossesBook { price: i64 },
 }
 
 impl fmt::Display for ValidationError {
@@ -27,6 +30,9 @@ impl fmt::Display for ValidationError {
                 f,
                 "notional overflow: price={price} * quantity={quantity} exceeds i64 range"
             ),
+            ValidationError::CrossesBook { price } => {
+                write!(f, "order at price={price} would lock or cross the book")
+            }
         }
     }
 }
@@ -8,8 +8,10 @@
 
 #[cfg(feature = "event-log")]
 use crate::Exchange;
+use crate::exchange::{HaltMode, PositionLimitMode};
+use crate::session::SessionState;
 use crate::stop::TrailMethod;
-use crate::{OrderId, Price, Quantity, Side, TimeInForce, Trade};
+use crate::{OrderId, OrderOwner, Price, Quantity, Side, TimeInForce, Timestamp, Trade};
 
 /// An event that can be applied to an exchange.
 ///
@@ -18,12 +20,21 @@ use crate::{OrderId, Price, Quantity, Side, TimeInForce, Trade};
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
-    /// Submit a limit order
+    /// Submit a limit order, optionally tagged with an owner
     SubmitLimit {
         side: Side,
         price: Price,
         quantity: Quantity,
         time_in_force: TimeInForce,
+        #[cfg_attr(feature = "serde", serde(default))]
+        owner: Option<OrderOwner>,
+    },
+    /// Submit a hidden (non-displayed) limit order
+    SubmitHiddenLimit {
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
     },
     /// Submit a market order
     SubmitMarket { side: Side, quantity: Quantity },
@@ -65,6 +76,34 @@ pub enum Event {
         time_in_force: TimeInForce,
         trail_method: TrailMethod,
     },
+    /// Submit a bracket order (entry with stop-loss and take-profit)
+    SubmitBracket {
+        side: Side,
+        entry_price: Price,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        stop_price: Price,
+        target_price: Price,
+    },
+    /// Link two orders as one-cancels-other
+    LinkOco { order_a: OrderId, order_b: OrderId },
+    /// Advance the logical clock that stamps trades
+    SetClock { timestamp: Timestamp },
+    /// Halt trading
+    Halt { mode: HaltMode },
+    /// Resume trading after a halt
+    Resume,
+    /// Change the trading session state
+    SetSession { state: SessionState },
+    /// Set an owner's position limit, under the exchange's limit mode at
+    /// the time
+    SetPositionLimit {
+        owner: OrderOwner,
+        max_position: Quantity,
+        mode: PositionLimitMode,
+    },
+    /// Remove an owner's position limit
+    RemovePositionLimit { owner: OrderOwner },
 }
 
 impl Event {
@@ -80,6 +119,7 @@ impl Event {
             price,
             quantity,
             time_in_force,
+            owner: None,
         }
     }
 
@@ -190,8 +230,35 @@ impl Exchange {
                 price,
                 quantity,
                 time_in_force,
+                owner,
             } => {
-                let result = self.submit_limit_internal(*side, *price, *quantity, *time_in_force);
+                let result = self.submit_limit_internal_with_owner(
+                    *side,
+                    *price,
+                    *quantity,
+                    *time_in_force,
+                    *owner,
+                );
+                if !result.trades.is_empty() {
+                    self.last_trade_price = Some(result.trades.last().unwrap().price);
+                    self.process_trade_triggers();
+                }
+                result.trades
+            }
+            Event::SubmitHiddenLimit {
+                side,
+                price,
+                quantity,
+                time_in_force,
+            } => {
+                let result = self.submit_order_internal(
+                    *side,
+                    *price,
+                    *quantity,
+                    *time_in_force,
+                    None,
+                    true,
+                );
                 if !result.trades.is_empty() {
                     self.last_trade_price = Some(result.trades.last().unwrap().price);
                     self.process_trade_triggers();
@@ -284,6 +351,64 @@ impl Exchange {
                 );
                 Vec::new()
             }
+            Event::SubmitBracket {
+                side,
+                entry_price,
+                quantity,
+                time_in_force,
+                stop_price,
+                target_price,
+            } => {
+                let result = self.submit_bracket_internal(
+                    *side,
+                    *entry_price,
+                    *quantity,
+                    *time_in_force,
+                    *stop_price,
+                    *target_price,
+                );
+                if !result.entry.trades.is_empty() {
+                    self.last_trade_price = Some(result.entry.trades.last().unwrap().price);
+                    self.process_trade_triggers();
+                }
+                result.entry.trades
+            }
+            Event::LinkOco { order_a, order_b } => {
+                self.link_oco_internal(*order_a, *order_b);
+                Vec::new()
+            }
+            Event::SetClock { timestamp } => {
+                self.book.set_clock(*timestamp);
+                let first = self.trades.len();
+                self.advance_session(*timestamp);
+                self.trades[first..].to_vec()
+            }
+            Event::Halt { mode } => {
+                self.halt = Some(*mode);
+                Vec::new()
+            }
+            Event::Resume => {
+                self.halt = None;
+                Vec::new()
+            }
+            Event::SetSession { state } => {
+                let first = self.trades.len();
+                self.set_session_internal(*state);
+                self.trades[first..].to_vec()
+            }
+            Event::SetPositionLimit {
+                owner,
+                max_position,
+                mode,
+            } => {
+                self.position_limit_mode = *mode;
+                self.position_limits.insert(*owner, *max_position);
+                Vec::new()
+            }
+            Event::RemovePositionLimit { owner } => {
+                self.position_limits.remove(owner);
+                Vec::new()
+            }
         };
 
         ApplyResult { trades }
@@ -543,6 +668,34 @@ mod tests {
         assert_eq!(replayed.last_trade_price(), original.last_trade_price());
     }
 
+    #[test]
+    fn replay_enforces_position_limits() {
+        use crate::{OrderOwner, RejectReason};
+
+        let fund = OrderOwner(1);
+        for mode in [PositionLimitMode::Reject, PositionLimitMode::Clip] {
+            let mut original = Exchange::new().with_position_limit_mode(mode);
+            original.set_position_limit(fund, 100);
+            original.submit_limit(Side::Sell, Price(100_00), 200, TimeInForce::GTC);
+            let result = original.submit_limit_with_owner(
+                Side::Buy,
+                Price(100_00),
+                150,
+                TimeInForce::GTC,
+                fund,
+            );
+            assert_eq!(result.reject_reason, Some(RejectReason::PositionLimit));
+            original.remove_position_limit(fund);
+            original.submit_limit_with_owner(Side::Buy, Price(100_00), 20, TimeInForce::IOC, fund);
+
+            let replayed = Exchange::replay(original.events());
+            assert_eq!(replayed.trades(), original.trades());
+            assert_eq!(replayed.net_position(fund), original.net_position(fund));
+            assert_eq!(replayed.position_limit(fund), None);
+            assert_eq!(replayed.position_limit_mode(), mode);
+        }
+    }
+
     #[test]
     fn events_are_equal() {
         let e1 = Event::submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
@@ -7,16 +7,46 @@
 #[cfg(feature = "event-log")]
 use crate::event::Event;
 use crate::{
-    Order, OrderBook, OrderId, OrderStatus, Price, Quantity, Side, TimeInForce, Trade,
+    Bracket, Order, OrderBook, OrderId, OrderStatus, Price, Quantity, Side, TimeInForce, Timestamp,
+    Trade,
     error::ValidationError,
-    matching::StpPolicy,
+    matching::{LevelPriority, MatchingPolicy, StpPolicy, TieBreak},
     order::OrderOwner,
     result::{
-        CancelError, CancelResult, ModifyError, ModifyResult, StopSubmitResult, SubmitResult,
+        CancelError, CancelResult, FlowStats, ModifyError, ModifyResult, RejectReason,
+        StopSubmitResult, SubmitResult, SweepEstimate, TradeStats,
     },
+    session::SessionState,
     snapshot::BookSnapshot,
     stop::{StopBook, StopOrder, StopStatus, TrailMethod},
 };
+use rustc_hash::FxHashMap;
+
+/// Best price and total resting quantity on one side, if any.
+type TopLevel = Option<(Price, Quantity)>;
+
+/// Which new orders a halted exchange rejects (see [`Exchange::halt`]).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HaltMode {
+    /// Orders that would match on arrival; others may still rest.
+    Marketable,
+    /// Every order.
+    All,
+}
+
+/// What the exchange does with an owner's order that would breach its
+/// position limit (see [`Exchange::set_position_limit`]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionLimitMode {
+    /// Reject the whole order; nothing trades or rests.
+    #[default]
+    Reject,
+    /// Cut the order down to the quantity that keeps the owner within its
+    /// limit and cancel the rest. An order with nothing left is rejected.
+    Clip,
+}
 
 /// The exchange: processes orders and maintains the order book.
 ///
@@ -45,6 +75,7 @@ use crate::{
 /// assert_eq!(remaining.remaining_quantity, 60);
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Exchange {
     /// The underlying order book
     pub(crate) book: OrderBook,
@@ -56,6 +87,53 @@ pub struct Exchange {
     pub(crate) last_trade_price: Option<Price>,
     /// Self-trade prevention policy; applied when owners match.
     pub(crate) stp_policy: StpPolicy,
+    /// Price units per whole currency unit, for display
+    pub(crate) price_scale: i64,
+    /// Bracket orders by entry order ID
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
+    pub(crate) brackets: FxHashMap<OrderId, Bracket>,
+    /// One-cancels-other partner of each linked order
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
+    pub(crate) oco_links: FxHashMap<OrderId, OrderId>,
+    /// Maximum absolute net position per owner
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "crate::sorted_map::serialize")
+    )]
+    pub(crate) position_limits: FxHashMap<OrderOwner, Quantity>,
+    /// How orders breaching a position limit are handled
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) position_limit_mode: PositionLimitMode,
+    /// Net filled position per owner (buys minus sells)
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "crate::sorted_map::serialize")
+    )]
+    pub(crate) positions: FxHashMap<OrderOwner, i64>,
+    /// Current trading halt, if any
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) halt: Option<HaltMode>,
+    /// Current trading session state
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) session: SessionState,
+    /// Clock-driven session transitions, in time order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) session_schedule: Vec<(Timestamp, SessionState)>,
+    /// Index of the next transition in `session_schedule`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) next_session: usize,
+    /// Orders accepted pre-open, in arrival order, to match at the open
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) queued_orders: Vec<OrderId>,
+    /// Signed aggressor volume since the last flow reset
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) order_flow: i64,
     /// Event log for replay (only with "event-log" feature)
     #[cfg(feature = "event-log")]
     pub(crate) events: Vec<crate::event::Event>,
@@ -73,11 +151,44 @@ impl Exchange {
             stop_book: StopBook::new(),
             last_trade_price: None,
             stp_policy: StpPolicy::Off,
+            price_scale: Price::DEFAULT_SCALE,
+            brackets: FxHashMap::default(),
+            oco_links: FxHashMap::default(),
+            position_limits: FxHashMap::default(),
+            position_limit_mode: PositionLimitMode::Reject,
+            positions: FxHashMap::default(),
+            halt: None,
+            session: SessionState::Open,
+            session_schedule: Vec::new(),
+            next_session: 0,
+            queued_orders: Vec::new(),
+            order_flow: 0,
             #[cfg(feature = "event-log")]
             events: Vec::new(),
         }
     }
 
+    /// Create a new exchange whose order and trade IDs start at the given
+    /// values instead of 1.
+    ///
+    /// IDs still increase by one per order or trade and are private to this
+    /// exchange. This is useful for aligning IDs with external data or for
+    /// making fixtures from separate runs line up.
+    ///
+    /// The starting IDs are not part of the event log, so
+    /// [`Self::replay`] always starts from 1. To reproduce such an exchange,
+    /// create it with the same starts and call [`Self::apply_all`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order_id_start` is 0 (reserved).
+    pub fn with_id_start(order_id_start: u64, trade_id_start: u64) -> Self {
+        Self {
+            book: OrderBook::with_id_start(order_id_start, trade_id_start),
+            ..Self::new()
+        }
+    }
+
     /// Set the self-trade prevention policy for this exchange.
     ///
     /// Builder form; consumes and returns `Self`. The policy is consulted
@@ -88,12 +199,227 @@ impl Exchange {
         self
     }
 
+    /// Set how orders with equal timestamps at the same price are queued.
+    ///
+    /// Builder form; consumes and returns `Self`. See [`TieBreak`]. Orders
+    /// submitted through the exchange always get distinct timestamps, so
+    /// this only affects orders added directly via [`Self::book_mut`].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.book = self.book.with_tie_break(tie_break);
+        self
+    }
+
+    /// Set the number of price units per whole currency unit.
+    ///
+    /// Builder form; consumes and returns `Self`. Prices stay integers; the
+    /// scale only tells formatting code how to show them, e.g. `10_000`
+    /// for prices quoted to four decimals. Defaults to
+    /// [`Price::DEFAULT_SCALE`] (cents).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `price_scale` is not positive.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        assert!(price_scale > 0, "price_scale must be positive");
+        self.price_scale = price_scale;
+        self
+    }
+
+    /// Price units per whole currency unit (100 by default).
+    pub fn price_scale(&self) -> i64 {
+        self.price_scale
+    }
+
+    /// Set how resting orders within a price level are ranked.
+    ///
+    /// Builder form; consumes and returns `Self`. See [`LevelPriority`].
+    pub fn with_level_priority(mut self, level_priority: LevelPriority) -> Self {
+        self.book = self.book.with_level_priority(level_priority);
+        self
+    }
+
+    /// Halt trading: reject every new order that would match on arrival.
+    ///
+    /// Resting orders stay on the book and can still be cancelled or
+    /// reduced in place; non-marketable orders are accepted and rest.
+    /// Rejected orders carry [`RejectReason::Halted`], and a modify whose
+    /// replacement would be rejected fails with [`ModifyError::Halted`],
+    /// leaving the original order untouched. Stop orders may still be
+    /// submitted; nothing trades, so none trigger. Lasts until
+    /// [`Self::resume`].
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, RejectReason, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// let ask = exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+    /// exchange.halt();
+    ///
+    /// let taker = exchange.submit_market(Side::Buy, 5);
+    /// assert_eq!(taker.reject_reason, Some(RejectReason::Halted));
+    /// assert!(exchange.cancel(ask.order_id).success);
+    ///
+    /// exchange.resume();
+    /// assert!(!exchange.is_halted());
+    /// ```
+    pub fn halt(&mut self) {
+        self.set_halt(Some(HaltMode::Marketable));
+    }
+
+    /// Halt trading and reject every new order, marketable or not.
+    ///
+    /// Otherwise behaves like [`Self::halt`].
+    pub fn halt_all(&mut self) {
+        self.set_halt(Some(HaltMode::All));
+    }
+
+    /// Lift a halt set by [`Self::halt`] or [`Self::halt_all`]. Does
+    /// nothing if the exchange is not halted.
+    pub fn resume(&mut self) {
+        self.set_halt(None);
+    }
+
+    /// The current halt, or `None` if the exchange is trading.
+    pub fn halt_mode(&self) -> Option<HaltMode> {
+        self.halt
+    }
+
+    /// Returns true while the exchange is halted.
+    pub fn is_halted(&self) -> bool {
+        self.halt.is_some()
+    }
+
+    fn set_halt(&mut self, mode: Option<HaltMode>) {
+        self.halt = mode;
+        #[cfg(feature = "event-log")]
+        self.events.push(match mode {
+            Some(mode) => Event::Halt { mode },
+            None => Event::Resume,
+        });
+    }
+
+    /// Set how orders that would breach a position limit are handled.
+    ///
+    /// Builder form; consumes and returns `Self`. Defaults to
+    /// [`PositionLimitMode::Reject`].
+    pub fn with_position_limit_mode(mut self, mode: PositionLimitMode) -> Self {
+        self.position_limit_mode = mode;
+        self
+    }
+
+    /// Returns how orders that would breach a position limit are handled.
+    pub fn position_limit_mode(&self) -> PositionLimitMode {
+        self.position_limit_mode
+    }
+
+    /// Cap `owner`'s absolute net position at `max_position`.
+    ///
+    /// Checked when an order tagged with `owner` is submitted (or modified):
+    /// the owner's filled position plus all of its open quantity on the
+    /// order's side, plus the order itself, must stay within
+    /// `±max_position`, so the cap holds however the open orders later
+    /// fill. Orders that reduce the position are never limited by more than
+    /// that. A breaching order is rejected with
+    /// [`RejectReason::PositionLimit`] or clipped, per
+    /// [`Self::with_position_limit_mode`]. Orders without an owner are not
+    /// checked. Replaces any earlier limit for `owner`.
+    ///
+    /// Limit changes are recorded in the event log together with the
+    /// current [`PositionLimitMode`], so a replay enforces them the same
+    /// way.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, OrderOwner, Price, RejectReason, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// let fund = OrderOwner(1);
+    /// exchange.set_position_limit(fund, 100);
+    ///
+    /// exchange.submit_limit_with_owner(Side::Buy, Price(99_00), 80, TimeInForce::GTC, fund);
+    /// let result =
+    ///     exchange.submit_limit_with_owner(Side::Buy, Price(98_00), 30, TimeInForce::GTC, fund);
+    /// assert_eq!(result.reject_reason, Some(RejectReason::PositionLimit));
+    /// ```
+    pub fn set_position_limit(&mut self, owner: OrderOwner, max_position: Quantity) {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SetPositionLimit {
+            owner,
+            max_position,
+            mode: self.position_limit_mode,
+        });
+
+        self.position_limits.insert(owner, max_position);
+    }
+
+    /// Remove `owner`'s position limit, returning it if one was set.
+    pub fn remove_position_limit(&mut self, owner: OrderOwner) -> Option<Quantity> {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::RemovePositionLimit { owner });
+
+        self.position_limits.remove(&owner)
+    }
+
+    /// The position limit set for `owner`, if any.
+    pub fn position_limit(&self, owner: OrderOwner) -> Option<Quantity> {
+        self.position_limits.get(&owner).copied()
+    }
+
+    /// `owner`'s net filled position: quantity bought minus quantity sold
+    /// by its orders, as maker or taker. Zero if it never traded.
+    pub fn net_position(&self, owner: OrderOwner) -> i64 {
+        self.positions.get(&owner).copied().unwrap_or(0)
+    }
+
     /// Returns the currently configured self-trade prevention policy.
     #[inline]
     pub fn stp_policy(&self) -> StpPolicy {
         self.stp_policy
     }
 
+    /// Returns the matching rules this exchange runs. See
+    /// [`MatchingPolicy`] for the full priority specification.
+    pub fn matching_policy(&self) -> MatchingPolicy {
+        MatchingPolicy {
+            level_priority: self.book.level_priority(),
+            tie_break: self.book.tie_break(),
+            stp_policy: self.stp_policy,
+        }
+    }
+
+    /// Advance the logical clock that stamps trades.
+    ///
+    /// Once set, every trade carries the current clock value, so all fills
+    /// of one aggressor — and every trade until the next call — share a
+    /// timestamp, which makes time-window queries over [`Self::trades`]
+    /// line up with the caller's clock. Without a clock, each trade takes a
+    /// distinct value from the book's internal counter. See
+    /// [`OrderBook::set_clock`]. Session transitions scheduled at or
+    /// before `now` (see [`Self::set_session_schedule`]) then take effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `now` is earlier than [`Self::min_clock`], so the trade
+    /// tape stays in timestamp order.
+    pub fn set_clock(&mut self, now: Timestamp) {
+        self.book.set_clock(now);
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SetClock { timestamp: now });
+        self.advance_session(now);
+    }
+
+    /// Current logical clock value (`None` until [`Self::set_clock`] is
+    /// first called).
+    pub fn clock(&self) -> Option<Timestamp> {
+        self.book.clock()
+    }
+
+    /// Earliest value [`Self::set_clock`] accepts: the current clock, or
+    /// until it is first set, the last timestamp the book's counter gave
+    /// an order or trade. See [`OrderBook::min_clock`].
+    pub fn min_clock(&self) -> Timestamp {
+        self.book.min_clock()
+    }
+
     // === Order Submission ===
 
     /// Submit a limit order.
@@ -132,6 +458,7 @@ impl Exchange {
             price,
             quantity,
             time_in_force: tif,
+            owner: None,
         });
 
         let result = self.submit_limit_internal(side, price, quantity, tif);
@@ -164,6 +491,7 @@ impl Exchange {
             price,
             quantity,
             time_in_force: tif,
+            owner: Some(owner),
         });
 
         let result = self.submit_limit_internal_with_owner(side, price, quantity, tif, Some(owner));
@@ -175,6 +503,51 @@ impl Exchange {
         result
     }
 
+    /// Submit a hidden (non-displayed) limit order.
+    ///
+    /// The order matches on arrival and any GTC remainder rests exactly like
+    /// [`Self::submit_limit`], but while resting it is left out of
+    /// [`Self::best_bid_ask`], [`Self::depth`], and the other displayed-book
+    /// queries. Incoming orders reach it by price first: it fills ahead of
+    /// displayed orders at worse prices, and behind displayed orders at its
+    /// own price.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_hidden_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC);
+    /// assert_eq!(exchange.best_ask(), Some(Price(101_00)));
+    ///
+    /// let result = exchange.submit_market(Side::Buy, 60);
+    /// assert_eq!(result.trades[0].price, Price(100_00));
+    /// assert_eq!(result.trades[1].price, Price(101_00));
+    /// ```
+    pub fn submit_hidden_limit(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+    ) -> SubmitResult {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SubmitHiddenLimit {
+            side,
+            price,
+            quantity,
+            time_in_force: tif,
+        });
+
+        let result = self.submit_order_internal(side, price, quantity, tif, None, true);
+        if !result.trades.is_empty() {
+            let last_price = result.trades.last().unwrap().price;
+            self.last_trade_price = Some(last_price);
+            self.process_trade_triggers();
+        }
+        result
+    }
+
     /// Submit a market order.
     ///
     /// Market orders execute immediately at the best available prices.
@@ -255,8 +628,46 @@ impl Exchange {
         tif: TimeInForce,
         owner: Option<OrderOwner>,
     ) -> SubmitResult {
-        // FOK: Check feasibility before doing anything
-        if tif == TimeInForce::FOK && !self.book.can_fully_fill(side, price, quantity) {
+        self.submit_order_internal(side, price, quantity, tif, owner, false)
+    }
+
+    /// Internal: submit a displayed or hidden limit order without recording
+    /// event.
+    pub(crate) fn submit_order_internal(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        owner: Option<OrderOwner>,
+        hidden: bool,
+    ) -> SubmitResult {
+        if let Some(reason) = self.admission_reject(side, price, tif) {
+            return self.reject_order(side, price, quantity, tif, reason);
+        }
+
+        let submitted = quantity;
+        let quantity = match owner.map(|owner| self.position_headroom(owner, side, 0)) {
+            Some(Some(headroom)) if headroom < quantity => {
+                if self.position_limit_mode == PositionLimitMode::Reject || headroom == 0 {
+                    return self.reject_order(
+                        side,
+                        price,
+                        quantity,
+                        tif,
+                        RejectReason::PositionLimit,
+                    );
+                }
+                headroom
+            }
+            _ => quantity,
+        };
+        let clipped = submitted - quantity;
+
+        // FOK: Check feasibility before doing anything. The probe only reads
+        // the book; what it finds is reported as `max_fillable`.
+        let available = (tif == TimeInForce::FOK).then(|| self.book.available_to_fill(side, price));
+        if let Some(available) = available.filter(|&available| available < quantity) {
             // Reject the order. We still consume an OrderId for consistency
             // (the caller gets a valid ID even for rejected orders).
             // Note: This creates gaps in the OrderId sequence for rejected FOKs,
@@ -269,13 +680,48 @@ impl Exchange {
                 filled_quantity: 0,
                 resting_quantity: 0,
                 cancelled_quantity: quantity,
+                average_fill_price: None,
+                filled_notional: 0,
+                reject_reason: Some(RejectReason::InsufficientLiquidity),
+                max_fillable: Some(available),
+                fills_by_level: Vec::new(),
             };
         }
 
         // Create the order
         let mut order = self.book.create_order(side, price, quantity, tif);
         order.owner = owner;
-        let order_id = order.id;
+        order.hidden = hidden;
+
+        // Pre-open: rest without matching until the session opens.
+        if self.session == SessionState::PreOpen {
+            let order_id = order.id;
+            self.book.add_order(order);
+            self.queued_orders.push(order_id);
+            return SubmitResult {
+                order_id,
+                status: OrderStatus::New,
+                trades: Vec::new(),
+                filled_quantity: 0,
+                resting_quantity: quantity,
+                cancelled_quantity: clipped,
+                average_fill_price: None,
+                filled_notional: 0,
+                reject_reason: (clipped > 0).then_some(RejectReason::PositionLimit),
+                max_fillable: None,
+                fills_by_level: Vec::new(),
+            };
+        }
+
+        self.execute_order(order, clipped)
+    }
+
+    /// Match a new (or re-queued) order against the book and rest, fill, or
+    /// cancel it per its time in force. `clipped` is the quantity a
+    /// position limit already cut from it.
+    pub(crate) fn execute_order(&mut self, mut order: Order, clipped: Quantity) -> SubmitResult {
+        let (order_id, side, owner, tif) = (order.id, order.side, order.owner, order.time_in_force);
+        let quantity = order.remaining_quantity;
 
         // Match against the book under the configured STP policy.
         let policy = self.stp_policy;
@@ -283,6 +729,12 @@ impl Exchange {
 
         // Record trades
         self.trades.extend(match_result.trades.iter().cloned());
+        self.record_positions(owner, side, &match_result.trades);
+        let volume: i64 = match_result.trades.iter().map(|t| t.quantity as i64).sum();
+        self.order_flow += match side {
+            Side::Buy => volume,
+            Side::Sell => -volume,
+        };
 
         let filled = order.filled_quantity;
         let remaining = order.remaining_quantity;
@@ -333,13 +785,128 @@ impl Exchange {
             (status, 0, remaining)
         };
 
+        self.process_linked_fills(&match_result.trades);
+
+        let filled_notional = match_result.filled_notional;
+        let average_fill_price = (filled > 0).then(|| filled_notional as f64 / filled as f64);
+
         SubmitResult {
             order_id,
             status,
             trades: match_result.trades,
             filled_quantity: filled,
             resting_quantity: resting,
-            cancelled_quantity: cancelled,
+            cancelled_quantity: cancelled + clipped,
+            average_fill_price,
+            filled_notional,
+            reject_reason: if stp_cancelled {
+                Some(RejectReason::SelfTrade)
+            } else {
+                (clipped > 0).then_some(RejectReason::PositionLimit)
+            },
+            max_fillable: None,
+            fills_by_level: match_result.fills_by_level,
+        }
+    }
+
+    /// Reject an order before it reaches the book. Like a failed FOK, it
+    /// consumes an order ID but is not stored.
+    fn reject_order(
+        &mut self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+        reason: RejectReason,
+    ) -> SubmitResult {
+        let order = self.book.create_order(side, price, quantity, tif);
+        SubmitResult {
+            order_id: order.id,
+            status: OrderStatus::Cancelled,
+            trades: Vec::new(),
+            filled_quantity: 0,
+            resting_quantity: 0,
+            cancelled_quantity: quantity,
+            average_fill_price: None,
+            filled_notional: 0,
+            reject_reason: Some(reason),
+            max_fillable: None,
+            fills_by_level: Vec::new(),
+        }
+    }
+
+    /// Why the current halt or session refuses a new order, if it does.
+    fn admission_reject(&self, side: Side, price: Price, tif: TimeInForce) -> Option<RejectReason> {
+        if self.halt_rejects(side, price) {
+            return Some(RejectReason::Halted);
+        }
+        match self.session {
+            SessionState::Open => None,
+            SessionState::PreOpen if tif == TimeInForce::GTC => None,
+            SessionState::PreOpen | SessionState::Closed => Some(RejectReason::Session),
+        }
+    }
+
+    /// Whether the current halt blocks an order at `price` on `side`.
+    fn halt_rejects(&self, side: Side, price: Price) -> bool {
+        match self.halt {
+            None => false,
+            Some(HaltMode::All) => true,
+            Some(HaltMode::Marketable) => {
+                let resting = side.opposite();
+                let crosses = |best: Option<Price>| {
+                    best.is_some_and(|best| match side {
+                        Side::Buy => best <= price,
+                        Side::Sell => best >= price,
+                    })
+                };
+                crosses(self.book.side(resting).best_price())
+                    || crosses(self.book.hidden_side(resting).best_price())
+            }
+        }
+    }
+
+    /// How much more `owner` may buy (or sell) under its position limit,
+    /// counting its open orders on that side as if they filled, less
+    /// `released` of that open quantity (an order about to be replaced).
+    /// `None` if the owner has no limit.
+    fn position_headroom(
+        &self,
+        owner: OrderOwner,
+        side: Side,
+        released: Quantity,
+    ) -> Option<Quantity> {
+        let limit = *self.position_limits.get(&owner)? as i64;
+        let open = self
+            .book
+            .owner_open_quantity(owner, side)
+            .saturating_sub(released);
+        let position = self.net_position(owner);
+        let exposure = match side {
+            Side::Buy => position + open as i64,
+            Side::Sell => open as i64 - position,
+        };
+        Some((limit - exposure).max(0) as Quantity)
+    }
+
+    /// Apply fills to the net positions of the owners on both sides.
+    fn record_positions(&mut self, aggressor: Option<OrderOwner>, side: Side, trades: &[Trade]) {
+        let signed = |side: Side, qty: Quantity| match side {
+            Side::Buy => qty as i64,
+            Side::Sell => -(qty as i64),
+        };
+        for trade in trades {
+            if let Some(owner) = aggressor {
+                *self.positions.entry(owner).or_insert(0) += signed(side, trade.quantity);
+            }
+            let passive = self
+                .book
+                .get_order(trade.passive_order_id)
+                .and_then(|o| o.owner);
+            if let Some(owner) = passive {
+                *self.positions.entry(owner).or_insert(0) +=
+                    signed(side.opposite(), trade.quantity);
+            }
         }
     }
 
@@ -347,7 +914,12 @@ impl Exchange {
 
     /// Cancel an order.
     ///
-    /// Returns the cancelled quantity if successful.
+    /// Returns the cancelled quantity if successful. Cancelling an ID the
+    /// exchange does not know (never issued, or dropped by
+    /// [`Self::clear_order_history`]) fails with
+    /// [`CancelError::OrderNotFound`]; cancelling a filled or already
+    /// cancelled order fails with [`CancelError::OrderNotActive`]. Neither
+    /// changes any state.
     pub fn cancel(&mut self, order_id: OrderId) -> CancelResult {
         #[cfg(feature = "event-log")]
         self.events.push(Event::Cancel { order_id });
@@ -357,6 +929,15 @@ impl Exchange {
 
     /// Internal: cancel without recording event.
     pub(crate) fn cancel_internal(&mut self, order_id: OrderId) -> CancelResult {
+        let result = self.cancel_unlinked(order_id);
+        if result.success {
+            self.process_linked_cancel(order_id);
+        }
+        result
+    }
+
+    /// Cancel a resting or stop order, ignoring brackets and OCO links.
+    fn cancel_unlinked(&mut self, order_id: OrderId) -> CancelResult {
         // Check stop book first
         if self.stop_book.contains_pending(order_id) {
             if let Some(stop) = self.stop_book.get(order_id) {
@@ -384,13 +965,44 @@ impl Exchange {
         }
     }
 
-    /// Modify an order (cancel and replace).
+    /// Modify an order's price and/or quantity.
+    ///
+    /// `new_quantity` is the desired open (remaining) quantity.
+    ///
+    /// **Priority rule.** If `new_price` equals the current price and
+    /// `new_quantity` is at most the current remaining quantity, the order is
+    /// amended in place: it keeps its ID and queue position, and
+    /// `priority_retained` is set on the result. Under
+    /// [`LevelPriority::SizeTime`] a reduction re-ranks the order by its new
+    /// size, and `priority_retained` is set only if that leaves it where it
+    /// was in the queue. Any other change (new
+    /// price, or a size increase) is a cancel and replace: the old order is
+    /// cancelled and a new order with a new ID is submitted at the back of
+    /// the queue, **losing time priority**.
+    ///
+    /// The replacement inherits the original order's time-in-force, owner,
+    /// and hidden flag.
     ///
-    /// The old order is cancelled and a new order is submitted with
-    /// the new price and quantity. The new order gets a new ID and
-    /// **loses time priority**.
+    /// **Stale IDs.** Modifying an unknown ID fails with
+    /// [`ModifyError::OrderNotFound`] and a filled or cancelled order with
+    /// [`ModifyError::OrderNotActive`], whatever the new price and
+    /// quantity. A failed modify never panics, creates an order, or
+    /// consumes an order ID.
     ///
-    /// The new order inherits the original order's time-in-force.
+    /// **Crossing.** A replacement whose new price crosses the spread is
+    /// treated exactly like a fresh aggressive limit order at that price: it
+    /// matches against the opposite side at the resting orders' prices
+    /// (best first), the fills are returned in [`ModifyResult::trades`], and
+    /// any remainder rests at `new_price` under the new ID. Trades update
+    /// `last_trade_price` and may trigger stop orders, as with
+    /// [`Self::submit_limit`].
+    ///
+    /// **Position limits.** A replacement that the owner's position limit
+    /// would reject, counting the original's open quantity as released,
+    /// fails with [`ModifyError::PositionLimit`] and leaves the original
+    /// order and its OCO and bracket links untouched. Under
+    /// [`PositionLimitMode::Clip`] a replacement that still fits in part is
+    /// clipped like a new order.
     pub fn modify(
         &mut self,
         order_id: OrderId,
@@ -404,7 +1016,13 @@ impl Exchange {
             new_quantity,
         });
 
-        self.modify_internal(order_id, new_price, new_quantity)
+        let result = self.modify_internal(order_id, new_price, new_quantity);
+        if !result.trades.is_empty() {
+            let last_price = result.trades.last().unwrap().price;
+            self.last_trade_price = Some(last_price);
+            self.process_trade_triggers();
+        }
+        result
     }
 
     /// Internal: modify without recording event.
@@ -414,17 +1032,61 @@ impl Exchange {
         new_price: Price,
         new_quantity: Quantity,
     ) -> ModifyResult {
+        // Get the old order's details
+        let (side, tif, price, remaining, owner, hidden) = match self.book.get_order(order_id) {
+            Some(o) if o.is_active() => (
+                o.side,
+                o.time_in_force,
+                o.price,
+                o.remaining_quantity,
+                o.owner,
+                o.hidden,
+            ),
+            Some(_) => return ModifyResult::failure(order_id, ModifyError::OrderNotActive),
+            None => return ModifyResult::failure(order_id, ModifyError::OrderNotFound),
+        };
+
         // Validate quantity
         if new_quantity == 0 {
             return ModifyResult::failure(order_id, ModifyError::InvalidQuantity);
         }
 
-        // Get the old order's details
-        let (side, tif) = match self.book.get_order(order_id) {
-            Some(o) if o.is_active() => (o.side, o.time_in_force),
-            Some(_) => return ModifyResult::failure(order_id, ModifyError::OrderNotActive),
-            None => return ModifyResult::failure(order_id, ModifyError::OrderNotFound),
-        };
+        // Same price, no size increase: amend in place and keep priority,
+        // unless a size-time re-rank moves the order
+        if new_price == price && new_quantity <= remaining {
+            let reduction = remaining - new_quantity;
+            let mut result = ModifyResult::amended(order_id, reduction);
+            if reduction > 0 {
+                let reranked = self.book.level_priority() == LevelPriority::SizeTime;
+                let before = reranked.then(|| self.book.queue_index(order_id));
+                self.book.reduce_order(order_id, reduction);
+                result.priority_retained =
+                    before.is_none_or(|before| before == self.book.queue_index(order_id));
+            }
+            return result;
+        }
+
+        // A halt or session that would reject the replacement leaves the
+        // order as is
+        match self.admission_reject(side, new_price, tif) {
+            Some(RejectReason::Halted) => {
+                return ModifyResult::failure(order_id, ModifyError::Halted);
+            }
+            Some(_) => return ModifyResult::failure(order_id, ModifyError::Session),
+            None => {}
+        }
+
+        // So does a position limit that would reject it, counting the old
+        // order's open quantity as released (a clipped replacement goes ahead)
+        let limited = owner
+            .and_then(|owner| self.position_headroom(owner, side, remaining))
+            .is_some_and(|headroom| {
+                headroom < new_quantity
+                    && (self.position_limit_mode == PositionLimitMode::Reject || headroom == 0)
+            });
+        if limited {
+            return ModifyResult::failure(order_id, ModifyError::PositionLimit);
+        }
 
         // Cancel the old order
         let cancelled = match self.book.cancel_order(order_id) {
@@ -432,8 +1094,10 @@ impl Exchange {
             None => return ModifyResult::failure(order_id, ModifyError::OrderNotActive),
         };
 
-        // Submit the new order
-        let result = self.submit_limit_internal(side, new_price, new_quantity, tif);
+        // Submit the new order; if it crosses, it matches like any incoming order
+        let new_id = self.book.peek_next_order_id();
+        self.move_links(order_id, new_id);
+        let result = self.submit_order_internal(side, new_price, new_quantity, tif, owner, hidden);
 
         ModifyResult::success(order_id, result.order_id, cancelled, result.trades)
     }
@@ -670,6 +1334,7 @@ impl Exchange {
             let mut new_last_price = None;
 
             for stop in triggered {
+                self.reduce_oco_partner(stop.id, stop.quantity);
                 let result = match stop.limit_price {
                     Some(limit) => self.submit_limit_internal(
                         stop.side,
@@ -716,6 +1381,39 @@ impl Exchange {
         self.book.best_bid_ask()
     }
 
+    /// Get the best bid and ask with the total resting quantity at each.
+    ///
+    /// Returns `(bid, ask)` where each side is `Some((price, quantity))` or
+    /// `None` if that side is empty. O(1): reads only the best level.
+    pub fn top_of_book(&self) -> (TopLevel, TopLevel) {
+        fn top(levels: &crate::PriceLevels) -> TopLevel {
+            let price = levels.best_price()?;
+            let level = levels.best_level()?;
+            Some((price, level.total_quantity()))
+        }
+        (top(self.book.bids()), top(self.book.asks()))
+    }
+
+    /// Number of orders resting on both sides of the book, hidden ones
+    /// included. O(levels).
+    ///
+    /// Unlike [`OrderBook::active_order_count`], this reads the cached
+    /// per-level counts instead of scanning the order history.
+    pub fn resting_order_count(&self) -> usize {
+        [Side::Buy, Side::Sell]
+            .into_iter()
+            .map(|side| {
+                self.book.side(side).order_count() + self.book.hidden_side(side).order_count()
+            })
+            .sum()
+    }
+
+    /// Total quantity resting on one side of the book, hidden orders
+    /// included. O(levels).
+    pub fn total_resting_quantity(&self, side: Side) -> Quantity {
+        self.book.side(side).total_quantity() + self.book.hidden_side(side).total_quantity()
+    }
+
     /// Get the best bid price.
     pub fn best_bid(&self) -> Option<Price> {
         self.book.best_bid()
@@ -731,6 +1429,20 @@ impl Exchange {
         self.book.spread()
     }
 
+    /// Returns true if the book is crossed (best bid ≥ best ask).
+    ///
+    /// Matching never leaves the book crossed, so this only reports
+    /// states created by adding orders directly via [`Self::book_mut`]. Use
+    /// [`OrderBook::try_add_order`] to reject such orders instead.
+    pub fn is_crossed(&self) -> bool {
+        self.book.is_crossed()
+    }
+
+    /// Returns true if the book is locked (best bid == best ask).
+    pub fn is_locked(&self) -> bool {
+        self.book.is_locked()
+    }
+
     /// Get a snapshot of the top N levels on each side.
     pub fn depth(&self, levels: usize) -> BookSnapshot {
         self.book.snapshot(levels)
@@ -741,24 +1453,315 @@ impl Exchange {
         self.book.full_snapshot()
     }
 
+    /// Visible `(price, quantity)` pairs for the top `depth` levels of one
+    /// side, best to worst.
+    ///
+    /// A flat alternative to [`depth`](Self::depth) for signal code that
+    /// only needs prices and sizes: prices are raw integers, and hidden
+    /// orders are not included. Pass `usize::MAX` for every level.
+    pub fn levels(&self, side: Side, depth: usize) -> Vec<(i64, Quantity)> {
+        self.book
+            .side(side)
+            .iter_best_to_worst()
+            .take(depth)
+            .map(|(price, level)| (price.0, level.total_quantity()))
+            .collect()
+    }
+
+    /// Logical time an order has held its current queue priority:
+    /// [`Self::clock`] minus [`Order::queued_at`].
+    ///
+    /// The count restarts whenever the order takes a new place in the
+    /// queue, i.e. on a modify that loses priority (which also gives it a
+    /// new ID) or a [`LevelPriority::SizeTime`] re-rank. Orders resting
+    /// before the clock was first set count from 0. Returns `None` if the
+    /// order is not resting on the book or the clock is unset.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_clock(1_000);
+    /// let bid = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+    /// exchange.set_clock(1_250);
+    /// assert_eq!(exchange.time_in_queue(bid.order_id), Some(250));
+    ///
+    /// let moved = exchange.modify(bid.order_id, Price(98_00), 10);
+    /// assert_eq!(exchange.time_in_queue(moved.new_order_id.unwrap()), Some(0));
+    /// ```
+    pub fn time_in_queue(&self, order_id: OrderId) -> Option<Timestamp> {
+        let now = self.clock()?;
+        let order = self.book.get_order(order_id).filter(|o| o.is_active())?;
+        Some(now.saturating_sub(order.queued_at))
+    }
+
+    /// The orders resting at `price` on `side`, in the order they fill.
+    ///
+    /// Displayed orders come first in queue priority, then hidden orders
+    /// at the same price. Empty if nothing rests at that price. Cheaper
+    /// than [`Self::full_book`] when only one level's queue matters.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// let first = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+    /// let second = exchange.submit_limit(Side::Buy, Price(99_00), 5, TimeInForce::GTC);
+    ///
+    /// let queue = exchange.orders_at(Side::Buy, Price(99_00));
+    /// assert_eq!(queue[0].id, first.order_id);
+    /// assert_eq!(queue[1].id, second.order_id);
+    /// assert!(exchange.orders_at(Side::Sell, Price(99_00)).is_empty());
+    /// ```
+    pub fn orders_at(&self, side: Side, price: Price) -> Vec<Order> {
+        [self.book.side(side), self.book.hidden_side(side)]
+            .into_iter()
+            .filter_map(|levels| levels.get_level(price))
+            .flat_map(|level| level.iter())
+            .filter_map(|id| self.book.get_order(id).cloned())
+            .collect()
+    }
+
+    /// Preview a limit order without submitting it.
+    ///
+    /// Returns the [`SubmitResult`] that [`Self::submit_limit`] would
+    /// return right now — per-trade detail, resting and cancelled
+    /// quantities, and any rejection, under the current time in force,
+    /// self-trade, halt, and session rules — while leaving the exchange
+    /// unchanged. The order ID is the one the order would get. Stops the
+    /// fills would trigger are not simulated. The preview matches against
+    /// a copy of the resting orders (not the filled and cancelled order
+    /// history, the trade tape, or pending stops), so it costs time
+    /// proportional to the resting book.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Sell, Price(101_00), 30, TimeInForce::GTC);
+    ///
+    /// let preview = exchange.preview(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+    /// assert_eq!(preview.trades.len(), 1);
+    /// assert_eq!(preview.resting_quantity, 20);
+    /// assert_eq!(exchange.best_ask(), Some(Price(100_00)));
+    ///
+    /// let submitted = exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+    /// assert_eq!(submitted.order_id, preview.order_id);
+    /// assert_eq!(submitted.trades, preview.trades);
+    /// ```
+    pub fn preview(
+        &self,
+        side: Side,
+        price: Price,
+        quantity: Quantity,
+        tif: TimeInForce,
+    ) -> SubmitResult {
+        let mut scratch = Exchange {
+            book: self.book.clone_resting(),
+            stp_policy: self.stp_policy,
+            halt: self.halt,
+            session: self.session,
+            ..Exchange::new()
+        };
+        scratch.submit_order_internal(side, price, quantity, tif, None, false)
+    }
+
     /// Get all trades that have occurred.
     pub fn trades(&self) -> &[Trade] {
         &self.trades
     }
 
-    /// Get the underlying order book (for advanced queries).
-    pub fn book(&self) -> &OrderBook {
-        &self.book
-    }
+    /// Volume and notional that `owner`'s orders provided (maker) and took
+    /// (taker) across the trade tape.
+    ///
+    /// Owners are looked up from order history, so trades whose orders were
+    /// dropped by [`Self::clear_order_history`] are not counted, and
+    /// [`Self::clear_trades`] resets the totals. All fields are zero if
+    /// `owner` never traded.
+    pub fn flow_stats(&self, owner: OrderOwner) -> FlowStats {
+        let owned = |id: OrderId| {
+            self.book
+                .get_order(id)
+                .is_some_and(|o| o.owner == Some(owner))
+        };
 
-    /// Get mutable access to the underlying order book.
-    pub fn book_mut(&mut self) -> &mut OrderBook {
-        &mut self.book
+        let mut stats = FlowStats::default();
+        for trade in &self.trades {
+            let notional = trade.price.0.saturating_mul(trade.quantity as i64);
+            if owned(trade.passive_order_id) {
+                stats.maker_volume += trade.quantity;
+                stats.maker_notional = stats.maker_notional.saturating_add(notional);
+            }
+            if owned(trade.aggressor_order_id) {
+                stats.taker_volume += trade.quantity;
+                stats.taker_notional = stats.taker_notional.saturating_add(notional);
+            }
+        }
+        stats
     }
 
-    /// Get a stop order by ID.
-    pub fn get_stop_order(&self, order_id: OrderId) -> Option<&StopOrder> {
-        self.stop_book.get(order_id)
+    /// Buy/sell volume, trade count, and VWAP of trades with timestamps in
+    /// `from_ts..=to_ts`.
+    ///
+    /// Volume is split by [`Trade::aggressor_side`] into unsigned
+    /// `buy_volume` and `sell_volume`. The window is found by binary
+    /// search: the tape is in timestamp order because the logical clock
+    /// never moves backwards or starts behind the counter (see
+    /// [`Self::set_clock`]). All fields are zero (`vwap` is `None`) if no
+    /// trade falls in the window.
+    pub fn trade_stats(&self, from_ts: Timestamp, to_ts: Timestamp) -> TradeStats {
+        let start = self.trades.partition_point(|t| t.timestamp < from_ts);
+        let end = self.trades.partition_point(|t| t.timestamp <= to_ts);
+        let window = &self.trades[start..end.max(start)];
+
+        let mut stats = TradeStats {
+            trade_count: window.len(),
+            vwap: Trade::vwap(window),
+            ..TradeStats::default()
+        };
+        for trade in window {
+            match trade.aggressor_side {
+                Side::Buy => stats.buy_volume += trade.quantity,
+                Side::Sell => stats.sell_volume += trade.quantity,
+            }
+        }
+        stats
+    }
+
+    /// Running order-flow imbalance: buy-aggressor volume minus
+    /// sell-aggressor volume since the last [`Self::reset_flow`].
+    ///
+    /// Updated as trades occur, so reading it is O(1) where
+    /// [`Self::trade_stats`] scans the tape. It is independent of the tape:
+    /// [`Self::clear_trades`] leaves it alone.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+    /// exchange.submit_market(Side::Buy, 30);
+    /// exchange.submit_market(Side::Sell, 10);
+    /// assert_eq!(exchange.order_flow_imbalance(), 20);
+    ///
+    /// exchange.reset_flow();
+    /// assert_eq!(exchange.order_flow_imbalance(), 0);
+    /// ```
+    pub fn order_flow_imbalance(&self) -> i64 {
+        self.order_flow
+    }
+
+    /// Zero the running [`Self::order_flow_imbalance`], e.g. at the start
+    /// of each bar.
+    pub fn reset_flow(&mut self) {
+        self.order_flow = 0;
+    }
+
+    /// Estimate a market order of `quantity` on `side` without submitting
+    /// it.
+    ///
+    /// Walks the opposite side best to worst, taking from each resting
+    /// order in queue order exactly as [`submit_market`](Self::submit_market)
+    /// would, and reports the fillable quantity, notional, VWAP, and worst
+    /// price reached. The book is not modified. Hidden orders are counted
+    /// too, at their price and behind the displayed orders at the same
+    /// price, as in matching.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+    /// exchange.submit_limit(Side::Sell, Price(102_00), 150, TimeInForce::GTC);
+    ///
+    /// let est = exchange.sweep_vwap(Side::Buy, 100);
+    /// assert_eq!(est.filled_quantity, 100);
+    /// assert_eq!(est.vwap, Some(Price(101_00)));
+    /// assert_eq!(est.worst_price, Some(Price(102_00)));
+    /// assert_eq!(exchange.best_ask(), Some(Price(100_00)));
+    /// ```
+    pub fn sweep_vwap(&self, side: Side, quantity: Quantity) -> SweepEstimate {
+        let mut est = SweepEstimate::default();
+        for (price, qty) in self.sweep_fills(side, quantity) {
+            est.filled_quantity += qty;
+            est.notional = est
+                .notional
+                .saturating_add(price.0.saturating_mul(qty as i64));
+            est.worst_price = Some(price);
+        }
+        if est.filled_quantity > 0 {
+            est.vwap = Some(Price(est.notional / est.filled_quantity as i64));
+        }
+        est
+    }
+
+    /// The `(price, quantity)` trades a market order of `quantity` on
+    /// `side` would produce, one per resting order, without matching.
+    pub(crate) fn sweep_fills(&self, side: Side, quantity: Quantity) -> Vec<(Price, Quantity)> {
+        let mut displayed = self
+            .book
+            .opposite_side(side)
+            .iter_best_to_worst()
+            .peekable();
+        let mut hidden = self
+            .book
+            .hidden_side(side.opposite())
+            .iter_best_to_worst()
+            .peekable();
+        let better = |a: Price, b: Price| match side {
+            Side::Buy => a < b,
+            Side::Sell => a > b,
+        };
+
+        let mut fills = Vec::new();
+        let mut remaining = quantity;
+        while remaining > 0 {
+            // Hidden levels go first only at a strictly better price
+            let from_hidden = match (displayed.peek(), hidden.peek()) {
+                (Some(&(&d, _)), Some(&(&h, _))) => better(h, d),
+                (None, _) => true,
+                (Some(_), None) => false,
+            };
+            let next = if from_hidden {
+                hidden.next()
+            } else {
+                displayed.next()
+            };
+            let Some((&price, level)) = next else {
+                break;
+            };
+            for id in level.iter() {
+                if remaining == 0 {
+                    break;
+                }
+                let Some(order) = self.book.get_order(id) else {
+                    continue;
+                };
+                let take = remaining.min(order.remaining_quantity);
+                if take > 0 {
+                    fills.push((price, take));
+                    remaining -= take;
+                }
+            }
+        }
+        fills
+    }
+
+    /// Get the underlying order book (for advanced queries).
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Get mutable access to the underlying order book.
+    pub fn book_mut(&mut self) -> &mut OrderBook {
+        &mut self.book
+    }
+
+    /// Get a stop order by ID.
+    pub fn get_stop_order(&self, order_id: OrderId) -> Option<&StopOrder> {
+        self.stop_book.get(order_id)
     }
 
     /// Get the number of pending stop orders.
@@ -801,6 +1804,35 @@ impl Exchange {
     pub fn compact(&mut self) {
         self.book.compact();
     }
+
+    /// Reset to an empty exchange, keeping its configuration.
+    ///
+    /// Removes every order (both sides of the book, order history, pending
+    /// stops, and brackets), the trade tape, the last trade price, owner net
+    /// positions, the order-flow imbalance, and the event log. It also lifts
+    /// any halt, reopens the session (rewinding its schedule), and unsets
+    /// the logical clock.
+    ///
+    /// The self-trade prevention, tie-break, and level priority policies,
+    /// the position limits, and the price scale are kept. Order and trade
+    /// IDs restart from the values given to [`Self::with_id_start`] (1 by
+    /// default), so a cleared exchange behaves like a freshly built one.
+    pub fn clear(&mut self) {
+        self.book.clear();
+        self.trades.clear();
+        self.stop_book = StopBook::new();
+        self.last_trade_price = None;
+        self.brackets.clear();
+        self.oco_links.clear();
+        self.positions.clear();
+        self.halt = None;
+        self.session = SessionState::Open;
+        self.next_session = 0;
+        self.queued_orders.clear();
+        self.order_flow = 0;
+        #[cfg(feature = "event-log")]
+        self.events.clear();
+    }
 }
 
 impl Default for Exchange {
@@ -812,6 +1844,7 @@ impl Default for Exchange {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::TradeId;
 
     // === Basic submission ===
 
@@ -929,6 +1962,7 @@ mod tests {
         assert_eq!(result.status, OrderStatus::Filled);
         assert_eq!(result.filled_quantity, 100);
         assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.max_fillable, None);
     }
 
     #[test]
@@ -944,6 +1978,11 @@ mod tests {
         assert_eq!(result.filled_quantity, 0);
         assert_eq!(result.cancelled_quantity, 100);
         assert!(result.trades.is_empty()); // No trades!
+        assert_eq!(
+            result.reject_reason,
+            Some(RejectReason::InsufficientLiquidity)
+        );
+        assert_eq!(result.max_fillable, Some(50));
 
         // Ask should still be there
         assert_eq!(exchange.best_ask(), Some(Price(100_00)));
@@ -957,6 +1996,7 @@ mod tests {
 
         assert_eq!(result.status, OrderStatus::Cancelled);
         assert!(result.trades.is_empty());
+        assert_eq!(result.max_fillable, Some(0));
     }
 
     // === Market orders ===
@@ -994,6 +2034,61 @@ mod tests {
 
         assert_eq!(result.status, OrderStatus::Cancelled);
         assert_eq!(result.filled_quantity, 0);
+        assert_eq!(result.average_fill_price, None);
+        assert_eq!(result.filled_notional, 0);
+    }
+
+    #[test]
+    fn submit_reports_average_fill_price() {
+        let mut exchange = Exchange::new();
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 25, TimeInForce::GTC);
+
+        let result = exchange.submit_market(Side::Buy, 100);
+
+        // 50 @ 100.00 + 25 @ 101.00
+        assert_eq!(result.filled_notional, 50 * 100_00 + 25 * 101_00);
+        let expected = (50.0 * 100_00.0 + 25.0 * 101_00.0) / 75.0;
+        assert!((result.average_fill_price.unwrap() - expected).abs() < 1e-9);
+        let notional: i64 = result.trades.iter().map(|t| t.notional().unwrap()).sum();
+        assert_eq!(result.filled_notional, notional);
+    }
+
+    #[test]
+    fn submit_reports_fills_by_level() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_hidden_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_00), 20, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 50, TimeInForce::GTC);
+
+        // Displayed and hidden fills at one price share a level entry.
+        let result = exchange.submit_market(Side::Buy, 80);
+        assert_eq!(
+            result.fills_by_level,
+            vec![(Price(100_00), 60), (Price(101_00), 20)]
+        );
+        assert_eq!(result.trades.len(), 4);
+
+        let resting = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        assert!(resting.fills_by_level.is_empty());
+    }
+
+    #[test]
+    fn crossed_and_locked_books_are_reported() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_00), 40, TimeInForce::GTC);
+        assert!(!exchange.is_crossed());
+        assert!(!exchange.is_locked());
+
+        // Bypass matching, as when loading a book from external data
+        let book = exchange.book_mut();
+        let ask = book.create_order(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        book.add_order(ask);
+        assert!(exchange.is_locked());
+        assert!(exchange.is_crossed());
     }
 
     // === Cancel ===
@@ -1036,6 +2131,139 @@ mod tests {
 
     // === Modify ===
 
+    #[test]
+    fn with_id_start_seeds_order_and_trade_ids() {
+        let mut exchange = Exchange::with_id_start(500, 9_000);
+
+        let ask = exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let bid = exchange.submit_limit(Side::Buy, Price(100_00), 4, TimeInForce::GTC);
+        let bid2 = exchange.submit_limit(Side::Buy, Price(100_00), 4, TimeInForce::GTC);
+
+        assert_eq!(ask.order_id, OrderId(500));
+        assert_eq!(bid.order_id, OrderId(501));
+        assert_eq!(bid2.order_id, OrderId(502));
+        assert_eq!(bid.trades[0].id, TradeId(9_000));
+        assert_eq!(bid2.trades[0].id, TradeId(9_001));
+    }
+
+    #[test]
+    fn cancel_after_head_fill_removes_right_order() {
+        let mut exchange = Exchange::new();
+
+        let a = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let b = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let c = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let d = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+
+        // Filling the head shifts the rest of the queue forward
+        exchange.submit_market(Side::Sell, 100);
+        assert_eq!(
+            exchange.get_order(a.order_id).unwrap().status,
+            OrderStatus::Filled
+        );
+
+        assert!(exchange.cancel(c.order_id).success);
+        assert_eq!(exchange.top_of_book().0, Some((Price(100_00), 200)));
+
+        let fill = exchange.submit_market(Side::Sell, 300);
+        let passive: Vec<_> = fill.trades.iter().map(|t| t.passive_order_id).collect();
+        assert_eq!(passive, vec![b.order_id, d.order_id]);
+        assert_eq!(fill.filled_quantity, 200);
+    }
+
+    #[test]
+    fn tie_break_orders_equal_timestamps() {
+        for (tie_break, expected) in [
+            (TieBreak::Sequence, [OrderId(9), OrderId(3)]),
+            (TieBreak::OrderId, [OrderId(3), OrderId(9)]),
+        ] {
+            let mut exchange = Exchange::new().with_tie_break(tie_break);
+
+            // Two resting asks stamped by a shared clock, added out of ID order
+            let book = exchange.book_mut();
+            book.add_order(Order::new(
+                OrderId(9),
+                Side::Sell,
+                Price(100_00),
+                10,
+                1_000,
+                TimeInForce::GTC,
+            ));
+            book.add_order(Order::new(
+                OrderId(3),
+                Side::Sell,
+                Price(100_00),
+                10,
+                1_000,
+                TimeInForce::GTC,
+            ));
+
+            let fill = exchange.submit_market(Side::Buy, 20);
+            let passive: Vec<_> = fill.trades.iter().map(|t| t.passive_order_id).collect();
+            assert_eq!(passive, expected, "{tie_break:?}");
+        }
+    }
+
+    #[test]
+    fn size_time_fills_largest_first() {
+        let mut exchange = Exchange::new().with_level_priority(LevelPriority::SizeTime);
+
+        let small = exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let big = exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        let big_later = exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        let mid = exchange.submit_limit(Side::Sell, Price(100_00), 20, TimeInForce::GTC);
+
+        // Partially filled head keeps its rank
+        exchange.submit_market(Side::Buy, 30);
+        assert_eq!(
+            exchange.get_order(big.order_id).unwrap().remaining_quantity,
+            20
+        );
+
+        let fill = exchange.submit_market(Side::Buy, 100);
+        let passive: Vec<_> = fill.trades.iter().map(|t| t.passive_order_id).collect();
+        assert_eq!(
+            passive,
+            [
+                big.order_id,
+                big_later.order_id,
+                mid.order_id,
+                small.order_id
+            ]
+        );
+    }
+
+    #[test]
+    fn size_time_reduce_reranks_and_cancel_finds_order() {
+        let mut exchange = Exchange::new().with_level_priority(LevelPriority::SizeTime);
+
+        let a = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let b = exchange.submit_limit(Side::Buy, Price(100_00), 40, TimeInForce::GTC);
+        let c = exchange.submit_limit(Side::Buy, Price(100_00), 30, TimeInForce::GTC);
+
+        // Shrinking `a` but staying above `b` keeps its place
+        let result = exchange.modify(a.order_id, Price(100_00), 60);
+        assert!(result.success);
+        assert!(result.priority_retained);
+
+        // Shrinking `a` below `b` moves it behind `b` but ahead of `c`
+        let result = exchange.modify(a.order_id, Price(100_00), 35);
+        assert!(result.success);
+        assert!(!result.priority_retained);
+        let queue: Vec<_> = exchange
+            .orders_at(Side::Buy, Price(100_00))
+            .iter()
+            .map(|o| o.id)
+            .collect();
+        assert_eq!(queue, [b.order_id, a.order_id, c.order_id]);
+        assert!(exchange.cancel(b.order_id).success);
+
+        let fill = exchange.submit_market(Side::Sell, 65);
+        let passive: Vec<_> = fill.trades.iter().map(|t| t.passive_order_id).collect();
+        assert_eq!(passive, [a.order_id, c.order_id]);
+        assert_eq!(exchange.best_bid(), None);
+    }
+
     #[test]
     fn modify_order() {
         let mut exchange = Exchange::new();
@@ -1073,6 +2301,589 @@ mod tests {
         assert_eq!(result.trades[0].quantity, 50);
     }
 
+    #[test]
+    fn modify_crossing_rests_remainder_at_new_price() {
+        let mut exchange = Exchange::new();
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 20, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(103_00), 50, TimeInForce::GTC);
+        let bid = exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+
+        // Reprice through two ask levels
+        let result = exchange.modify(bid.order_id, Price(102_00), 100);
+
+        assert!(result.success);
+        assert!(!result.priority_retained);
+        assert_eq!(result.cancelled_quantity, 100);
+        let prices: Vec<_> = result
+            .trades
+            .iter()
+            .map(|t| (t.price, t.quantity))
+            .collect();
+        assert_eq!(prices, vec![(Price(100_00), 30), (Price(101_00), 20)]);
+
+        // Remainder rests at the new price under the new ID
+        let new_id = result.new_order_id.unwrap();
+        let order = exchange.get_order(new_id).unwrap();
+        assert_eq!(order.price, Price(102_00));
+        assert_eq!(order.remaining_quantity, 50);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(
+            exchange.top_of_book(),
+            (Some((Price(102_00), 50)), Some((Price(103_00), 50)))
+        );
+        assert_eq!(exchange.last_trade_price(), Some(Price(101_00)));
+    }
+
+    #[test]
+    fn modify_crossing_triggers_stops() {
+        let mut exchange = Exchange::new();
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(105_00), 10, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Buy, Price(100_00), 10);
+        let bid = exchange.submit_limit(Side::Buy, Price(95_00), 10, TimeInForce::GTC);
+
+        let result = exchange.modify(bid.order_id, Price(100_00), 10);
+        assert_eq!(result.trades.len(), 1);
+
+        // The buy stop fired and lifted the next ask
+        assert_eq!(exchange.pending_stop_count(), 0);
+        assert_eq!(exchange.best_ask(), None);
+        assert_eq!(exchange.last_trade_price(), Some(Price(105_00)));
+    }
+
+    #[test]
+    fn logical_clock_stamps_trades() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+        assert_eq!(exchange.clock(), None);
+
+        exchange.set_clock(1_000);
+        let first = exchange.submit_market(Side::Buy, 15);
+        let second = exchange.submit_market(Side::Buy, 5);
+        assert!(
+            first
+                .trades
+                .iter()
+                .chain(&second.trades)
+                .all(|t| t.timestamp == 1_000)
+        );
+
+        exchange.set_clock(2_000);
+        let third = exchange.submit_market(Side::Buy, 5);
+        assert_eq!(third.trades[0].timestamp, 2_000);
+        assert_eq!(exchange.trade_stats(0, 1_999).trade_count, 3);
+
+        #[cfg(feature = "event-log")]
+        {
+            let replayed = Exchange::replay(exchange.events());
+            assert_eq!(replayed.trades(), exchange.trades());
+        }
+        exchange.clear();
+        assert_eq!(exchange.clock(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "backwards")]
+    fn logical_clock_cannot_rewind() {
+        let mut exchange = Exchange::new();
+        exchange.set_clock(10);
+        exchange.set_clock(9);
+    }
+
+    #[test]
+    #[should_panic(expected = "backwards")]
+    fn logical_clock_cannot_start_behind_counter_stamps() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 5);
+        assert_eq!(exchange.min_clock(), exchange.trades()[0].timestamp);
+        exchange.set_clock(exchange.min_clock() - 1);
+    }
+
+    #[test]
+    fn reject_reason_marks_stp_cancel_but_not_ioc_expiry() {
+        let mut exchange = Exchange::new().with_stp_policy(StpPolicy::CancelNewest);
+        let owner = OrderOwner(7);
+        exchange.submit_limit_with_owner(Side::Sell, Price(100_00), 50, TimeInForce::GTC, owner);
+
+        let result =
+            exchange.submit_limit_with_owner(Side::Buy, Price(100_00), 80, TimeInForce::GTC, owner);
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert!(result.is_rejected());
+        assert_eq!(result.reject_reason, Some(RejectReason::SelfTrade));
+        assert_eq!(RejectReason::SelfTrade.as_str(), "self_trade");
+        assert_eq!(RejectReason::SelfTrade.to_string(), "self_trade");
+
+        // An IOC remainder expiring is not a rejection.
+        let result = exchange.submit_limit(Side::Buy, Price(100_00), 80, TimeInForce::IOC);
+        assert_eq!(result.cancelled_quantity, 30);
+        assert_eq!(result.reject_reason, None);
+    }
+
+    #[test]
+    fn modify_crossing_keeps_owner_for_stp() {
+        let mut exchange = Exchange::new().with_stp_policy(StpPolicy::CancelNewest);
+
+        exchange.submit_limit_with_owner(
+            Side::Sell,
+            Price(100_00),
+            50,
+            TimeInForce::GTC,
+            OrderOwner(7),
+        );
+        let bid = exchange.submit_limit_with_owner(
+            Side::Buy,
+            Price(99_00),
+            50,
+            TimeInForce::GTC,
+            OrderOwner(7),
+        );
+
+        let result = exchange.modify(bid.order_id, Price(100_00), 50);
+
+        assert!(result.success);
+        assert!(result.trades.is_empty());
+        let order = exchange.get_order(result.new_order_id.unwrap()).unwrap();
+        assert_eq!(order.owner, Some(OrderOwner(7)));
+        assert_eq!(exchange.best_ask(), Some(Price(100_00)));
+    }
+
+    #[test]
+    fn trade_stats_over_window() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 30);
+        exchange.submit_market(Side::Sell, 10);
+        exchange.submit_market(Side::Buy, 20);
+
+        let ts: Vec<_> = exchange.trades().iter().map(|t| t.timestamp).collect();
+        let all = exchange.trade_stats(0, u64::MAX);
+        assert_eq!(all.trade_count, 3);
+        assert_eq!(all.buy_volume, 50);
+        assert_eq!(all.sell_volume, 10);
+        // (30 * 100 + 10 * 99 + 20 * 100) / 60
+        assert_eq!(all.vwap, Some(Price(99_83)));
+
+        let tail = exchange.trade_stats(ts[1], ts[2]);
+        assert_eq!(
+            (tail.trade_count, tail.buy_volume, tail.sell_volume),
+            (2, 20, 10)
+        );
+
+        assert_eq!(
+            exchange.trade_stats(ts[2] + 1, u64::MAX),
+            TradeStats::default()
+        );
+        assert_eq!(exchange.trade_stats(ts[2], ts[0]), TradeStats::default());
+    }
+
+    #[test]
+    fn time_in_queue_restarts_on_lost_priority() {
+        let mut exchange = Exchange::new();
+        let early = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        assert_eq!(exchange.time_in_queue(early.order_id), None);
+
+        exchange.set_clock(100);
+        let bid = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        exchange.set_clock(160);
+        assert_eq!(exchange.time_in_queue(early.order_id), Some(160));
+        assert_eq!(exchange.time_in_queue(bid.order_id), Some(60));
+
+        // An in-place reduction keeps priority and the count.
+        let reduced = exchange.modify(bid.order_id, Price(99_00), 6);
+        assert_eq!(reduced.new_order_id, Some(bid.order_id));
+        assert_eq!(exchange.time_in_queue(bid.order_id), Some(60));
+
+        let grown = exchange.modify(bid.order_id, Price(99_00), 20);
+        let grown_id = grown.new_order_id.unwrap();
+        assert_eq!(exchange.time_in_queue(grown_id), Some(0));
+        assert_eq!(exchange.time_in_queue(bid.order_id), None);
+        exchange.set_clock(200);
+        assert_eq!(exchange.time_in_queue(grown_id), Some(40));
+
+        exchange.submit_market(Side::Sell, 10);
+        assert_eq!(exchange.time_in_queue(early.order_id), None);
+    }
+
+    #[test]
+    fn order_flow_imbalance_tracks_aggressor_volume() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 30);
+        exchange.submit_limit(Side::Sell, Price(98_00), 50, TimeInForce::IOC);
+        let stats = exchange.trade_stats(0, u64::MAX);
+        assert_eq!(
+            exchange.order_flow_imbalance(),
+            stats.buy_volume as i64 - stats.sell_volume as i64
+        );
+        assert_eq!(exchange.order_flow_imbalance(), -20);
+
+        exchange.clear_trades();
+        assert_eq!(exchange.order_flow_imbalance(), -20);
+        exchange.reset_flow();
+        exchange.submit_market(Side::Buy, 5);
+        assert_eq!(exchange.order_flow_imbalance(), 5);
+        exchange.clear();
+        assert_eq!(exchange.order_flow_imbalance(), 0);
+    }
+
+    #[test]
+    fn flow_stats_split_maker_and_taker() {
+        let mut exchange = Exchange::new();
+        let mm = OrderOwner(1);
+
+        exchange.submit_limit_with_owner(Side::Sell, Price(100_00), 100, TimeInForce::GTC, mm);
+        exchange.submit_limit(Side::Buy, Price(100_00), 60, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 30, TimeInForce::GTC);
+        exchange.submit_limit_with_owner(Side::Sell, Price(99_00), 20, TimeInForce::IOC, mm);
+
+        let stats = exchange.flow_stats(mm);
+        assert_eq!(stats.maker_volume, 60);
+        assert_eq!(stats.maker_notional, 6_000_00);
+        assert_eq!(stats.taker_volume, 20);
+        assert_eq!(stats.taker_notional, 1_980_00);
+
+        assert_eq!(exchange.flow_stats(OrderOwner(2)), FlowStats::default());
+    }
+
+    #[test]
+    fn sweep_vwap_counts_hidden_liquidity() {
+        let mut exchange = Exchange::new();
+        exchange.submit_hidden_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        exchange.submit_hidden_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+
+        // 10 @ 100 hidden, 10 @ 101 displayed, 5 @ 101 hidden
+        assert_eq!(
+            exchange.sweep_fills(Side::Buy, 25),
+            vec![(Price(100_00), 10), (Price(101_00), 10), (Price(101_00), 5)]
+        );
+        let est = exchange.sweep_vwap(Side::Buy, 25);
+        let result = exchange.submit_market(Side::Buy, 25);
+        assert_eq!(est.filled_quantity, result.filled_quantity);
+        assert_eq!(est.notional, 2_515_00);
+        assert_eq!(est.worst_price, Some(Price(101_00)));
+    }
+
+    #[test]
+    fn preview_matches_submit_without_mutating() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_hidden_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 30, TimeInForce::GTC);
+        let before = exchange.full_book();
+
+        let fok = exchange.preview(Side::Buy, Price(101_00), 100, TimeInForce::FOK);
+        assert_eq!(fok.reject_reason, Some(RejectReason::InsufficientLiquidity));
+        assert_eq!(fok.max_fillable, Some(70));
+
+        for tif in [TimeInForce::GTC, TimeInForce::IOC] {
+            let preview = exchange.preview(Side::Buy, Price(100_00), 50, tif);
+            assert!(exchange.full_book().diff(&before).is_empty());
+            assert!(exchange.trades().is_empty());
+
+            let mut copy = exchange.clone();
+            let actual = copy.submit_limit(Side::Buy, Price(100_00), 50, tif);
+            assert_eq!(preview.order_id, actual.order_id);
+            assert_eq!(preview.trades, actual.trades);
+            assert_eq!(preview.status, actual.status);
+            assert_eq!(preview.resting_quantity, actual.resting_quantity);
+            assert_eq!(preview.cancelled_quantity, actual.cancelled_quantity);
+        }
+
+        exchange.halt();
+        let halted = exchange.preview(Side::Buy, Price(100_00), 5, TimeInForce::GTC);
+        assert_eq!(halted.reject_reason, Some(RejectReason::Halted));
+    }
+
+    #[test]
+    fn orders_at_follows_fill_priority() {
+        let mut exchange = Exchange::new();
+        let hidden = exchange.submit_hidden_limit(Side::Sell, Price(100_00), 5, TimeInForce::GTC);
+        let a = exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let b = exchange.submit_limit(Side::Sell, Price(100_00), 20, TimeInForce::GTC);
+        let c = exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 40, TimeInForce::GTC);
+        exchange.cancel(b.order_id);
+        exchange.submit_market(Side::Buy, 4);
+
+        let queue = exchange.orders_at(Side::Sell, Price(100_00));
+        let ids: Vec<_> = queue.iter().map(|o| o.id).collect();
+        assert_eq!(ids, vec![a.order_id, c.order_id, hidden.order_id]);
+        assert_eq!(queue[0].remaining_quantity, 6);
+        assert!(exchange.orders_at(Side::Sell, Price(102_00)).is_empty());
+    }
+
+    #[test]
+    fn net_position_tracks_maker_and_taker_fills() {
+        let mut exchange = Exchange::new();
+        let (mm, taker) = (OrderOwner(1), OrderOwner(2));
+
+        exchange.submit_limit_with_owner(Side::Sell, Price(100_00), 100, TimeInForce::GTC, mm);
+        exchange.submit_limit_with_owner(Side::Buy, Price(100_00), 60, TimeInForce::IOC, taker);
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::IOC);
+        exchange.submit_limit_with_owner(Side::Sell, Price(100_00), 25, TimeInForce::GTC, taker);
+
+        assert_eq!(exchange.net_position(mm), -70);
+        assert_eq!(exchange.net_position(taker), 60);
+        assert_eq!(exchange.net_position(OrderOwner(3)), 0);
+
+        exchange.clear();
+        assert_eq!(exchange.net_position(mm), 0);
+    }
+
+    #[test]
+    fn position_limit_counts_open_orders_and_allows_reducing() {
+        let mut exchange = Exchange::new();
+        let fund = OrderOwner(1);
+        exchange.set_position_limit(fund, 100);
+
+        exchange.submit_limit(Side::Sell, Price(100_00), 70, TimeInForce::GTC);
+        let bought =
+            exchange.submit_limit_with_owner(Side::Buy, Price(100_00), 70, TimeInForce::GTC, fund);
+        assert_eq!(bought.filled_quantity, 70);
+        exchange.submit_limit_with_owner(Side::Buy, Price(99_00), 30, TimeInForce::GTC, fund);
+
+        // 70 filled + 30 open: one more share could breach the cap.
+        let rejected =
+            exchange.submit_limit_with_owner(Side::Buy, Price(98_00), 1, TimeInForce::GTC, fund);
+        assert_eq!(rejected.status, OrderStatus::Cancelled);
+        assert_eq!(rejected.cancelled_quantity, 1);
+        assert_eq!(rejected.reject_reason, Some(RejectReason::PositionLimit));
+        assert!(exchange.get_order(rejected.order_id).is_none());
+
+        // Selling down to -100 is fine; past it is not.
+        let sell = exchange.submit_limit_with_owner(
+            Side::Sell,
+            Price(101_00),
+            170,
+            TimeInForce::GTC,
+            fund,
+        );
+        assert_eq!(sell.reject_reason, None);
+        let over =
+            exchange.submit_limit_with_owner(Side::Sell, Price(102_00), 1, TimeInForce::GTC, fund);
+        assert_eq!(over.reject_reason, Some(RejectReason::PositionLimit));
+
+        // Unowned orders and other owners are not limited.
+        let free = exchange.submit_limit(Side::Buy, Price(98_00), 500, TimeInForce::GTC);
+        assert_eq!(free.reject_reason, None);
+        assert_eq!(exchange.remove_position_limit(fund), Some(100));
+        assert_eq!(exchange.position_limit(fund), None);
+    }
+
+    #[test]
+    fn modify_over_position_limit_leaves_order_untouched() {
+        let mut exchange = Exchange::new();
+        let fund = OrderOwner(1);
+        exchange.set_position_limit(fund, 100);
+
+        let bid =
+            exchange.submit_limit_with_owner(Side::Buy, Price(99_00), 60, TimeInForce::GTC, fund);
+        let other =
+            exchange.submit_limit_with_owner(Side::Buy, Price(98_00), 30, TimeInForce::GTC, fund);
+        exchange.link_oco(bid.order_id, other.order_id).unwrap();
+
+        // Releasing the 60 leaves room for 70, not 80.
+        let over = exchange.modify(bid.order_id, Price(99_00), 80);
+        assert_eq!(over.error, Some(ModifyError::PositionLimit));
+        assert_eq!(over.new_order_id, None);
+        let order = exchange.get_order(bid.order_id).unwrap();
+        assert!(order.is_active());
+        assert_eq!(order.remaining_quantity, 60);
+        assert_eq!(exchange.oco_partner(bid.order_id), Some(other.order_id));
+
+        let up = exchange.modify(bid.order_id, Price(99_00), 70);
+        assert!(up.success);
+        assert_eq!(exchange.oco_partner(other.order_id), up.new_order_id);
+    }
+
+    #[test]
+    fn halt_rejects_marketable_orders_but_allows_cancels() {
+        let mut exchange = Exchange::new();
+        let ask = exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_hidden_limit(Side::Buy, Price(98_00), 10, TimeInForce::GTC);
+        exchange.halt();
+        assert_eq!(exchange.halt_mode(), Some(HaltMode::Marketable));
+
+        let taker = exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        assert_eq!(taker.status, OrderStatus::Cancelled);
+        assert_eq!(taker.reject_reason, Some(RejectReason::Halted));
+        assert!(exchange.get_order(taker.order_id).is_none());
+        // Hidden liquidity makes an order marketable too.
+        let hidden_cross = exchange.submit_limit(Side::Sell, Price(98_00), 5, TimeInForce::GTC);
+        assert_eq!(hidden_cross.reject_reason, Some(RejectReason::Halted));
+
+        let passive = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        assert_eq!(passive.status, OrderStatus::New);
+        let crossing = exchange.modify(passive.order_id, Price(100_00), 10);
+        assert_eq!(crossing.error, Some(ModifyError::Halted));
+        assert!(exchange.get_order(passive.order_id).unwrap().is_active());
+        assert!(exchange.modify(passive.order_id, Price(99_00), 4).success);
+
+        assert!(exchange.cancel(ask.order_id).success);
+        assert!(exchange.trades().is_empty());
+
+        exchange.halt_all();
+        let rest = exchange.submit_limit(Side::Sell, Price(105_00), 10, TimeInForce::GTC);
+        assert_eq!(rest.reject_reason, Some(RejectReason::Halted));
+
+        exchange.resume();
+        assert!(!exchange.is_halted());
+        let rest = exchange.submit_limit(Side::Sell, Price(105_00), 10, TimeInForce::GTC);
+        assert_eq!(rest.status, OrderStatus::New);
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn halt_is_replayed_from_event_log() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+        exchange.halt();
+        exchange.submit_market(Side::Buy, 10);
+        exchange.resume();
+        exchange.submit_market(Side::Buy, 20);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.trades().len(), 1);
+    }
+
+    #[test]
+    fn position_limit_clip_mode_trims_order() {
+        let mut exchange = Exchange::new().with_position_limit_mode(PositionLimitMode::Clip);
+        let fund = OrderOwner(1);
+        exchange.set_position_limit(fund, 50);
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+
+        let result =
+            exchange.submit_limit_with_owner(Side::Buy, Price(100_00), 80, TimeInForce::GTC, fund);
+        assert_eq!(result.filled_quantity, 50);
+        assert_eq!(result.cancelled_quantity, 30);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.reject_reason, Some(RejectReason::PositionLimit));
+        assert_eq!(exchange.net_position(fund), 50);
+
+        let none_left =
+            exchange.submit_limit_with_owner(Side::Buy, Price(100_00), 10, TimeInForce::IOC, fund);
+        assert_eq!(none_left.status, OrderStatus::Cancelled);
+        assert_eq!(none_left.filled_quantity, 0);
+        assert_eq!(exchange.net_position(fund), 50);
+    }
+
+    #[test]
+    fn modify_reduce_keeps_priority() {
+        let mut exchange = Exchange::new();
+
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let second = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+
+        let result = exchange.modify(first.order_id, Price(100_00), 40);
+        assert!(result.success);
+        assert!(result.priority_retained);
+        assert_eq!(result.new_order_id, Some(first.order_id));
+        assert_eq!(result.cancelled_quantity, 60);
+        assert_eq!(exchange.top_of_book().0, Some((Price(100_00), 140)));
+
+        // First order is still ahead of the second
+        let fill = exchange.submit_market(Side::Sell, 40);
+        assert_eq!(fill.trades[0].passive_order_id, first.order_id);
+        assert_eq!(
+            exchange.get_order(first.order_id).unwrap().status,
+            OrderStatus::Filled
+        );
+        assert_eq!(
+            exchange
+                .get_order(second.order_id)
+                .unwrap()
+                .remaining_quantity,
+            100
+        );
+    }
+
+    #[test]
+    fn modify_same_quantity_is_noop() {
+        let mut exchange = Exchange::new();
+
+        let submit = exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+        let result = exchange.modify(submit.order_id, Price(101_00), 100);
+        assert!(result.success);
+        assert!(result.priority_retained);
+        assert_eq!(result.cancelled_quantity, 0);
+        assert_eq!(exchange.top_of_book().1, Some((Price(101_00), 100)));
+    }
+
+    #[test]
+    fn modify_size_increase_loses_priority() {
+        let mut exchange = Exchange::new();
+
+        let first = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        let second = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+
+        let result = exchange.modify(first.order_id, Price(100_00), 150);
+        assert!(result.success);
+        assert!(!result.priority_retained);
+        let new_id = result.new_order_id.unwrap();
+        assert_ne!(new_id, first.order_id);
+
+        let fill = exchange.submit_market(Side::Sell, 10);
+        assert_eq!(fill.trades[0].passive_order_id, second.order_id);
+    }
+
+    #[test]
+    fn modify_reduce_after_partial_fill() {
+        let mut exchange = Exchange::new();
+
+        let bid = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_market(Side::Sell, 30);
+
+        // Remaining is 70; reducing to 50 keeps priority
+        let result = exchange.modify(bid.order_id, Price(100_00), 50);
+        assert!(result.priority_retained);
+        assert_eq!(result.cancelled_quantity, 20);
+        let order = exchange.get_order(bid.order_id).unwrap();
+        assert_eq!(order.remaining_quantity, 50);
+        assert_eq!(order.filled_quantity, 30);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+    }
+
+    #[test]
+    fn hidden_limit_is_matched_but_not_displayed() {
+        let mut exchange = Exchange::new();
+        let hidden = exchange.submit_hidden_limit(Side::Buy, Price(100_00), 40, TimeInForce::GTC);
+        assert_eq!(hidden.resting_quantity, 40);
+        assert_eq!(exchange.best_bid(), None);
+        assert!(exchange.depth(5).bids.is_empty());
+        assert_eq!(exchange.resting_order_count(), 1);
+        assert_eq!(exchange.total_resting_quantity(Side::Buy), 40);
+
+        // A price change re-queues it, still hidden
+        let moved = exchange.modify(hidden.order_id, Price(101_00), 40);
+        let new_id = moved.new_order_id.unwrap();
+        assert!(exchange.get_order(new_id).unwrap().hidden);
+        assert_eq!(exchange.best_bid(), None);
+
+        // FOK sees hidden liquidity
+        let fok = exchange.submit_limit(Side::Sell, Price(101_00), 40, TimeInForce::FOK);
+        assert_eq!(fok.filled_quantity, 40);
+        assert_eq!(exchange.last_trade_price(), Some(Price(101_00)));
+        assert_eq!(exchange.resting_order_count(), 0);
+
+        #[cfg(feature = "event-log")]
+        {
+            let replayed = Exchange::replay(exchange.events());
+            assert_eq!(replayed.trades(), exchange.trades());
+        }
+    }
+
     #[test]
     fn modify_nonexistent() {
         let mut exchange = Exchange::new();
@@ -1083,6 +2894,51 @@ mod tests {
         assert_eq!(result.error, Some(ModifyError::OrderNotFound));
     }
 
+    #[test]
+    fn modify_filled_order_fails_without_new_order() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        let buy = exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        assert_eq!(buy.status, OrderStatus::Filled);
+
+        for (price, quantity) in [(Price(99_00), 50), (Price(100_00), 100), (Price(1), 0)] {
+            let result = exchange.modify(buy.order_id, price, quantity);
+            assert!(!result.success);
+            assert_eq!(result.error, Some(ModifyError::OrderNotActive));
+            assert_eq!(result.new_order_id, None);
+            assert!(result.trades.is_empty());
+        }
+
+        // No order ID was consumed and nothing rests.
+        let next = exchange.submit_limit(Side::Buy, Price(98_00), 1, TimeInForce::GTC);
+        assert_eq!(next.order_id, OrderId(buy.order_id.0 + 1));
+        assert_eq!(exchange.book().bids().order_count(), 1);
+    }
+
+    #[test]
+    fn modify_cancelled_order_fails_without_new_order() {
+        let mut exchange = Exchange::new();
+        let bid = exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        assert!(exchange.cancel(bid.order_id).success);
+
+        let result = exchange.modify(bid.order_id, Price(98_00), 100);
+        assert!(!result.success);
+        assert_eq!(result.error, Some(ModifyError::OrderNotActive));
+        assert_eq!(result.new_order_id, None);
+        assert_eq!(result.cancelled_quantity, 0);
+        assert_eq!(exchange.best_bid(), None);
+
+        let again = exchange.cancel(bid.order_id);
+        assert_eq!(again.error, Some(CancelError::OrderNotActive));
+
+        // Once history is dropped the ID is simply unknown.
+        exchange.clear_order_history();
+        let stale = exchange.modify(bid.order_id, Price(98_00), 0);
+        assert_eq!(stale.error, Some(ModifyError::OrderNotFound));
+        let stale = exchange.cancel(bid.order_id);
+        assert_eq!(stale.error, Some(CancelError::OrderNotFound));
+    }
+
     #[test]
     fn modify_zero_quantity() {
         let mut exchange = Exchange::new();
@@ -1322,6 +3178,93 @@ mod tests {
         assert_eq!(snap.best_ask(), Some(Price(101_00)));
     }
 
+    #[test]
+    fn top_of_book_includes_sizes() {
+        let mut exchange = Exchange::new();
+        assert_eq!(exchange.top_of_book(), (None, None));
+
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 50, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 200, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 150, TimeInForce::GTC);
+
+        assert_eq!(
+            exchange.top_of_book(),
+            (Some((Price(100_00), 150)), Some((Price(101_00), 150)))
+        );
+
+        // Partial fill reduces the top size
+        exchange.submit_limit(Side::Sell, Price(100_00), 120, TimeInForce::IOC);
+        assert_eq!(exchange.top_of_book().0, Some((Price(100_00), 30)));
+    }
+
+    #[test]
+    fn matching_policy_reports_builder_settings() {
+        assert_eq!(Exchange::new().matching_policy(), MatchingPolicy::default());
+
+        let exchange = Exchange::new()
+            .with_level_priority(LevelPriority::SizeTime)
+            .with_tie_break(TieBreak::OrderId)
+            .with_stp_policy(StpPolicy::CancelNewest);
+        assert_eq!(
+            exchange.matching_policy(),
+            MatchingPolicy {
+                level_priority: LevelPriority::SizeTime,
+                tie_break: TieBreak::OrderId,
+                stp_policy: StpPolicy::CancelNewest,
+            }
+        );
+    }
+
+    #[test]
+    fn levels_are_sorted_best_to_worst() {
+        let mut exchange = Exchange::new();
+        assert!(exchange.levels(Side::Buy, 5).is_empty());
+
+        for (price, qty) in [(99_00, 10), (100_00, 20), (98_00, 30), (100_00, 5)] {
+            exchange.submit_limit(Side::Buy, Price(price), qty, TimeInForce::GTC);
+        }
+        exchange.submit_limit(Side::Sell, Price(102_00), 7, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 8, TimeInForce::GTC);
+
+        assert_eq!(
+            exchange.levels(Side::Buy, usize::MAX),
+            vec![(100_00, 25), (99_00, 10), (98_00, 30)]
+        );
+        assert_eq!(
+            exchange.levels(Side::Buy, 2),
+            vec![(100_00, 25), (99_00, 10)]
+        );
+        assert_eq!(exchange.levels(Side::Sell, 1), vec![(101_00, 8)]);
+        let depth = exchange.depth(3);
+        let from_snapshot: Vec<_> = depth.asks.iter().map(|l| (l.price.0, l.quantity)).collect();
+        assert_eq!(exchange.levels(Side::Sell, 3), from_snapshot);
+    }
+
+    #[test]
+    fn resting_counts_track_fills_and_cancels() {
+        let mut exchange = Exchange::new();
+        assert_eq!(exchange.resting_order_count(), 0);
+
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 50, TimeInForce::GTC);
+        let ask = exchange.submit_limit(Side::Sell, Price(101_00), 70, TimeInForce::GTC);
+        assert_eq!(exchange.resting_order_count(), 3);
+        assert_eq!(exchange.total_resting_quantity(Side::Buy), 150);
+        assert_eq!(exchange.total_resting_quantity(Side::Sell), 70);
+
+        // A full fill and a cancel both remove orders from the count
+        exchange.submit_market(Side::Sell, 100);
+        exchange.cancel(ask.order_id);
+        assert_eq!(exchange.resting_order_count(), 1);
+        assert_eq!(exchange.total_resting_quantity(Side::Buy), 50);
+        assert_eq!(exchange.total_resting_quantity(Side::Sell), 0);
+        assert_eq!(
+            exchange.resting_order_count(),
+            exchange.book().active_order_count()
+        );
+    }
+
     // === Trailing Stop Orders ===
 
     #[test]
@@ -1469,6 +3412,31 @@ mod tests {
         assert!(exchange.trades().is_empty());
     }
 
+    #[test]
+    fn clear_resets_state_and_keeps_config() {
+        let mut exchange =
+            Exchange::with_id_start(100, 200).with_stp_policy(StpPolicy::CancelOldest);
+        exchange.submit_limit(Side::Sell, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 40, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 50, TimeInForce::GTC);
+        exchange.submit_stop_market(Side::Sell, Price(95_00), 10);
+
+        exchange.clear();
+
+        assert_eq!(exchange.best_bid_ask(), (None, None));
+        assert!(exchange.trades().is_empty());
+        assert_eq!(exchange.pending_stop_count(), 0);
+        assert_eq!(exchange.last_trade_price(), None);
+        assert!(exchange.get_order(OrderId(100)).is_none());
+        assert_eq!(exchange.stp_policy(), StpPolicy::CancelOldest);
+
+        // IDs restart from the configured start
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let bid = exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        assert_eq!(bid.order_id, OrderId(101));
+        assert_eq!(bid.trades[0].id, TradeId(200));
+    }
+
     /// Regression for I3: `Exchange::compact` forwarding to
     /// `OrderBook::compact` was unverified at the Exchange layer,
     /// letting the `()` mutant survive. This test creates a
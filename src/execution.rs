@@ -0,0 +1,542 @@
+//! Execution algorithms: work a parent order into child orders over time.
+//!
+//! Time is a caller-driven logical clock of integer ticks. A schedule says
+//! which tick each child order belongs to, and an executor submits the
+//! children that are due whenever the caller advances the clock, so a run
+//! is fully deterministic given the book and the sequence of ticks.
+//!
+//! ```
+//! use nanobook::execution::TwapExecutor;
+//! use nanobook::{Exchange, Price, Side, TimeInForce};
+//!
+//! let mut exchange = Exchange::new();
+//! exchange.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+//! exchange.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+//! exchange.submit_limit(Side::Sell, Price(102_00), 100, TimeInForce::GTC);
+//!
+//! // Buy 150 in three slices at ticks 0, 1, 2; arrival mid is $100.00.
+//! let mut twap = TwapExecutor::new(&exchange, Side::Buy, 150, 3);
+//! for tick in 0..3 {
+//!     twap.advance(&mut exchange, tick);
+//! }
+//!
+//! let report = twap.report();
+//! assert!(twap.is_done());
+//! assert_eq!(report.filled_quantity, 150);
+//! assert_eq!(report.arrival_price, Some(100_00.0));
+//! // 100 @ $101 + 50 @ $102 → $101.33 average, 133 bps above arrival.
+//! assert!((report.slippage_bps.unwrap() - 133.33).abs() < 0.01);
+//! ```
+
+use crate::{Exchange, Quantity, Side, SubmitResult, Trade};
+
+/// One child slice of a parent order, due at a logical-clock tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChildOrder {
+    /// Tick at which the child becomes due
+    pub tick: u64,
+    /// Side of the parent order
+    pub side: Side,
+    /// Quantity of this slice
+    pub quantity: Quantity,
+}
+
+/// Split `quantity` into `n_slices` equal children at ticks `0..n_slices`.
+///
+/// When `quantity` does not divide evenly, the first `quantity % n_slices`
+/// slices get one extra unit, so the children always sum to `quantity`.
+/// Slices that would be empty (fewer units than slices) are omitted.
+///
+/// # Panics
+///
+/// Panics if `n_slices` is 0.
+///
+/// ```
+/// use nanobook::Side;
+/// use nanobook::execution::twap_schedule;
+///
+/// let slices = twap_schedule(Side::Sell, 10, 4);
+/// let sizes: Vec<u64> = slices.iter().map(|c| c.quantity).collect();
+/// assert_eq!(sizes, vec![3, 3, 2, 2]);
+/// assert_eq!(slices[3].tick, 3);
+/// ```
+pub fn twap_schedule(side: Side, quantity: Quantity, n_slices: usize) -> Vec<ChildOrder> {
+    assert!(n_slices > 0, "n_slices must be positive");
+    let n = n_slices as u64;
+    let (base, extra) = (quantity / n, quantity % n);
+    (0..n)
+        .map(|tick| ChildOrder {
+            tick,
+            side,
+            quantity: base + u64::from(tick < extra),
+        })
+        .filter(|child| child.quantity > 0)
+        .collect()
+}
+
+/// Outcome of an execution run measured against the arrival price.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionReport {
+    /// Side of the parent order
+    pub side: Side,
+    /// Parent order quantity
+    pub target_quantity: Quantity,
+    /// Quantity filled so far
+    pub filled_quantity: Quantity,
+    /// Sum of `price × quantity` over all fills
+    pub filled_notional: i64,
+    /// Volume-weighted fill price (`None` if nothing filled)
+    pub average_price: Option<f64>,
+    /// Book mid when the run started (`None` if either side was empty)
+    pub arrival_price: Option<f64>,
+    /// Average price relative to arrival in basis points, signed so that a
+    /// positive value is a cost: paying up on a buy or selling down on a sell
+    pub slippage_bps: Option<f64>,
+}
+
+impl ExecutionReport {
+    fn new(
+        side: Side,
+        target_quantity: Quantity,
+        arrival_price: Option<f64>,
+        fills: &[Trade],
+    ) -> Self {
+        let filled_quantity: Quantity = fills.iter().map(|t| t.quantity).sum();
+        let filled_notional: i64 = fills
+            .iter()
+            .map(|t| t.price.0.saturating_mul(t.quantity as i64))
+            .sum();
+        let average_price =
+            (filled_quantity > 0).then(|| filled_notional as f64 / filled_quantity as f64);
+        let slippage_bps = match (average_price, arrival_price) {
+            (Some(avg), Some(arrival)) if arrival > 0.0 => {
+                let diff = match side {
+                    Side::Buy => avg - arrival,
+                    Side::Sell => arrival - avg,
+                };
+                Some(diff / arrival * 10_000.0)
+            }
+            _ => None,
+        };
+        Self {
+            side,
+            target_quantity,
+            filled_quantity,
+            filled_notional,
+            average_price,
+            arrival_price,
+            slippage_bps,
+        }
+    }
+}
+
+/// Implementation shortfall of `fills` against the arrival (decision)
+/// price, in basis points.
+///
+/// This is the execution cost per unit filled: the signed difference
+/// between each fill and `arrival_price`, summed over the fills and
+/// divided by the arrival value of the filled quantity. Positive values
+/// are a cost (buying above or selling below arrival). Returns NaN if
+/// nothing filled or `arrival_price` is not a positive finite number.
+///
+/// ```
+/// use nanobook::execution::implementation_shortfall;
+/// use nanobook::{OrderId, Price, Side, Trade, TradeId};
+///
+/// let fill = Trade::new(TradeId(1), Price(100_50), 100, OrderId(2), OrderId(1), Side::Buy, 0);
+/// let bps = implementation_shortfall(100_00.0, &[fill], Side::Buy);
+/// assert!((bps - 50.0).abs() < 1e-9);
+/// ```
+pub fn implementation_shortfall(arrival_price: f64, fills: &[Trade], side: Side) -> f64 {
+    let filled: Quantity = fills.iter().map(|t| t.quantity).sum();
+    shortfall_bps(arrival_price, fills, side, filled, 0.0)
+}
+
+/// Implementation shortfall of a parent order of `target_quantity`,
+/// including the opportunity cost of the unfilled remainder, in basis
+/// points.
+///
+/// The unfilled quantity is charged at `final_price` (typically the price
+/// when the execution ended), and the total cost is divided by the arrival
+/// value of the whole target. Fills beyond the target are still charged as
+/// execution cost. Returns NaN if `target_quantity` is 0 or either price is
+/// not a positive finite number.
+///
+/// ```
+/// use nanobook::execution::implementation_shortfall_with_target;
+/// use nanobook::{OrderId, Price, Side, Trade, TradeId};
+///
+/// // Bought 50 of 100 at arrival; the price ran 2% before we gave up.
+/// let fill = Trade::new(TradeId(1), Price(100_00), 50, OrderId(2), OrderId(1), Side::Buy, 0);
+/// let bps = implementation_shortfall_with_target(100_00.0, &[fill], Side::Buy, 100, 102_00.0);
+/// assert!((bps - 100.0).abs() < 1e-9);
+/// ```
+pub fn implementation_shortfall_with_target(
+    arrival_price: f64,
+    fills: &[Trade],
+    side: Side,
+    target_quantity: Quantity,
+    final_price: f64,
+) -> f64 {
+    if !(final_price.is_finite() && final_price > 0.0) {
+        return f64::NAN;
+    }
+    shortfall_bps(arrival_price, fills, side, target_quantity, final_price)
+}
+
+fn shortfall_bps(
+    arrival_price: f64,
+    fills: &[Trade],
+    side: Side,
+    denominator_quantity: Quantity,
+    final_price: f64,
+) -> f64 {
+    if !(arrival_price.is_finite() && arrival_price > 0.0) || denominator_quantity == 0 {
+        return f64::NAN;
+    }
+    let sign = match side {
+        Side::Buy => 1.0,
+        Side::Sell => -1.0,
+    };
+    let filled: Quantity = fills.iter().map(|t| t.quantity).sum();
+    let execution: f64 = fills
+        .iter()
+        .map(|t| (t.price.0 as f64 - arrival_price) * t.quantity as f64)
+        .sum();
+    let unfilled = denominator_quantity.saturating_sub(filled) as f64;
+    let opportunity = (final_price - arrival_price) * unfilled;
+    sign * (execution + opportunity) / (arrival_price * denominator_quantity as f64) * 10_000.0
+}
+
+/// Mid of the best bid and ask, if both sides are quoted.
+fn mid_price(exchange: &Exchange) -> Option<f64> {
+    match exchange.best_bid_ask() {
+        (Some(bid), Some(ask)) => Some((bid.0 as f64 + ask.0 as f64) / 2.0),
+        _ => None,
+    }
+}
+
+/// Works a parent order as a TWAP schedule of market orders.
+///
+/// Each call to [`advance`](Self::advance) submits every child due at or
+/// before the given tick. Children are market orders, so any quantity the
+/// book cannot absorb at that moment is cancelled rather than carried to a
+/// later slice; the shortfall shows up in [`report`](Self::report).
+#[derive(Clone, Debug)]
+pub struct TwapExecutor {
+    side: Side,
+    quantity: Quantity,
+    schedule: Vec<ChildOrder>,
+    next: usize,
+    arrival_price: Option<f64>,
+    fills: Vec<Trade>,
+}
+
+impl TwapExecutor {
+    /// Plan `quantity` over `n_slices` ticks, recording the current mid of
+    /// `exchange` as the arrival price.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_slices` is 0.
+    pub fn new(exchange: &Exchange, side: Side, quantity: Quantity, n_slices: usize) -> Self {
+        Self {
+            side,
+            quantity,
+            schedule: twap_schedule(side, quantity, n_slices),
+            next: 0,
+            arrival_price: mid_price(exchange),
+            fills: Vec::new(),
+        }
+    }
+
+    /// The planned child orders.
+    pub fn schedule(&self) -> &[ChildOrder] {
+        &self.schedule
+    }
+
+    /// Submit every child due at or before `now`, in schedule order.
+    ///
+    /// Returns the results of the children submitted by this call (empty
+    /// if none were due).
+    pub fn advance(&mut self, exchange: &mut Exchange, now: u64) -> Vec<SubmitResult> {
+        let mut results = Vec::new();
+        while let Some(child) = self.schedule.get(self.next).filter(|c| c.tick <= now) {
+            let result = exchange.submit_market(child.side, child.quantity);
+            self.fills.extend(result.trades.iter().cloned());
+            results.push(result);
+            self.next += 1;
+        }
+        results
+    }
+
+    /// Whether every child has been submitted.
+    pub fn is_done(&self) -> bool {
+        self.next == self.schedule.len()
+    }
+
+    /// All fills received so far, in execution order.
+    pub fn fills(&self) -> &[Trade] {
+        &self.fills
+    }
+
+    /// Fill summary and slippage against the arrival price so far.
+    pub fn report(&self) -> ExecutionReport {
+        ExecutionReport::new(self.side, self.quantity, self.arrival_price, &self.fills)
+    }
+}
+
+/// Works a parent order at a target percentage of market volume.
+///
+/// The executor watches the exchange's trade tape. Each call to
+/// [`advance`](Self::advance) counts the volume other participants traded
+/// since the previous call and sends a market order that brings the
+/// executor's filled quantity up to `participation ×` the market volume
+/// seen so far, never exceeding the parent quantity. Its own fills are not
+/// counted as market volume. Clearing the tape with
+/// [`Exchange::clear_trades`] restarts the count from the cleared tape.
+///
+/// ```
+/// use nanobook::execution::PovExecutor;
+/// use nanobook::{Exchange, Price, Side, TimeInForce};
+///
+/// let mut exchange = Exchange::new();
+/// exchange.submit_limit(Side::Sell, Price(100_00), 1_000, TimeInForce::GTC);
+/// let mut pov = PovExecutor::new(&exchange, Side::Buy, 100, 0.25);
+///
+/// exchange.submit_market(Side::Buy, 200); // someone else trades 200
+/// let child = pov.advance(&mut exchange).unwrap();
+/// assert_eq!(child.filled_quantity, 50);
+/// assert_eq!(pov.realized_participation(), Some(0.25));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PovExecutor {
+    side: Side,
+    quantity: Quantity,
+    participation: f64,
+    tape_cursor: usize,
+    market_volume: Quantity,
+    arrival_price: Option<f64>,
+    fills: Vec<Trade>,
+}
+
+impl PovExecutor {
+    /// Start targeting `participation` of market volume from the current
+    /// end of the tape, recording the current mid as the arrival price.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `participation` is in `(0, 1]`.
+    pub fn new(exchange: &Exchange, side: Side, quantity: Quantity, participation: f64) -> Self {
+        assert!(
+            participation > 0.0 && participation <= 1.0,
+            "participation must be in (0, 1]"
+        );
+        Self {
+            side,
+            quantity,
+            participation,
+            tape_cursor: exchange.trades().len(),
+            market_volume: 0,
+            arrival_price: mid_price(exchange),
+            fills: Vec::new(),
+        }
+    }
+
+    /// Count new market volume on the tape and submit the child order
+    /// needed to catch up to the target participation.
+    ///
+    /// Returns `None` if no child was due (no new volume, already on
+    /// target, or the parent is complete).
+    pub fn advance(&mut self, exchange: &mut Exchange) -> Option<SubmitResult> {
+        let tape = exchange.trades();
+        self.tape_cursor = self.tape_cursor.min(tape.len());
+        self.market_volume += tape[self.tape_cursor..]
+            .iter()
+            .map(|t| t.quantity)
+            .sum::<Quantity>();
+        self.tape_cursor = tape.len();
+
+        let filled = self.filled_quantity();
+        let target = (self.participation * self.market_volume as f64) as Quantity;
+        let child = target.min(self.quantity).saturating_sub(filled);
+        if child == 0 {
+            return None;
+        }
+
+        let result = exchange.submit_market(self.side, child);
+        // Our own trades come first on the tape; anything after them (such
+        // as triggered stops) is market volume for the next interval.
+        self.tape_cursor += result.trades.len();
+        self.fills.extend(result.trades.iter().cloned());
+        Some(result)
+    }
+
+    /// Quantity filled so far.
+    pub fn filled_quantity(&self) -> Quantity {
+        self.fills.iter().map(|t| t.quantity).sum()
+    }
+
+    /// Whether the parent quantity is completely filled.
+    pub fn is_done(&self) -> bool {
+        self.filled_quantity() >= self.quantity
+    }
+
+    /// Market volume observed since the executor started, excluding its own
+    /// fills.
+    pub fn market_volume(&self) -> Quantity {
+        self.market_volume
+    }
+
+    /// Filled quantity as a fraction of observed market volume (`None`
+    /// before any market volume was seen).
+    pub fn realized_participation(&self) -> Option<f64> {
+        (self.market_volume > 0).then(|| self.filled_quantity() as f64 / self.market_volume as f64)
+    }
+
+    /// All fills received so far, in execution order.
+    pub fn fills(&self) -> &[Trade] {
+        &self.fills
+    }
+
+    /// Fill summary and slippage against the arrival price so far.
+    pub fn report(&self) -> ExecutionReport {
+        ExecutionReport::new(self.side, self.quantity, self.arrival_price, &self.fills)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Price, TimeInForce};
+
+    #[test]
+    fn schedule_sums_to_parent_and_drops_empty_slices() {
+        let slices = twap_schedule(Side::Buy, 1_003, 4);
+        assert_eq!(slices.iter().map(|c| c.quantity).sum::<u64>(), 1_003);
+        assert_eq!(slices[0].quantity, 251);
+        assert_eq!(slices[3].quantity, 250);
+
+        let sparse = twap_schedule(Side::Buy, 2, 5);
+        assert_eq!(sparse.len(), 2);
+        assert!(twap_schedule(Side::Buy, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn advance_submits_only_due_children_and_reports_shortfall() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 100, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 30, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+
+        let mut twap = TwapExecutor::new(&exchange, Side::Sell, 160, 4);
+        assert_eq!(twap.advance(&mut exchange, 0).len(), 1);
+        assert!(twap.advance(&mut exchange, 0).is_empty());
+        // Ticks 1 and 2 become due together.
+        assert_eq!(twap.advance(&mut exchange, 2).len(), 2);
+        assert!(!twap.is_done());
+        // The book runs dry in the last slice; the rest is not carried over.
+        let results = twap.advance(&mut exchange, 10);
+        assert_eq!(results[0].cancelled_quantity, 30);
+        assert!(twap.is_done());
+
+        let report = twap.report();
+        assert_eq!(report.filled_quantity, 130);
+        assert_eq!(report.filled_notional, 100 * 100_00 + 30 * 99_00);
+        assert_eq!(report.arrival_price, Some(101_00.0));
+        // Selling below arrival is a positive cost.
+        assert!(report.slippage_bps.unwrap() > 0.0);
+        assert_eq!(twap.fills().len(), 5);
+    }
+
+    #[test]
+    fn pov_tracks_market_volume_and_caps_at_parent() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(101_00), 1_000, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 500); // before start: not counted
+
+        let mut pov = PovExecutor::new(&exchange, Side::Buy, 120, 0.1);
+        assert!(pov.advance(&mut exchange).is_none());
+
+        exchange.submit_market(Side::Buy, 300);
+        assert_eq!(pov.advance(&mut exchange).unwrap().filled_quantity, 30);
+        // Own fills don't count as market volume.
+        assert!(pov.advance(&mut exchange).is_none());
+        assert_eq!(pov.market_volume(), 300);
+
+        exchange.submit_market(Side::Buy, 5);
+        assert!(pov.advance(&mut exchange).is_none()); // 30.5 rounds down
+        exchange.submit_market(Side::Buy, 100);
+        assert_eq!(pov.advance(&mut exchange).unwrap().filled_quantity, 10);
+
+        // A large print would exceed the parent; the child is capped.
+        exchange.submit_market(Side::Buy, 5);
+        exchange.submit_limit(Side::Sell, Price(101_00), 5_000, TimeInForce::GTC);
+        exchange.submit_market(Side::Buy, 4_000);
+        assert_eq!(pov.advance(&mut exchange).unwrap().filled_quantity, 80);
+        assert!(pov.is_done());
+        assert!(pov.advance(&mut exchange).is_none());
+
+        let participation = pov.realized_participation().unwrap();
+        assert!((participation - 120.0 / 4_410.0).abs() < 1e-12);
+        assert_eq!(pov.report().filled_quantity, 120);
+    }
+
+    #[test]
+    #[should_panic(expected = "participation")]
+    fn pov_rejects_out_of_range_participation() {
+        PovExecutor::new(&Exchange::new(), Side::Buy, 10, 1.5);
+    }
+
+    fn fill(price: i64, quantity: Quantity) -> Trade {
+        Trade::new(
+            crate::TradeId(1),
+            Price(price),
+            quantity,
+            crate::OrderId(2),
+            crate::OrderId(1),
+            Side::Sell,
+            0,
+        )
+    }
+
+    #[test]
+    fn shortfall_matches_report_and_charges_unfilled() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Buy, Price(100_00), 40, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(99_00), 40, TimeInForce::GTC);
+        exchange.submit_limit(Side::Sell, Price(100_50), 40, TimeInForce::GTC);
+        let mut twap = TwapExecutor::new(&exchange, Side::Sell, 60, 2);
+        twap.advance(&mut exchange, 1);
+        let report = twap.report();
+        let is = implementation_shortfall(100_25.0, twap.fills(), Side::Sell);
+        assert!((is - report.slippage_bps.unwrap()).abs() < 1e-9);
+
+        // Sold 60 of 100: execution cost plus 40 left behind as the price fell.
+        let fills = [fill(100_00, 40), fill(99_00, 20)];
+        let full = implementation_shortfall_with_target(100_25.0, &fills, Side::Sell, 100, 98_00.0);
+        let expected = (25.0 * 40.0 + 125.0 * 20.0 + 225.0 * 40.0) / (100_25.0 * 100.0) * 1e4;
+        assert!((full - expected).abs() < 1e-9);
+        // A favorable move after giving up is a negative opportunity cost.
+        let gain = implementation_shortfall_with_target(100_25.0, &[], Side::Sell, 10, 101_25.0);
+        assert!((gain + 100.0 / 100_25.0 * 1e4).abs() < 1e-9);
+
+        assert!(implementation_shortfall(100_00.0, &[], Side::Buy).is_nan());
+        assert!(implementation_shortfall(0.0, &fills, Side::Buy).is_nan());
+        assert!(implementation_shortfall_with_target(1.0, &fills, Side::Buy, 0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn one_sided_book_has_no_arrival_price() {
+        let mut exchange = Exchange::new();
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        let mut twap = TwapExecutor::new(&exchange, Side::Buy, 10, 1);
+        twap.advance(&mut exchange, 0);
+
+        let report = twap.report();
+        assert_eq!(report.average_price, Some(100_00.0));
+        assert_eq!(report.arrival_price, None);
+        assert_eq!(report.slippage_bps, None);
+    }
+}
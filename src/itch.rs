@@ -321,6 +321,7 @@ pub fn itch_to_event(msg: ItchMessage) -> Option<(String, Event)> {
                     price: Price(nb_price),
                     quantity: shares as u64,
                     time_in_force: TimeInForce::GTC,
+                    owner: None,
                 },
             ))
         }
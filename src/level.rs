@@ -5,22 +5,47 @@
 
 use std::collections::VecDeque;
 
-use crate::{OrderId, Price, Quantity};
+use crate::{OrderId, Price, Quantity, Timestamp};
+
+/// Queue priority of an order within a level: `(rank, timestamp, tie-break)`.
+///
+/// Lower keys fill first. The rank is 0 under time priority and orders by
+/// size under [`LevelPriority::SizeTime`](crate::LevelPriority). The
+/// tie-break is the order's sequence number or ID, depending on the book's
+/// [`TieBreak`](crate::TieBreak).
+pub type PriorityKey = (u64, Timestamp, u64);
+
+/// One slot in a level's queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct QueueEntry {
+    /// Priority key; kept on tombstones so the queue stays searchable.
+    pub(crate) key: PriorityKey,
+    /// Order ID, or `OrderId(0)` for a tombstone.
+    pub(crate) id: OrderId,
+}
 
 /// A queue of orders at a single price level.
 ///
-/// Orders are processed FIFO (first-in-first-out) for time priority.
+/// Orders are kept sorted by [`PriorityKey`] and processed front to back.
+/// Orders arriving in time order are simply appended (FIFO).
 /// The level tracks total quantity for efficient depth queries.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Level {
     /// The price for all orders in this level
     price: Price,
-    /// Order IDs in FIFO order
-    pub(crate) orders: VecDeque<OrderId>,
+    /// Queue entries in priority order
+    pub(crate) orders: VecDeque<QueueEntry>,
     /// Sum of remaining quantities (cached for O(1) access)
     total_quantity: Quantity,
     /// Number of tombstones (cancelled orders still in the queue)
     tombstone_count: usize,
+    /// Entries ever popped from the front, so `front_position + index` is
+    /// an entry's position for as long as nothing ahead of it is removed
+    /// or inserted
+    #[cfg_attr(feature = "serde", serde(default))]
+    front_position: u64,
 }
 
 impl Level {
@@ -31,6 +56,7 @@ impl Level {
             orders: VecDeque::new(),
             total_quantity: 0,
             tombstone_count: 0,
+            front_position: 0,
         }
     }
 
@@ -78,11 +104,12 @@ impl Level {
     /// Returns the OrderId at the front of the queue (next to fill).
     /// Skips tombstones.
     pub fn front(&mut self) -> Option<OrderId> {
-        while let Some(&id) = self.orders.front() {
+        while let Some(&QueueEntry { id, .. }) = self.orders.front() {
             if id.0 == 0 {
                 // It's a tombstone
                 self.orders.pop_front();
                 self.tombstone_count -= 1;
+                self.front_position += 1;
             } else {
                 return Some(id);
             }
@@ -92,11 +119,41 @@ impl Level {
 
     /// Add an order to the back of the queue.
     ///
-    /// The quantity is added to the level's total (saturating on overflow).
+    /// The order is given the priority key just after the current back, so
+    /// it fills last. The quantity is added to the level's total
+    /// (saturating on overflow).
     pub fn push_back(&mut self, order_id: OrderId, quantity: Quantity) {
+        let key = self
+            .orders
+            .back()
+            .map_or((0, 0, 0), |e| (e.key.0, e.key.1, e.key.2 + 1));
+        self.insert(order_id, key, quantity);
+    }
+
+    /// Insert an order at its priority position.
+    ///
+    /// Appends in O(1) when `key` is the largest in the queue (the usual
+    /// case); otherwise shifts later entries back. An order with a key equal
+    /// to an existing one goes after it.
+    ///
+    /// Returns the entry's queue position, a hint for
+    /// [`tombstone_at`](Self::tombstone_at).
+    pub fn insert(&mut self, order_id: OrderId, key: PriorityKey, quantity: Quantity) -> u64 {
         assert!(order_id.0 != 0, "OrderId(0) reserved for tombstones");
-        self.orders.push_back(order_id);
+        let entry = QueueEntry { key, id: order_id };
+        let index = match self.orders.back() {
+            Some(back) if back.key > key => {
+                let pos = self.orders.partition_point(|e| e.key <= key);
+                self.orders.insert(pos, entry);
+                pos
+            }
+            _ => {
+                self.orders.push_back(entry);
+                self.orders.len() - 1
+            }
+        };
         self.total_quantity = self.total_quantity.saturating_add(quantity);
+        self.front_position + index as u64
     }
 
     /// Remove and return the order at the front of the queue.
@@ -106,7 +163,8 @@ impl Level {
     ///
     /// Returns `None` if the level is empty.
     pub fn pop_front(&mut self, quantity: Quantity) -> Option<OrderId> {
-        while let Some(id) = self.orders.pop_front() {
+        while let Some(QueueEntry { id, .. }) = self.orders.pop_front() {
+            self.front_position += 1;
             if id.0 == 0 {
                 self.tombstone_count -= 1;
                 continue;
@@ -117,20 +175,62 @@ impl Level {
         None
     }
 
-    /// Mark an order as a tombstone (O(1) cancellation).
+    /// Mark the entry at raw queue `index` as a tombstone.
     ///
-    /// The caller provides the index into the VecDeque (tracked in OrderBook's
-    /// HashMap). The order's quantity is subtracted from the level total.
+    /// Indices shift whenever entries are popped from the front or
+    /// compacted, so prefer [`tombstone_at`](Self::tombstone_at) or
+    /// [`tombstone`](Self::tombstone). The order's quantity is subtracted
+    /// from the level total.
     pub fn mark_tombstone(&mut self, index: usize, quantity: Quantity) {
-        if let Some(id_ref) = self.orders.get_mut(index) {
-            if id_ref.0 != 0 {
-                id_ref.0 = 0; // Set to tombstone ID
+        if let Some(entry) = self.orders.get_mut(index) {
+            if entry.id.0 != 0 {
+                entry.id = OrderId(0); // Set to tombstone ID
                 self.total_quantity = self.total_quantity.saturating_sub(quantity);
                 self.tombstone_count += 1;
             }
         }
     }
 
+    /// Mark `order_id` as a tombstone at the queue `position` returned by
+    /// [`insert`](Self::insert) (O(1)).
+    ///
+    /// Returns `false`, changing nothing, if the entry there is not
+    /// `order_id`: the position goes stale once an entry ahead of it is
+    /// removed or inserted, after which [`tombstone`](Self::tombstone)
+    /// finds the order by key instead.
+    pub fn tombstone_at(&mut self, position: u64, order_id: OrderId, quantity: Quantity) -> bool {
+        let index = position
+            .checked_sub(self.front_position)
+            .map(|i| i as usize);
+        match index.filter(|&i| self.orders.get(i).is_some_and(|e| e.id == order_id)) {
+            Some(index) => {
+                self.mark_tombstone(index, quantity);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Mark `order_id` as a tombstone, locating it by its priority key
+    /// (O(log n)).
+    ///
+    /// Returns `false` if no live entry with that key and ID is queued.
+    pub fn tombstone(&mut self, order_id: OrderId, key: PriorityKey, quantity: Quantity) -> bool {
+        let start = self.orders.partition_point(|e| e.key < key);
+        let found = self
+            .orders
+            .range(start..)
+            .take_while(|e| e.key == key)
+            .position(|e| e.id == order_id);
+        match found {
+            Some(offset) => {
+                self.mark_tombstone(start + offset, quantity);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Remove a specific order from anywhere in the queue (for cancellation).
     ///
     /// Returns `true` if the order was found and removed, `false` otherwise.
@@ -139,7 +239,7 @@ impl Level {
     /// Note: This is O(n) where n is the number of orders at this price level.
     /// For O(1) cancel, we now use `mark_tombstone` called from OrderBook.
     pub fn remove(&mut self, order_id: OrderId, quantity: Quantity) -> bool {
-        if let Some(pos) = self.orders.iter().position(|&id| id == order_id) {
+        if let Some(pos) = self.orders.iter().position(|e| e.id == order_id) {
             self.orders.remove(pos);
             self.total_quantity = self.total_quantity.saturating_sub(quantity);
             true
@@ -153,7 +253,7 @@ impl Level {
         if self.tombstone_count == 0 {
             return;
         }
-        self.orders.retain(|id| id.0 != 0);
+        self.orders.retain(|e| e.id.0 != 0);
         self.tombstone_count = 0;
     }
 
@@ -164,9 +264,16 @@ impl Level {
         self.total_quantity = self.total_quantity.saturating_sub(amount);
     }
 
-    /// Returns an iterator over the active order IDs in FIFO order.
+    /// Returns an iterator over the active order IDs in priority order.
     pub fn iter(&self) -> impl Iterator<Item = OrderId> + '_ {
-        self.orders.iter().copied().filter(|id| id.0 != 0)
+        self.orders.iter().map(|e| e.id).filter(|id| id.0 != 0)
+    }
+
+    /// Active order IDs with their current queue positions.
+    pub(crate) fn positions(&self) -> impl Iterator<Item = (u64, OrderId)> + '_ {
+        (self.front_position..)
+            .zip(self.orders.iter().map(|e| e.id))
+            .filter(|(_, id)| id.0 != 0)
     }
 }
 
@@ -331,7 +438,7 @@ mod tests {
 
         assert_eq!(level.orders.len(), 1);
         assert_eq!(level.tombstone_count(), 0);
-        assert_eq!(level.orders[0], OrderId(2));
+        assert_eq!(level.orders[0].id, OrderId(2));
     }
 
     #[test]
@@ -427,4 +534,60 @@ mod tests {
         );
         assert_eq!(level.raw_len(), 0);
     }
+
+    #[test]
+    fn insert_orders_by_priority_key() {
+        let mut level = Level::new(Price(100_00));
+        level.insert(OrderId(1), (0, 5, 1), 10);
+        level.insert(OrderId(2), (0, 7, 2), 10);
+        level.insert(OrderId(3), (0, 5, 3), 10);
+        level.insert(OrderId(4), (0, 1, 4), 10);
+
+        let ids: Vec<_> = level.iter().collect();
+        assert_eq!(ids, vec![OrderId(4), OrderId(1), OrderId(3), OrderId(2)]);
+        assert_eq!(level.total_quantity(), 40);
+    }
+
+    #[test]
+    fn tombstone_by_key_survives_front_pops() {
+        let mut level = Level::new(Price(100_00));
+        level.insert(OrderId(1), (0, 1, 1), 10);
+        level.insert(OrderId(2), (0, 2, 2), 20);
+        level.insert(OrderId(3), (0, 3, 3), 30);
+        level.insert(OrderId(4), (0, 4, 4), 40);
+
+        // Raw indices shift after the head is popped
+        assert_eq!(level.pop_front(10), Some(OrderId(1)));
+
+        assert!(level.tombstone(OrderId(3), (0, 3, 3), 30));
+        assert!(!level.tombstone(OrderId(3), (0, 3, 3), 30));
+        assert!(!level.tombstone(OrderId(9), (0, 9, 9), 1));
+
+        let ids: Vec<_> = level.iter().collect();
+        assert_eq!(ids, vec![OrderId(2), OrderId(4)]);
+        assert_eq!(level.total_quantity(), 60);
+    }
+
+    #[test]
+    fn tombstone_at_position_survives_front_pops() {
+        let mut level = Level::new(Price(100_00));
+        let positions: Vec<_> = (1..=4)
+            .map(|i| level.insert(OrderId(i), (0, i, i), 10))
+            .collect();
+        assert_eq!(positions, vec![0, 1, 2, 3]);
+
+        assert_eq!(level.pop_front(10), Some(OrderId(1)));
+        assert!(level.tombstone_at(positions[2], OrderId(3), 10));
+        assert!(!level.tombstone_at(positions[2], OrderId(3), 10));
+        assert!(!level.tombstone_at(positions[0], OrderId(1), 10));
+
+        // An out-of-order insert shifts the entries behind it
+        assert_eq!(level.insert(OrderId(5), (0, 0, 0), 10), 1);
+        assert!(!level.tombstone_at(positions[3], OrderId(4), 10));
+        assert!(level.tombstone_at(positions[3] + 1, OrderId(4), 10));
+
+        let ids: Vec<_> = level.iter().collect();
+        assert_eq!(ids, vec![OrderId(5), OrderId(2)]);
+        assert_eq!(level.total_quantity(), 20);
+    }
 }
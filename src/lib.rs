@@ -151,10 +151,14 @@
 #[cfg(feature = "portfolio")]
 pub mod backtest_bridge;
 mod book;
+mod bracket;
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
 pub mod cv;
 mod error;
 mod event;
 mod exchange;
+pub mod execution;
 pub mod garch;
 pub mod indicators;
 #[cfg(feature = "itch")]
@@ -170,10 +174,15 @@ pub mod persistence;
 pub mod portfolio;
 mod price_levels;
 mod result;
+pub mod rng;
+mod session;
 mod side;
 mod snapshot;
+#[cfg(feature = "serde")]
+mod sorted_map;
 pub mod stats;
 pub mod stop;
+mod symbol_table;
 mod tif;
 mod trade;
 mod types;
@@ -181,20 +190,25 @@ pub mod volatility;
 
 // Re-export public API
 pub use book::OrderBook;
+pub use bracket::Bracket;
 pub use error::ValidationError;
 pub use event::{ApplyResult, Event};
-pub use exchange::Exchange;
-pub use level::Level;
-pub use matching::{MatchResult, StpPolicy};
-pub use multi_exchange::MultiExchange;
+pub use exchange::{Exchange, HaltMode, PositionLimitMode};
+pub use level::{Level, PriorityKey};
+pub use matching::{LevelPriority, MatchResult, MatchingPolicy, StpPolicy, TieBreak};
+pub use multi_exchange::{MultiExchange, best_consolidated, route_order};
 pub use order::{Order, OrderOwner, OrderStatus};
 pub use price_levels::PriceLevels;
 pub use result::{
-    CancelError, CancelResult, ModifyError, ModifyResult, StopSubmitResult, SubmitResult,
+    BracketResult, CancelError, CancelResult, FlowStats, ModifyError, ModifyResult, OcoError,
+    RejectReason, RouteResult, SpreadError, SpreadResult, StopSubmitResult, SubmitResult,
+    SweepEstimate, TradeStats, VenueFill,
 };
+pub use session::SessionState;
 pub use side::Side;
-pub use snapshot::{BookSnapshot, LevelSnapshot};
+pub use snapshot::{BookSnapshot, LevelChange, LevelChangeKind, LevelSnapshot, SnapshotError};
 pub use stop::{StopBook, StopOrder, StopStatus, TrailMethod};
+pub use symbol_table::SymbolTable;
 pub use tif::TimeInForce;
-pub use trade::Trade;
+pub use trade::{Liquidity, Trade};
 pub use types::{OrderId, Price, Quantity, Symbol, Timestamp, TradeId};
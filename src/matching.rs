@@ -1,9 +1,16 @@
 //! Matching engine: the core algorithm for executing trades.
 //!
 //! The matching engine implements price-time priority:
-//! 1. Better prices match first (higher bids, lower asks)
-//! 2. At the same price, earlier orders match first (FIFO)
-//! 3. Trades execute at the resting order's price (price improvement for aggressor)
+//! 1. Better prices match first (higher bids, lower asks), whether the
+//!    resting order is displayed or [hidden](crate::Order::hidden)
+//! 2. At the same price, displayed orders match before hidden ones
+//! 3. Within each, earlier orders match first (FIFO by timestamp, with
+//!    equal timestamps ordered by [`TieBreak`]), or larger ones first under
+//!    [`LevelPriority::SizeTime`]
+//! 4. Trades execute at the resting order's price (price improvement for aggressor)
+//!
+//! [`MatchingPolicy`] spells out the full specification and reports which
+//! configurable rules an exchange is running.
 
 use crate::{Order, OrderBook, Price, Quantity, Side, Trade};
 
@@ -34,6 +41,77 @@ pub enum StpPolicy {
     DecrementAndCancel,
 }
 
+/// How orders at the same price with equal timestamps are queued.
+///
+/// Orders at a price level fill in timestamp order. This policy decides
+/// the order among equal timestamps, which arise when callers stamp orders
+/// from a shared logical clock and add them with
+/// [`OrderBook::add_order`](crate::OrderBook::add_order). Orders submitted
+/// through an [`Exchange`](crate::Exchange) always get distinct timestamps,
+/// so the policy only matters for books built directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TieBreak {
+    /// Earlier arrival (lower [`Order::sequence`](crate::Order::sequence))
+    /// fills first.
+    #[default]
+    Sequence,
+    /// Lower order ID fills first.
+    OrderId,
+}
+
+/// How resting orders at one price level are ranked.
+///
+/// Under either policy, equal ranks fall back to timestamp and then
+/// [`TieBreak`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LevelPriority {
+    /// Price-time (FIFO): earlier orders fill first.
+    #[default]
+    Time,
+    /// Size-time: larger orders fill first, then earlier ones.
+    ///
+    /// An order is ranked by its remaining quantity when it joins the level
+    /// ([`Order::queue_size`](crate::Order::queue_size)). It keeps that rank
+    /// while partially filled; an in-place size reduction re-ranks it by
+    /// the new size, keeping its timestamp. Hidden orders are ranked the
+    /// same way within their own queue; there is no partially displayed
+    /// (iceberg) quantity, so an order's displayed and total size are the
+    /// same.
+    SizeTime,
+}
+
+/// The active matching rules of an [`Exchange`](crate::Exchange), as
+/// returned by [`Exchange::matching_policy`](crate::Exchange::matching_policy).
+///
+/// An incoming order fills against resting orders in this priority:
+///
+/// 1. **Price**: better prices first, whether the resting order is
+///    displayed or [hidden](crate::Order::hidden).
+/// 2. **Visibility**: at one price, every displayed order before any
+///    hidden one. This rule is fixed.
+/// 3. **Rank**: within the displayed and hidden queues, by
+///    [`level_priority`](Self::level_priority).
+/// 4. **Ties**: equal timestamps by [`tie_break`](Self::tie_break).
+///
+/// Each resting order fills in full before the next one is touched;
+/// there is no pro-rata allocation. No resting order is all-or-none:
+/// [`TimeInForce::FOK`](crate::TimeInForce::FOK) is the only all-or-none
+/// condition and applies to the incoming order. Same-owner crosses are
+/// handled by [`stp_policy`](Self::stp_policy), and every trade executes
+/// at the resting order's price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchingPolicy {
+    /// How orders within a price level are ranked
+    pub level_priority: LevelPriority,
+    /// How equal timestamps are ordered
+    pub tie_break: TieBreak,
+    /// Self-trade prevention between same-owner orders
+    pub stp_policy: StpPolicy,
+}
+
 /// Result of matching an incoming order against the book.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -46,6 +124,11 @@ pub struct MatchResult {
     /// order. When true, callers must treat any remainder as cancelled
     /// regardless of TIF: a GTC remainder must NOT rest on the book.
     pub stp_cancelled: bool,
+    /// Sum of `price × quantity` over `trades`, accumulated while matching
+    /// (saturating at `i64::MAX`/`i64::MIN`).
+    pub filled_notional: i64,
+    /// Quantity filled at each price, in the order the levels were swept.
+    pub fills_by_level: Vec<(Price, Quantity)>,
 }
 
 impl MatchResult {
@@ -108,15 +191,24 @@ impl OrderBook {
             trades: Vec::new(),
             remaining_quantity: incoming.remaining_quantity,
             stp_cancelled: false,
+            filled_notional: 0,
+            fills_by_level: Vec::new(),
         };
 
         // Match until no more crosses or order is filled
         while incoming.remaining_quantity > 0 {
-            // Get the best price on the opposite side
-            let opposite = self.opposite_side(incoming.side);
-            let best_price = match opposite.best_price() {
-                Some(p) => p,
-                None => break, // No liquidity
+            // Get the best price on the opposite side, displayed or hidden.
+            // Hidden orders only go first at a strictly better price.
+            let resting_side = incoming.side.opposite();
+            let displayed = self.side(resting_side).best_price();
+            let hidden = self.hidden_side(resting_side).best_price();
+            let (best_price, from_hidden) = match (displayed, hidden) {
+                (Some(d), Some(h)) if Self::prices_cross(incoming.side, d, h) && d != h => {
+                    (h, true)
+                }
+                (Some(d), _) => (d, false),
+                (None, Some(h)) => (h, true),
+                (None, None) => break, // No liquidity
             };
 
             // Check if prices cross
@@ -125,7 +217,7 @@ impl OrderBook {
             }
 
             // Match against orders at the best price level
-            self.match_at_price(incoming, best_price, policy, &mut result);
+            self.match_at_price(incoming, best_price, from_hidden, policy, &mut result);
 
             // If STP cancelled the incoming order, stop matching entirely.
             if result.stp_cancelled {
@@ -137,18 +229,21 @@ impl OrderBook {
         result
     }
 
-    /// Match an incoming order against all orders at a specific price level.
+    /// Match an incoming order against all orders at a specific price level
+    /// of the displayed or hidden queues.
     fn match_at_price(
         &mut self,
         incoming: &mut Order,
         price: Price,
+        hidden: bool,
         policy: StpPolicy,
         result: &mut MatchResult,
     ) {
+        let resting_side = incoming.side.opposite();
         // Process orders at this price level until exhausted or incoming filled
         while incoming.remaining_quantity > 0 {
             // Get the front order at this price (skips tombstones)
-            let opposite = self.opposite_side_mut(incoming.side);
+            let opposite = self.queues_mut(resting_side, hidden);
             let resting_id = match opposite.get_level_mut(price).and_then(|l| l.front()) {
                 Some(id) => id,
                 _ => break, // Level exhausted or only tombstones left
@@ -159,7 +254,7 @@ impl OrderBook {
                 Some(o) => (o.remaining_quantity, o.owner),
                 None => {
                     // Orphaned order ID in level — shouldn't happen, but handle gracefully
-                    self.opposite_side_mut(incoming.side)
+                    self.queues_mut(resting_side, hidden)
                         .get_level_mut(price)
                         .map(|l| l.pop_front(0));
                     continue;
@@ -213,9 +308,16 @@ impl OrderBook {
                 incoming.id,
                 resting_id,
                 incoming.side,
-                self.next_timestamp(),
+                self.trade_timestamp(),
             );
             result.trades.push(trade);
+            result.filled_notional = result
+                .filled_notional
+                .saturating_add(price.0.saturating_mul(fill_qty as i64));
+            match result.fills_by_level.last_mut() {
+                Some((level, filled)) if *level == price => *filled += fill_qty,
+                _ => result.fills_by_level.push((price, fill_qty)),
+            }
 
             // Update the incoming order
             incoming.fill(fill_qty);
@@ -228,9 +330,10 @@ impl OrderBook {
                 resting.fill(fill_qty);
                 resting.remaining_quantity == 0
             };
+            self.track_owner_open(resting_owner, resting_side, fill_qty, false);
 
             // Update the price level
-            let opposite = self.opposite_side_mut(incoming.side);
+            let opposite = self.queues_mut(resting_side, hidden);
             if resting_fully_filled {
                 // Remove the fully filled order from the level
                 if let Some(level) = opposite.get_level_mut(price) {
@@ -248,11 +351,14 @@ impl OrderBook {
         }
     }
 
-    /// Calculate how much quantity is available at prices that would cross.
+    /// Calculate how much quantity is available at prices that would cross,
+    /// hidden orders included.
     ///
     /// This is used for FOK (fill-or-kill) feasibility checks.
     pub fn available_to_fill(&self, side: Side, price: Price) -> Quantity {
-        self.opposite_side(side).quantity_at_or_better(price)
+        let resting_side = side.opposite();
+        self.side(resting_side).quantity_at_or_better(price)
+            + self.hidden_side(resting_side).quantity_at_or_better(price)
     }
 
     /// Check if an order can be fully filled (for FOK orders).
@@ -535,6 +641,79 @@ mod tests {
         assert!(!book.can_fully_fill(Side::Buy, Price(99_00), 50)); // Price doesn't cross
     }
 
+    // === Hidden orders ===
+
+    fn add_hidden(book: &mut OrderBook, side: Side, price: i64, qty: u64) -> OrderId {
+        let order = book
+            .create_order(side, Price(price), qty, TimeInForce::GTC)
+            .with_hidden();
+        let id = order.id;
+        book.add_order(order);
+        id
+    }
+
+    #[test]
+    fn hidden_at_better_price_fills_before_worse_displayed() {
+        let mut book = book_with_asks(&[(101_00, 50)]);
+        let hidden = add_hidden(&mut book, Side::Sell, 100_00, 30);
+
+        // Not displayed
+        assert_eq!(book.best_ask(), Some(Price(101_00)));
+        assert_eq!(book.available_to_fill(Side::Buy, Price(101_00)), 80);
+
+        let mut order = book.create_order(Side::Buy, Price(101_00), 40, TimeInForce::GTC);
+        let result = book.match_order(&mut order);
+        assert_eq!(result.trades[0].passive_order_id, hidden);
+        assert_eq!(result.trades[0].price, Price(100_00));
+        assert_eq!(result.trades[0].quantity, 30);
+        assert_eq!(result.trades[1].price, Price(101_00));
+        assert_eq!(result.trades[1].quantity, 10);
+        assert_eq!(book.hidden_side(Side::Sell).level_count(), 0);
+    }
+
+    #[test]
+    fn hidden_at_same_price_yields_to_displayed() {
+        let mut book = OrderBook::new();
+        // The hidden bid arrives first but still fills after the displayed one
+        let hidden = add_hidden(&mut book, Side::Buy, 100_00, 30);
+        let shown = book.create_order(Side::Buy, Price(100_00), 20, TimeInForce::GTC);
+        let shown_id = shown.id;
+        book.add_order(shown);
+        let worse = book.create_order(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+        book.add_order(worse);
+
+        let mut order = book.create_order(Side::Sell, Price(99_00), 60, TimeInForce::GTC);
+        let result = book.match_order(&mut order);
+        let fills: Vec<(OrderId, Price, u64)> = result
+            .trades
+            .iter()
+            .map(|t| (t.passive_order_id, t.price, t.quantity))
+            .collect();
+        assert_eq!(
+            fills,
+            [
+                (shown_id, Price(100_00), 20),
+                (hidden, Price(100_00), 30),
+                (OrderId(3), Price(99_00), 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_orders_cancel_and_reduce_from_their_own_queue() {
+        let mut book = book_with_bids(&[(100_00, 10)]);
+        let hidden = add_hidden(&mut book, Side::Buy, 100_00, 40);
+        assert_eq!(book.hidden_side(Side::Buy).total_quantity(), 40);
+
+        assert_eq!(book.reduce_order(hidden, 15), Some(15));
+        assert_eq!(book.hidden_side(Side::Buy).total_quantity(), 25);
+        assert_eq!(book.bids().total_quantity(), 10);
+
+        assert_eq!(book.cancel_order(hidden), Some(25));
+        assert!(book.hidden_side(Side::Buy).is_empty());
+        assert_eq!(book.best_bid(), Some(Price(100_00)));
+    }
+
     // === Edge cases ===
 
     #[test]
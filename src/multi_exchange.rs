@@ -1,13 +1,22 @@
 //! Multi-symbol exchange: one LOB per symbol.
 
-use crate::{Exchange, Price, Symbol};
-use rustc_hash::FxHashMap;
+use crate::{
+    BookSnapshot, Exchange, Price, Quantity, RouteResult, SessionState, Side, SpreadError,
+    SpreadResult, Symbol, SymbolTable, TimeInForce, VenueFill,
+};
+use std::collections::BTreeMap;
 
 /// A collection of per-symbol `Exchange` instances.
 ///
 /// Each symbol gets its own independent order book. This is the entry point
 /// for multi-asset simulations.
 ///
+/// Books are kept in symbol order, so [`symbols`](Self::symbols),
+/// [`best_prices`](Self::best_prices), [`snapshot_all`](Self::snapshot_all),
+/// and every other per-symbol walk visit symbols in the same deterministic
+/// order on every run: ascending [`Symbol`] bytes, which for plain ticker
+/// names is alphabetical.
+///
 /// ```
 /// use nanobook::{MultiExchange, Symbol, Side, Price, TimeInForce};
 ///
@@ -23,9 +32,22 @@ use rustc_hash::FxHashMap;
 /// assert_eq!(multi.get(&aapl).unwrap().best_ask(), Some(Price(150_00)));
 /// assert_eq!(multi.get(&msft).unwrap().best_ask(), Some(Price(300_00)));
 /// ```
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MultiExchange {
-    exchanges: FxHashMap<Symbol, Exchange>,
+    exchanges: BTreeMap<Symbol, Exchange>,
+    symbol_table: SymbolTable,
+    price_scale: i64,
+}
+
+impl Default for MultiExchange {
+    fn default() -> Self {
+        Self {
+            exchanges: BTreeMap::new(),
+            symbol_table: SymbolTable::default(),
+            price_scale: Price::DEFAULT_SCALE,
+        }
+    }
 }
 
 impl MultiExchange {
@@ -34,9 +56,60 @@ impl MultiExchange {
         Self::default()
     }
 
+    /// Set the price scale of every exchange, including ones created later.
+    ///
+    /// Builder form; see [`Exchange::with_price_scale`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `price_scale` is not positive.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        assert!(price_scale > 0, "price_scale must be positive");
+        self.price_scale = price_scale;
+        for exchange in self.exchanges.values_mut() {
+            exchange.price_scale = price_scale;
+        }
+        self
+    }
+
+    /// Price units per whole currency unit (100 by default).
+    pub fn price_scale(&self) -> i64 {
+        self.price_scale
+    }
+
     /// Get or create the exchange for a symbol.
     pub fn get_or_create(&mut self, symbol: &Symbol) -> &mut Exchange {
-        self.exchanges.entry(*symbol).or_default()
+        let price_scale = self.price_scale;
+        self.exchanges
+            .entry(*symbol)
+            .or_insert_with(|| Exchange::new().with_price_scale(price_scale))
+    }
+
+    /// Get or create the exchange for a name of any length.
+    ///
+    /// The name is interned in this exchange's [`SymbolTable`], so names
+    /// longer than 8 bytes get a compact generated symbol.
+    pub fn get_or_create_named(&mut self, name: &str) -> &mut Exchange {
+        let symbol = self.symbol_table.intern(name);
+        self.get_or_create(&symbol)
+    }
+
+    /// Intern `name` without creating an exchange, returning its symbol.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        self.symbol_table.intern(name)
+    }
+
+    /// The original name for `symbol`, falling back to the symbol itself
+    /// when it was never interned.
+    pub fn name<'a>(&'a self, symbol: &'a Symbol) -> &'a str {
+        self.symbol_table
+            .name(symbol)
+            .unwrap_or_else(|| symbol.as_str())
+    }
+
+    /// The table of interned names.
+    pub fn symbol_table(&self) -> &SymbolTable {
+        &self.symbol_table
     }
 
     /// Get a reference to the exchange for a symbol, if it exists.
@@ -49,7 +122,7 @@ impl MultiExchange {
         self.exchanges.get_mut(symbol)
     }
 
-    /// Iterator over all symbols that have exchanges.
+    /// Iterator over all symbols that have exchanges, in ascending order.
     pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
         self.exchanges.keys()
     }
@@ -64,7 +137,138 @@ impl MultiExchange {
         self.exchanges.is_empty()
     }
 
-    /// Get the best bid and ask for all symbols.
+    /// [`Exchange::clear`] every exchange, keeping the symbols and their
+    /// configuration.
+    pub fn clear_all(&mut self) {
+        for exchange in self.exchanges.values_mut() {
+            exchange.clear();
+        }
+    }
+
+    /// Submit a multi-leg spread order that fills every leg or none.
+    ///
+    /// Each leg is `(symbol, side, ratio)` and trades `ratio × quantity`
+    /// on that symbol's book. The net price per spread unit is what the
+    /// buy legs cost minus what the sell legs bring in, and the spread
+    /// only executes if it is at most `net_limit` (a negative limit
+    /// requires a net credit).
+    ///
+    /// Every leg is priced against its book before anything trades. If
+    /// every leg's exchange is open and not halted, all books are deep
+    /// enough, and the net price is within the limit, each leg executes as
+    /// an IOC order sweeping exactly the priced levels; otherwise no book
+    /// is touched.
+    ///
+    /// ```
+    /// use nanobook::{MultiExchange, Price, Side, Symbol, TimeInForce};
+    ///
+    /// let mut multi = MultiExchange::new();
+    /// let (front, back) = (Symbol::new("CLZ5"), Symbol::new("CLF6"));
+    /// multi.get_or_create(&front).submit_limit(Side::Sell, Price(70_00), 10, TimeInForce::GTC);
+    /// multi.get_or_create(&back).submit_limit(Side::Buy, Price(71_00), 10, TimeInForce::GTC);
+    ///
+    /// // Buy the front month, sell the back month: a $1 credit per unit.
+    /// let legs = [(front, Side::Buy, 1), (back, Side::Sell, 1)];
+    /// assert!(!multi.submit_spread(&legs, Price(-2_00), 10).success);
+    ///
+    /// let result = multi.submit_spread(&legs, Price(-1_00), 10);
+    /// assert!(result.success);
+    /// assert_eq!(result.net_notional, -1_00 * 10);
+    /// assert_eq!(result.legs[0].filled_quantity, 10);
+    /// ```
+    pub fn submit_spread(
+        &mut self,
+        legs: &[(Symbol, Side, Quantity)],
+        net_limit: Price,
+        quantity: Quantity,
+    ) -> SpreadResult {
+        if legs.is_empty() || quantity == 0 || legs.iter().any(|&(_, _, ratio)| ratio == 0) {
+            return SpreadResult::failure(SpreadError::InvalidQuantity);
+        }
+        for (i, (symbol, _, _)) in legs.iter().enumerate() {
+            if legs[..i].iter().any(|(other, _, _)| other == symbol) {
+                return SpreadResult::failure(SpreadError::DuplicateSymbol);
+            }
+        }
+
+        // Price every leg before touching any book.
+        let mut sweeps = Vec::with_capacity(legs.len());
+        let mut net_notional: i128 = 0;
+        for &(symbol, side, ratio) in legs {
+            if let Some(exchange) = self.exchanges.get(&symbol) {
+                if exchange.is_halted() {
+                    return SpreadResult::failure(SpreadError::Halted);
+                }
+                if exchange.session() != SessionState::Open {
+                    return SpreadResult::failure(SpreadError::Session);
+                }
+            }
+            let Some(leg_quantity) = ratio.checked_mul(quantity) else {
+                return SpreadResult::failure(SpreadError::InvalidQuantity);
+            };
+            let Some((worst_price, notional)) = self
+                .exchanges
+                .get(&symbol)
+                .and_then(|ex| sweep_cost(ex, side, leg_quantity))
+            else {
+                return SpreadResult::failure(SpreadError::InsufficientLiquidity);
+            };
+            net_notional += match side {
+                Side::Buy => notional,
+                Side::Sell => -notional,
+            };
+            sweeps.push((worst_price, leg_quantity));
+        }
+        if net_notional > i128::from(net_limit.0) * i128::from(quantity) {
+            return SpreadResult::failure(SpreadError::LimitExceeded);
+        }
+
+        let mut net_notional = 0;
+        let results = legs
+            .iter()
+            .zip(sweeps)
+            .map(|(&(symbol, side, _), (worst_price, leg_quantity))| {
+                let exchange = self
+                    .exchanges
+                    .get_mut(&symbol)
+                    .expect("leg was priced against this exchange");
+                let result =
+                    exchange.submit_limit(side, worst_price, leg_quantity, TimeInForce::IOC);
+                net_notional += match side {
+                    Side::Buy => result.filled_notional,
+                    Side::Sell => -result.filled_notional,
+                };
+                result
+            })
+            .collect();
+
+        SpreadResult {
+            success: true,
+            legs: results,
+            net_notional,
+            error: None,
+        }
+    }
+
+    /// Save the full state to a JSON file.
+    ///
+    /// Unlike [`Exchange::save`], which writes the event log, this writes
+    /// the state itself: every book with its queue order, order history,
+    /// pending stops, trades, ID counters, and the symbol table.
+    #[cfg(feature = "persistence")]
+    pub fn save_json(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Load a multi-exchange saved with [`save_json`](Self::save_json).
+    #[cfg(feature = "persistence")]
+    pub fn load_json(path: &std::path::Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(std::io::Error::other)
+    }
+
+    /// Get the best bid and ask for all symbols, in ascending symbol order.
     pub fn best_prices(&self) -> Vec<(Symbol, Option<Price>, Option<Price>)> {
         self.exchanges
             .iter()
@@ -74,6 +278,151 @@ impl MultiExchange {
             })
             .collect()
     }
+
+    /// Depth-limited book snapshot for every symbol, keyed and ordered by
+    /// symbol.
+    ///
+    /// Each value is [`Exchange::depth`]`(depth)`.
+    ///
+    /// ```
+    /// use nanobook::{MultiExchange, Price, Side, Symbol, TimeInForce};
+    ///
+    /// let mut multi = MultiExchange::new();
+    /// for (name, px) in [("MSFT", 300_00), ("AAPL", 150_00)] {
+    ///     let ex = multi.get_or_create(&Symbol::new(name));
+    ///     ex.submit_limit(Side::Sell, Price(px), 10, TimeInForce::GTC);
+    ///     ex.submit_limit(Side::Sell, Price(px + 1), 10, TimeInForce::GTC);
+    /// }
+    ///
+    /// let books = multi.snapshot_all(1);
+    /// let names: Vec<_> = books.keys().map(|s| s.as_str()).collect();
+    /// assert_eq!(names, ["AAPL", "MSFT"]);
+    /// assert_eq!(books[&Symbol::new("AAPL")].asks.len(), 1);
+    /// ```
+    pub fn snapshot_all(&self, depth: usize) -> BTreeMap<Symbol, BookSnapshot> {
+        self.exchanges
+            .iter()
+            .map(|(sym, ex)| (*sym, ex.depth(depth)))
+            .collect()
+    }
+}
+
+/// Consolidated best bid and ask (NBBO) across several books for the same
+/// instrument.
+///
+/// Returns `(bid, ask)`: the highest bid and the lowest ask over all
+/// `exchanges`, each `None` if no book has orders on that side. The result
+/// can be crossed or locked when one venue's bid reaches another's ask,
+/// the trade-through condition a router would act on.
+///
+/// ```
+/// use nanobook::{Exchange, Price, Side, TimeInForce, best_consolidated};
+///
+/// let (mut a, mut b) = (Exchange::new(), Exchange::new());
+/// a.submit_limit(Side::Buy, Price(99_00), 100, TimeInForce::GTC);
+/// a.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+/// b.submit_limit(Side::Buy, Price(99_50), 100, TimeInForce::GTC);
+///
+/// assert_eq!(best_consolidated(&[&a, &b]), (Some(Price(99_50)), Some(Price(101_00))));
+/// ```
+pub fn best_consolidated(exchanges: &[&Exchange]) -> (Option<Price>, Option<Price>) {
+    exchanges
+        .iter()
+        .fold((None, None), |(best_bid, best_ask), ex| {
+            let (bid, ask) = ex.best_bid_ask();
+            (best_bid.max(bid), min_some(best_ask, ask))
+        })
+}
+
+/// Route `quantity` across several books for the same instrument, taking
+/// the best-priced liquidity first regardless of venue.
+///
+/// The router walks every venue's opposite side from best to worst price,
+/// allocating to each level in turn; when venues show the same price, the
+/// one earlier in `venues` is filled first. Each venue that was allocated
+/// anything then receives one IOC limit order for its share, priced at the
+/// worst level it reaches, so the fills match the allocation exactly. Any
+/// quantity beyond the combined depth is left unfilled.
+///
+/// ```
+/// use nanobook::{Exchange, Price, Side, TimeInForce, route_order};
+///
+/// let (mut a, mut b) = (Exchange::new(), Exchange::new());
+/// a.submit_limit(Side::Sell, Price(101_00), 100, TimeInForce::GTC);
+/// b.submit_limit(Side::Sell, Price(100_00), 50, TimeInForce::GTC);
+///
+/// let routed = route_order(&mut [&mut a, &mut b], Side::Buy, 120);
+/// assert_eq!(routed.filled_quantity, 120);
+/// // The cheaper venue is swept first, then the rest comes from venue 0.
+/// assert_eq!(routed.fills[0].venue, 0);
+/// assert_eq!(routed.fills[0].result.filled_quantity, 70);
+/// assert_eq!(routed.fills[1].result.filled_quantity, 50);
+/// ```
+pub fn route_order(venues: &mut [&mut Exchange], side: Side, quantity: Quantity) -> RouteResult {
+    // (price, venue, quantity) for every level a venue could contribute.
+    let mut levels: Vec<(Price, usize, Quantity)> = Vec::new();
+    for (venue, ex) in venues.iter().enumerate() {
+        let mut depth = 0;
+        for (&price, level) in ex.book().opposite_side(side).iter_best_to_worst() {
+            if depth >= quantity {
+                break;
+            }
+            levels.push((price, venue, level.total_quantity()));
+            depth += level.total_quantity();
+        }
+    }
+    // Stable sort keeps venue order among equal prices.
+    match side {
+        Side::Buy => levels.sort_by_key(|&(price, _, _)| price),
+        Side::Sell => levels.sort_by_key(|&(price, _, _)| std::cmp::Reverse(price)),
+    }
+
+    // Per venue: allocated quantity and the worst price reached.
+    let mut allocation: Vec<(Quantity, Option<Price>)> = vec![(0, None); venues.len()];
+    let mut remaining = quantity;
+    for (price, venue, available) in levels {
+        if remaining == 0 {
+            break;
+        }
+        let take = remaining.min(available);
+        allocation[venue].0 += take;
+        allocation[venue].1 = Some(price);
+        remaining -= take;
+    }
+
+    let mut routed = RouteResult::default();
+    for (venue, (qty, limit)) in allocation.into_iter().enumerate() {
+        let Some(limit) = limit else { continue };
+        let result = venues[venue].submit_limit(side, limit, qty, TimeInForce::IOC);
+        routed.filled_quantity += result.filled_quantity;
+        routed.filled_notional += result.filled_notional;
+        routed.fills.push(VenueFill { venue, result });
+    }
+    routed
+}
+
+/// Minimum of two optional prices, ignoring `None`.
+fn min_some(a: Option<Price>, b: Option<Price>) -> Option<Price> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Worst price reached and total notional of taking `quantity` from the
+/// side opposite `side`, or `None` if the book is too thin.
+fn sweep_cost(exchange: &Exchange, side: Side, quantity: Quantity) -> Option<(Price, i128)> {
+    let mut remaining = quantity;
+    let mut notional: i128 = 0;
+    for (&price, level) in exchange.book().opposite_side(side).iter_best_to_worst() {
+        let take = remaining.min(level.total_quantity());
+        notional += i128::from(price.0) * i128::from(take);
+        remaining -= take;
+        if remaining == 0 {
+            return Some((price, notional));
+        }
+    }
+    None
 }
 
 #[cfg(test)]
@@ -89,6 +438,98 @@ mod tests {
         Symbol::new("MSFT")
     }
 
+    #[test]
+    fn snapshot_all_is_depth_limited_and_ordered() {
+        let mut multi = MultiExchange::new();
+        for sym in [msft(), aapl()] {
+            let ex = multi.get_or_create(&sym);
+            for i in 0..3 {
+                ex.submit_limit(Side::Buy, Price(100_00 - i), 10, TimeInForce::GTC);
+                ex.submit_limit(Side::Sell, Price(101_00 + i), 10, TimeInForce::GTC);
+            }
+        }
+        multi.get_or_create(&Symbol::new("EMPTY"));
+
+        let books = multi.snapshot_all(2);
+        assert_eq!(
+            books.keys().copied().collect::<Vec<_>>(),
+            [aapl(), Symbol::new("EMPTY"), msft()]
+        );
+        let aapl_book = &books[&aapl()];
+        assert_eq!(aapl_book.bids.len(), 2);
+        assert_eq!(aapl_book.asks[0].price, Price(101_00));
+        assert!(
+            aapl_book
+                .diff(&multi.get(&aapl()).unwrap().depth(2))
+                .is_empty()
+        );
+        assert!(books[&Symbol::new("EMPTY")].bids.is_empty());
+    }
+
+    #[test]
+    fn best_consolidated_takes_best_of_each_side() {
+        assert_eq!(best_consolidated(&[]), (None, None));
+
+        let (mut a, mut b, empty) = (Exchange::new(), Exchange::new(), Exchange::new());
+        a.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        a.submit_limit(Side::Sell, Price(102_00), 10, TimeInForce::GTC);
+        b.submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        assert_eq!(
+            best_consolidated(&[&empty, &a, &b]),
+            (Some(Price(99_00)), Some(Price(101_00)))
+        );
+
+        // A bid on one venue above another's ask crosses the NBBO
+        b.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        a.submit_limit(Side::Sell, Price(99_50), 5, TimeInForce::GTC);
+        let (bid, ask) = best_consolidated(&[&a, &b]);
+        assert_eq!(bid, Some(Price(100_00)));
+        assert_eq!(ask, Some(Price(99_50)));
+    }
+
+    #[test]
+    fn route_order_sweeps_across_venues_by_price_then_venue() {
+        let (mut a, mut b, mut c) = (Exchange::new(), Exchange::new(), Exchange::new());
+        a.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        a.submit_limit(Side::Sell, Price(102_00), 100, TimeInForce::GTC);
+        b.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        b.submit_limit(Side::Sell, Price(101_00), 20, TimeInForce::GTC);
+        c.submit_limit(Side::Buy, Price(99_00), 500, TimeInForce::GTC);
+
+        // 100.00 from a then b (venue order), 101.00 from b, 102.00 from a
+        let routed = route_order(&mut [&mut a, &mut b, &mut c], Side::Buy, 90);
+        assert_eq!(routed.filled_quantity, 90);
+        assert_eq!(
+            routed.filled_notional,
+            60 * 100_00 + 20 * 101_00 + 10 * 102_00
+        );
+        let per_venue: Vec<(usize, Quantity)> = routed
+            .fills
+            .iter()
+            .map(|f| (f.venue, f.result.filled_quantity))
+            .collect();
+        assert_eq!(per_venue, [(0, 40), (1, 50)]);
+        assert_eq!(a.best_ask(), Some(Price(102_00)));
+        assert_eq!(b.best_ask(), None);
+
+        // Equal prices with too little to go round: the earlier venue wins
+        a.submit_limit(Side::Buy, Price(98_00), 10, TimeInForce::GTC);
+        b.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        let routed = route_order(&mut [&mut a, &mut b, &mut c], Side::Sell, 15);
+        let per_venue: Vec<(usize, Quantity)> = routed
+            .fills
+            .iter()
+            .map(|f| (f.venue, f.result.filled_quantity))
+            .collect();
+        assert_eq!(per_venue, [(1, 10), (2, 5)]);
+        assert_eq!(c.total_resting_quantity(Side::Buy), 495);
+
+        // Thin books leave the rest unfilled
+        let routed = route_order(&mut [&mut b], Side::Buy, 10);
+        assert_eq!(routed.filled_quantity, 0);
+        assert!(routed.fills.is_empty());
+    }
+
     #[test]
     fn create_and_query() {
         let mut multi = MultiExchange::new();
@@ -125,6 +566,51 @@ mod tests {
         assert_eq!(syms.len(), 2);
     }
 
+    #[test]
+    fn symbol_order_is_sorted_regardless_of_insertion() {
+        let names = ["NVDA", "A", "MSFT", "AA", "AAPL", "B"];
+        let mut multi = MultiExchange::new();
+        for name in names {
+            multi.get_or_create(&Symbol::new(name)).submit_limit(
+                Side::Buy,
+                Price(1_00),
+                1,
+                TimeInForce::GTC,
+            );
+        }
+
+        let expected = ["A", "AA", "AAPL", "B", "MSFT", "NVDA"];
+        let syms: Vec<&str> = multi.symbols().map(|s| s.as_str()).collect();
+        assert_eq!(syms, expected);
+        let best = multi.best_prices();
+        let best: Vec<&str> = best.iter().map(|(s, _, _)| s.as_str()).collect();
+        assert_eq!(best, expected);
+    }
+
+    #[test]
+    fn named_exchanges_round_trip_long_names() {
+        let mut multi = MultiExchange::new();
+        multi.get_or_create_named("AAPL").submit_limit(
+            Side::Sell,
+            Price(150_00),
+            100,
+            TimeInForce::GTC,
+        );
+        multi
+            .get_or_create_named("BERKSHIRE_HATHAWAY_B")
+            .submit_limit(Side::Sell, Price(400_00), 10, TimeInForce::GTC);
+
+        let long = multi.intern("BERKSHIRE_HATHAWAY_B");
+        assert_eq!(multi.get(&long).unwrap().best_ask(), Some(Price(400_00)));
+        assert_eq!(multi.name(&long), "BERKSHIRE_HATHAWAY_B");
+        assert_eq!(multi.name(&aapl()), "AAPL");
+
+        // Symbols created without the table resolve to themselves.
+        multi.get_or_create(&msft());
+        assert_eq!(multi.name(&msft()), "MSFT");
+        assert_eq!(multi.symbol_table().len(), 2);
+    }
+
     #[test]
     fn empty() {
         let multi = MultiExchange::new();
@@ -152,4 +638,164 @@ mod tests {
         assert_eq!(multi.get(&aapl()).unwrap().trades().len(), 1);
         assert_eq!(multi.get(&msft()).unwrap().trades().len(), 0);
     }
+
+    #[test]
+    fn clear_all_keeps_symbols() {
+        let mut multi = MultiExchange::new();
+        multi
+            .get_or_create(&aapl())
+            .submit_limit(Side::Sell, Price(150_00), 100, TimeInForce::GTC);
+        multi
+            .get_or_create(&msft())
+            .submit_limit(Side::Buy, Price(300_00), 200, TimeInForce::GTC);
+
+        multi.clear_all();
+
+        assert_eq!(multi.len(), 2);
+        assert_eq!(multi.get(&aapl()).unwrap().best_ask(), None);
+        assert_eq!(multi.get(&msft()).unwrap().best_bid(), None);
+    }
+
+    #[test]
+    fn price_scale_applies_to_all_exchanges() {
+        let mut multi = MultiExchange::new();
+        multi.get_or_create(&aapl());
+        let mut multi = multi.with_price_scale(10_000);
+        multi.get_or_create(&msft());
+
+        assert_eq!(multi.price_scale(), 10_000);
+        assert_eq!(multi.get(&aapl()).unwrap().price_scale(), 10_000);
+        assert_eq!(multi.get(&msft()).unwrap().price_scale(), 10_000);
+        assert_eq!(MultiExchange::new().price_scale(), Price::DEFAULT_SCALE);
+    }
+
+    fn spread_books() -> MultiExchange {
+        let mut multi = MultiExchange::new();
+        let a = multi.get_or_create(&aapl());
+        a.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        a.submit_limit(Side::Sell, Price(101_00), 10, TimeInForce::GTC);
+        multi
+            .get_or_create(&msft())
+            .submit_limit(Side::Buy, Price(50_00), 40, TimeInForce::GTC);
+        multi
+    }
+
+    #[test]
+    fn spread_fills_all_legs_with_ratios() {
+        let mut multi = spread_books();
+        // Buy 1 AAPL, sell 2 MSFT per unit; 15 units sweep two AAPL levels.
+        let legs = [(aapl(), Side::Buy, 1), (msft(), Side::Sell, 2)];
+        let result = multi.submit_spread(&legs, Price(1_00), 15);
+
+        assert!(result.success);
+        assert_eq!(result.error, None);
+        assert_eq!(result.legs.len(), 2);
+        assert_eq!(result.legs[0].filled_quantity, 15);
+        assert_eq!(result.legs[1].filled_quantity, 30);
+        // 10 × 100 + 5 × 101 − 30 × 50 = 5 dollars over 15 units
+        assert_eq!(result.net_notional, 5_00);
+        assert_eq!(multi.get(&aapl()).unwrap().best_ask(), Some(Price(101_00)));
+        assert_eq!(multi.get(&msft()).unwrap().best_bid(), Some(Price(50_00)));
+    }
+
+    #[test]
+    fn spread_rejects_without_touching_books() {
+        let mut multi = spread_books();
+        let legs = [(aapl(), Side::Buy, 1), (msft(), Side::Sell, 2)];
+
+        // Net price of 5_00 / 15 per unit is above a zero limit.
+        let result = multi.submit_spread(&legs, Price(0), 15);
+        assert_eq!(result.error, Some(SpreadError::LimitExceeded));
+
+        // MSFT has only 40 to sell into; 25 units need 50.
+        let result = multi.submit_spread(&legs, Price(100_00), 25);
+        assert_eq!(result.error, Some(SpreadError::InsufficientLiquidity));
+        assert!(result.legs.is_empty());
+
+        let unknown = [(aapl(), Side::Buy, 1), (Symbol::new("IBM"), Side::Sell, 1)];
+        let result = multi.submit_spread(&unknown, Price(100_00), 1);
+        assert_eq!(result.error, Some(SpreadError::InsufficientLiquidity));
+        assert!(multi.get(&Symbol::new("IBM")).is_none());
+
+        let dup = [(aapl(), Side::Buy, 1), (aapl(), Side::Sell, 1)];
+        let result = multi.submit_spread(&dup, Price(100_00), 1);
+        assert_eq!(result.error, Some(SpreadError::DuplicateSymbol));
+        let result = multi.submit_spread(&legs, Price(100_00), 0);
+        assert_eq!(result.error, Some(SpreadError::InvalidQuantity));
+
+        multi.get_mut(&msft()).unwrap().halt();
+        let result = multi.submit_spread(&legs, Price(100_00), 10);
+        assert_eq!(result.error, Some(SpreadError::Halted));
+        multi.get_mut(&msft()).unwrap().resume();
+
+        for sym in [aapl(), msft()] {
+            assert!(multi.get(&sym).unwrap().trades().is_empty());
+        }
+        assert_eq!(
+            multi.get(&aapl()).unwrap().book().asks().total_quantity(),
+            20
+        );
+    }
+
+    #[test]
+    fn spread_rejects_legs_outside_the_open_session() {
+        for state in [SessionState::Closed, SessionState::PreOpen] {
+            let mut multi = spread_books();
+            multi.get_mut(&msft()).unwrap().set_session(state);
+            let legs = [(aapl(), Side::Buy, 1), (msft(), Side::Sell, 2)];
+
+            let result = multi.submit_spread(&legs, Price(100_00), 10);
+            assert!(!result.success);
+            assert_eq!(result.error, Some(SpreadError::Session));
+            assert!(result.legs.is_empty());
+            for sym in [aapl(), msft()] {
+                assert!(multi.get(&sym).unwrap().trades().is_empty());
+            }
+            assert_eq!(
+                multi.get(&aapl()).unwrap().book().asks().total_quantity(),
+                20
+            );
+            assert_eq!(
+                multi.get(&msft()).unwrap().book().bids().total_quantity(),
+                40
+            );
+        }
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn json_round_trip_keeps_queue_order() {
+        let path = std::env::temp_dir().join("nanobook_multi_round_trip.json");
+
+        let mut multi = MultiExchange::new();
+        let ex = multi.get_or_create(&aapl());
+        ex.submit_limit(Side::Sell, Price(150_00), 10, TimeInForce::GTC);
+        ex.submit_limit(Side::Sell, Price(150_00), 20, TimeInForce::GTC);
+        ex.submit_limit(Side::Sell, Price(150_00), 30, TimeInForce::GTC);
+        ex.cancel(crate::OrderId(2));
+        ex.submit_stop_market(Side::Buy, Price(155_00), 5);
+        multi
+            .get_or_create_named("BERKSHIRE_HATHAWAY_B")
+            .submit_limit(Side::Buy, Price(400_00), 7, TimeInForce::GTC);
+
+        multi.save_json(&path).unwrap();
+        let mut loaded = MultiExchange::load_json(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let long = loaded.intern("BERKSHIRE_HATHAWAY_B");
+        assert_eq!(loaded.symbol_table().len(), 1);
+        assert_eq!(loaded.get(&long).unwrap().best_bid(), Some(Price(400_00)));
+
+        let ex = loaded.get_or_create(&aapl());
+        assert_eq!(ex.pending_stop_count(), 1);
+        let result = ex.submit_market(Side::Buy, 35);
+        let fills: Vec<_> = result
+            .trades
+            .iter()
+            .map(|t| (t.passive_order_id.0, t.quantity))
+            .collect();
+        assert_eq!(fills, vec![(1, 10), (3, 25)]);
+        // IDs continue from the saved counters.
+        assert_eq!(result.order_id, crate::OrderId(5));
+    }
 }
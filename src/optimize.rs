@@ -3,13 +3,17 @@
 //! The implementations here are deterministic and safety-first:
 //! - invalid inputs return empty weights,
 //! - valid outputs are finite, non-negative, and sum to ~1.
+//!
+//! The covariance-input allocators ([`min_variance_weights`],
+//! [`mean_variance_weights`]) can also solve without the long-only
+//! constraint, and report invalid or singular input as an [`OptimizeError`].
 
 /// Errors returned by helpers in this module.
 ///
 /// The high-level optimizers (`optimize_min_variance`, `optimize_max_sharpe`,
 /// etc.) swallow these and fall back to their own safe defaults; the error
 /// variants surface only through direct calls to low-level primitives
-/// like [`project_simplex`].
+/// like [`project_simplex`] and the covariance-input allocators.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptimizeError {
@@ -20,6 +24,18 @@ pub enum OptimizeError {
     /// an error prevents masking upstream convergence failures (e.g. an
     /// optimizer that converged to a zero gradient).
     DegenerateProjection,
+    /// The covariance matrix is empty, not square, or has non-finite
+    /// entries.
+    InvalidCovariance,
+    /// The covariance matrix is not positive definite, so the
+    /// unconstrained solution is not unique. Shrink it first (e.g. with
+    /// [`cov_shrinkage`]) or solve long-only.
+    SingularCovariance,
+    /// The expected-return vector length differs from the covariance
+    /// dimension.
+    DimensionMismatch,
+    /// Risk aversion must be positive and finite.
+    InvalidRiskAversion,
 }
 
 impl core::fmt::Display for OptimizeError {
@@ -29,6 +45,14 @@ impl core::fmt::Display for OptimizeError {
             Self::DegenerateProjection => {
                 f.write_str("simplex projection is degenerate (no positive finite component)")
             }
+            Self::InvalidCovariance => {
+                f.write_str("covariance matrix is empty, non-square, or non-finite")
+            }
+            Self::SingularCovariance => f.write_str("covariance matrix is not positive definite"),
+            Self::DimensionMismatch => {
+                f.write_str("expected returns do not match the covariance dimension")
+            }
+            Self::InvalidRiskAversion => f.write_str("risk aversion must be positive and finite"),
         }
     }
 }
@@ -312,6 +336,493 @@ pub fn correlation_matrix(returns: &[Vec<f64>]) -> Vec<Vec<f64>> {
     corr
 }
 
+/// Correlation matrix of every trailing window across per-asset series.
+///
+/// `series[i]` is asset `i`'s return series; all series must share one
+/// length. Output `k` is the correlation matrix over periods
+/// `k..k + window`, so there are `len - window + 1` matrices. Entries follow
+/// [`correlation_matrix`]: unit diagonal, `0.0` for a zero-variance pair,
+/// clamped to `[-1, 1]`. Invalid input (no series, unequal lengths,
+/// non-finite values, `window < 2`, or fewer periods than `window`) returns
+/// an empty vector.
+///
+/// Each window is computed fresh with centered sums rather than by
+/// add-and-evict updates, which cancel catastrophically on long series.
+/// Windows are independent, so they are computed in parallel under the
+/// `parallel` feature.
+pub fn rolling_corr_matrix(series: &[&[f64]], window: usize) -> Vec<Vec<Vec<f64>>> {
+    let Some(len) = series.first().map(|s| s.len()) else {
+        return Vec::new();
+    };
+    if window < 2
+        || len < window
+        || series
+            .iter()
+            .any(|s| s.len() != len || s.iter().any(|x| !x.is_finite()))
+    {
+        return Vec::new();
+    }
+
+    let starts = 0..=len - window;
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        starts
+            .into_par_iter()
+            .map(|start| window_correlation(series, start, window))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        starts
+            .map(|start| window_correlation(series, start, window))
+            .collect()
+    }
+}
+
+fn window_correlation(series: &[&[f64]], start: usize, window: usize) -> Vec<Vec<f64>> {
+    let n = series.len();
+    let centered: Vec<Vec<f64>> = series
+        .iter()
+        .map(|s| {
+            let slice = &s[start..start + window];
+            let mean = slice.iter().sum::<f64>() / window as f64;
+            slice.iter().map(|x| x - mean).collect()
+        })
+        .collect();
+    let sum_sq: Vec<f64> = centered.iter().map(|c| dot(c, c)).collect();
+
+    let mut corr = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        corr[i][i] = 1.0;
+        for j in i + 1..n {
+            let denom = (sum_sq[i] * sum_sq[j]).sqrt();
+            let value = if denom.is_finite() && denom > 1e-12 {
+                (dot(&centered[i], &centered[j]) / denom).clamp(-1.0, 1.0)
+            } else {
+                0.0
+            };
+            corr[i][j] = value;
+            corr[j][i] = value;
+        }
+    }
+    corr
+}
+
+/// Group per-asset return series into clusters of correlated assets.
+///
+/// `series[i]` is asset `i`'s return series; all series must share one
+/// length. Correlations are computed over the full sample as in
+/// [`rolling_corr_matrix`] with a single window.
+///
+/// Linkage is single linkage cut at `threshold`: two assets share a
+/// cluster if they are joined by a chain of pairs whose correlation is at
+/// least `threshold`. This equals cutting the single-linkage dendrogram
+/// that [`optimize_hrp`] builds at the matching correlation. A name with
+/// no correlation at or above `threshold` to any other forms its own
+/// cluster, so picking one asset per cluster gives a set whose pairwise
+/// correlations all stay below `threshold`.
+///
+/// Clusters are ordered by their lowest index, with members ascending.
+/// Invalid input (no series, unequal lengths, non-finite values, fewer
+/// than two periods, or a NaN `threshold`) returns an empty vector.
+///
+/// ```
+/// use nanobook::optimize::correlation_clusters;
+///
+/// let a = [0.01, -0.02, 0.015, 0.005];
+/// let b = [0.02, -0.04, 0.03, 0.01]; // a scaled copy of a
+/// let c = [0.01, 0.01, -0.01, -0.01];
+/// let clusters = correlation_clusters(&[&a, &c, &b], 0.9);
+/// assert_eq!(clusters, vec![vec![0, 2], vec![1]]);
+/// ```
+pub fn correlation_clusters(series: &[&[f64]], threshold: f64) -> Vec<Vec<usize>> {
+    let Some(len) = series.first().map(|s| s.len()) else {
+        return Vec::new();
+    };
+    if threshold.is_nan()
+        || len < 2
+        || series
+            .iter()
+            .any(|s| s.len() != len || s.iter().any(|x| !x.is_finite()))
+    {
+        return Vec::new();
+    }
+
+    let corr = window_correlation(series, 0, len);
+    let n = series.len();
+    // Union-find where every root is its cluster's lowest index.
+    let mut parent: Vec<usize> = (0..n).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for (i, row) in corr.iter().enumerate() {
+        for (j, &c) in row.iter().enumerate().skip(i + 1) {
+            if c >= threshold {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut slot = vec![usize::MAX; n];
+    for i in 0..n {
+        let r = root(&mut parent, i);
+        if slot[r] == usize::MAX {
+            slot[r] = clusters.len();
+            clusters.push(Vec::new());
+        }
+        clusters[slot[r]].push(i);
+    }
+    clusters
+}
+
+/// Ledoit-Wolf shrunk covariance matrix of per-asset return series.
+///
+/// Shrinks the sample covariance `S` toward the scaled identity `μI`, where
+/// `μ = trace(S) / n`, with the intensity that minimizes expected Frobenius
+/// loss (Ledoit & Wolf 2004, "A well-conditioned estimator for
+/// large-dimensional covariance matrices"):
+///
+/// - `d² = ‖S − μI‖²`
+/// - `b² = min(d², (1/T²) Σₜ ‖xₜxₜᵀ − S‖²)`
+/// - result `= (1 − b²/d²)·S + (b²/d²)·μI`
+///
+/// with norms scaled by `1/n`. `S` uses the `1/T` normalization, matching
+/// scikit-learn's `LedoitWolf`. The result is symmetric positive definite
+/// whenever any asset has nonzero variance. Invalid input (no series,
+/// unequal lengths, non-finite values, or fewer than two periods) returns
+/// an empty matrix.
+pub fn cov_shrinkage(series: &[&[f64]]) -> Vec<Vec<f64>> {
+    let n = series.len();
+    let Some(t) = series.first().map(|s| s.len()) else {
+        return Vec::new();
+    };
+    if t < 2
+        || series
+            .iter()
+            .any(|s| s.len() != t || s.iter().any(|x| !x.is_finite()))
+    {
+        return Vec::new();
+    }
+
+    let centered: Vec<Vec<f64>> = series
+        .iter()
+        .map(|s| {
+            let mean = s.iter().sum::<f64>() / t as f64;
+            s.iter().map(|x| x - mean).collect()
+        })
+        .collect();
+
+    let periods = t as f64;
+    let mut cov = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i..n {
+            let v = dot(&centered[i], &centered[j]) / periods;
+            cov[i][j] = v;
+            cov[j][i] = v;
+        }
+    }
+
+    let mu = (0..n).map(|i| cov[i][i]).sum::<f64>() / n as f64;
+    let mut d2 = 0.0;
+    for (i, row) in cov.iter().enumerate() {
+        for (j, &v) in row.iter().enumerate() {
+            let target = if i == j { mu } else { 0.0 };
+            d2 += (v - target).powi(2);
+        }
+    }
+    d2 /= n as f64;
+
+    let mut b2 = 0.0;
+    #[allow(clippy::needless_range_loop)]
+    for k in 0..t {
+        for (i, row) in cov.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                b2 += (centered[i][k] * centered[j][k] - v).powi(2);
+            }
+        }
+    }
+    b2 /= periods * periods * n as f64;
+
+    let shrinkage = if d2 > 0.0 { b2.min(d2) / d2 } else { 0.0 };
+    for (i, row) in cov.iter_mut().enumerate() {
+        for v in row.iter_mut() {
+            *v *= 1.0 - shrinkage;
+        }
+        row[i] += shrinkage * mu;
+    }
+    cov
+}
+
+/// Weight constraint for [`min_variance_weights`] and
+/// [`mean_variance_weights`]. Weights always sum to 1.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WeightConstraint {
+    /// Non-negative weights (no shorting).
+    #[default]
+    LongOnly,
+    /// Any real weights; shorts appear as negative entries.
+    Unconstrained,
+}
+
+/// Iteration cap for the long-only projected-gradient solver.
+const LONG_ONLY_MAX_ITERS: usize = 20_000;
+
+/// Minimum-variance weights for a covariance matrix.
+///
+/// Unconstrained, this is the closed form `Σ⁻¹1 / (1ᵀΣ⁻¹1)`, solved by
+/// Cholesky factorization. Long-only, it is the projected-gradient
+/// solution on the unit simplex (see [`mean_variance_weights`]).
+///
+/// # Errors
+///
+/// - [`OptimizeError::InvalidCovariance`] if `cov` is empty, non-square,
+///   or non-finite.
+/// - [`OptimizeError::SingularCovariance`] if the unconstrained problem
+///   has no unique solution, or `cov` is all zeros.
+pub fn min_variance_weights(
+    cov: &[Vec<f64>],
+    constraint: WeightConstraint,
+) -> Result<Vec<f64>, OptimizeError> {
+    let n = square_dim(cov)?;
+    mean_variance_weights(cov, &vec![0.0; n], 1.0, constraint)
+}
+
+/// Mean-variance weights: maximize `μᵀw − (λ/2)·wᵀΣw` subject to
+/// `Σwᵢ = 1`, where `λ = risk_aversion`.
+///
+/// Unconstrained, the solution is
+/// `w = Σ⁻¹μ/λ + (1 − 1ᵀΣ⁻¹μ/λ) · Σ⁻¹1 / (1ᵀΣ⁻¹1)`, solved by Cholesky
+/// factorization. Long-only, it runs projected gradient descent on the
+/// simplex with step `1/L`, where `L` bounds the largest eigenvalue of
+/// `λΣ` (Gershgorin). It stops when the squared step falls below `1e-20`
+/// or after 20,000 iterations. A singular `Σ` is fine long-only: the
+/// simplex keeps the problem bounded.
+///
+/// # Errors
+///
+/// - [`OptimizeError::InvalidCovariance`] if `cov` is empty, non-square,
+///   or non-finite, or `mu` has non-finite entries.
+/// - [`OptimizeError::DimensionMismatch`] if `mu.len()` differs from the
+///   covariance dimension.
+/// - [`OptimizeError::InvalidRiskAversion`] unless `risk_aversion` is
+///   positive and finite.
+/// - [`OptimizeError::SingularCovariance`] if the unconstrained problem
+///   has no unique solution, or `cov` is all zeros.
+pub fn mean_variance_weights(
+    cov: &[Vec<f64>],
+    mu: &[f64],
+    risk_aversion: f64,
+    constraint: WeightConstraint,
+) -> Result<Vec<f64>, OptimizeError> {
+    let n = square_dim(cov)?;
+    if mu.len() != n {
+        return Err(OptimizeError::DimensionMismatch);
+    }
+    if mu.iter().any(|x| !x.is_finite()) {
+        return Err(OptimizeError::InvalidCovariance);
+    }
+    if !risk_aversion.is_finite() || risk_aversion <= 0.0 {
+        return Err(OptimizeError::InvalidRiskAversion);
+    }
+
+    match constraint {
+        WeightConstraint::Unconstrained => {
+            let chol = cholesky(cov).ok_or(OptimizeError::SingularCovariance)?;
+            let inv_ones = cholesky_solve(&chol, &vec![1.0; n]);
+            let inv_mu = cholesky_solve(&chol, mu);
+            let ones_inv_ones: f64 = inv_ones.iter().sum();
+            let ones_inv_mu: f64 = inv_mu.iter().sum();
+            let scale = (1.0 - ones_inv_mu / risk_aversion) / ones_inv_ones;
+            Ok(inv_mu
+                .iter()
+                .zip(&inv_ones)
+                .map(|(m, o)| m / risk_aversion + scale * o)
+                .collect())
+        }
+        WeightConstraint::LongOnly => {
+            let lipschitz = risk_aversion
+                * cov
+                    .iter()
+                    .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+                    .fold(0.0, f64::max);
+            if lipschitz <= 0.0 || !lipschitz.is_finite() {
+                return Err(OptimizeError::SingularCovariance);
+            }
+            let step = 1.0 / lipschitz;
+
+            let mut w = equal_weights(n);
+            for _ in 0..LONG_ONLY_MAX_ITERS {
+                let sigma_w = mat_vec_mul(cov, &w);
+                let mut candidate: Vec<f64> = w
+                    .iter()
+                    .zip(sigma_w.iter().zip(mu))
+                    .map(|(wi, (sw, m))| wi - step * (risk_aversion * sw - m))
+                    .collect();
+                // Projection onto the simplex is invariant to adding a
+                // constant, so shift to guarantee a positive component.
+                let max = candidate.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                for x in &mut candidate {
+                    *x += 1.0 - max;
+                }
+                let next = project_simplex(&candidate)?;
+                let done = squared_distance(&next, &w) < 1e-20;
+                w = next;
+                if done {
+                    break;
+                }
+            }
+            Ok(w)
+        }
+    }
+}
+
+/// Equal-risk-contribution weights plus convergence diagnostics, returned
+/// by [`risk_parity_weights`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RiskParityResult {
+    /// Long-only weights summing to 1.
+    pub weights: Vec<f64>,
+    /// Achieved dispersion: `maxᵢ |RCᵢ − 1/n|`, where
+    /// `RCᵢ = wᵢ(Σw)ᵢ / wᵀΣw` is asset `i`'s fraction of portfolio variance.
+    pub dispersion: f64,
+    /// Coordinate-descent sweeps performed.
+    pub iters: usize,
+    /// True if `dispersion` fell below the tolerance before the cap.
+    pub converged: bool,
+}
+
+/// Dispersion tolerance for [`risk_parity_weights`].
+const RISK_PARITY_TOL: f64 = 1e-10;
+
+/// Sweep cap for [`risk_parity_weights`].
+const RISK_PARITY_MAX_ITERS: usize = 10_000;
+
+/// Risk-parity (equal risk contribution) weights for a covariance matrix.
+///
+/// Solves `min ½·yᵀΣy − (1/n)·Σ ln yᵢ` by cyclical coordinate descent
+/// (Griveau-Billion, Richard & Roncalli 2013), where each coordinate has
+/// the closed-form update `yᵢ = (−cᵢ + √(cᵢ² + 4σᵢᵢ/n)) / (2σᵢᵢ)` with
+/// `cᵢ = Σⱼ≠ᵢ σᵢⱼyⱼ`. The weights are `y / Σy`.
+///
+/// Convergence: after each full sweep the risk-contribution dispersion is
+/// measured, and the solver stops once it is below `1e-10`. It gives up
+/// after 10,000 sweeps, returning the last iterate with
+/// `converged = false`. Unlike [`optimize_risk_parity`], this takes a
+/// covariance matrix directly.
+///
+/// # Errors
+///
+/// - [`OptimizeError::InvalidCovariance`] if `cov` is empty, non-square,
+///   or non-finite.
+/// - [`OptimizeError::SingularCovariance`] if any asset has non-positive
+///   variance, which makes its equal-risk weight unbounded.
+pub fn risk_parity_weights(cov: &[Vec<f64>]) -> Result<RiskParityResult, OptimizeError> {
+    let n = square_dim(cov)?;
+    if (0..n).any(|i| cov[i][i] <= 0.0) {
+        return Err(OptimizeError::SingularCovariance);
+    }
+
+    let budget = 1.0 / n as f64;
+    let mut y: Vec<f64> = (0..n).map(|i| 1.0 / cov[i][i].sqrt()).collect();
+    let mut dispersion = f64::INFINITY;
+    let mut iters = 0;
+    while iters < RISK_PARITY_MAX_ITERS {
+        iters += 1;
+        for i in 0..n {
+            let c = dot(&cov[i], &y) - cov[i][i] * y[i];
+            let var = cov[i][i];
+            y[i] = (-c + (c * c + 4.0 * var * budget).sqrt()) / (2.0 * var);
+        }
+        dispersion = risk_contribution_dispersion(cov, &y);
+        if dispersion < RISK_PARITY_TOL {
+            break;
+        }
+    }
+
+    let total: f64 = y.iter().sum();
+    Ok(RiskParityResult {
+        weights: y.iter().map(|v| v / total).collect(),
+        dispersion,
+        iters,
+        converged: dispersion < RISK_PARITY_TOL,
+    })
+}
+
+/// `maxᵢ |RCᵢ − 1/n|` for risk-contribution fractions `RCᵢ`. Scale-free,
+/// so unnormalized weights are fine.
+fn risk_contribution_dispersion(cov: &[Vec<f64>], w: &[f64]) -> f64 {
+    let sigma_w = mat_vec_mul(cov, w);
+    let var = dot(w, &sigma_w);
+    if var <= 0.0 || !var.is_finite() {
+        return f64::INFINITY;
+    }
+    let target = 1.0 / w.len() as f64;
+    w.iter()
+        .zip(&sigma_w)
+        .map(|(wi, sw)| (wi * sw / var - target).abs())
+        .fold(0.0, f64::max)
+}
+
+/// Dimension of a non-empty, square, finite matrix.
+fn square_dim(matrix: &[Vec<f64>]) -> Result<usize, OptimizeError> {
+    let n = matrix.len();
+    if n == 0
+        || matrix
+            .iter()
+            .any(|row| row.len() != n || row.iter().any(|x| !x.is_finite()))
+    {
+        return Err(OptimizeError::InvalidCovariance);
+    }
+    Ok(n)
+}
+
+/// Lower-triangular Cholesky factor, or `None` if the matrix is not
+/// numerically positive definite (a pivot below `1e-12` of the largest
+/// diagonal entry).
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let max_diag = (0..n).map(|i| matrix[i][i]).fold(0.0, f64::max);
+    let floor = 1e-12 * max_diag;
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = matrix[i][j] - dot(&l[i][..j], &l[j][..j]);
+            if i == j {
+                if sum <= floor || sum <= 0.0 {
+                    return None;
+                }
+                l[i][i] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solve `L Lᵀ x = b` by forward then back substitution.
+fn cholesky_solve(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = l.len();
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        y[i] = (b[i] - dot(&l[i][..i], &y[..i])) / l[i][i];
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let tail: f64 = (i + 1..n).map(|k| l[k][i] * x[k]).sum();
+        x[i] = (y[i] - tail) / l[i][i];
+    }
+    x
+}
+
 /// Convert a correlation matrix into López de Prado's clustering distance.
 ///
 /// Uses `d[i][j] = sqrt(0.5 * (1 - corr[i][j]))`. Invalid or non-square input
@@ -1042,6 +1553,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop)]
     fn correlation_matrix_is_symmetric_with_unit_diagonal() {
         let r = sample_returns();
         let corr = correlation_matrix(&r);
@@ -1402,4 +1914,241 @@ mod tests {
         assert_close(&cvar, &[0.1875, 0.3750, 0.1875, 0.2500], 1e-15);
         assert_close(&cdar, &[0.1875, 0.3750, 0.1875, 0.2500], 1e-12);
     }
+
+    #[test]
+    fn rolling_corr_matches_full_window_correlation() {
+        let rows = qtrade_reference_returns();
+        let cols = columns(&rows);
+        let series: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+        let window = 5;
+
+        let rolling = rolling_corr_matrix(&series, window);
+        assert_eq!(rolling.len(), rows.len() - window + 1);
+        for (k, matrix) in rolling.iter().enumerate() {
+            let expected = correlation_matrix(&rows[k..k + window]);
+            for (row, exp) in matrix.iter().zip(&expected) {
+                assert_close(row, exp, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn correlation_clusters_chain_by_single_linkage() {
+        let t: Vec<f64> = (0..12).map(|i| (i as f64 * 0.9).sin()).collect();
+        let noise: Vec<f64> = (0..12).map(|i| (i as f64 * 2.3).cos()).collect();
+        let mix = |w: f64| -> Vec<f64> { t.iter().zip(&noise).map(|(a, b)| a + w * b).collect() };
+        // x0 and x2 are too far apart to link directly but chain through x1.
+        let (x0, x1, x2) = (mix(0.0), mix(0.4), mix(0.8));
+        let other: Vec<f64> = noise.iter().map(|b| -b).collect();
+        let series: Vec<&[f64]> = vec![&x2, &other, &x0, &x1];
+
+        let corr = correlation_matrix(
+            &(0..12)
+                .map(|k| vec![x0[k], x1[k], x2[k]])
+                .collect::<Vec<_>>(),
+        );
+        let threshold = corr[0][1].min(corr[1][2]) - 1e-9;
+        assert!(corr[0][2] < threshold);
+
+        let clusters = correlation_clusters(&series, threshold);
+        assert_eq!(clusters, vec![vec![0, 2, 3], vec![1]]);
+        assert_eq!(correlation_clusters(&series, -1.0), vec![vec![0, 1, 2, 3]]);
+        assert_eq!(
+            correlation_clusters(&series, 1.1),
+            vec![vec![0], vec![1], vec![2], vec![3]]
+        );
+
+        assert!(correlation_clusters(&[], 0.5).is_empty());
+        assert!(correlation_clusters(&[&x0, &x1[..5]], 0.5).is_empty());
+        assert!(correlation_clusters(&series, f64::NAN).is_empty());
+    }
+
+    /// Reference values from scikit-learn's `LedoitWolf` formula
+    /// (shrinkage ≈ 0.1483 on this sample).
+    #[test]
+    fn cov_shrinkage_matches_reference() {
+        let cols = columns(&qtrade_reference_returns());
+        let series: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+        let shrunk = cov_shrinkage(&series);
+        let expected = [
+            [
+                1.659326243662138e-05,
+                -4.223106203686676e-06,
+                -4.3295710659644906e-06,
+                1.0167394347531363e-05,
+            ],
+            [
+                -4.223106203686676e-06,
+                6.331232655954182e-06,
+                -3.3122401597542544e-07,
+                -2.5906449820935062e-06,
+            ],
+            [
+                -4.3295710659644906e-06,
+                -3.3122401597542544e-07,
+                5.1246308834722754e-06,
+                -3.903711616853228e-06,
+            ],
+            [
+                1.0167394347531363e-05,
+                -2.5906449820935062e-06,
+                -3.903711616853228e-06,
+                9.35365180172994e-06,
+            ],
+        ];
+        assert_eq!(shrunk.len(), 4);
+        for (row, exp) in shrunk.iter().zip(&expected) {
+            assert_close(row, exp, 1e-18);
+        }
+    }
+
+    #[test]
+    fn cov_shrinkage_regularizes_singular_sample() {
+        // Two perfectly correlated assets: the sample covariance is singular.
+        let a = [0.01, -0.02, 0.015, 0.005];
+        let b = [0.02, -0.04, 0.03, 0.01];
+        let cov = cov_shrinkage(&[&a, &b]);
+        let det = cov[0][0] * cov[1][1] - cov[0][1] * cov[1][0];
+        assert!(det > 0.0, "det={det}");
+        assert!(cov[0][1] > 0.0);
+
+        assert!(cov_shrinkage(&[]).is_empty());
+        assert!(cov_shrinkage(&[&a[..1]]).is_empty());
+        assert!(cov_shrinkage(&[&a, &b[..3]]).is_empty());
+    }
+
+    #[test]
+    fn min_variance_closed_form() {
+        let cov = vec![vec![1.0, 0.0], vec![0.0, 4.0]];
+        for constraint in [WeightConstraint::Unconstrained, WeightConstraint::LongOnly] {
+            let w = min_variance_weights(&cov, constraint).unwrap();
+            assert_close(&w, &[0.8, 0.2], 1e-9);
+        }
+    }
+
+    #[test]
+    fn long_only_matches_unconstrained_when_interior() {
+        let cols = columns(&qtrade_reference_returns());
+        let series: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+        let cov = cov_shrinkage(&series);
+        let free = min_variance_weights(&cov, WeightConstraint::Unconstrained).unwrap();
+        assert!((free.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(free.iter().all(|w| *w > 0.0));
+        let long = min_variance_weights(&cov, WeightConstraint::LongOnly).unwrap();
+        assert_close(&long, &free, 1e-6);
+    }
+
+    #[test]
+    fn unconstrained_shorts_where_long_only_cannot() {
+        // Asset 1 is a noisier copy of asset 0: hedging it out lowers risk.
+        let cov = vec![vec![1.0, 0.9], vec![0.9, 1.0]];
+        let mu = [0.50, 0.0];
+        let free = mean_variance_weights(&cov, &mu, 2.0, WeightConstraint::Unconstrained).unwrap();
+        assert_close(&free, &[1.75, -0.75], 1e-12);
+        // Check the first-order conditions: λΣw − μ is the same in every
+        // coordinate (the budget multiplier).
+        let grad: Vec<f64> = mat_vec_mul(&cov, &free)
+            .iter()
+            .zip(&mu)
+            .map(|(sw, m)| 2.0 * sw - m)
+            .collect();
+        assert!((grad[0] - grad[1]).abs() < 1e-12);
+
+        let long = mean_variance_weights(&cov, &mu, 2.0, WeightConstraint::LongOnly).unwrap();
+        assert_valid_weights(&long, 2);
+        assert_close(&long, &[1.0, 0.0], 1e-9);
+    }
+
+    #[test]
+    fn singular_covariance_is_reported() {
+        let cov = vec![vec![1.0, 1.0], vec![1.0, 1.0]];
+        assert_eq!(
+            min_variance_weights(&cov, WeightConstraint::Unconstrained),
+            Err(OptimizeError::SingularCovariance)
+        );
+        let w = min_variance_weights(&cov, WeightConstraint::LongOnly).unwrap();
+        assert_valid_weights(&w, 2);
+
+        let zero = vec![vec![0.0, 0.0], vec![0.0, 0.0]];
+        assert_eq!(
+            min_variance_weights(&zero, WeightConstraint::LongOnly),
+            Err(OptimizeError::SingularCovariance)
+        );
+    }
+
+    #[test]
+    fn mean_variance_rejects_bad_input() {
+        let cov = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let lo = WeightConstraint::LongOnly;
+        assert_eq!(
+            mean_variance_weights(&cov, &[0.1], 1.0, lo),
+            Err(OptimizeError::DimensionMismatch)
+        );
+        assert_eq!(
+            mean_variance_weights(&cov, &[0.1, 0.2], 0.0, lo),
+            Err(OptimizeError::InvalidRiskAversion)
+        );
+        assert_eq!(
+            min_variance_weights(&[vec![1.0, 0.0]], lo),
+            Err(OptimizeError::InvalidCovariance)
+        );
+        assert_eq!(
+            min_variance_weights(&[], lo),
+            Err(OptimizeError::InvalidCovariance)
+        );
+    }
+
+    #[test]
+    fn risk_parity_diagonal_is_inverse_volatility() {
+        let cov = vec![vec![1.0, 0.0], vec![0.0, 4.0]];
+        let rp = risk_parity_weights(&cov).unwrap();
+        assert!(rp.converged);
+        assert_close(&rp.weights, &[2.0 / 3.0, 1.0 / 3.0], 1e-9);
+    }
+
+    #[test]
+    fn risk_parity_equalizes_contributions() {
+        let cols = columns(&qtrade_reference_returns());
+        let series: Vec<&[f64]> = cols.iter().map(Vec::as_slice).collect();
+        let cov = cov_shrinkage(&series);
+        let rp = risk_parity_weights(&cov).unwrap();
+        assert!(rp.converged, "dispersion={}", rp.dispersion);
+        assert!(rp.dispersion < 1e-10);
+        assert!(rp.iters < RISK_PARITY_MAX_ITERS);
+        assert_valid_weights(&rp.weights, 4);
+
+        let sigma_w = mat_vec_mul(&cov, &rp.weights);
+        let var = dot(&rp.weights, &sigma_w);
+        for (w, sw) in rp.weights.iter().zip(&sigma_w) {
+            assert!((w * sw / var - 0.25).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn risk_parity_rejects_zero_variance() {
+        let cov = vec![vec![1.0, 0.0], vec![0.0, 0.0]];
+        assert_eq!(
+            risk_parity_weights(&cov),
+            Err(OptimizeError::SingularCovariance)
+        );
+        assert_eq!(
+            risk_parity_weights(&[]),
+            Err(OptimizeError::InvalidCovariance)
+        );
+    }
+
+    #[test]
+    fn rolling_corr_rejects_invalid_input() {
+        let a = [0.01, 0.02, -0.01];
+        let b = [0.02, 0.01];
+        assert!(rolling_corr_matrix(&[], 2).is_empty());
+        assert!(rolling_corr_matrix(&[&a, &b], 2).is_empty());
+        assert!(rolling_corr_matrix(&[&a], 1).is_empty());
+        assert!(rolling_corr_matrix(&[&a], 4).is_empty());
+        assert!(rolling_corr_matrix(&[&a, &[0.0, f64::NAN, 0.0]], 2).is_empty());
+        // A flat series correlates 0 with everything but itself.
+        let flat = [0.01; 3];
+        let m = rolling_corr_matrix(&[&a, &flat], 3);
+        assert_eq!(m, vec![vec![vec![1.0, 0.0], vec![0.0, 1.0]]]);
+    }
 }
@@ -91,8 +91,30 @@ pub struct Order {
     pub status: OrderStatus,
     /// Owner for self-trade prevention; `None` opts out of STP.
     pub owner: Option<OrderOwner>,
-    /// Position index within the price level queue (for O(1) cancel)
-    pub(crate) position_in_level: usize,
+    /// Arrival sequence number, assigned by the book when the order rests.
+    ///
+    /// Strictly increasing per book, so it breaks ties between orders with
+    /// equal timestamps under [`TieBreak::Sequence`](crate::TieBreak).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sequence: u64,
+    /// Size the order is ranked by under
+    /// [`LevelPriority::SizeTime`](crate::LevelPriority), set by the book
+    /// when the order rests.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queue_size: Quantity,
+    /// Hidden (non-displayed) order: it matches like any resting order but
+    /// is left out of best prices and depth snapshots, and at its price it
+    /// fills only after the displayed orders.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hidden: bool,
+    /// Logical clock value when the order took its current queue priority,
+    /// set by the book when the order rests (0 if the clock was unset).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queued_at: Timestamp,
+    /// Position in the price level queue when the order was queued (for
+    /// O(1) cancel while it is still current)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) position_in_level: u64,
 }
 
 impl Order {
@@ -119,6 +141,10 @@ impl Order {
             time_in_force,
             status: OrderStatus::New,
             owner: None,
+            sequence: 0,
+            queue_size: 0,
+            hidden: false,
+            queued_at: 0,
             position_in_level: 0,
         }
     }
@@ -133,11 +159,19 @@ impl Order {
         self
     }
 
+    /// Mark the order as hidden (non-displayed), consuming `self`.
+    #[inline]
+    pub fn with_hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
     /// Decrement `remaining_quantity` without recording a fill.
     ///
     /// Used by the matching engine when self-trade prevention causes a
     /// portion of the order to be cancelled without a trade being
-    /// generated (see [`crate::matching::StpPolicy::DecrementAndCancel`]).
+    /// generated (see [`crate::matching::StpPolicy::DecrementAndCancel`]),
+    /// and by in-place size reductions that keep queue priority.
     /// Sets status to `Cancelled` when the remainder reaches zero.
     ///
     /// # Panics
@@ -1,19 +1,35 @@
 //! Transaction cost modeling.
 
+use crate::Liquidity;
+
 /// Models transaction costs for portfolio rebalancing.
 ///
 /// Costs are computed as a percentage of notional value (in basis points)
-/// plus a minimum per-trade fee.
+/// plus an optional fixed fee per share, floored at a minimum and capped at
+/// a maximum per-trade fee.
+///
+/// Notionals and fixed fees are integers in price units: cents at the
+/// default scale. For a portfolio with another
+/// [`price_scale`](super::Portfolio::with_price_scale), express the fees
+/// in that scale, e.g. with [`rescaled`](Self::rescaled).
 ///
-/// ```ignore
+/// The struct is `#[non_exhaustive]`: start from [`zero`](Self::zero) and
+/// set fees with the `with_*` builders, so new fee kinds do not break
+/// existing callers.
+///
+/// ```
 /// use nanobook::portfolio::CostModel;
 ///
-/// let model = CostModel { commission_bps: 10, slippage_bps: 5, min_trade_fee: 1_00 };
-/// // 15 bps on $10,000 notional = $1.50, but min fee is $1.00, so result = $1.50
-/// assert_eq!(model.compute_cost(1_000_000), 1500);
+/// let model = CostModel::zero()
+///     .with_commission_bps(10)
+///     .with_slippage_bps(5)
+///     .with_min_trade_fee(1_00);
+/// // 15 bps on $10,000 notional = $15.00, above the $1.00 minimum
+/// assert_eq!(model.compute_cost(1_000_000), 15_00);
 /// ```
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct CostModel {
     /// Commission in basis points (1 bps = 0.01%)
     pub commission_bps: u32,
@@ -21,6 +37,28 @@ pub struct CostModel {
     pub slippage_bps: u32,
     /// Minimum fee per trade (cents)
     pub min_trade_fee: i64,
+    /// Maximum fee per trade (cents); `i64::MAX` for no cap
+    #[cfg_attr(feature = "serde", serde(default = "no_fee_cap"))]
+    pub max_trade_fee: i64,
+    /// Extra fee in basis points for passive (maker) fills. Negative values
+    /// are a rebate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub maker_bps: i32,
+    /// Extra fee in basis points for aggressive (taker) fills
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub taker_bps: u32,
+    /// Commission per share traded (cents), added to the bps cost. Only
+    /// the quantity-aware methods such as
+    /// [`compute_cost_with_quantity`](Self::compute_cost_with_quantity)
+    /// can charge it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub per_share_fee: i64,
+    /// Charge half the quoted bid-ask spread per share on
+    /// [`Portfolio::rebalance_lob`](super::Portfolio::rebalance_lob) taker
+    /// fills, in place of `slippage_bps`. See
+    /// [`compute_spread_cost`](Self::compute_spread_cost).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub half_spread: bool,
 }
 
 impl CostModel {
@@ -30,23 +68,210 @@ impl CostModel {
             commission_bps: 0,
             slippage_bps: 0,
             min_trade_fee: 0,
+            max_trade_fee: i64::MAX,
+            maker_bps: 0,
+            taker_bps: 0,
+            per_share_fee: 0,
+            half_spread: false,
         }
     }
 
-    /// Compute the total cost for a trade with the given absolute notional value (cents).
+    /// Set the commission in basis points, consuming `self`.
+    pub fn with_commission_bps(mut self, bps: u32) -> Self {
+        self.commission_bps = bps;
+        self
+    }
+
+    /// Set the slippage estimate in basis points, consuming `self`.
+    pub fn with_slippage_bps(mut self, bps: u32) -> Self {
+        self.slippage_bps = bps;
+        self
+    }
+
+    /// Set the minimum fee per trade (price units), consuming `self`.
+    pub fn with_min_trade_fee(mut self, fee: i64) -> Self {
+        self.min_trade_fee = fee;
+        self
+    }
+
+    /// Set the maximum fee per trade (price units), consuming `self`.
+    /// `i64::MAX` means no cap.
+    pub fn with_max_trade_fee(mut self, fee: i64) -> Self {
+        self.max_trade_fee = fee;
+        self
+    }
+
+    /// Set the extra maker fee in basis points, consuming `self`. Negative
+    /// values are a rebate.
+    pub fn with_maker_bps(mut self, bps: i32) -> Self {
+        self.maker_bps = bps;
+        self
+    }
+
+    /// Set the extra taker fee in basis points, consuming `self`.
+    pub fn with_taker_bps(mut self, bps: u32) -> Self {
+        self.taker_bps = bps;
+        self
+    }
+
+    /// Set the commission per share traded (price units), consuming `self`.
+    pub fn with_per_share_fee(mut self, fee: i64) -> Self {
+        self.per_share_fee = fee;
+        self
+    }
+
+    /// Charge half the quoted spread on
+    /// [`Portfolio::rebalance_lob`](super::Portfolio::rebalance_lob) taker
+    /// fills in place of `slippage_bps`, consuming `self`.
+    pub fn with_half_spread(mut self, half_spread: bool) -> Self {
+        self.half_spread = half_spread;
+        self
+    }
+
+    /// Convert the fixed fees from `from_scale` to `to_scale` price units
+    /// per currency unit, rounding to the nearest unit.
+    ///
+    /// `min_trade_fee`, `max_trade_fee` (unless uncapped), and
+    /// `per_share_fee` are converted; basis-point rates are scale-free.
     ///
-    /// The notional should be `|quantity * price|`. Returns the cost in cents,
-    /// which is always non-negative.
+    /// ```
+    /// use nanobook::portfolio::CostModel;
+    ///
+    /// let cents = CostModel::zero().with_min_trade_fee(1_00);
+    /// let model = cents.rescaled(100, 10_000);
+    /// assert_eq!(model.min_trade_fee, 1_0000);
+    /// assert_eq!(model.max_trade_fee, i64::MAX);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if either scale is not positive.
+    pub fn rescaled(self, from_scale: i64, to_scale: i64) -> Self {
+        assert!(
+            from_scale > 0 && to_scale > 0,
+            "price scales must be positive"
+        );
+        let convert = |fee: i64| {
+            let scaled = (fee as i128 * to_scale as i128 * 2 + from_scale as i128)
+                .div_euclid(from_scale as i128 * 2);
+            i64::try_from(scaled).unwrap_or(if fee < 0 { i64::MIN } else { i64::MAX })
+        };
+        Self {
+            min_trade_fee: convert(self.min_trade_fee),
+            max_trade_fee: if self.max_trade_fee == i64::MAX {
+                i64::MAX
+            } else {
+                convert(self.max_trade_fee)
+            },
+            per_share_fee: convert(self.per_share_fee),
+            ..self
+        }
+    }
+
+    /// Compute the total cost for a trade with the given absolute notional
+    /// value (price units, e.g. cents).
+    ///
+    /// The notional should be `|quantity * price|`. Returns the cost in the
+    /// same units,
+    /// which is always non-negative: commission plus slippage, raised to
+    /// `min_trade_fee` and then capped at `max_trade_fee`.
     pub fn compute_cost(&self, notional: i64) -> i64 {
+        self.compute_cost_with_quantity(notional, 0)
+    }
+
+    /// Like [`compute_cost`](Self::compute_cost), plus `per_share_fee` for
+    /// each of the `|quantity|` shares traded, before the minimum and
+    /// maximum fees apply.
+    pub fn compute_cost_with_quantity(&self, notional: i64, quantity: i64) -> i64 {
+        self.bps_cost(
+            notional,
+            self.commission_bps as u128 + self.slippage_bps as u128,
+            quantity,
+        )
+    }
+
+    /// Compute the cost of a fill that added (maker) or removed (taker)
+    /// liquidity.
+    ///
+    /// Taker fills pay `commission_bps + slippage_bps + taker_bps`, so with
+    /// `taker_bps == 0` this equals [`compute_cost`](Self::compute_cost).
+    /// Maker fills rest at their own limit price and pay no slippage:
+    /// `commission_bps + maker_bps`. The minimum and maximum fees apply to
+    /// both.
+    ///
+    /// If a negative `maker_bps` outweighs the commission, the maker fill
+    /// earns a rebate: the result is negative (cash is credited) and the
+    /// fee limits do not apply. Rebates round toward zero.
+    pub fn compute_fill_cost(&self, notional: i64, liquidity: Liquidity) -> i64 {
+        self.compute_fill_cost_with_quantity(notional, 0, liquidity)
+    }
+
+    /// Like [`compute_fill_cost`](Self::compute_fill_cost), plus
+    /// `per_share_fee` for each of the `|quantity|` shares traded. The
+    /// per-share fee also offsets a maker rebate.
+    pub fn compute_fill_cost_with_quantity(
+        &self,
+        notional: i64,
+        quantity: i64,
+        liquidity: Liquidity,
+    ) -> i64 {
+        let bps = match liquidity {
+            Liquidity::Maker => self.commission_bps as i128 + self.maker_bps as i128,
+            Liquidity::Taker => {
+                self.commission_bps as i128 + self.slippage_bps as i128 + self.taker_bps as i128
+            }
+        };
+        if bps < 0 {
+            let net =
+                notional.unsigned_abs() as i128 * bps / 10_000 + self.share_fee(quantity) as i128;
+            if net <= 0 {
+                return i64::try_from(net).unwrap_or(i64::MIN);
+            }
+            let net = i64::try_from(net).unwrap_or(i64::MAX);
+            return net.max(self.min_trade_fee).min(self.max_trade_fee);
+        }
+        self.bps_cost(notional, bps as u128, quantity)
+    }
+
+    /// Compute the cost of a taker fill that pays half the quoted
+    /// `spread` (cents) on each of its `|quantity|` shares.
+    ///
+    /// The half-spread stands in for `slippage_bps`, so the charge is
+    /// `commission_bps + taker_bps` on the notional, plus `per_share_fee`
+    /// and `|quantity| * spread / 2` (rounded down), with the minimum and
+    /// maximum fees applied to the total.
+    pub fn compute_spread_cost(&self, notional: i64, quantity: i64, spread: i64) -> i64 {
+        let half_spread = (quantity.unsigned_abs() as i64).saturating_mul(spread.max(0)) / 2;
+        let bps = self.commission_bps as u128 + self.taker_bps as u128;
+        let notional = notional.unsigned_abs() as u128;
+        i64::try_from(notional * bps / 10_000)
+            .unwrap_or(i64::MAX)
+            .saturating_add(self.share_fee(quantity))
+            .saturating_add(half_spread)
+            .max(self.min_trade_fee)
+            .min(self.max_trade_fee)
+    }
+
+    fn share_fee(&self, quantity: i64) -> i64 {
+        (quantity.unsigned_abs() as i64).saturating_mul(self.per_share_fee)
+    }
+
+    fn bps_cost(&self, notional: i64, total_bps: u128, quantity: i64) -> i64 {
         let notional = notional.unsigned_abs() as u128;
-        let total_bps = self.commission_bps as u128 + self.slippage_bps as u128;
         // notional * bps / 10_000 — use u128 to prevent overflow
         let raw = notional * total_bps / 10_000;
-        let bps_cost = i64::try_from(raw).unwrap_or(i64::MAX);
-        bps_cost.max(self.min_trade_fee)
+        let bps_cost = i64::try_from(raw)
+            .unwrap_or(i64::MAX)
+            .saturating_add(self.share_fee(quantity));
+        bps_cost.max(self.min_trade_fee).min(self.max_trade_fee)
     }
 }
 
+#[cfg(feature = "serde")]
+fn no_fee_cap() -> i64 {
+    i64::MAX
+}
+
 impl Default for CostModel {
     fn default() -> Self {
         Self::zero()
@@ -57,6 +282,23 @@ impl Default for CostModel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn rescaled_converts_fixed_fees_only() {
+        let model = CostModel {
+            commission_bps: 10,
+            min_trade_fee: 1_2345,
+            max_trade_fee: 50_0000,
+            per_share_fee: 5,
+            ..CostModel::zero()
+        };
+        let cents = model.rescaled(10_000, 100);
+        assert_eq!(cents.min_trade_fee, 1_23);
+        assert_eq!(cents.max_trade_fee, 50_00);
+        assert_eq!(cents.per_share_fee, 0);
+        assert_eq!(cents.commission_bps, 10);
+        assert_eq!(cents.rescaled(100, 10_000).min_trade_fee, 1_2300);
+    }
+
     #[test]
     fn zero_cost() {
         let model = CostModel::zero();
@@ -68,7 +310,7 @@ mod tests {
         let model = CostModel {
             commission_bps: 10,
             slippage_bps: 5,
-            min_trade_fee: 0,
+            ..CostModel::zero()
         };
         // 15 bps on 1_000_000 cents ($10,000) = 1500 cents ($15)
         assert_eq!(model.compute_cost(1_000_000), 1500);
@@ -78,8 +320,8 @@ mod tests {
     fn min_fee_applied() {
         let model = CostModel {
             commission_bps: 1,
-            slippage_bps: 0,
             min_trade_fee: 1_00, // $1 minimum
+            ..CostModel::zero()
         };
         // 1 bps on 10_000 cents ($100) = 1 cent, but min is $1.00
         assert_eq!(model.compute_cost(10_000), 1_00);
@@ -89,8 +331,7 @@ mod tests {
     fn negative_notional_uses_abs() {
         let model = CostModel {
             commission_bps: 10,
-            slippage_bps: 0,
-            min_trade_fee: 0,
+            ..CostModel::zero()
         };
         assert_eq!(
             model.compute_cost(-1_000_000),
@@ -104,4 +345,127 @@ mod tests {
         assert!(model.compute_cost(0) >= 0);
         assert!(model.compute_cost(-100) >= 0);
     }
+
+    #[test]
+    fn maker_and_taker_rates() {
+        let model = CostModel {
+            commission_bps: 1,
+            slippage_bps: 5,
+            maker_bps: 2,
+            taker_bps: 4,
+            ..CostModel::zero()
+        };
+        // Maker: 1 + 2 bps; taker: 1 + 5 + 4 bps
+        assert_eq!(model.compute_fill_cost(1_000_000, Liquidity::Maker), 300);
+        assert_eq!(model.compute_fill_cost(1_000_000, Liquidity::Taker), 1000);
+
+        let flat = CostModel {
+            taker_bps: 0,
+            per_share_fee: 0,
+            half_spread: false,
+            ..model
+        };
+        assert_eq!(
+            flat.compute_fill_cost(1_000_000, Liquidity::Taker),
+            flat.compute_cost(1_000_000)
+        );
+    }
+
+    #[test]
+    fn maker_rebate_is_negative_cost() {
+        let model = CostModel {
+            commission_bps: 1,
+            slippage_bps: 5,
+            min_trade_fee: 1_00,
+            maker_bps: -3,
+            taker_bps: 2,
+            ..CostModel::zero()
+        };
+        // Maker: 1 - 3 = -2 bps on $10,000 = $2 rebate, no minimum fee
+        assert_eq!(model.compute_fill_cost(1_000_000, Liquidity::Maker), -200);
+        assert_eq!(model.compute_fill_cost(-1_000_000, Liquidity::Maker), -200);
+        // Taker still pays 1 + 5 + 2 bps
+        assert_eq!(model.compute_fill_cost(1_000_000, Liquidity::Taker), 800);
+
+        // A rebate that only cancels the commission charges the minimum fee
+        let even = CostModel {
+            maker_bps: -1,
+            ..model
+        };
+        assert_eq!(even.compute_fill_cost(1_000_000, Liquidity::Maker), 1_00);
+    }
+
+    #[test]
+    fn per_share_fee_adds_to_bps() {
+        let model = CostModel {
+            commission_bps: 10,
+            per_share_fee: 1,
+            min_trade_fee: 2_00,
+            max_trade_fee: 20_00,
+            ..CostModel::zero()
+        };
+        // 10 bps on $10,000 = $10, plus 100 shares × 1¢
+        assert_eq!(model.compute_cost_with_quantity(1_000_000, -100), 11_00);
+        // The notional-only form ignores the per-share fee
+        assert_eq!(model.compute_cost(1_000_000), 10_00);
+        // Min and max apply to the combined fee
+        assert_eq!(model.compute_cost_with_quantity(1_000, 10), 2_00);
+        assert_eq!(model.compute_cost_with_quantity(1_000_000, 5_000), 20_00);
+        assert_eq!(
+            model.compute_fill_cost_with_quantity(1_000_000, 100, Liquidity::Taker),
+            11_00
+        );
+
+        // A per-share fee offsets a maker rebate: -2 bps on $10,000 = -$2
+        let rebate = CostModel {
+            maker_bps: -12,
+            min_trade_fee: 0,
+            ..model
+        };
+        let maker = |qty| rebate.compute_fill_cost_with_quantity(1_000_000, qty, Liquidity::Maker);
+        assert_eq!(maker(0), -2_00);
+        assert_eq!(maker(50), -1_50);
+        assert_eq!(maker(300), 1_00);
+    }
+
+    #[test]
+    fn spread_cost_replaces_slippage() {
+        let model = CostModel {
+            commission_bps: 10,
+            slippage_bps: 50,
+            taker_bps: 2,
+            per_share_fee: 1,
+            ..CostModel::zero()
+        };
+        // 12 bps on $10,000 = $12, plus 100 × 1¢, plus 100 × 5¢ half-spread
+        assert_eq!(model.compute_spread_cost(1_000_000, -100, 10), 18_00);
+        // Odd spreads round the half-spread charge down
+        assert_eq!(model.compute_spread_cost(0, 3, 1), 3 + 1);
+        let capped = CostModel {
+            max_trade_fee: 5_00,
+            ..model
+        };
+        assert_eq!(capped.compute_spread_cost(1_000_000, 100, 10), 5_00);
+    }
+
+    #[test]
+    fn max_fee_caps_cost() {
+        let model = CostModel {
+            commission_bps: 10,
+            slippage_bps: 5,
+            min_trade_fee: 1_00,
+            max_trade_fee: 50_00,
+            ..CostModel::zero()
+        };
+        // 15 bps on $1M = $1,500, capped at $50
+        assert_eq!(model.compute_cost(100_000_000), 50_00);
+        assert_eq!(
+            model.compute_fill_cost(100_000_000, Liquidity::Taker),
+            50_00
+        );
+        // Small trades still pay the minimum
+        assert_eq!(model.compute_cost(10_000), 1_00);
+        // Between the limits the bps cost is unchanged
+        assert_eq!(model.compute_cost(1_000_000), 15_00);
+    }
 }
@@ -0,0 +1,125 @@
+//! Rolling buffer of recent bar prices for lookback strategies.
+
+use std::collections::VecDeque;
+
+use crate::types::Symbol;
+
+/// Ring buffer of the most recent bars, newest last.
+///
+/// [`run_backtest`](super::run_backtest) keeps one of these with room for
+/// [`Strategy::history_len`](super::Strategy::history_len) bars and passes
+/// it to [`Strategy::compute_weights_with_history`](super::Strategy::compute_weights_with_history),
+/// so strategies don't each maintain their own lookback window.
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::PriceHistory;
+///
+/// let aapl = Symbol::new("AAPL");
+/// let mut history = PriceHistory::new(2);
+/// history.push(vec![(aapl, 100_00)]);
+/// history.push(vec![(aapl, 101_00)]);
+/// history.push(vec![(aapl, 103_00)]);
+///
+/// assert_eq!(history.len(), 2);
+/// assert_eq!(history.price(&aapl, 0), Some(103_00));
+/// assert_eq!(history.price(&aapl, 1), Some(101_00));
+/// assert_eq!(history.series(&aapl), vec![101_00, 103_00]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PriceHistory {
+    capacity: usize,
+    bars: VecDeque<Vec<(Symbol, i64)>>,
+}
+
+impl PriceHistory {
+    /// Create an empty buffer holding at most `capacity` bars.
+    ///
+    /// A capacity of 0 is treated as 1, so the current bar is always kept.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            bars: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Append a bar, evicting the oldest one when full.
+    pub fn push(&mut self, bar: Vec<(Symbol, i64)>) {
+        if self.bars.len() == self.capacity {
+            self.bars.pop_front();
+        }
+        self.bars.push_back(bar);
+    }
+
+    /// Maximum number of bars retained.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of bars currently held.
+    pub fn len(&self) -> usize {
+        self.bars.len()
+    }
+
+    /// Whether no bar has been pushed yet.
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// The bar `lag` steps back (0 = most recent), if still buffered.
+    pub fn bar(&self, lag: usize) -> Option<&[(Symbol, i64)]> {
+        let idx = self.bars.len().checked_sub(lag + 1)?;
+        Some(&self.bars[idx])
+    }
+
+    /// Price of `symbol` `lag` bars back (0 = most recent), if that bar is
+    /// buffered and quotes the symbol.
+    pub fn price(&self, symbol: &Symbol, lag: usize) -> Option<i64> {
+        self.bar(lag)?
+            .iter()
+            .find(|(s, _)| s == symbol)
+            .map(|&(_, p)| p)
+    }
+
+    /// Buffered prices of `symbol`, oldest first. Bars that don't quote the
+    /// symbol are skipped.
+    pub fn series(&self, symbol: &Symbol) -> Vec<i64> {
+        self.bars
+            .iter()
+            .filter_map(|bar| bar.iter().find(|(s, _)| s == symbol).map(|&(_, p)| p))
+            .collect()
+    }
+
+    /// Iterate over buffered bars, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &[(Symbol, i64)]> {
+        self.bars.iter().map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_and_skips_missing_symbols() {
+        let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+        let mut history = PriceHistory::new(3);
+        assert!(history.is_empty());
+        assert_eq!(history.bar(0), None);
+
+        history.push(vec![(a, 1)]);
+        history.push(vec![(a, 2), (b, 20)]);
+        history.push(vec![(a, 3)]);
+        history.push(vec![(a, 4), (b, 40)]);
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.series(&a), vec![2, 3, 4]);
+        assert_eq!(history.series(&b), vec![20, 40]);
+        assert_eq!(history.price(&b, 1), None);
+        assert_eq!(history.price(&a, 2), Some(2));
+        assert_eq!(history.price(&a, 3), None);
+        assert_eq!(history.iter().count(), 3);
+        assert_eq!(PriceHistory::new(0).capacity(), 1);
+    }
+}
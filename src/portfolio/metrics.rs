@@ -16,11 +16,14 @@ pub struct Metrics {
     pub volatility: f64,
     /// Annualized Sharpe ratio: (mean return - risk_free) / volatility
     pub sharpe: f64,
-    /// Annualized Sortino ratio: (mean return - risk_free) / downside_deviation
+    /// Annualized Sortino ratio: (mean return - MAR) / downside_deviation below
+    /// the minimum acceptable return (MAR, `risk_free` unless overridden in
+    /// [`MetricsOptions`])
     pub sortino: f64,
     /// Maximum drawdown (as positive fraction, e.g., 0.20 = 20% peak-to-trough)
     pub max_drawdown: f64,
-    /// Calmar ratio: CAGR / max_drawdown
+    /// Calmar ratio: CAGR / max_drawdown, over the trailing
+    /// [`MetricsOptions::calmar_lookback`] periods when set
     pub calmar: f64,
     /// Number of return periods
     pub num_periods: usize,
@@ -40,6 +43,16 @@ pub struct Metrics {
     pub payoff_ratio: f64,
     /// Kelly criterion: win_rate - (1 - win_rate) / payoff_ratio
     pub kelly: f64,
+    /// Mean of the positive returns (NaN when there are none)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub avg_win: f64,
+    /// Mean of the negative returns, itself negative (NaN when there are none)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub avg_loss: f64,
+    /// Expected return per period: win_rate * avg_win + loss_rate * avg_loss,
+    /// with an undefined side contributing zero
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub expectancy: f64,
 }
 
 impl std::fmt::Display for Metrics {
@@ -61,12 +74,32 @@ impl std::fmt::Display for Metrics {
         writeln!(f, "  Win rate:        {:>8.2}%", self.win_rate * 100.0)?;
         writeln!(f, "  Profit factor:   {:>8.2}", self.profit_factor)?;
         writeln!(f, "  Payoff ratio:    {:>8.2}", self.payoff_ratio)?;
-        write!(f, "  Kelly:           {:>8.2}%", self.kelly * 100.0)
+        writeln!(f, "  Kelly:           {:>8.2}%", self.kelly * 100.0)?;
+        writeln!(f, "  Avg win:         {:>8.2}%", self.avg_win * 100.0)?;
+        writeln!(f, "  Avg loss:        {:>8.2}%", self.avg_loss * 100.0)?;
+        write!(f, "  Expectancy:      {:>8.4}%", self.expectancy * 100.0)
     }
 }
 
+/// Optional settings for [`compute_metrics_ex`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MetricsOptions {
+    /// Minimum acceptable return per period for the Sortino ratio. The
+    /// ratio becomes `(mean - mar) / downside_dev`, where the downside
+    /// deviation squares only returns below `mar` and divides by the total
+    /// count. `None` (default) uses `risk_free`.
+    pub minimum_acceptable_return: Option<f64>,
+    /// Trailing window, in periods, for the Calmar ratio's CAGR and max
+    /// drawdown (conventionally 36 monthly periods). `None` (default) uses
+    /// the full sample, as does a series shorter than the lookback. Only
+    /// `calmar` is affected; `cagr` and `max_drawdown` stay full-sample.
+    pub calmar_lookback: Option<usize>,
+}
+
 /// Compute performance metrics from a series of periodic returns.
 ///
+/// Equivalent to [`compute_metrics_ex`] with [`MetricsOptions::default`].
+///
 /// # Arguments
 ///
 /// * `returns` — Slice of simple returns (e.g., `[0.01, -0.005, 0.02]`)
@@ -79,6 +112,71 @@ impl std::fmt::Display for Metrics {
 /// Sharpe/Sortino values from `periods_per_year.sqrt()` on
 /// non-positive or infinite inputs.
 pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -> Option<Metrics> {
+    compute_metrics_ex(
+        returns,
+        periods_per_year,
+        risk_free,
+        MetricsOptions::default(),
+    )
+}
+
+/// Compute [`compute_metrics`] for many return series at once.
+///
+/// Output `i` is exactly `compute_metrics(series[i], periods_per_year,
+/// risk_free)`. Series are independent, so they are computed in parallel
+/// under the `parallel` feature.
+///
+/// ```
+/// use nanobook::portfolio::metrics::{compute_metrics, compute_metrics_batch};
+///
+/// let a = [0.01, -0.005, 0.02];
+/// let b = [0.03, 0.01];
+/// let results = compute_metrics_batch(&[&a, &[], &b], 252.0, 0.0);
+/// let single = compute_metrics(&a, 252.0, 0.0).unwrap();
+/// assert_eq!(results[0].as_ref().unwrap().sharpe, single.sharpe);
+/// assert!(results[1].is_none());
+/// ```
+pub fn compute_metrics_batch(
+    series: &[&[f64]],
+    periods_per_year: f64,
+    risk_free: f64,
+) -> Vec<Option<Metrics>> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        series
+            .par_iter()
+            .map(|returns| compute_metrics(returns, periods_per_year, risk_free))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        series
+            .iter()
+            .map(|returns| compute_metrics(returns, periods_per_year, risk_free))
+            .collect()
+    }
+}
+
+/// Compute performance metrics with non-default [`MetricsOptions`].
+///
+/// ```
+/// use nanobook::portfolio::metrics::{MetricsOptions, compute_metrics_ex};
+///
+/// let returns = [0.02, -0.01, 0.015, 0.004, -0.003];
+/// let options = MetricsOptions {
+///     minimum_acceptable_return: Some(0.005),
+///     ..MetricsOptions::default()
+/// };
+/// let m = compute_metrics_ex(&returns, 252.0, 0.0, options).unwrap();
+/// assert!(m.sortino > 0.0);
+/// ```
+pub fn compute_metrics_ex(
+    returns: &[f64],
+    periods_per_year: f64,
+    risk_free: f64,
+    options: MetricsOptions,
+) -> Option<Metrics> {
     if returns.is_empty() {
         return None;
     }
@@ -91,15 +189,7 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
     // Total return: product of (1 + r_i) - 1
     let total_return = returns.iter().fold(1.0_f64, |acc, &r| acc * (1.0 + r)) - 1.0;
 
-    // CAGR: (1 + total_return)^(periods_per_year / n) - 1
-    let years = n as f64 / periods_per_year;
-    let cagr = if years > 0.0 && total_return > -1.0 {
-        (1.0 + total_return).powf(1.0 / years) - 1.0
-    } else if total_return <= -1.0 {
-        -1.0 // total or leveraged loss — clamp to -100%
-    } else {
-        0.0
-    };
+    let cagr = compute_cagr(total_return, n, periods_per_year);
 
     // Mean return
     let mean = returns.iter().sum::<f64>() / n as f64;
@@ -126,14 +216,27 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
     // `quantstats.stats.sortino` and the standard practitioner
     // convention. v0.9 used ddof=1 (Bessel correction); callers who
     // need that value can use `sortino(returns, rf, periods, 1)`.
-    let sortino = sortino(returns, risk_free, periods_per_year, 0);
+    let mar = options.minimum_acceptable_return.unwrap_or(risk_free);
+    let sortino = sortino(returns, mar, periods_per_year, 0);
 
     // Max drawdown
     let max_drawdown = compute_max_drawdown(returns);
 
-    // Calmar ratio
-    let calmar = if max_drawdown > 0.0 {
-        cagr / max_drawdown
+    // Calmar ratio, optionally over a trailing window. A series shorter
+    // than the lookback falls back to the full sample.
+    let (calmar_cagr, calmar_dd) = match options.calmar_lookback {
+        Some(lookback) if lookback > 0 && lookback < n => {
+            let window = &returns[n - lookback..];
+            let window_return = window.iter().fold(1.0_f64, |acc, &r| acc * (1.0 + r)) - 1.0;
+            (
+                compute_cagr(window_return, lookback, periods_per_year),
+                compute_max_drawdown(window),
+            )
+        }
+        _ => (cagr, max_drawdown),
+    };
+    let calmar = if calmar_dd > 0.0 {
+        calmar_cagr / calmar_dd
     } else {
         0.0
     };
@@ -185,6 +288,21 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
         0.0
     };
 
+    // Average win/loss, NaN when that side is empty. Expectancy weights
+    // each by its frequency; an empty side has zero weight.
+    let avg_win = if winning_periods > 0 {
+        mean_winning
+    } else {
+        f64::NAN
+    };
+    let avg_loss = if losing_periods > 0 {
+        mean_losing
+    } else {
+        f64::NAN
+    };
+    let loss_rate = losing_periods as f64 / n as f64;
+    let expectancy = win_rate * mean_winning + loss_rate * mean_losing;
+
     // Kelly criterion: w - (1 - w) / b
     let kelly = if payoff_ratio > 0.0 && payoff_ratio.is_finite() {
         win_rate - (1.0 - win_rate) / payoff_ratio
@@ -208,9 +326,237 @@ pub fn compute_metrics(returns: &[f64], periods_per_year: f64, risk_free: f64) -
         profit_factor,
         payoff_ratio,
         kelly,
+        avg_win,
+        avg_loss,
+        expectancy,
+    })
+}
+
+/// Performance of a return series relative to a benchmark.
+///
+/// Dispersion statistics use the sample (`n - 1`) convention, like
+/// [`Metrics::volatility`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelativeMetrics {
+    /// Covariance with the benchmark divided by benchmark variance
+    pub beta: f64,
+    /// Annualized Jensen's alpha: mean excess return not explained by beta
+    pub alpha: f64,
+    /// Annualized standard deviation of active returns (strategy - benchmark)
+    pub tracking_error: f64,
+    /// Annualized mean active return divided by tracking error
+    pub information_ratio: f64,
+}
+
+/// Compute beta, alpha, tracking error, and information ratio against a
+/// benchmark return series aligned period by period with `returns`.
+///
+/// Returns `None` if the series are empty, differ in length, or
+/// `periods_per_year` is not positive. Beta and the information ratio are 0
+/// when their denominator (benchmark variance, tracking error) is 0.
+///
+/// ```
+/// use nanobook::portfolio::metrics::compute_relative_metrics;
+///
+/// let bench = [0.01, -0.02, 0.015, 0.005];
+/// let levered: Vec<f64> = bench.iter().map(|r| 2.0 * r).collect();
+/// let rel = compute_relative_metrics(&levered, &bench, 252.0, 0.0).unwrap();
+/// assert!((rel.beta - 2.0).abs() < 1e-12);
+/// assert!(rel.alpha.abs() < 1e-12);
+/// ```
+pub fn compute_relative_metrics(
+    returns: &[f64],
+    benchmark: &[f64],
+    periods_per_year: f64,
+    risk_free: f64,
+) -> Option<RelativeMetrics> {
+    let n = returns.len();
+    if n == 0 || benchmark.len() != n {
+        return None;
+    }
+    if !periods_per_year.is_finite() || periods_per_year <= 0.0 {
+        return None;
+    }
+
+    let mean_r = returns.iter().sum::<f64>() / n as f64;
+    let mean_b = benchmark.iter().sum::<f64>() / n as f64;
+    let (mut cov, mut var_b, mut var_active) = (0.0, 0.0, 0.0);
+    for (&r, &b) in returns.iter().zip(benchmark) {
+        let (dr, db) = (r - mean_r, b - mean_b);
+        cov += dr * db;
+        var_b += db * db;
+        var_active += (dr - db).powi(2);
+    }
+    if n > 1 {
+        let denom = (n - 1) as f64;
+        cov /= denom;
+        var_b /= denom;
+        var_active /= denom;
+    } else {
+        (cov, var_b, var_active) = (0.0, 0.0, 0.0);
+    }
+
+    let beta = if var_b > 0.0 { cov / var_b } else { 0.0 };
+    let alpha = ((mean_r - risk_free) - beta * (mean_b - risk_free)) * periods_per_year;
+    let tracking_error = var_active.sqrt() * periods_per_year.sqrt();
+    let information_ratio = if tracking_error > 0.0 {
+        (mean_r - mean_b) * periods_per_year / tracking_error
+    } else {
+        0.0
+    };
+
+    Some(RelativeMetrics {
+        beta,
+        alpha,
+        tracking_error,
+        information_ratio,
     })
 }
 
+/// Incrementally maintained Sharpe, Sortino, and drawdown statistics.
+///
+/// Each [`update`](Self::update) is O(1): the mean and variance use
+/// Welford's recurrence, the downside variance is a running sum of squared
+/// shortfalls below `risk_free`, and drawdown tracks the compounded equity
+/// against its running peak. On the same series the results match
+/// [`compute_metrics`] (sample volatility, `ddof = 0` Sortino).
+///
+/// ```
+/// use nanobook::portfolio::{StreamingMetrics, compute_metrics};
+///
+/// let returns = [0.01, -0.02, 0.015, 0.003, -0.004];
+/// let mut live = StreamingMetrics::new(252.0, 0.0);
+/// for &r in &returns {
+///     live.update(r);
+/// }
+/// let batch = compute_metrics(&returns, 252.0, 0.0).unwrap();
+/// assert!((live.sharpe() - batch.sharpe).abs() < 1e-12);
+/// assert!((live.max_drawdown() - batch.max_drawdown).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingMetrics {
+    periods_per_year: f64,
+    risk_free: f64,
+    count: usize,
+    mean: f64,
+    m2: f64,
+    downside_sum: f64,
+    equity: f64,
+    peak: f64,
+    max_drawdown: f64,
+}
+
+impl StreamingMetrics {
+    /// Create an empty accumulator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `periods_per_year` is not strictly positive and finite.
+    pub fn new(periods_per_year: f64, risk_free: f64) -> Self {
+        assert!(
+            periods_per_year.is_finite() && periods_per_year > 0.0,
+            "periods_per_year must be positive and finite"
+        );
+        Self {
+            periods_per_year,
+            risk_free,
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            downside_sum: 0.0,
+            equity: 1.0,
+            peak: 1.0,
+            max_drawdown: 0.0,
+        }
+    }
+
+    /// Fold one periodic return into the running statistics.
+    pub fn update(&mut self, r: f64) {
+        self.count += 1;
+        let delta = r - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (r - self.mean);
+
+        let excess = r - self.risk_free;
+        if excess < 0.0 {
+            self.downside_sum += excess * excess;
+        }
+
+        self.equity *= 1.0 + r;
+        if self.equity > self.peak {
+            self.peak = self.equity;
+        }
+        let dd = (self.peak - self.equity) / self.peak;
+        if dd > self.max_drawdown {
+            self.max_drawdown = dd;
+        }
+    }
+
+    /// Number of returns seen so far.
+    pub fn num_periods(&self) -> usize {
+        self.count
+    }
+
+    /// Mean periodic return (0 before the first update).
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Cumulative compounded return.
+    pub fn total_return(&self) -> f64 {
+        self.equity - 1.0
+    }
+
+    /// Annualized sample volatility (0 with fewer than two returns).
+    pub fn volatility(&self) -> f64 {
+        self.variance().sqrt() * self.periods_per_year.sqrt()
+    }
+
+    /// Annualized Sharpe ratio; 0 when volatility is zero.
+    pub fn sharpe(&self) -> f64 {
+        let std = self.variance().sqrt();
+        if std > 0.0 {
+            (self.mean - self.risk_free) * self.periods_per_year.sqrt() / std
+        } else {
+            0.0
+        }
+    }
+
+    /// Annualized Sortino ratio (`ddof = 0`); 0 when no return fell below
+    /// `risk_free`.
+    pub fn sortino(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let downside_dev = (self.downside_sum / self.count as f64).sqrt();
+        if downside_dev > 0.0 {
+            (self.mean - self.risk_free) * self.periods_per_year.sqrt() / downside_dev
+        } else {
+            0.0
+        }
+    }
+
+    /// Largest peak-to-trough decline so far, as a positive fraction.
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    /// Current decline from the running equity peak.
+    pub fn drawdown(&self) -> f64 {
+        (self.peak - self.equity) / self.peak
+    }
+
+    fn variance(&self) -> f64 {
+        if self.count > 1 {
+            (self.m2 / (self.count - 1) as f64).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DrawdownEvent {
     pub drawdown_pct: f64,
@@ -306,6 +652,19 @@ pub fn rolling_max_drawdown(equity: &[f64], window: usize) -> Vec<f64> {
     out
 }
 
+/// CAGR from a total return over `n` periods:
+/// `(1 + total_return)^(periods_per_year / n) - 1`.
+fn compute_cagr(total_return: f64, n: usize, periods_per_year: f64) -> f64 {
+    let years = n as f64 / periods_per_year;
+    if years > 0.0 && total_return > -1.0 {
+        (1.0 + total_return).powf(1.0 / years) - 1.0
+    } else if total_return <= -1.0 {
+        -1.0 // total or leveraged loss — clamp to -100%
+    } else {
+        0.0
+    }
+}
+
 /// Compute maximum drawdown from a return series.
 fn compute_max_drawdown(returns: &[f64]) -> f64 {
     let mut peak = 1.0_f64;
@@ -624,10 +983,184 @@ pub fn rolling_volatility(returns: &[f64], window: usize, periods_per_year: usiz
     })
 }
 
+/// Distribution of one metric across sweep results, from [`summarize`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetricsSummary {
+    /// Number of results summarized (skipped entries excluded)
+    pub count: usize,
+    /// Mean of the metric
+    pub mean: f64,
+    /// Sample standard deviation of the metric (0 for a single result)
+    pub std: f64,
+    /// Highest value
+    pub best: f64,
+    /// Lowest value
+    pub worst: f64,
+    /// Index of the highest value in the input slice
+    pub best_index: usize,
+    /// Index of the lowest value in the input slice
+    pub worst_index: usize,
+}
+
+/// Index of the result with the highest `key`, as returned by a sweep.
+///
+/// `None` entries and NaN keys are skipped; ties keep the first. Returns
+/// `None` if nothing is left. Negate the key for lower-is-better metrics
+/// such as `max_drawdown`.
+///
+/// ```
+/// use nanobook::portfolio::metrics::{argmax_by, compute_metrics};
+///
+/// let results = vec![
+///     compute_metrics(&[0.01, 0.02, -0.01], 12.0, 0.0),
+///     None,
+///     compute_metrics(&[0.02, 0.03, 0.01], 12.0, 0.0),
+/// ];
+/// assert_eq!(argmax_by(&results, |m| m.sharpe), Some(2));
+/// ```
+pub fn argmax_by<T, F>(results: &[Option<T>], key: F) -> Option<usize>
+where
+    F: FnMut(&T) -> f64,
+{
+    summarize(results, key).map(|s| s.best_index)
+}
+
+/// Summarize `key` across sweep results: mean, standard deviation, and the
+/// best and worst values with their indices.
+///
+/// `None` entries and NaN keys are skipped, as in [`argmax_by`]. Returns
+/// `None` if nothing is left.
+pub fn summarize<T, F>(results: &[Option<T>], mut key: F) -> Option<MetricsSummary>
+where
+    F: FnMut(&T) -> f64,
+{
+    let values: Vec<(usize, f64)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| Some((i, key(r.as_ref()?))))
+        .filter(|(_, v)| !v.is_nan())
+        .collect();
+    let &(first, v0) = values.first()?;
+    let n = values.len() as f64;
+    let mean = values.iter().map(|(_, v)| v).sum::<f64>() / n;
+    let std = if values.len() > 1 {
+        (values.iter().map(|(_, v)| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+    let mut summary = MetricsSummary {
+        count: values.len(),
+        mean,
+        std,
+        best: v0,
+        worst: v0,
+        best_index: first,
+        worst_index: first,
+    };
+    for &(i, v) in &values[1..] {
+        if v > summary.best {
+            (summary.best, summary.best_index) = (v, i);
+        }
+        if v < summary.worst {
+            (summary.worst, summary.worst_index) = (v, i);
+        }
+    }
+    Some(summary)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn batch_matches_single_series() {
+        let series: Vec<Vec<f64>> = (0..40)
+            .map(|i| {
+                (0..(i % 7) * 10)
+                    .map(|t| ((i * 31 + t * 17) % 23) as f64 / 500.0 - 0.02)
+                    .collect()
+            })
+            .collect();
+        let refs: Vec<&[f64]> = series.iter().map(Vec::as_slice).collect();
+
+        let batch = compute_metrics_batch(&refs, 252.0, 0.0001);
+        assert_eq!(batch.len(), series.len());
+        for (returns, got) in refs.iter().zip(&batch) {
+            let want = compute_metrics(returns, 252.0, 0.0001);
+            // Debug output covers every field, bit-for-bit.
+            assert_eq!(format!("{got:?}"), format!("{want:?}"));
+        }
+        assert!(batch[0].is_none());
+        assert!(
+            compute_metrics_batch(&refs, 0.0, 0.0)
+                .iter()
+                .all(Option::is_none)
+        );
+    }
+
+    #[test]
+    fn summarize_skips_missing_results() {
+        let results = [
+            Some(1.0),
+            None,
+            Some(3.0),
+            Some(f64::NAN),
+            Some(-1.0),
+            Some(3.0),
+        ];
+        let s = summarize(&results, |&v| v).unwrap();
+        assert_eq!(s.count, 4);
+        assert_eq!(s.mean, 1.5);
+        assert!((s.std - (11.0_f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!((s.best, s.best_index), (3.0, 2));
+        assert_eq!((s.worst, s.worst_index), (-1.0, 4));
+        assert_eq!(argmax_by(&results, |&v| -v), Some(4));
+
+        assert_eq!(summarize(&[Some(2.0)], |&v| v).unwrap().std, 0.0);
+        assert!(summarize::<f64, _>(&[None, Some(f64::NAN)], |&v| v).is_none());
+    }
+
+    #[test]
+    fn relative_metrics_against_benchmark() {
+        let bench = [0.01, -0.02, 0.015, 0.005, -0.01];
+        let active = [0.002, -0.001, 0.003, 0.0, 0.001];
+        let returns: Vec<f64> = bench
+            .iter()
+            .zip(&active)
+            .map(|(b, a)| 0.5 * b + a + 0.001)
+            .collect();
+        let rel = compute_relative_metrics(&returns, &bench, 12.0, 0.0).unwrap();
+
+        // Reference: numpy cov/var with ddof=1
+        let n = 5.0;
+        let mean = |xs: &[f64]| xs.iter().sum::<f64>() / n;
+        let (mr, mb) = (mean(&returns), mean(&bench));
+        let cov: f64 = returns
+            .iter()
+            .zip(&bench)
+            .map(|(r, b)| (r - mr) * (b - mb))
+            .sum::<f64>()
+            / (n - 1.0);
+        let var_b: f64 = bench.iter().map(|b| (b - mb).powi(2)).sum::<f64>() / (n - 1.0);
+        let beta = cov / var_b;
+        assert!((rel.beta - beta).abs() < 1e-12);
+        assert!((rel.alpha - (mr - beta * mb) * 12.0).abs() < 1e-12);
+
+        let diff: Vec<f64> = returns.iter().zip(&bench).map(|(r, b)| r - b).collect();
+        let md = mean(&diff);
+        let te =
+            (diff.iter().map(|d| (d - md).powi(2)).sum::<f64>() / (n - 1.0)).sqrt() * 12f64.sqrt();
+        assert!((rel.tracking_error - te).abs() < 1e-12);
+        assert!((rel.information_ratio - md * 12.0 / te).abs() < 1e-12);
+
+        assert!(compute_relative_metrics(&returns, &bench[..4], 12.0, 0.0).is_none());
+        assert!(compute_relative_metrics(&[], &[], 12.0, 0.0).is_none());
+        let same = compute_relative_metrics(&bench, &bench, 12.0, 0.0).unwrap();
+        assert_eq!(same.tracking_error, 0.0);
+        assert_eq!(same.information_ratio, 0.0);
+    }
+
     #[test]
     fn empty_returns() {
         assert!(compute_metrics(&[], 252.0, 0.0).is_none());
@@ -733,6 +1266,112 @@ mod tests {
         assert!(sortino(&returns, 0.0, 252.0, 0) > 1e-12);
     }
 
+    #[test]
+    fn streaming_matches_batch_at_every_step() {
+        let returns = [
+            0.012, -0.021, 0.004, 0.033, -0.008, -0.015, 0.0, 0.019, -0.027, 0.011,
+        ];
+        let rf = 0.0002;
+        let mut live = StreamingMetrics::new(252.0, rf);
+        for (i, &r) in returns.iter().enumerate() {
+            live.update(r);
+            let batch = compute_metrics(&returns[..=i], 252.0, rf).unwrap();
+            assert_eq!(live.num_periods(), batch.num_periods);
+            assert!((live.total_return() - batch.total_return).abs() < 1e-12);
+            assert!((live.volatility() - batch.volatility).abs() < 1e-12);
+            assert!((live.sharpe() - batch.sharpe).abs() < 1e-9);
+            assert!((live.sortino() - batch.sortino).abs() < 1e-9);
+            assert!((live.max_drawdown() - batch.max_drawdown).abs() < 1e-12);
+        }
+        assert!(live.drawdown() > 0.0);
+        assert!(live.drawdown() <= live.max_drawdown());
+    }
+
+    #[test]
+    fn sortino_uses_minimum_acceptable_return() {
+        let returns = [0.02, -0.01, 0.015, 0.004, -0.003, 0.008];
+        let default = compute_metrics(&returns, 252.0, 0.001).unwrap();
+        let same = compute_metrics_ex(
+            &returns,
+            252.0,
+            0.001,
+            MetricsOptions {
+                minimum_acceptable_return: Some(0.001),
+                ..MetricsOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(default.sortino, same.sortino);
+
+        let mar = 0.005;
+        let m = compute_metrics_ex(
+            &returns,
+            252.0,
+            0.001,
+            MetricsOptions {
+                minimum_acceptable_return: Some(mar),
+                ..MetricsOptions::default()
+            },
+        )
+        .unwrap();
+        // Shortfalls below MAR, squared, divided by the full count.
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let downside = returns
+            .iter()
+            .map(|&r| (r - mar).min(0.0).powi(2))
+            .sum::<f64>()
+            / n;
+        let expected = (mean - mar) * 252.0_f64.sqrt() / downside.sqrt();
+        assert!((m.sortino - expected).abs() < 1e-12);
+        assert!(m.sortino < default.sortino);
+        // Sharpe still uses risk_free.
+        assert_eq!(m.sharpe, default.sharpe);
+    }
+
+    #[test]
+    fn calmar_lookback_uses_trailing_window() {
+        // Deep early drawdown, then a shallow one in the trailing window.
+        let mut returns = vec![0.05, -0.30, 0.10];
+        returns.extend([0.02, -0.01, 0.03, -0.02, 0.01, 0.02]);
+        let full = compute_metrics(&returns, 12.0, 0.0).unwrap();
+        let opts = |lookback| MetricsOptions {
+            calmar_lookback: Some(lookback),
+            ..MetricsOptions::default()
+        };
+
+        let m = compute_metrics_ex(&returns, 12.0, 0.0, opts(6)).unwrap();
+        let tail = compute_metrics(&returns[3..], 12.0, 0.0).unwrap();
+        assert!((m.calmar - tail.cagr / tail.max_drawdown).abs() < 1e-12);
+        assert!(m.calmar > full.calmar);
+        // Only calmar changes.
+        assert_eq!(m.cagr, full.cagr);
+        assert_eq!(m.max_drawdown, full.max_drawdown);
+
+        // Lookback at or beyond the series length is full-sample.
+        let long = compute_metrics_ex(&returns, 12.0, 0.0, opts(36)).unwrap();
+        assert_eq!(long.calmar, full.calmar);
+    }
+
+    #[test]
+    fn average_win_loss_and_expectancy() {
+        let returns = [0.03, -0.01, 0.0, 0.01, -0.02];
+        let m = compute_metrics(&returns, 252.0, 0.0).unwrap();
+        assert!((m.avg_win - 0.02).abs() < 1e-12);
+        assert!((m.avg_loss + 0.015).abs() < 1e-12);
+        // 0.4 * 0.02 + 0.4 * -0.015
+        assert!((m.expectancy - 0.002).abs() < 1e-12);
+
+        let wins = compute_metrics(&[0.01, 0.02], 252.0, 0.0).unwrap();
+        assert!((wins.avg_win - 0.015).abs() < 1e-12);
+        assert!(wins.avg_loss.is_nan());
+        assert!((wins.expectancy - 0.015).abs() < 1e-12);
+
+        let losses = compute_metrics(&[-0.01, -0.03], 252.0, 0.0).unwrap();
+        assert!(losses.avg_win.is_nan());
+        assert!((losses.expectancy + 0.02).abs() < 1e-12);
+    }
+
     #[test]
     fn sharpe_positive_for_positive_returns() {
         let returns = vec![0.01, 0.02, 0.015, 0.005, 0.01];
@@ -26,18 +26,37 @@
 //! ```
 
 pub mod cost_model;
+pub mod history;
 pub mod metrics;
 pub mod position;
+pub mod series;
+pub mod sizing;
 pub mod strategy;
 #[cfg(feature = "parallel")]
 pub mod sweep;
 
 pub use cost_model::CostModel;
-pub use metrics::{Metrics, compute_metrics};
-pub use position::Position;
-pub use strategy::{BacktestResult, EqualWeight, Strategy, run_backtest};
+pub use history::PriceHistory;
+pub use metrics::{
+    Metrics, MetricsOptions, MetricsSummary, RelativeMetrics, StreamingMetrics, argmax_by,
+    compute_metrics, compute_metrics_batch, compute_metrics_ex, compute_relative_metrics,
+    summarize,
+};
+pub use position::{CostBasis, Lot, Position};
+pub use series::{MissingPrice, price_series_from_matrix};
+pub use sizing::{
+    DuplicatePolicy, RebalanceOrder, WeightError, WeightPolicy, gross_weight, merge_duplicates,
+    normalize_weights, shares_to_weights, weights_to_shares,
+};
+pub use strategy::{
+    BacktestOptions, BacktestResult, EqualWeight, LongShortQuantile, Strategy, TargetWeights, TopK,
+    run_backtest, run_backtest_ex,
+};
+
+use std::borrow::Cow;
 
 use crate::types::Symbol;
+use crate::{Liquidity, OrderId, Price, Side, Trade};
 use rustc_hash::FxHashMap;
 
 /// Serde helper for `FxHashMap<Symbol, Position>` — serializes as `Vec<(Symbol, Position)>`.
@@ -65,7 +84,9 @@ mod serde_positions {
 
 /// A portfolio tracking cash, positions, returns, and equity.
 ///
-/// All monetary values (cash, equity) are in the smallest currency unit (cents).
+/// All monetary values (cash, equity, costs) are integers in the same
+/// units as prices: cents by default, or `1 / price_scale` of a currency
+/// unit after [`with_price_scale`](Self::with_price_scale).
 ///
 /// ```
 /// use nanobook::portfolio::{CostModel, Portfolio};
@@ -102,6 +123,60 @@ pub struct Portfolio {
     equity_curve: Vec<i64>,
     /// Previous equity for return calculation
     prev_equity: i64,
+    /// Incentive fee rate on gains above the hurdle (0.2 = 20%)
+    #[cfg_attr(feature = "serde", serde(default))]
+    incentive_fee: f64,
+    /// Return over the high-water mark required before fees accrue
+    #[cfg_attr(feature = "serde", serde(default))]
+    hurdle: f64,
+    /// Equity level above which incentive fees are charged (cents)
+    #[cfg_attr(feature = "serde", serde(default))]
+    high_water_mark: i64,
+    /// Total incentive fees debited so far (cents)
+    #[cfg_attr(feature = "serde", serde(default))]
+    incentive_fees: i64,
+    /// Cumulative one-way turnover from rebalances (units of equity)
+    #[cfg_attr(feature = "serde", serde(default))]
+    turnover: f64,
+    /// Interest rate per period credited on idle cash (0.0001 = 1bp)
+    #[cfg_attr(feature = "serde", serde(default))]
+    cash_yield: f64,
+    /// Cash held at the last recorded return, the base for the next accrual
+    #[cfg_attr(feature = "serde", serde(default))]
+    accrual_cash: i64,
+    /// Total cash interest credited so far (cents)
+    #[cfg_attr(feature = "serde", serde(default))]
+    cash_interest: i64,
+    /// Accounting method for positions opened by this portfolio
+    #[cfg_attr(feature = "serde", serde(default))]
+    cost_basis: CostBasis,
+    /// How rebalances treat target weights
+    #[cfg_attr(feature = "serde", serde(default))]
+    weight_policy: WeightPolicy,
+    /// How rebalances treat repeated target symbols
+    #[cfg_attr(feature = "serde", serde(default))]
+    duplicate_policy: DuplicatePolicy,
+    /// Order in which simple-fill rebalances apply their fills
+    #[cfg_attr(feature = "serde", serde(default))]
+    rebalance_order: RebalanceOrder,
+    /// Whether simple-fill buys may spend more cash than is held
+    #[cfg_attr(feature = "serde", serde(default))]
+    allow_leverage: bool,
+    /// Price units per whole currency unit
+    #[cfg_attr(feature = "serde", serde(default = "default_price_scale"))]
+    price_scale: i64,
+    /// Currency each symbol is quoted in; unlisted symbols are in the base
+    /// currency
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, serialize_with = "crate::sorted_map::serialize")
+    )]
+    currencies: FxHashMap<Symbol, Symbol>,
+}
+
+#[cfg(feature = "serde")]
+fn default_price_scale() -> i64 {
+    Price::DEFAULT_SCALE
 }
 
 impl Portfolio {
@@ -121,12 +196,200 @@ impl Portfolio {
             returns: Vec::new(),
             equity_curve: vec![initial_cash],
             prev_equity: initial_cash,
+            incentive_fee: 0.0,
+            hurdle: 0.0,
+            high_water_mark: initial_cash,
+            incentive_fees: 0,
+            turnover: 0.0,
+            cash_yield: 0.0,
+            accrual_cash: 0,
+            cash_interest: 0,
+            cost_basis: CostBasis::AverageCost,
+            weight_policy: WeightPolicy::AsGiven,
+            duplicate_policy: DuplicatePolicy::Allow,
+            rebalance_order: RebalanceOrder::SellsFirst,
+            allow_leverage: false,
+            price_scale: Price::DEFAULT_SCALE,
+            currencies: FxHashMap::default(),
         }
     }
 
+    /// Set the number of price units per whole currency unit, consuming
+    /// `self`.
+    ///
+    /// Cash, prices, equity, and the cost model's fixed fees are all
+    /// integers in these units; the scale only matters when converting
+    /// them to currency with [`to_currency`](Self::to_currency). Defaults
+    /// to [`Price::DEFAULT_SCALE`] (cents).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `price_scale` is not positive.
+    pub fn with_price_scale(mut self, price_scale: i64) -> Self {
+        assert!(price_scale > 0, "price_scale must be positive");
+        self.price_scale = price_scale;
+        self
+    }
+
+    /// Price units per whole currency unit (100 by default).
+    pub fn price_scale(&self) -> i64 {
+        self.price_scale
+    }
+
+    /// Convert an amount in price units (cash, equity, a cost) to whole
+    /// currency units.
+    ///
+    /// ```
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    ///
+    /// let cents = Portfolio::new(1_234_50, CostModel::zero());
+    /// assert_eq!(cents.to_currency(cents.cash()), 1_234.5);
+    ///
+    /// let bps = Portfolio::new(1_234_5000, CostModel::zero()).with_price_scale(10_000);
+    /// assert_eq!(bps.to_currency(bps.cash()), 1_234.5);
+    /// ```
+    pub fn to_currency(&self, amount: i64) -> f64 {
+        amount as f64 / self.price_scale as f64
+    }
+
+    /// Assign quote currencies to symbols, consuming `self`.
+    ///
+    /// Each `(symbol, currency)` pair says `symbol` is priced in
+    /// `currency` (a code such as `"EUR"`). Cash and unlisted symbols are
+    /// in the base currency. The `_fx` methods
+    /// ([`rebalance_simple_fx`](Self::rebalance_simple_fx),
+    /// [`record_return_fx`](Self::record_return_fx),
+    /// [`total_equity_fx`](Self::total_equity_fx),
+    /// [`current_weights_fx`](Self::current_weights_fx)) use this to
+    /// convert local prices and values; the other methods ignore it.
+    ///
+    /// Cash is debited and credited in the base currency, so every other
+    /// trading method takes base-currency prices. Trade with
+    /// `rebalance_simple_fx`, or convert prices yourself, or FX-valued
+    /// equity will not match the cash spent.
+    pub fn with_currencies(
+        mut self,
+        currencies: impl IntoIterator<Item = (Symbol, Symbol)>,
+    ) -> Self {
+        self.currencies.extend(currencies);
+        self
+    }
+
+    /// Quote currency assigned to `symbol`, if any (see
+    /// [`with_currencies`](Self::with_currencies)).
+    pub fn currency(&self, symbol: &Symbol) -> Option<Symbol> {
+        self.currencies.get(symbol).copied()
+    }
+
+    /// Charge a high-water-mark incentive fee, consuming `self`.
+    ///
+    /// At each [`record_return`](Self::record_return), if equity exceeds
+    /// `high_water_mark × (1 + hurdle)`, `incentive_fee` times the excess
+    /// is debited from cash and the high-water mark moves to the after-fee
+    /// equity. Recorded returns and the equity curve are after fees. The
+    /// high-water mark starts at the initial cash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `incentive_fee` is outside `[0, 1]` or `hurdle` is
+    /// negative.
+    pub fn with_incentive_fee(mut self, incentive_fee: f64, hurdle: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&incentive_fee),
+            "incentive_fee must be in [0, 1], got {incentive_fee}"
+        );
+        assert!(hurdle >= 0.0, "hurdle must be non-negative, got {hurdle}");
+        self.incentive_fee = incentive_fee;
+        self.hurdle = hurdle;
+        self
+    }
+
+    /// Credit interest on idle cash, consuming `self`.
+    ///
+    /// At each [`record_return`](Self::record_return), the cash held at the
+    /// previous one earns `cash_yield` (a per-period rate, e.g. the
+    /// risk-free rate divided by periods per year) before equity is
+    /// measured. Negative balances (margin) earn nothing, and the first
+    /// recorded period accrues nothing. A rate of 0 leaves returns unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cash_yield` is negative or not finite.
+    pub fn with_cash_yield(mut self, cash_yield: f64) -> Self {
+        assert!(
+            cash_yield.is_finite() && cash_yield >= 0.0,
+            "cash_yield must be finite and non-negative, got {cash_yield}"
+        );
+        self.cash_yield = cash_yield;
+        self
+    }
+
+    /// Account for realized PnL by `cost_basis`, consuming `self`.
+    ///
+    /// Applies to positions opened from now on; the default is
+    /// [`CostBasis::AverageCost`]. Under [`CostBasis::Fifo`] each position
+    /// keeps its entry lots and partial exits realize PnL against the
+    /// oldest first, which changes `realized_pnl` (but not cash or equity).
+    pub fn with_cost_basis(mut self, cost_basis: CostBasis) -> Self {
+        self.cost_basis = cost_basis;
+        self
+    }
+
+    /// Check or rescale rebalance targets by `weight_policy`, consuming
+    /// `self`.
+    ///
+    /// Every rebalance method, including
+    /// [`estimate_rebalance_cost`](Self::estimate_rebalance_cost), applies
+    /// the policy to its targets first. Under [`WeightPolicy::Strict`] they
+    /// panic on targets over the cap; call
+    /// [`check_targets`](Self::check_targets) first to handle that as an
+    /// error. The default, [`WeightPolicy::AsGiven`], trades the weights
+    /// unchanged.
+    pub fn with_weight_policy(mut self, weight_policy: WeightPolicy) -> Self {
+        self.weight_policy = weight_policy;
+        self
+    }
+
+    /// Handle repeated target symbols by `duplicate_policy`, consuming
+    /// `self`.
+    ///
+    /// By default ([`DuplicatePolicy::Allow`]) a symbol listed twice is
+    /// traded once per entry, so the last weight wins.
+    /// [`DuplicatePolicy::Reject`] makes rebalances panic on such targets,
+    /// as [`check_targets`](Self::check_targets) reports, and
+    /// [`DuplicatePolicy::Sum`] adds the weights up before the
+    /// [`WeightPolicy`] applies.
+    pub fn with_duplicate_policy(mut self, duplicate_policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = duplicate_policy;
+        self
+    }
+
+    /// Choose the order in which
+    /// [`rebalance_simple`](Self::rebalance_simple) applies its fills,
+    /// consuming `self`.
+    ///
+    /// The default, [`RebalanceOrder::SellsFirst`], sells before it buys;
+    /// [`RebalanceOrder::AsListed`] keeps the older order (closes, then
+    /// targets as listed).
+    pub fn with_rebalance_order(mut self, rebalance_order: RebalanceOrder) -> Self {
+        self.rebalance_order = rebalance_order;
+        self
+    }
+
+    /// Let [`rebalance_simple`](Self::rebalance_simple) buy on margin,
+    /// consuming `self`.
+    ///
+    /// By default a buy is capped at the cash on hand, costs included, and
+    /// the rest is reported as a [`RebalanceShortfall`]. With leverage
+    /// allowed, buys fill in full and cash may go negative.
+    pub fn with_leverage(mut self, allow: bool) -> Self {
+        self.allow_leverage = allow;
+        self
+    }
+
     // === Queries ===
 
-    /// Current cash balance (cents).
+    /// Current cash balance (price units; cents by default).
     #[inline]
     pub fn cash(&self) -> i64 {
         self.cash
@@ -144,7 +407,8 @@ impl Portfolio {
 
     /// Total equity: cash + sum of all position market values.
     ///
-    /// `prices` maps symbols to current prices (cents).
+    /// `prices` maps symbols to current prices; the result is in the same
+    /// units (cents by default, see [`Self::with_price_scale`]).
     pub fn total_equity(&self, prices: &[(Symbol, i64)]) -> i64 {
         let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
         self.total_equity_from_price_map(&price_map)
@@ -160,6 +424,115 @@ impl Portfolio {
         self.current_weights_from_price_map(&price_map, equity)
     }
 
+    /// Total equity in the base currency.
+    ///
+    /// Like [`total_equity`](Self::total_equity), but each position's
+    /// market value is multiplied by the rate of its symbol's currency
+    /// (see [`with_currencies`](Self::with_currencies)) and rounded to the
+    /// nearest unit. `fx_rates` gives base-currency units per unit of each
+    /// currency; a symbol with no currency, or whose currency has no rate,
+    /// converts at 1.0, so empty `fx_rates` reproduce `total_equity`.
+    ///
+    /// ```
+    /// use nanobook::Symbol;
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    ///
+    /// let (sap, eur) = (Symbol::new("SAP"), Symbol::new("EUR"));
+    /// let mut portfolio =
+    ///     Portfolio::new(1_000_00, CostModel::zero()).with_currencies([(sap, eur)]);
+    /// // 4 shares at EUR 100.00, at 1.25 USD per EUR
+    /// portfolio.rebalance_simple_fx(&[(sap, 0.5)], &[(sap, 100_00)], &[(eur, 1.25)]);
+    /// assert_eq!(portfolio.cash(), 500_00);
+    ///
+    /// let equity = portfolio.total_equity_fx(&[(sap, 100_00)], &[(eur, 1.25)]);
+    /// assert_eq!(equity, 1_000_00);
+    /// // The euro falls to 1.00 USD
+    /// let equity = portfolio.total_equity_fx(&[(sap, 100_00)], &[(eur, 1.0)]);
+    /// assert_eq!(equity, 500_00 + 400_00);
+    /// ```
+    pub fn total_equity_fx(&self, prices: &[(Symbol, i64)], fx_rates: &[(Symbol, f64)]) -> i64 {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let fx: FxHashMap<Symbol, f64> = fx_rates.iter().copied().collect();
+        self.total_equity_fx_map(&price_map, &fx)
+    }
+
+    /// Current weights from base-currency values, as in
+    /// [`total_equity_fx`](Self::total_equity_fx).
+    pub fn current_weights_fx(
+        &self,
+        prices: &[(Symbol, i64)],
+        fx_rates: &[(Symbol, f64)],
+    ) -> Vec<(Symbol, f64)> {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let fx: FxHashMap<Symbol, f64> = fx_rates.iter().copied().collect();
+        let equity = self.total_equity_fx_map(&price_map, &fx);
+        self.current_weights_fx_map(&price_map, &fx, equity)
+    }
+
+    /// Gross exposure: sum of absolute position values divided by equity.
+    ///
+    /// A fully invested long-only book is 1.0; 130/30 long/short is 1.6.
+    /// Returns 0.0 when there are no positions or equity is zero.
+    pub fn gross_exposure(&self, prices: &[(Symbol, i64)]) -> f64 {
+        self.exposure(prices, i64::saturating_abs)
+    }
+
+    /// Net exposure: sum of signed position values divided by equity.
+    ///
+    /// Shorts count negatively, so 130/30 long/short is 1.0. Returns 0.0
+    /// when there are no positions or equity is zero.
+    pub fn net_exposure(&self, prices: &[(Symbol, i64)]) -> f64 {
+        self.exposure(prices, |mv| mv)
+    }
+
+    /// Realized PnL summed over every position, closed ones included
+    /// (cents).
+    pub fn realized_pnl(&self) -> i64 {
+        self.positions.values().map(|p| p.realized_pnl).sum()
+    }
+
+    /// Unrealized PnL of the open positions at `prices`, summed (cents).
+    ///
+    /// A position whose symbol has no price in `prices` contributes zero,
+    /// as if marked at its entry price, rather than failing; price every
+    /// held symbol for a full mark.
+    ///
+    /// ```
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    /// use nanobook::Symbol;
+    ///
+    /// let (aapl, msft) = (Symbol::new("AAPL"), Symbol::new("MSFT"));
+    /// let mut portfolio = Portfolio::new(100_000_00, CostModel::zero());
+    /// portfolio.rebalance_simple(&[(aapl, 0.5), (msft, 0.5)], &[(aapl, 100_00), (msft, 50_00)]);
+    ///
+    /// assert_eq!(portfolio.unrealized_pnl(&[(aapl, 110_00), (msft, 40_00)]), -5_000_00);
+    /// assert_eq!(portfolio.unrealized_pnl(&[(aapl, 110_00)]), 5_000_00);
+    /// ```
+    pub fn unrealized_pnl(&self, prices: &[(Symbol, i64)]) -> i64 {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        self.positions
+            .iter()
+            .filter_map(|(sym, pos)| Some(pos.unrealized_pnl(*price_map.get(sym)?)))
+            .sum()
+    }
+
+    fn exposure(&self, prices: &[(Symbol, i64)], value: impl Fn(i64) -> i64) -> f64 {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let equity = self.total_equity_from_price_map(&price_map);
+        if equity == 0 {
+            return 0.0;
+        }
+        let total: f64 = self
+            .positions
+            .iter()
+            .map(|(sym, pos)| {
+                let price = price_map.get(sym).copied().unwrap_or(0);
+                value(pos.market_value(price)) as f64
+            })
+            .sum();
+        total / equity as f64
+    }
+
     /// The accumulated return series.
     pub fn returns(&self) -> &[f64] {
         &self.returns
@@ -175,6 +548,100 @@ impl Portfolio {
         &self.cost_model
     }
 
+    /// Total incentive fees debited from cash so far (cents).
+    pub fn incentive_fees(&self) -> i64 {
+        self.incentive_fees
+    }
+
+    /// Current high-water mark for incentive fees (cents).
+    pub fn high_water_mark(&self) -> i64 {
+        self.high_water_mark
+    }
+
+    /// Per-period interest rate credited on idle cash.
+    pub fn cash_yield(&self) -> f64 {
+        self.cash_yield
+    }
+
+    /// Total interest credited to cash so far (cents).
+    pub fn cash_interest(&self) -> i64 {
+        self.cash_interest
+    }
+
+    /// Cumulative one-way turnover: half the sum of absolute weight changes
+    /// traded by rebalances, each measured against the pre-trade equity.
+    ///
+    /// A full switch from one asset to another adds 1.0. Rebalances out of
+    /// an all-cash book (initial construction) are not counted, so a
+    /// buy-and-hold portfolio stays at 0.
+    pub fn turnover(&self) -> f64 {
+        self.turnover
+    }
+
+    /// Accounting method for newly opened positions.
+    pub fn cost_basis(&self) -> CostBasis {
+        self.cost_basis
+    }
+
+    /// How rebalances treat target weights.
+    pub fn weight_policy(&self) -> WeightPolicy {
+        self.weight_policy
+    }
+
+    /// How rebalances treat repeated target symbols.
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Order in which simple-fill rebalances apply their fills.
+    pub fn rebalance_order(&self) -> RebalanceOrder {
+        self.rebalance_order
+    }
+
+    /// Whether simple-fill buys may take cash negative.
+    pub fn allows_leverage(&self) -> bool {
+        self.allow_leverage
+    }
+
+    /// Check `targets` against the [`DuplicatePolicy`] and
+    /// [`WeightPolicy`] without trading.
+    pub fn check_targets(&self, targets: &[(Symbol, f64)]) -> Result<(), WeightError> {
+        self.resolve_targets(targets).map(drop)
+    }
+
+    /// `targets` with duplicates merged and the weight policy applied.
+    fn resolve_targets<'a>(
+        &self,
+        targets: &'a [(Symbol, f64)],
+    ) -> Result<Cow<'a, [(Symbol, f64)]>, WeightError> {
+        match merge_duplicates(targets, self.duplicate_policy)
+            .map_err(WeightError::DuplicateSymbol)?
+        {
+            Cow::Borrowed(targets) => self.weight_policy.apply(targets),
+            Cow::Owned(merged) => Ok(Cow::Owned(self.weight_policy.apply(&merged)?.into_owned())),
+        }
+    }
+
+    /// `targets` after the duplicate and weight policies; panics where `check_targets`
+    /// would fail.
+    fn policy_targets<'a>(&self, targets: &'a [(Symbol, f64)]) -> Cow<'a, [(Symbol, f64)]> {
+        self.resolve_targets(targets)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Average number of periods closed quantity was held, across all
+    /// positions, or `None` if nothing has been closed yet.
+    ///
+    /// Each closed share counts once, at the age it was closed at (see
+    /// [`Position::closed_holding_period`]). Periods are counted by
+    /// [`record_return`](Self::record_return).
+    pub fn average_holding_period(&self) -> Option<f64> {
+        let (quantity, age) = self.positions.values().fold((0_i64, 0.0), |(q, a), p| {
+            (q + p.closed_quantity, a + p.closed_age)
+        });
+        (quantity > 0).then(|| age / quantity as f64)
+    }
+
     // === Execution ===
 
     /// Rebalance the portfolio to target weights using simple fill (instant execution).
@@ -182,67 +649,447 @@ impl Portfolio {
     /// This is the hot path for parameter sweeps. Orders execute at the provided
     /// bar prices with no market microstructure simulation.
     ///
-    /// `targets`: desired (symbol, weight) pairs. Weights should sum to ≤ 1.0.
+    /// `targets`: desired (symbol, weight) pairs. Weights should sum to ≤ 1.0;
+    /// see [`with_weight_policy`](Self::with_weight_policy) to rescale or
+    /// enforce that, and [`with_duplicate_policy`](Self::with_duplicate_policy)
+    /// to reject or sum symbols listed twice.
     /// `prices`: current (symbol, price_in_cents) for each symbol.
     ///
     /// Positions not in `targets` are closed. Costs are deducted from cash.
-    pub fn rebalance_simple(&mut self, targets: &[(Symbol, f64)], prices: &[(Symbol, i64)]) {
+    /// Sells are applied before buys unless
+    /// [`with_rebalance_order`](Self::with_rebalance_order) says otherwise.
+    ///
+    /// A buy the cash on hand cannot cover, costs included, is cut to the
+    /// largest quantity it can, possibly zero, and reported in the returned
+    /// shortfalls, one per capped buy in fill order. Cash never goes
+    /// negative this way unless [`with_leverage`](Self::with_leverage)
+    /// allows it, in which case every buy fills in full.
+    ///
+    /// ```
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    /// use nanobook::Symbol;
+    ///
+    /// let (aapl, msft) = (Symbol::new("AAPL"), Symbol::new("MSFT"));
+    /// let prices = [(aapl, 100_00), (msft, 100_00)];
+    /// let mut portfolio = Portfolio::new(10_000_00, CostModel::zero());
+    /// let shortfalls = portfolio.rebalance_simple(&[(aapl, 0.75), (msft, 0.75)], &prices);
+    ///
+    /// assert_eq!(portfolio.cash(), 0);
+    /// assert_eq!(shortfalls[0].symbol, msft);
+    /// assert_eq!((shortfalls[0].requested, shortfalls[0].filled), (75, 25));
+    /// ```
+    pub fn rebalance_simple(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+    ) -> Vec<RebalanceShortfall> {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        self.rebalance_simple_from_price_map(targets, &price_map)
+    }
+
+    /// [`rebalance_simple`](Self::rebalance_simple) with execution prices
+    /// separate from valuation prices.
+    ///
+    /// Equity, and therefore each target's value, is marked at `prices`
+    /// (e.g. the signal bar's close), while orders are sized and filled at
+    /// `fill_prices` (e.g. the next bar's open). Symbols missing from
+    /// `fill_prices` fill at their valuation price, so passing the same
+    /// slice twice is identical to `rebalance_simple`.
+    ///
+    /// ```
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    /// use nanobook::Symbol;
+    ///
+    /// let aapl = Symbol::new("AAPL");
+    /// let mut portfolio = Portfolio::new(100_000_00, CostModel::zero());
+    /// // Decide on the $100 close, buy at the $125 open.
+    /// portfolio.rebalance_simple_with_fill_prices(
+    ///     &[(aapl, 1.0)],
+    ///     &[(aapl, 100_00)],
+    ///     &[(aapl, 125_00)],
+    /// );
+    /// let pos = portfolio.position(&aapl).unwrap();
+    /// assert_eq!(pos.quantity, 800);
+    /// assert_eq!(pos.avg_entry_price, 125_00);
+    /// ```
+    pub fn rebalance_simple_with_fill_prices(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+        fill_prices: &[(Symbol, i64)],
+    ) -> Vec<RebalanceShortfall> {
         let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
-        self.rebalance_simple_from_price_map(targets, &price_map);
+        let fill_map: FxHashMap<Symbol, i64> = fill_prices.iter().copied().collect();
+        self.rebalance_simple_at(targets, &price_map, Some(&fill_map))
+    }
+
+    /// [`rebalance_simple`](Self::rebalance_simple) with `prices` in each
+    /// symbol's own currency.
+    ///
+    /// Each price is converted to the base currency at its currency's rate
+    /// in `fx_rates` (see [`total_equity_fx`](Self::total_equity_fx)) and
+    /// rounded to the nearest unit; sizing, cash, costs, and the positions'
+    /// entry prices are all in base-currency units. Right after a zero-cost
+    /// rebalance, `total_equity_fx` at the same prices and rates equals the
+    /// equity before it, up to rounding.
+    pub fn rebalance_simple_fx(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+        fx_rates: &[(Symbol, f64)],
+    ) -> Vec<RebalanceShortfall> {
+        let fx: FxHashMap<Symbol, f64> = fx_rates.iter().copied().collect();
+        let price_map: FxHashMap<Symbol, i64> = prices
+            .iter()
+            .map(|&(sym, price)| (sym, self.to_base(&sym, price, &fx)))
+            .collect();
+        self.rebalance_simple_from_price_map(targets, &price_map)
     }
 
     pub(crate) fn rebalance_simple_from_price_map(
         &mut self,
         targets: &[(Symbol, f64)],
         price_map: &FxHashMap<Symbol, i64>,
-    ) {
+    ) -> Vec<RebalanceShortfall> {
+        self.rebalance_simple_at(targets, price_map, None)
+    }
+
+    /// Simple-fill rebalance valued at `price_map` and filled at `fill_map`
+    /// where it quotes the symbol (valuation price otherwise).
+    pub(crate) fn rebalance_simple_at(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        price_map: &FxHashMap<Symbol, i64>,
+        fill_map: Option<&FxHashMap<Symbol, i64>>,
+    ) -> Vec<RebalanceShortfall> {
+        let targets = &*self.policy_targets(targets);
+        let fill_price = |sym: &Symbol| {
+            fill_map
+                .and_then(|m| m.get(sym))
+                .or_else(|| price_map.get(sym))
+                .copied()
+        };
         let equity = self.total_equity_from_price_map(price_map);
         if equity <= 0 {
-            return;
+            return Vec::new();
+        }
+        let was_flat = self.is_all_cash();
+        let mut traded = 0_i64;
+        let mut shortfalls = Vec::new();
+
+        for (sym, requested, price) in self.simple_fills(targets, equity, fill_price) {
+            let mut qty = requested;
+            if qty > 0 && !self.allow_leverage {
+                qty = self.affordable_quantity(qty, price);
+                if qty < requested {
+                    shortfalls.push(RebalanceShortfall {
+                        symbol: sym,
+                        requested,
+                        filled: qty,
+                        price,
+                    });
+                }
+            }
+            self.execute_fill(sym, qty, price);
+            traded = traded.saturating_add(qty.saturating_abs().saturating_mul(price));
         }
+        self.add_turnover(was_flat, traded, equity);
+        shortfalls
+    }
+
+    /// The largest buy of at most `qty` at `price` that current cash covers
+    /// with its taker cost.
+    fn affordable_quantity(&self, qty: i64, price: i64) -> i64 {
+        let fits = |q: i64| {
+            let notional = q.saturating_mul(price);
+            let cost =
+                self.cost_model
+                    .compute_fill_cost_with_quantity(notional, q, Liquidity::Taker);
+            notional.saturating_add(cost) <= self.cash
+        };
+        if fits(qty) {
+            return qty;
+        }
+        // Costs only grow with quantity, so bisect between a fitting `lo`
+        // (buying nothing always fits) and a failing `hi`.
+        let (mut lo, mut hi) = (0, qty.min(self.cash.max(0) / price + 1));
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if fits(mid) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
 
+    /// The `(symbol, quantity, price)` fills of a simple-fill rebalance to
+    /// `targets` at `equity`, in the configured [`RebalanceOrder`].
+    fn simple_fills(
+        &self,
+        targets: &[(Symbol, f64)],
+        equity: i64,
+        fill_price: impl Fn(&Symbol) -> Option<i64>,
+    ) -> Vec<(Symbol, i64, i64)> {
         let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
+        let mut fills = Vec::with_capacity(targets.len());
 
         // Close positions not in targets
-        let to_close: Vec<Symbol> = self
-            .positions
-            .keys()
-            .filter(|sym| !target_map.contains_key(sym))
-            .copied()
-            .collect();
+        for (sym, pos) in &self.positions {
+            if pos.is_flat() || target_map.contains_key(sym) {
+                continue;
+            }
+            if let Some(price) = fill_price(sym) {
+                fills.push((*sym, -pos.quantity, price));
+            }
+        }
 
-        for sym in to_close {
-            if let Some(price) = price_map.get(&sym).copied() {
-                let qty = match self.positions.get(&sym) {
-                    Some(pos) if !pos.is_flat() => -pos.quantity,
-                    _ => continue,
-                };
-                self.execute_fill(sym, qty, price);
+        // Rebalance each target. A repeated symbol is sized against its
+        // earlier fills, so the last weight wins.
+        let repeats = target_map.len() < targets.len();
+        let mut planned: FxHashMap<Symbol, i64> = FxHashMap::default();
+        for &(sym, target_weight) in targets {
+            let price = match fill_price(&sym) {
+                Some(p) if p > 0 => p,
+                _ => continue,
+            };
+
+            let mut held = self.positions.get(&sym).map_or(0, |p| p.quantity);
+            if repeats {
+                held += planned.get(&sym).copied().unwrap_or(0);
+            }
+            let current_value = held * price;
+
+            let target_value = (equity as f64 * target_weight) as i64;
+            let diff_value = target_value - current_value;
+
+            // Convert value difference to shares
+            let diff_qty = diff_value / price;
+            if diff_qty != 0 {
+                fills.push((sym, diff_qty, price));
+                if repeats {
+                    *planned.entry(sym).or_insert(0) += diff_qty;
+                }
             }
         }
 
-        // Rebalance each target
+        if self.rebalance_order == RebalanceOrder::SellsFirst {
+            // Stable, so each side keeps the listed order
+            fills.sort_by_key(|&(_, qty, _)| qty > 0);
+        }
+        fills
+    }
+
+    /// Batched [`rebalance_simple`](Self::rebalance_simple) for dense
+    /// universes.
+    ///
+    /// Each target is filled in place through a single position lookup, and
+    /// the scan for positions to close is skipped when every held position
+    /// was targeted, which is the common case for a stable universe. Buys
+    /// are never capped at the cash on hand, so the resulting positions,
+    /// cash, and turnover are identical to `rebalance_simple` with
+    /// [`with_leverage`](Self::with_leverage) allowed; only the order in
+    /// which fills are applied differs.
+    pub fn rebalance_simple_batched(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+    ) {
+        let targets = &*self.policy_targets(targets);
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let equity = self.total_equity_from_price_map(&price_map);
+        if equity <= 0 {
+            return;
+        }
+        let was_flat = self.is_all_cash();
+        let held = self.positions.len();
+        let mut traded = 0_i64;
+        let mut cash_delta = 0_i64;
+        let mut hits: Vec<Symbol> = Vec::with_capacity(held.min(targets.len()));
+        let mut opened = false;
+
+        // Target fills are independent of the closes below: each depends
+        // only on its own position and the pre-trade equity.
         for &(sym, target_weight) in targets {
             let price = match price_map.get(&sym).copied() {
                 Some(p) if p > 0 => p,
                 _ => continue,
             };
+            let target_value = (equity as f64 * target_weight) as i64;
+            let diff_qty = match self.positions.get_mut(&sym) {
+                Some(pos) => {
+                    hits.push(sym);
+                    let diff_qty = (target_value - pos.market_value(price)) / price;
+                    pos.apply_fill(diff_qty, price);
+                    diff_qty
+                }
+                None => {
+                    let diff_qty = target_value / price;
+                    if diff_qty != 0 {
+                        let mut pos = Position::with_cost_basis(sym, self.cost_basis);
+                        pos.apply_fill(diff_qty, price);
+                        self.positions.insert(sym, pos);
+                        opened = true;
+                    }
+                    diff_qty
+                }
+            };
+            if diff_qty != 0 {
+                let notional = diff_qty.saturating_abs().saturating_mul(price);
+                let cost = self.cost_model.compute_fill_cost_with_quantity(
+                    notional,
+                    diff_qty,
+                    Liquidity::Taker,
+                );
+                cash_delta =
+                    cash_delta.saturating_add(diff_qty.saturating_mul(price).saturating_add(cost));
+                traded = traded.saturating_add(notional);
+            }
+        }
+        self.cash = self.cash.saturating_sub(cash_delta);
+
+        // Close positions not in targets, unless every held one was hit.
+        // Hits only count pre-existing positions when nothing was opened,
+        // and duplicate targets can inflate the count, so confirm with a sort.
+        let all_hit = !opened && hits.len() == held && {
+            hits.sort_unstable();
+            hits.windows(2).all(|w| w[0] != w[1])
+        };
+        if !all_hit {
+            let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
+            let to_close: Vec<(Symbol, i64, i64)> = self
+                .positions
+                .iter()
+                .filter(|(sym, pos)| !pos.is_flat() && !target_map.contains_key(sym))
+                .filter_map(|(sym, pos)| Some((*sym, -pos.quantity, *price_map.get(sym)?)))
+                .collect();
+            for (sym, qty, price) in to_close {
+                self.execute_fill(sym, qty, price);
+                traded = traded.saturating_add(qty.saturating_abs().saturating_mul(price));
+            }
+        }
+        self.add_turnover(was_flat, traded, equity);
+    }
+
+    /// Rebalance toward target weights, trading only where the reduction in
+    /// tracking error pays for the transaction cost.
+    ///
+    /// This sits between a full [`rebalance_simple`](Self::rebalance_simple)
+    /// and a fixed-band rebalance: the no-trade band is derived from the
+    /// portfolio's cost model instead of being chosen by hand.
+    ///
+    /// # Model
+    ///
+    /// For each symbol let `d = w_target - w_current` be the weight deviation
+    /// and `r = (commission_bps + slippage_bps) / 10_000` the proportional
+    /// cost rate. Holding a deviation is penalized as `½ · aversion · d²`
+    /// (per unit of equity), and trading `Δ` weight costs `r · |Δ|`.
+    /// Minimizing penalty plus cost gives a no-trade band of half-width
+    /// `b = r / aversion` around the target:
+    ///
+    /// - `|d| ≤ b`: no trade.
+    /// - `|d| > b`: trade only up to the band edge, `Δ = d - sign(d) · b`.
+    ///
+    /// A trade that passes the band test is still skipped when its penalty
+    /// reduction `½ · aversion · (d² - b²) · equity` is below the cost the
+    /// model actually charges (which includes `min_trade_fee`).
+    ///
+    /// Larger `aversion` narrows the band; `f64::INFINITY` degenerates to a
+    /// full rebalance. A non-positive or NaN `aversion` never trades.
+    ///
+    /// The costs come from the portfolio's own cost model rather than a
+    /// `cost_model` argument: that model charges every fill, so pricing the
+    /// decision with another one would let the band disagree with what the
+    /// trades actually cost. `aversion` is the parameter the caller is left
+    /// to choose, since the cost alone cannot say what a deviation is worth.
+    ///
+    /// Unlike `rebalance_simple`, held symbols missing from `targets` are
+    /// treated as target weight 0 and are subject to the same band, so small
+    /// residual positions are not force-closed. Trades are sized in symbol
+    /// order from the pre-trade equity, executed in the configured
+    /// [`RebalanceOrder`] (sells first by default), and fill at the
+    /// provided prices.
+    ///
+    /// Returns the fills actually executed, in execution order.
+    pub fn rebalance_cost_aware(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        prices: &[(Symbol, i64)],
+        aversion: f64,
+    ) -> Vec<RebalanceFill> {
+        let targets = &*self.policy_targets(targets);
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let equity = self.total_equity_from_price_map(&price_map);
+        if equity <= 0 || aversion.is_nan() || aversion <= 0.0 {
+            return Vec::new();
+        }
+
+        let rate = (self.cost_model.commission_bps as f64 + self.cost_model.slippage_bps as f64)
+            / 10_000.0;
+        let band = rate / aversion;
+        let was_flat = self.is_all_cash();
+
+        let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
+        let mut symbols: Vec<Symbol> = target_map
+            .keys()
+            .chain(self.positions.keys())
+            .copied()
+            .collect();
+        symbols.sort();
+        symbols.dedup();
 
+        let mut planned = Vec::new();
+        for sym in symbols {
+            let price = match price_map.get(&sym).copied() {
+                Some(p) if p > 0 => p,
+                _ => continue,
+            };
             let current_value = self
                 .positions
                 .get(&sym)
                 .map(|p| p.market_value(price))
                 .unwrap_or(0);
+            let target = target_map.get(&sym).copied().unwrap_or(0.0);
+            let deviation = target - current_value as f64 / equity as f64;
+            if deviation.abs() <= band {
+                continue;
+            }
 
-            let target_value = (equity as f64 * target_weight) as i64;
-            let diff_value = target_value - current_value;
+            let trade_weight = deviation - band.copysign(deviation);
+            let qty = (equity as f64 * trade_weight) as i64 / price;
+            if qty == 0 {
+                continue;
+            }
 
-            // Convert value difference to shares
-            let diff_qty = diff_value / price;
-            if diff_qty != 0 {
-                self.execute_fill(sym, diff_qty, price);
+            let cost = self
+                .cost_model
+                .compute_cost_with_quantity(qty.saturating_abs().saturating_mul(price), qty);
+            let benefit = 0.5 * aversion * (deviation * deviation - band * band) * equity as f64;
+            if benefit < cost as f64 {
+                continue;
             }
+            planned.push((sym, qty, price));
+        }
+
+        if self.rebalance_order == RebalanceOrder::SellsFirst {
+            // Stable, so each side keeps the symbol order
+            planned.sort_by_key(|&(_, qty, _)| qty > 0);
         }
+        let fills: Vec<RebalanceFill> = planned
+            .into_iter()
+            .map(|(symbol, quantity, price)| RebalanceFill {
+                symbol,
+                quantity,
+                price,
+                cost: self.execute_fill(symbol, quantity, price),
+            })
+            .collect();
+        let traded = fills.iter().fold(0_i64, |acc, f| {
+            acc.saturating_add(f.quantity.saturating_abs().saturating_mul(f.price))
+        });
+        self.add_turnover(was_flat, traded, equity);
+        fills
     }
 
     /// Close a single symbol position at the provided price.
@@ -269,32 +1116,49 @@ impl Portfolio {
     ///
     /// `targets`: desired (symbol, weight) pairs.
     /// `exchanges`: mutable reference to a `MultiExchange` containing per-symbol LOBs.
+    ///
+    /// Fills pay the cost model's taker rate. With
+    /// [`CostModel::half_spread`] set, they instead pay half of the spread
+    /// quoted just before each order (see
+    /// [`CostModel::compute_spread_cost`]); orders into a one-sided book
+    /// fall back to the taker rate.
     pub fn rebalance_lob(
         &mut self,
         targets: &[(Symbol, f64)],
         exchanges: &mut crate::multi_exchange::MultiExchange,
     ) {
-        // Collect current prices from exchange BBO
-        let price_map: FxHashMap<Symbol, i64> = exchanges
-            .symbols()
-            .filter_map(|sym| {
-                let ex = exchanges.get(sym)?;
-                let mid = {
-                    let (bid, ask) = ex.best_bid_ask();
-                    match (bid, ask) {
-                        (Some(b), Some(a)) => b.0 + (a.0 - b.0) / 2,
-                        (Some(b), None) => b.0,
-                        (None, Some(a)) => a.0,
-                        (None, None) => return None,
-                    }
-                };
-                Some((*sym, mid))
-            })
-            .collect();
+        self.rebalance_lob_with_fill_ratio(targets, exchanges, 1.0);
+    }
+
+    /// Like [`rebalance_lob`](Self::rebalance_lob), but each order fills
+    /// only `fill_ratio` of the liquidity it could take.
+    ///
+    /// An order for `q` shares against `a` marketable shares is sent as a
+    /// market order for `floor(fill_ratio × min(q, a))`, so the rest is
+    /// never filled. The scaling is deterministic. A ratio of 1.0 is
+    /// exactly `rebalance_lob`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fill_ratio` is not in `(0, 1]`.
+    pub fn rebalance_lob_with_fill_ratio(
+        &mut self,
+        targets: &[(Symbol, f64)],
+        exchanges: &mut crate::multi_exchange::MultiExchange,
+        fill_ratio: f64,
+    ) {
+        assert!(
+            fill_ratio > 0.0 && fill_ratio <= 1.0,
+            "fill_ratio must be in (0, 1], got {fill_ratio}"
+        );
+        let targets = &*self.policy_targets(targets);
+        let price_map = lob_mid_prices(exchanges);
         let equity = self.total_equity_from_price_map(&price_map);
         if equity <= 0 {
             return;
         }
+        let was_flat = self.is_all_cash();
+        let mut traded = 0_i64;
 
         let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
 
@@ -319,6 +1183,11 @@ impl Portfolio {
                 _ => continue,
             };
             let exchange = exchanges.get_or_create(&sym);
+            let spread = exchange.spread();
+            let qty = scaled_fill_quantity(exchange, side, qty, fill_ratio);
+            if qty == 0 {
+                continue;
+            }
             let result = exchange.submit_market(side, qty);
             for trade in &result.trades {
                 let fill_qty = if side == crate::Side::Sell {
@@ -326,7 +1195,9 @@ impl Portfolio {
                 } else {
                     trade.quantity as i64
                 };
-                self.execute_fill(sym, fill_qty, trade.price.0);
+                self.execute_lob_fill(sym, fill_qty, trade.price.0, spread);
+                traded =
+                    traded.saturating_add((trade.quantity as i64).saturating_mul(trade.price.0));
             }
         }
 
@@ -358,6 +1229,11 @@ impl Portfolio {
             };
 
             let exchange = exchanges.get_or_create(&sym);
+            let spread = exchange.spread();
+            let diff_qty = scaled_fill_quantity(exchange, side, diff_qty, fill_ratio);
+            if diff_qty == 0 {
+                continue;
+            }
             let result = exchange.submit_market(side, diff_qty);
             for trade in &result.trades {
                 let fill_qty = if side == crate::Side::Buy {
@@ -365,9 +1241,152 @@ impl Portfolio {
                 } else {
                     -(trade.quantity as i64)
                 };
-                self.execute_fill(sym, fill_qty, trade.price.0);
+                self.execute_lob_fill(sym, fill_qty, trade.price.0, spread);
+                traded =
+                    traded.saturating_add((trade.quantity as i64).saturating_mul(trade.price.0));
+            }
+        }
+        self.add_turnover(was_flat, traded, equity);
+    }
+
+    /// Estimate what [`rebalance_lob`](Self::rebalance_lob) would cost,
+    /// without touching the books or the portfolio.
+    ///
+    /// Each order `rebalance_lob` would send is priced by walking its book
+    /// with [`Exchange::sweep_vwap`](crate::Exchange::sweep_vwap)'s logic.
+    /// Slippage is the fill notional's shortfall against the pre-trade mid
+    /// (the price `rebalance_lob` sizes orders at), and commission is the
+    /// cost model's charge on each resting order hit, taker or half-spread,
+    /// as `rebalance_lob` charges it. Orders the book can only partly fill are
+    /// costed on the fillable part.
+    ///
+    /// ```
+    /// use nanobook::portfolio::{CostModel, Portfolio};
+    /// use nanobook::{MultiExchange, Price, Side, Symbol, TimeInForce};
+    ///
+    /// let aapl = Symbol::new("AAPL");
+    /// let mut multi = MultiExchange::new();
+    /// let ex = multi.get_or_create(&aapl);
+    /// ex.submit_limit(Side::Buy, Price(99_00), 1_000, TimeInForce::GTC);
+    /// ex.submit_limit(Side::Sell, Price(101_00), 500, TimeInForce::GTC);
+    /// ex.submit_limit(Side::Sell, Price(103_00), 500, TimeInForce::GTC);
+    ///
+    /// let portfolio = Portfolio::new(100_000_00, CostModel::zero());
+    /// let est = portfolio.estimate_rebalance_cost(&[(aapl, 1.0)], &multi);
+    /// // 1_000 shares at a $100 mid: 500 @ 101 + 500 @ 103 = $2_000 over mid.
+    /// assert_eq!(est.symbols[0].filled_quantity, 1_000);
+    /// assert_eq!(est.slippage, 2_000_00);
+    /// assert_eq!(multi.get(&aapl).unwrap().best_ask(), Some(Price(101_00)));
+    /// ```
+    pub fn estimate_rebalance_cost(
+        &self,
+        targets: &[(Symbol, f64)],
+        exchanges: &crate::multi_exchange::MultiExchange,
+    ) -> RebalanceCostEstimate {
+        let targets = &*self.policy_targets(targets);
+        let price_map = lob_mid_prices(exchanges);
+        let mut estimate = RebalanceCostEstimate::default();
+        let equity = self.total_equity_from_price_map(&price_map);
+        if equity <= 0 {
+            return estimate;
+        }
+
+        let target_map: FxHashMap<Symbol, f64> = targets.iter().copied().collect();
+        let mut orders: Vec<(Symbol, i64)> = self
+            .positions
+            .iter()
+            .filter(|(sym, pos)| !pos.is_flat() && !target_map.contains_key(sym))
+            .map(|(sym, pos)| (*sym, -pos.quantity))
+            .collect();
+        orders.sort_by_key(|&(sym, _)| sym);
+        for &(sym, target_weight) in targets {
+            let price = match price_map.get(&sym).copied() {
+                Some(p) if p > 0 => p,
+                _ => continue,
+            };
+            let current_value = self
+                .positions
+                .get(&sym)
+                .map(|p| p.market_value(price))
+                .unwrap_or(0);
+            let target_value = (equity as f64 * target_weight) as i64;
+            let diff_qty = (target_value - current_value) / price;
+            if diff_qty != 0 {
+                orders.push((sym, diff_qty));
+            }
+        }
+
+        for (symbol, quantity) in orders {
+            let side = if quantity > 0 {
+                crate::Side::Buy
+            } else {
+                crate::Side::Sell
+            };
+            let fills = exchanges
+                .get(&symbol)
+                .map(|ex| ex.sweep_fills(side, quantity.unsigned_abs()))
+                .unwrap_or_default();
+            let spread = exchanges
+                .get(&symbol)
+                .and_then(|ex| ex.spread())
+                .filter(|_| self.cost_model.half_spread);
+            let reference_price = price_map.get(&symbol).copied().unwrap_or(0);
+
+            let mut filled = 0_i64;
+            let mut notional = 0_i64;
+            let mut commission = 0_i64;
+            for (price, qty) in fills {
+                let fill_notional = price.0.saturating_mul(qty as i64);
+                filled += qty as i64;
+                notional = notional.saturating_add(fill_notional);
+                let cost = match spread {
+                    Some(spread) => {
+                        self.cost_model
+                            .compute_spread_cost(fill_notional, qty as i64, spread)
+                    }
+                    None => self.cost_model.compute_fill_cost_with_quantity(
+                        fill_notional,
+                        qty as i64,
+                        Liquidity::Taker,
+                    ),
+                };
+                commission = commission.saturating_add(cost);
             }
+            let at_mid = filled.saturating_mul(reference_price);
+            let slippage = match side {
+                crate::Side::Buy => notional - at_mid,
+                crate::Side::Sell => at_mid - notional,
+            };
+
+            estimate.slippage = estimate.slippage.saturating_add(slippage);
+            estimate.commission = estimate.commission.saturating_add(commission);
+            estimate.symbols.push(SymbolCostEstimate {
+                symbol,
+                quantity,
+                filled_quantity: filled * quantity.signum(),
+                reference_price,
+                vwap: (filled > 0).then(|| notional / filled),
+                slippage,
+                commission,
+            });
         }
+        estimate
+    }
+
+    /// Apply one exchange trade in which `order_id` took part.
+    ///
+    /// The position changes by the trade quantity on `order_id`'s side, and
+    /// the cost model charges the maker rate if `order_id` was resting or
+    /// the taker rate if it was the aggressor. Returns the cost charged, or
+    /// `None` (leaving the portfolio unchanged) if `order_id` is not in the
+    /// trade.
+    pub fn apply_trade(&mut self, symbol: Symbol, trade: &Trade, order_id: OrderId) -> Option<i64> {
+        let liquidity = trade.liquidity(order_id)?;
+        let qty = match trade.side_of(order_id)? {
+            Side::Buy => trade.quantity as i64,
+            Side::Sell => -(trade.quantity as i64),
+        };
+        Some(self.execute_fill_with(symbol, qty, trade.price.0, liquidity))
     }
 
     /// Record a return for the current period.
@@ -379,14 +1398,55 @@ impl Portfolio {
         self.record_return_from_price_map(&price_map);
     }
 
+    /// Record a return for the current period, valuing positions in the
+    /// base currency.
+    ///
+    /// Equity is [`total_equity_fx`](Self::total_equity_fx) at `prices`
+    /// and `fx_rates`; everything else is as in
+    /// [`record_return`](Self::record_return), which this matches when
+    /// every rate is 1.0.
+    pub fn record_return_fx(&mut self, prices: &[(Symbol, i64)], fx_rates: &[(Symbol, f64)]) {
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let fx: FxHashMap<Symbol, f64> = fx_rates.iter().copied().collect();
+        self.record_return_fx_map(&price_map, &fx);
+    }
+
     pub(crate) fn record_return_from_price_map(&mut self, price_map: &FxHashMap<Symbol, i64>) {
-        let equity = self.total_equity_from_price_map(price_map);
+        self.record_return_fx_map(price_map, &FxHashMap::default());
+    }
+
+    fn record_return_fx_map(
+        &mut self,
+        price_map: &FxHashMap<Symbol, i64>,
+        fx: &FxHashMap<Symbol, f64>,
+    ) {
+        if self.cash_yield > 0.0 && self.accrual_cash > 0 {
+            let interest = (self.accrual_cash as f64 * self.cash_yield).round() as i64;
+            self.cash = self.cash.saturating_add(interest);
+            self.cash_interest = self.cash_interest.saturating_add(interest);
+        }
+        let mut equity = self.total_equity_fx_map(price_map, fx);
+        if self.incentive_fee > 0.0 {
+            let threshold = self.high_water_mark as f64 * (1.0 + self.hurdle);
+            let excess = equity as f64 - threshold;
+            if excess > 0.0 {
+                let fee = (excess * self.incentive_fee).round() as i64;
+                self.cash = self.cash.saturating_sub(fee);
+                self.incentive_fees = self.incentive_fees.saturating_add(fee);
+                equity -= fee;
+                self.high_water_mark = equity;
+            }
+        }
         if self.prev_equity > 0 {
             let ret = (equity - self.prev_equity) as f64 / self.prev_equity as f64;
             self.returns.push(ret);
         }
         self.equity_curve.push(equity);
         self.prev_equity = equity;
+        self.accrual_cash = self.cash;
+        for pos in self.positions.values_mut() {
+            pos.advance_period();
+        }
     }
 
     /// Take a snapshot of the portfolio state.
@@ -394,14 +1454,38 @@ impl Portfolio {
         let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
         let equity = self.total_equity_from_price_map(&price_map);
         let weights = self.current_weights_from_price_map(&price_map, equity);
-        let total_realized_pnl: i64 = self.positions.values().map(|p| p.realized_pnl).sum();
+        let total_realized_pnl = self.realized_pnl();
+
+        let mut positions: Vec<PositionSnapshot> = self
+            .positions
+            .iter()
+            .filter(|(_, pos)| !pos.is_flat())
+            .map(|(sym, pos)| {
+                let price = price_map.get(sym).copied().unwrap_or(0);
+                PositionSnapshot {
+                    symbol: *sym,
+                    quantity: pos.quantity,
+                    avg_entry_price: pos.avg_entry_price,
+                    unrealized_pnl: pos.unrealized_pnl(price),
+                    weight: if equity == 0 {
+                        0.0
+                    } else {
+                        pos.market_value(price) as f64 / equity as f64
+                    },
+                    holding_period: pos.holding_period(),
+                }
+            })
+            .collect();
+        positions.sort_by_key(|p| p.symbol);
 
         PortfolioSnapshot {
             cash: self.cash,
             equity,
             weights,
-            num_positions: self.positions.values().filter(|p| !p.is_flat()).count(),
+            num_positions: positions.len(),
             total_realized_pnl,
+            positions,
+            average_holding_period: self.average_holding_period(),
         }
     }
 
@@ -424,13 +1508,18 @@ impl Portfolio {
     // === Internal ===
 
     pub(crate) fn total_equity_from_price_map(&self, price_map: &FxHashMap<Symbol, i64>) -> i64 {
+        self.total_equity_fx_map(price_map, &FxHashMap::default())
+    }
+
+    fn total_equity_fx_map(
+        &self,
+        price_map: &FxHashMap<Symbol, i64>,
+        fx: &FxHashMap<Symbol, f64>,
+    ) -> i64 {
         let position_value: i64 = self
             .positions
             .iter()
-            .map(|(sym, pos)| {
-                let price = price_map.get(sym).copied().unwrap_or(0);
-                pos.market_value(price)
-            })
+            .map(|(sym, pos)| self.base_value(sym, pos, price_map, fx))
             .sum();
         self.cash + position_value
     }
@@ -439,6 +1528,15 @@ impl Portfolio {
         &self,
         price_map: &FxHashMap<Symbol, i64>,
         equity: i64,
+    ) -> Vec<(Symbol, f64)> {
+        self.current_weights_fx_map(price_map, &FxHashMap::default(), equity)
+    }
+
+    fn current_weights_fx_map(
+        &self,
+        price_map: &FxHashMap<Symbol, i64>,
+        fx: &FxHashMap<Symbol, f64>,
+        equity: i64,
     ) -> Vec<(Symbol, f64)> {
         if equity == 0 {
             return Vec::new();
@@ -448,36 +1546,213 @@ impl Portfolio {
             .iter()
             .filter(|(_, pos)| !pos.is_flat())
             .map(|(sym, pos)| {
-                let price = price_map.get(sym).copied().unwrap_or(0);
-                let mv = pos.market_value(price) as f64;
+                let mv = self.base_value(sym, pos, price_map, fx) as f64;
                 (*sym, mv / equity as f64)
             })
             .collect()
     }
 
-    /// Execute a fill: update position, deduct cost, adjust cash.
-    fn execute_fill(&mut self, symbol: Symbol, qty: i64, price: i64) {
-        if qty == 0 {
-            return;
+    /// Market value of `pos` converted to the base currency.
+    fn base_value(
+        &self,
+        sym: &Symbol,
+        pos: &Position,
+        price_map: &FxHashMap<Symbol, i64>,
+        fx: &FxHashMap<Symbol, f64>,
+    ) -> i64 {
+        let value = pos.market_value(price_map.get(sym).copied().unwrap_or(0));
+        self.to_base(sym, value, fx)
+    }
+
+    /// `amount` in `sym`'s currency converted to the base currency at its
+    /// rate (1.0 if it has none or the rate is missing).
+    fn to_base(&self, sym: &Symbol, amount: i64, fx: &FxHashMap<Symbol, f64>) -> i64 {
+        match self.currencies.get(sym).and_then(|c| fx.get(c)) {
+            Some(&rate) if rate != 1.0 => (amount as f64 * rate).round() as i64,
+            _ => amount,
+        }
+    }
+
+    /// True when no position is open.
+    fn is_all_cash(&self) -> bool {
+        self.positions.values().all(Position::is_flat)
+    }
+
+    /// Add a rebalance's traded notional to the turnover, unless it
+    /// started from an all-cash book.
+    fn add_turnover(&mut self, was_flat: bool, traded: i64, equity: i64) {
+        if !was_flat && equity > 0 {
+            self.turnover += traded as f64 / equity as f64 / 2.0;
+        }
+    }
+
+    /// Execute an aggressive (taker) fill: update position, deduct cost,
+    /// adjust cash.
+    fn execute_fill(&mut self, symbol: Symbol, qty: i64, price: i64) -> i64 {
+        self.execute_fill_with(symbol, qty, price, Liquidity::Taker)
+    }
+
+    /// Execute a fill charged at the maker or taker rate.
+    fn execute_fill_with(
+        &mut self,
+        symbol: Symbol,
+        qty: i64,
+        price: i64,
+        liquidity: Liquidity,
+    ) -> i64 {
+        if qty == 0 {
+            return 0;
+        }
+
+        let notional = qty.saturating_abs().saturating_mul(price);
+        let cost = self
+            .cost_model
+            .compute_fill_cost_with_quantity(notional, qty, liquidity);
+        self.apply_fill_at_cost(symbol, qty, price, cost)
+    }
+
+    /// Execute a `rebalance_lob` fill, charging half of the pre-trade
+    /// `spread` when the cost model asks for it and a spread was quoted.
+    fn execute_lob_fill(&mut self, symbol: Symbol, qty: i64, price: i64, spread: Option<i64>) {
+        match spread.filter(|_| self.cost_model.half_spread) {
+            Some(spread) => {
+                let notional = qty.saturating_abs().saturating_mul(price);
+                let cost = self.cost_model.compute_spread_cost(notional, qty, spread);
+                self.apply_fill_at_cost(symbol, qty, price, cost);
+            }
+            None => {
+                self.execute_fill(symbol, qty, price);
+            }
         }
+    }
 
-        let notional = qty.saturating_abs().saturating_mul(price);
-        let cost = self.cost_model.compute_cost(notional);
+    /// Update the position and cash for a fill whose cost is already known.
+    fn apply_fill_at_cost(&mut self, symbol: Symbol, qty: i64, price: i64, cost: i64) -> i64 {
+        if qty == 0 {
+            return 0;
+        }
 
         // Update position
         let pos = self
             .positions
             .entry(symbol)
-            .or_insert_with(|| Position::new(symbol));
+            .or_insert_with(|| Position::with_cost_basis(symbol, self.cost_basis));
         pos.apply_fill(qty, price);
 
         // Adjust cash: buying decreases cash, selling increases it
         self.cash = self
             .cash
             .saturating_sub(qty.saturating_mul(price).saturating_add(cost));
+        cost
+    }
+}
+
+/// A fill executed by a portfolio rebalance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RebalanceFill {
+    /// Symbol traded
+    pub symbol: Symbol,
+    /// Signed quantity (positive = buy, negative = sell)
+    pub quantity: i64,
+    /// Fill price (cents)
+    pub price: i64,
+    /// Transaction cost charged (cents); negative for a maker rebate
+    pub cost: i64,
+}
+
+/// A buy that [`Portfolio::rebalance_simple`] cut short for lack of cash.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RebalanceShortfall {
+    /// Symbol bought
+    pub symbol: Symbol,
+    /// Quantity the target called for
+    pub requested: i64,
+    /// Quantity actually bought (may be 0)
+    pub filled: i64,
+    /// Fill price (cents)
+    pub price: i64,
+}
+
+impl RebalanceShortfall {
+    /// Quantity left unbought.
+    pub fn unfilled(&self) -> i64 {
+        self.requested - self.filled
     }
 }
 
+/// Estimated cost of one order in a [`RebalanceCostEstimate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SymbolCostEstimate {
+    /// Symbol traded
+    pub symbol: Symbol,
+    /// Signed quantity the rebalance would send (positive = buy)
+    pub quantity: i64,
+    /// Signed quantity the book can fill
+    pub filled_quantity: i64,
+    /// Pre-trade mid price the order is sized at (cents; 0 if no quotes)
+    pub reference_price: i64,
+    /// Volume-weighted fill price (cents; `None` if nothing fills)
+    pub vwap: Option<i64>,
+    /// Fill notional paid above (buys) or received below (sells) the mid
+    pub slippage: i64,
+    /// Cost-model charge on the fills (cents)
+    pub commission: i64,
+}
+
+/// Pre-trade estimate returned by [`Portfolio::estimate_rebalance_cost`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RebalanceCostEstimate {
+    /// One entry per order: closes first (by symbol), then targets in order
+    pub symbols: Vec<SymbolCostEstimate>,
+    /// Total slippage against the mid (cents)
+    pub slippage: i64,
+    /// Total commission (cents)
+    pub commission: i64,
+}
+
+impl RebalanceCostEstimate {
+    /// Slippage plus commission (cents).
+    pub fn total(&self) -> i64 {
+        self.slippage.saturating_add(self.commission)
+    }
+}
+
+/// Mid price of every quoted book (the one-sided price if only one side
+/// has orders), as [`Portfolio::rebalance_lob`] values positions.
+/// `quantity` cut to `fill_ratio` of what a market order could fill now.
+fn scaled_fill_quantity(
+    exchange: &crate::Exchange,
+    side: Side,
+    quantity: u64,
+    fill_ratio: f64,
+) -> u64 {
+    if fill_ratio >= 1.0 {
+        return quantity;
+    }
+    let price = match side {
+        Side::Buy => crate::Price::MAX,
+        Side::Sell => crate::Price::MIN,
+    };
+    let marketable = quantity.min(exchange.book().available_to_fill(side, price));
+    (marketable as f64 * fill_ratio) as u64
+}
+
+fn lob_mid_prices(exchanges: &crate::multi_exchange::MultiExchange) -> FxHashMap<Symbol, i64> {
+    exchanges
+        .symbols()
+        .filter_map(|sym| {
+            let ex = exchanges.get(sym)?;
+            let mid = match ex.best_bid_ask() {
+                (Some(b), Some(a)) => b.0 + (a.0 - b.0) / 2,
+                (Some(b), None) => b.0,
+                (None, Some(a)) => a.0,
+                (None, None) => return None,
+            };
+            Some((*sym, mid))
+        })
+        .collect()
+}
+
 /// A point-in-time snapshot of portfolio state.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -492,6 +1767,34 @@ pub struct PortfolioSnapshot {
     pub num_positions: usize,
     /// Total realized PnL across all positions
     pub total_realized_pnl: i64,
+    /// Non-flat positions, sorted by symbol
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub positions: Vec<PositionSnapshot>,
+    /// [`Portfolio::average_holding_period`] at the snapshot
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub average_holding_period: Option<f64>,
+}
+
+/// One position within a [`PortfolioSnapshot`].
+///
+/// Valued at the snapshot's prices; a symbol without a price is valued at
+/// 0, as in [`Portfolio::total_equity`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionSnapshot {
+    /// Instrument
+    pub symbol: Symbol,
+    /// Signed quantity (negative = short)
+    pub quantity: i64,
+    /// Average entry price (cents)
+    pub avg_entry_price: i64,
+    /// Unrealized PnL at the snapshot price (cents)
+    pub unrealized_pnl: i64,
+    /// Market value as a fraction of equity
+    pub weight: f64,
+    /// Average periods the open quantity has been held
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub holding_period: f64,
 }
 
 #[cfg(test)]
@@ -512,6 +1815,28 @@ mod tests {
         assert_eq!(portfolio.total_equity(&[]), 1_000_000_00);
     }
 
+    #[test]
+    fn price_scale_agrees_with_cents_in_currency() {
+        let model = CostModel {
+            commission_bps: 10,
+            min_trade_fee: 1_00,
+            ..CostModel::zero()
+        };
+        let mut cents = Portfolio::new(100_000_00, model);
+        let mut fine =
+            Portfolio::new(100_000_0000, model.rescaled(100, 10_000)).with_price_scale(10_000);
+        assert_eq!(fine.price_scale(), 10_000);
+
+        cents.rebalance_simple(&[(aapl(), 0.5)], &[(aapl(), 150_00)]);
+        fine.rebalance_simple(&[(aapl(), 0.5)], &[(aapl(), 150_0000)]);
+        let equity = |p: &Portfolio, price| p.to_currency(p.total_equity(&[(aapl(), price)]));
+        assert_eq!(equity(&cents, 151_00), equity(&fine, 151_0000));
+        assert_eq!(
+            cents.to_currency(cents.cash()),
+            fine.to_currency(fine.cash())
+        );
+    }
+
     #[test]
     fn simple_buy() {
         let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
@@ -546,8 +1871,7 @@ mod tests {
     fn cost_model_deducts_fees() {
         let model = CostModel {
             commission_bps: 10,
-            slippage_bps: 0,
-            min_trade_fee: 0,
+            ..CostModel::zero()
         };
         let mut portfolio = Portfolio::new(1_000_000_00, model);
         let prices = [(aapl(), 150_00)];
@@ -560,6 +1884,140 @@ mod tests {
         assert!(equity < 1_000_000_00);
     }
 
+    #[test]
+    fn apply_trade_uses_maker_and_taker_rates() {
+        use crate::{Price, TradeId};
+
+        let model = CostModel {
+            maker_bps: 1,
+            taker_bps: 5,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(1_000_000_00, model);
+        // Order 7 rests (sell), order 9 lifts it (buy)
+        let trade = Trade::new(
+            TradeId(1),
+            Price(100_00),
+            1_000,
+            OrderId(9),
+            OrderId(7),
+            Side::Buy,
+            1,
+        );
+
+        // As the maker we sold: 1 bps on $100,000
+        assert_eq!(
+            portfolio.apply_trade(aapl(), &trade, OrderId(7)),
+            Some(10_00)
+        );
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, -1_000);
+        assert_eq!(portfolio.cash(), 1_000_000_00 + 100_000_00 - 10_00);
+
+        // As the taker we bought: 5 bps
+        assert_eq!(
+            portfolio.apply_trade(aapl(), &trade, OrderId(9)),
+            Some(50_00)
+        );
+        assert!(portfolio.position(&aapl()).unwrap().is_flat());
+
+        let cash = portfolio.cash();
+        assert_eq!(portfolio.apply_trade(aapl(), &trade, OrderId(1)), None);
+        assert_eq!(portfolio.cash(), cash);
+    }
+
+    #[test]
+    fn gross_and_net_exposure() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        assert_eq!(portfolio.gross_exposure(&prices), 0.0);
+        assert_eq!(portfolio.net_exposure(&prices), 0.0);
+
+        // 130/30: long 130%, short 30%
+        portfolio.rebalance_simple(&[(aapl(), 1.3), (msft(), -0.3)], &prices);
+        assert!((portfolio.gross_exposure(&prices) - 1.6).abs() < 1e-9);
+        assert!((portfolio.net_exposure(&prices) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn incentive_fee_charged_on_new_highs_above_hurdle() {
+        let mut portfolio =
+            Portfolio::new(1_000_000_00, CostModel::zero()).with_incentive_fee(0.2, 0.05);
+        portfolio.rebalance_simple(&[(aapl(), 1.0)], &[(aapl(), 100_00)]);
+
+        // +4%: below the 5% hurdle, no fee
+        portfolio.record_return(&[(aapl(), 104_00)]);
+        assert_eq!(portfolio.incentive_fees(), 0);
+
+        // +10%: 20% of the $50,000 above the hurdle
+        portfolio.record_return(&[(aapl(), 110_00)]);
+        assert_eq!(portfolio.incentive_fees(), 10_000_00);
+        assert_eq!(portfolio.high_water_mark(), 1_090_000_00);
+        assert_eq!(portfolio.total_equity(&[(aapl(), 110_00)]), 1_090_000_00);
+        assert!((portfolio.returns()[1] - (1_090_000.0 / 1_040_000.0 - 1.0)).abs() < 1e-12);
+
+        // Falling back and recovering to the old high charges nothing
+        portfolio.record_return(&[(aapl(), 100_00)]);
+        portfolio.record_return(&[(aapl(), 110_00)]);
+        assert_eq!(portfolio.incentive_fees(), 10_000_00);
+    }
+
+    #[test]
+    fn cash_yield_accrues_on_idle_cash() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero()).with_cash_yield(0.001);
+        portfolio.rebalance_simple(&[(aapl(), 0.6)], &[(aapl(), 100_00)]);
+
+        // First period: nothing accrues yet
+        portfolio.record_return(&[(aapl(), 100_00)]);
+        assert_eq!(portfolio.cash_interest(), 0);
+
+        // Second period: 10bp on the $400,000 left in cash
+        portfolio.record_return(&[(aapl(), 100_00)]);
+        assert_eq!(portfolio.cash_interest(), 400_00);
+        assert_eq!(portfolio.cash(), 400_400_00);
+        assert!((portfolio.returns()[1] - 0.0004).abs() < 1e-12);
+
+        // Zero yield keeps the old behavior
+        let mut flat = Portfolio::new(1_000_000_00, CostModel::zero());
+        flat.record_return(&[]);
+        flat.record_return(&[]);
+        assert_eq!(flat.cash(), 1_000_000_00);
+        assert_eq!(flat.returns(), &[0.0, 0.0]);
+    }
+
+    #[test]
+    fn maker_rebate_credits_cash() {
+        use crate::{Price, TradeId};
+
+        let model = CostModel {
+            min_trade_fee: 1_00,
+            maker_bps: -2,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(1_000_000_00, model);
+        let prices = [(aapl(), 100_00)];
+
+        let trade = Trade::new(
+            TradeId(1),
+            Price(100_00),
+            1_000,
+            OrderId(9),
+            OrderId(7),
+            Side::Sell,
+            1,
+        );
+        // Resting buy filled: 2 bps rebate on $100,000
+        assert_eq!(
+            portfolio.apply_trade(aapl(), &trade, OrderId(7)),
+            Some(-20_00)
+        );
+        assert_eq!(portfolio.cash(), 1_000_000_00 - 100_000_00 + 20_00);
+
+        // The rebate is income: equity and the period return rise
+        portfolio.record_return(&prices);
+        assert_eq!(portfolio.total_equity(&prices), 1_000_000_00 + 20_00);
+        assert!(portfolio.returns()[0] > 0.0);
+    }
+
     #[test]
     fn rebalance_closes_unneeded_positions() {
         let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
@@ -587,6 +2045,100 @@ mod tests {
         assert!(portfolio.position(&aapl()).unwrap().is_flat());
     }
 
+    #[test]
+    fn cost_aware_skips_small_deviation() {
+        let model = CostModel {
+            commission_bps: 10,
+            slippage_bps: 10,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(1_000_000_00, model);
+        let prices = [(aapl(), 100_00)];
+        portfolio.rebalance_simple(&[(aapl(), 0.50)], &prices);
+        let qty_before = portfolio.position(&aapl()).unwrap().quantity;
+
+        // r = 0.002, aversion = 0.1 → band = 0.02; a 1% drift stays inside it.
+        let fills = portfolio.rebalance_cost_aware(&[(aapl(), 0.51)], &prices, 0.1);
+        assert!(fills.is_empty());
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, qty_before);
+    }
+
+    #[test]
+    fn cost_aware_trades_to_band_edge() {
+        let model = CostModel {
+            commission_bps: 10,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(1_000_000_00, model);
+        let prices = [(aapl(), 100_00)];
+
+        // r = 0.001, aversion = 0.01 → band = 0.1. Target 0.5 from cash
+        // trades only to 0.4 of equity.
+        let fills = portfolio.rebalance_cost_aware(&[(aapl(), 0.5)], &prices, 0.01);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].symbol, aapl());
+        assert_eq!(fills[0].quantity, 4_000);
+        assert_eq!(fills[0].price, 100_00);
+        assert_eq!(fills[0].cost, 400_00);
+        assert_eq!(portfolio.position(&aapl()).unwrap().quantity, 4_000);
+    }
+
+    #[test]
+    fn cost_aware_infinite_aversion_is_full_rebalance() {
+        let mut simple = Portfolio::new(1_000_000_00, CostModel::zero());
+        let mut aware = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 150_00), (msft(), 300_00)];
+        let targets = [(aapl(), 0.6), (msft(), 0.4)];
+
+        simple.rebalance_simple(&targets, &prices);
+        aware.rebalance_cost_aware(&targets, &prices, f64::INFINITY);
+
+        for sym in [aapl(), msft()] {
+            assert_eq!(
+                simple.position(&sym).unwrap().quantity,
+                aware.position(&sym).unwrap().quantity
+            );
+        }
+        assert_eq!(simple.cash(), aware.cash());
+    }
+
+    #[test]
+    fn cost_aware_min_fee_blocks_tiny_trade() {
+        let model = CostModel {
+            min_trade_fee: 1_000_00,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(100_000_00, model);
+        let prices = [(aapl(), 100_00)];
+
+        // band = 0, but the penalty reduction (½·1·0.01²·$100k = $5) is
+        // below the $1,000 minimum fee.
+        let fills = portfolio.rebalance_cost_aware(&[(aapl(), 0.01)], &prices, 1.0);
+        assert!(fills.is_empty());
+        assert_eq!(portfolio.cash(), 100_000_00);
+    }
+
+    #[test]
+    fn cost_aware_sells_before_buying() {
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        let rotate = |order: RebalanceOrder| {
+            let mut p = Portfolio::new(1_000_000_00, CostModel::zero()).with_rebalance_order(order);
+            p.rebalance_simple(&[(msft(), 0.8)], &prices);
+            p.rebalance_cost_aware(&[(aapl(), 0.8)], &prices, f64::INFINITY)
+        };
+
+        let sells_first = rotate(RebalanceOrder::SellsFirst);
+        let symbols: Vec<_> = sells_first.iter().map(|f| f.symbol).collect();
+        assert_eq!(symbols, vec![msft(), aapl()]);
+        assert_eq!(sells_first[0].quantity, -8_000);
+
+        let symbols: Vec<_> = rotate(RebalanceOrder::AsListed)
+            .iter()
+            .map(|f| f.symbol)
+            .collect();
+        assert_eq!(symbols, vec![aapl(), msft()]);
+    }
+
     #[test]
     fn record_return_tracks_equity() {
         let mut portfolio = Portfolio::new(100_00, CostModel::zero());
@@ -603,6 +2155,402 @@ mod tests {
         assert!(ret > 0.0);
     }
 
+    #[test]
+    fn record_return_fx_converts_position_values() {
+        let eur = Symbol::new("EUR");
+        let build = || {
+            let mut p =
+                Portfolio::new(1_000_00, CostModel::zero()).with_currencies([(msft(), eur)]);
+            p.rebalance_simple(
+                &[(aapl(), 0.4), (msft(), 0.4)],
+                &[(aapl(), 10_00), (msft(), 10_00)],
+            );
+            p
+        };
+        let prices = [(aapl(), 10_00), (msft(), 10_00)];
+
+        // Bought with EUR at 1.0; unit rates reproduce record_return exactly.
+        let (mut plain, mut fx) = (build(), build());
+        plain.record_return(&prices);
+        fx.record_return_fx(&prices, &[(eur, 1.0)]);
+        assert_eq!(plain.equity_curve(), fx.equity_curve());
+        assert_eq!(plain.returns(), fx.returns());
+
+        // EUR up 10%: only the 400.00 MSFT position gains.
+        let rates = [(eur, 1.1)];
+        fx.record_return_fx(&prices, &rates);
+        assert_eq!(fx.equity_curve().last(), Some(&1_040_00));
+        assert!((fx.returns()[1] - 0.04).abs() < 1e-12);
+        assert_eq!(fx.total_equity_fx(&prices, &rates), 1_040_00);
+        assert_eq!(fx.total_equity(&prices), 1_000_00);
+
+        let weights: FxHashMap<Symbol, f64> =
+            fx.current_weights_fx(&prices, &rates).into_iter().collect();
+        assert!((weights[&msft()] - 440.0 / 1_040.0).abs() < 1e-12);
+        assert!((weights[&aapl()] - 400.0 / 1_040.0).abs() < 1e-12);
+        assert_eq!(fx.currency(&msft()), Some(eur));
+        assert_eq!(fx.currency(&aapl()), None);
+    }
+
+    #[test]
+    fn fx_rebalance_pays_base_cash_for_local_prices() {
+        let eur = Symbol::new("EUR");
+        let mut p = Portfolio::new(1_000_00, CostModel::zero()).with_currencies([(msft(), eur)]);
+        let prices = [(aapl(), 10_00), (msft(), 10_00)];
+        let rates = [(eur, 1.1)];
+
+        p.rebalance_simple_fx(&[(aapl(), 0.4), (msft(), 0.44)], &prices, &rates);
+        // 40 EUR-priced MSFT shares cost 440.00 in base cash.
+        assert_eq!(p.position(&msft()).unwrap().quantity, 40);
+        assert_eq!(p.position(&msft()).unwrap().avg_entry_price, 11_00);
+        assert_eq!(p.cash(), 1_000_00 - 400_00 - 440_00);
+        assert_eq!(p.total_equity_fx(&prices, &rates), 1_000_00);
+
+        p.record_return_fx(&prices, &rates);
+        assert_eq!(p.returns(), [0.0]);
+    }
+
+    #[test]
+    fn rotation_sells_before_buying() {
+        let prices = [(aapl(), 100_00), (msft(), 50_00)];
+        let invested = |order| {
+            let mut p = Portfolio::new(1_000_000_00, CostModel::zero()).with_rebalance_order(order);
+            p.rebalance_simple(&[(aapl(), 1.0)], &prices);
+            assert_eq!(p.cash(), 0);
+            p
+        };
+        let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+        let fill_price = |s: &Symbol| price_map.get(s).copied();
+        let rotate = [(msft(), 0.5), (aapl(), 0.5)];
+
+        let sells_first = invested(RebalanceOrder::SellsFirst);
+        assert_eq!(
+            sells_first.simple_fills(&rotate, 1_000_000_00, fill_price),
+            vec![(aapl(), -5_000, 100_00), (msft(), 10_000, 50_00)]
+        );
+        let as_listed = invested(RebalanceOrder::AsListed);
+        assert_eq!(
+            as_listed.simple_fills(&rotate, 1_000_000_00, fill_price),
+            vec![(msft(), 10_000, 50_00), (aapl(), -5_000, 100_00)]
+        );
+
+        // Closes come first in either order, so a full exit ends the same.
+        let (mut a, mut b) = (sells_first, as_listed);
+        a.rebalance_simple(&[(msft(), 1.0)], &prices);
+        b.rebalance_simple(&[(msft(), 1.0)], &prices);
+        assert_eq!(a.cash(), 0);
+        assert_eq!(a.cash(), b.cash());
+        assert_eq!(a.position(&msft()).unwrap().quantity, 20_000);
+        assert_eq!(b.position(&msft()).unwrap().quantity, 20_000);
+        assert!(a.position(&aapl()).unwrap().is_flat());
+        assert_eq!(a.turnover(), b.turnover());
+    }
+
+    #[test]
+    fn buys_are_capped_at_cash_unless_leveraged() {
+        let model = CostModel {
+            commission_bps: 10,
+            ..CostModel::zero()
+        };
+        let prices = [(aapl(), 100_00), (msft(), 50_00)];
+
+        // The full-weight buy would cost 100_00 in commission on top.
+        let mut p = Portfolio::new(1_000_000_00, model);
+        let shortfalls = p.rebalance_simple(&[(aapl(), 1.0)], &prices);
+        assert_eq!(
+            shortfalls,
+            vec![RebalanceShortfall {
+                symbol: aapl(),
+                requested: 10_000,
+                filled: 9_990,
+                price: 100_00,
+            }]
+        );
+        assert_eq!(shortfalls[0].unfilled(), 10);
+        assert_eq!(p.cash(), 1_00);
+
+        let mut levered = Portfolio::new(1_000_000_00, model).with_leverage(true);
+        assert!(
+            levered
+                .rebalance_simple(&[(aapl(), 1.0)], &prices)
+                .is_empty()
+        );
+        assert_eq!(levered.cash(), -1_000_00);
+
+        // Listed first, a buy can't spend the proceeds of a later sell.
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_rebalance_order(RebalanceOrder::AsListed);
+        p.rebalance_simple(&[(aapl(), 1.0)], &prices);
+        let shortfalls = p.rebalance_simple(&[(msft(), 0.5), (aapl(), 0.5)], &prices);
+        assert_eq!(shortfalls.len(), 1);
+        assert_eq!((shortfalls[0].symbol, shortfalls[0].filled), (msft(), 0));
+        assert_eq!(p.cash(), 500_000_00);
+    }
+
+    #[test]
+    fn repeated_targets_still_let_the_last_weight_win() {
+        let prices = [(aapl(), 100_00)];
+        let mut p = Portfolio::new(1_000_00, CostModel::zero());
+        p.rebalance_simple(&[(aapl(), 0.8), (aapl(), 0.3)], &prices);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 3);
+        assert_eq!(p.cash(), 700_00);
+    }
+
+    #[test]
+    fn turnover_counts_rebalances_not_initial_buy() {
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 100_00), (msft(), 100_00)];
+        p.rebalance_simple(&[(aapl(), 1.0)], &prices);
+        assert_eq!(p.turnover(), 0.0);
+
+        // Holding the same target trades nothing.
+        p.rebalance_simple(&[(aapl(), 1.0)], &prices);
+        assert_eq!(p.turnover(), 0.0);
+
+        // Half into MSFT: |Δw| = 0.5 + 0.5, one-way 0.5.
+        p.rebalance_simple(&[(aapl(), 0.5), (msft(), 0.5)], &prices);
+        assert!((p.turnover() - 0.5).abs() < 1e-9);
+
+        // Full switch to MSFT adds 0.5 more.
+        p.rebalance_simple(&[(msft(), 1.0)], &prices);
+        assert!((p.turnover() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn estimate_rebalance_cost_matches_lob_execution() {
+        use crate::{MultiExchange, Price, Side, TimeInForce};
+
+        let mut multi = MultiExchange::new();
+        for (sym, mid) in [(aapl(), 100_00), (msft(), 200_00)] {
+            let ex = multi.get_or_create(&sym);
+            for i in 1..=5 {
+                ex.submit_limit(Side::Buy, Price(mid - i * 10), 300, TimeInForce::GTC);
+                ex.submit_limit(Side::Sell, Price(mid + i * 10), 200, TimeInForce::GTC);
+                ex.submit_limit(Side::Sell, Price(mid + i * 10), 100, TimeInForce::GTC);
+            }
+        }
+        let model = CostModel {
+            commission_bps: 5,
+            min_trade_fee: 50,
+            ..CostModel::zero()
+        };
+        let mut portfolio = Portfolio::new(250_000_00, model);
+        portfolio.rebalance_lob(&[(msft(), 0.4)], &mut multi);
+
+        // Switch from MSFT into AAPL: one close and one buy.
+        let targets = [(aapl(), 0.8)];
+        let before = multi.get(&aapl()).unwrap().full_book();
+        let est = portfolio.estimate_rebalance_cost(&targets, &multi);
+        assert!(
+            multi
+                .get(&aapl())
+                .unwrap()
+                .full_book()
+                .diff(&before)
+                .is_empty()
+        );
+
+        assert_eq!(est.symbols.len(), 2);
+        assert_eq!(est.symbols[0].symbol, msft());
+        assert!(est.symbols[0].quantity < 0);
+        assert!(est.slippage > 0);
+        assert_eq!(
+            est.total(),
+            est.symbols
+                .iter()
+                .map(|s| s.slippage + s.commission)
+                .sum::<i64>()
+        );
+
+        // Executing the rebalance spends exactly the mid value plus the
+        // estimated slippage and commission.
+        let cash_before = portfolio.cash();
+        portfolio.rebalance_lob(&targets, &mut multi);
+        let at_mid: i64 = est
+            .symbols
+            .iter()
+            .map(|s| s.filled_quantity * s.reference_price)
+            .sum();
+        assert_eq!(cash_before - portfolio.cash(), at_mid + est.total());
+        assert_eq!(
+            portfolio.position(&aapl()).unwrap().quantity,
+            est.symbols[1].filled_quantity
+        );
+    }
+
+    #[test]
+    fn rebalance_lob_fill_ratio_scales_marketable_quantity() {
+        use crate::{MultiExchange, Price, Side, TimeInForce};
+
+        let mut multi = MultiExchange::new();
+        let ex = multi.get_or_create(&aapl());
+        ex.submit_limit(Side::Buy, Price(99_00), 1_000, TimeInForce::GTC);
+        ex.submit_limit(Side::Sell, Price(101_00), 300, TimeInForce::GTC);
+
+        // Wants 500 shares at the $100 mid but only 300 are offered.
+        let mut p = Portfolio::new(100_000_00, CostModel::zero());
+        p.rebalance_lob_with_fill_ratio(&[(aapl(), 0.5)], &mut multi, 0.5);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 150);
+        assert_eq!(multi.get(&aapl()).unwrap().best_ask(), Some(Price(101_00)));
+
+        // Selling 150 into 1_000 bid: a third fills.
+        p.rebalance_lob_with_fill_ratio(&[], &mut multi, 1.0 / 3.0);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "fill_ratio")]
+    fn rebalance_lob_rejects_zero_fill_ratio() {
+        let mut multi = crate::MultiExchange::new();
+        Portfolio::new(100_00, CostModel::zero()).rebalance_lob_with_fill_ratio(
+            &[],
+            &mut multi,
+            0.0,
+        );
+    }
+
+    #[test]
+    fn rebalance_lob_half_spread_cost() {
+        use crate::{MultiExchange, Price, Side, TimeInForce};
+
+        let book = || {
+            let mut multi = MultiExchange::new();
+            let ex = multi.get_or_create(&aapl());
+            ex.submit_limit(Side::Buy, Price(99_90), 1_000, TimeInForce::GTC);
+            ex.submit_limit(Side::Sell, Price(100_10), 1_000, TimeInForce::GTC);
+            multi
+        };
+        let flat = CostModel {
+            commission_bps: 1,
+            slippage_bps: 10,
+            ..CostModel::zero()
+        };
+        let spread = CostModel {
+            half_spread: true,
+            ..flat
+        };
+
+        // $50,000 at a $100 mid: 500 shares at $100.10 = $50,050 notional.
+        let mut multi = book();
+        let est =
+            Portfolio::new(100_000_00, spread).estimate_rebalance_cost(&[(aapl(), 0.5)], &multi);
+        let mut p = Portfolio::new(100_000_00, spread);
+        p.rebalance_lob(&[(aapl(), 0.5)], &mut multi);
+        // 1 bp = $5.00 (rounded down), plus 500 × 10¢ half-spread
+        let cost = 5_00 + 50_00;
+        assert_eq!(p.cash(), 100_000_00 - 50_050_00 - cost);
+        assert_eq!(est.commission, cost);
+
+        let mut q = Portfolio::new(100_000_00, flat);
+        q.rebalance_lob(&[(aapl(), 0.5)], &mut book());
+        // 11 bps on $50,050 = $55.05
+        assert_eq!(q.cash(), 100_000_00 - 50_050_00 - 55_05);
+
+        // With no quoted spread the taker rate applies.
+        let mut one_sided = MultiExchange::new();
+        one_sided.get_or_create(&aapl()).submit_limit(
+            Side::Sell,
+            Price(100_10),
+            1_000,
+            TimeInForce::GTC,
+        );
+        let mut r = Portfolio::new(100_000_00, spread);
+        r.rebalance_lob(&[(aapl(), 0.5)], &mut one_sided);
+        let shares = r.position(&aapl()).unwrap().quantity;
+        let notional = shares * 100_10;
+        assert_eq!(
+            r.cash(),
+            100_000_00 - notional - flat.compute_cost(notional)
+        );
+    }
+
+    #[test]
+    fn fill_prices_separate_from_valuation() {
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero());
+        let mut same = p.clone();
+        let close = [(aapl(), 100_00), (msft(), 200_00)];
+        p.rebalance_simple_with_fill_prices(&[(aapl(), 0.5), (msft(), 0.5)], &close, &close);
+        same.rebalance_simple(&[(aapl(), 0.5), (msft(), 0.5)], &close);
+        assert_eq!(p.cash(), same.cash());
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 5_000);
+
+        // Exit MSFT at the open; AAPL has no open quote and fills at the close.
+        let open = [(msft(), 180_00)];
+        p.rebalance_simple_with_fill_prices(&[(aapl(), 0.5)], &close, &open);
+        assert!(p.position(&msft()).unwrap().is_flat());
+        assert_eq!(p.position(&msft()).unwrap().realized_pnl, -2_500 * 20_00);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 5_000);
+        assert_eq!(p.cash(), 2_500 * 180_00);
+    }
+
+    #[test]
+    fn batched_rebalance_matches_per_symbol() {
+        let model = CostModel {
+            commission_bps: 7,
+            slippage_bps: 3,
+            min_trade_fee: 1_00,
+            ..CostModel::zero()
+        };
+        let symbols: Vec<Symbol> = (0..40).map(|i| Symbol::new(&format!("S{i:02}"))).collect();
+        let mut rng = crate::rng::Rng::from_seed(11);
+        let mut simple = Portfolio::new(10_000_000_00, model).with_leverage(true);
+        let mut batched = simple.clone();
+
+        for round in 0..30 {
+            // Random prices (some symbols unquoted) and random long/short
+            // targets over a random subset, in shuffled order. Every third
+            // round targets the full universe, so nothing needs closing.
+            let full = round % 3 == 2;
+            let mut prices = Vec::new();
+            let mut targets = Vec::new();
+            for &s in &symbols {
+                if full || rng.below(10) != 0 {
+                    prices.push((s, 10_00 + rng.below(500_00) as i64));
+                }
+                if full || rng.below(3) != 0 {
+                    targets.push((s, rng.next_f64() * 0.1 - 0.02));
+                }
+            }
+            rng.shuffle(&mut prices);
+            rng.shuffle(&mut targets);
+
+            simple.rebalance_simple(&targets, &prices);
+            batched.rebalance_simple_batched(&targets, &prices);
+
+            assert_eq!(simple.cash(), batched.cash());
+            assert_eq!(simple.turnover(), batched.turnover());
+            for sym in &symbols {
+                let (a, b) = (simple.position(sym), batched.position(sym));
+                assert_eq!(a.is_some(), b.is_some());
+                if let (Some(a), Some(b)) = (a, b) {
+                    assert_eq!(a.quantity, b.quantity);
+                    assert_eq!(a.avg_entry_price, b.avg_entry_price);
+                    assert_eq!(a.realized_pnl, b.realized_pnl);
+                    assert_eq!(a.total_cost, b.total_cost);
+                }
+            }
+        }
+
+        // Duplicate targets trade once per entry and must not hide a
+        // position that needs closing.
+        let prices = [(aapl(), 100_00), (msft(), 50_00)];
+        for targets in [
+            vec![(aapl(), 0.3), (aapl(), 0.6)],
+            vec![(msft(), 0.2), (msft(), 0.4)],
+        ] {
+            let mut a = Portfolio::new(1_000_000_00, model);
+            a.rebalance_simple(&[(aapl(), 0.5)], &prices);
+            let mut b = a.clone();
+            a.rebalance_simple(&targets, &prices);
+            b.rebalance_simple_batched(&targets, &prices);
+            assert_eq!(a.cash(), b.cash());
+            for sym in [aapl(), msft()] {
+                let qty = |p: &Portfolio| p.position(&sym).map(|pos| pos.quantity);
+                assert_eq!(qty(&a), qty(&b));
+            }
+        }
+    }
+
     #[test]
     fn snapshot() {
         let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
@@ -615,6 +2563,168 @@ mod tests {
         assert!((snap.equity - 1_000_000_00).abs() < 300_00);
     }
 
+    #[test]
+    fn snapshot_positions_report_unrealized_pnl() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        portfolio.rebalance_simple(
+            &[(msft(), 0.2), (aapl(), -0.1)],
+            &[(aapl(), 100_00), (msft(), 200_00)],
+        );
+
+        let prices = [(aapl(), 90_00), (msft(), 210_00)];
+        let snap = portfolio.snapshot(&prices);
+        let symbols: Vec<_> = snap.positions.iter().map(|p| p.symbol).collect();
+        assert_eq!(symbols, [aapl(), msft()]);
+
+        let short = &snap.positions[0];
+        assert_eq!(short.quantity, -1_000);
+        assert_eq!(short.avg_entry_price, 100_00);
+        assert_eq!(short.unrealized_pnl, 10_000_00);
+        let long = &snap.positions[1];
+        assert_eq!(long.quantity, 1_000);
+        assert_eq!(long.unrealized_pnl, 10_000_00);
+
+        let weights: FxHashMap<Symbol, f64> = snap.weights.iter().copied().collect();
+        for pos in &snap.positions {
+            assert_eq!(pos.weight, weights[&pos.symbol]);
+        }
+        assert_eq!(portfolio.unrealized_pnl(&prices), 20_000_00);
+    }
+
+    #[test]
+    fn pnl_totals_span_positions() {
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        let entry = [(aapl(), 100_00), (msft(), 200_00)];
+        portfolio.rebalance_simple(&[(aapl(), 0.2), (msft(), 0.2)], &entry);
+        assert_eq!(portfolio.realized_pnl(), 0);
+
+        // Close MSFT at a 10_00 gain; the closed position still counts.
+        portfolio.rebalance_simple(&[(aapl(), 0.2)], &[(aapl(), 100_00), (msft(), 210_00)]);
+        assert_eq!(portfolio.realized_pnl(), 10_000_00);
+        assert_eq!(
+            portfolio.realized_pnl(),
+            portfolio.snapshot(&entry).total_realized_pnl
+        );
+
+        // AAPL was topped up to 2_020 shares at 100_00.
+        let prices = [(aapl(), 95_00), (msft(), 300_00)];
+        assert_eq!(portfolio.unrealized_pnl(&prices), -10_100_00);
+        assert_eq!(portfolio.unrealized_pnl(&[]), 0);
+    }
+
+    #[test]
+    fn weight_policy_normalizes_or_rejects_targets() {
+        let prices = [(aapl(), 100_00), (msft(), 200_00)];
+        let over = [(aapl(), 1.2), (msft(), -0.4)];
+
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_weight_policy(WeightPolicy::Normalize { gross: 1.0 });
+        p.rebalance_simple(&over, &prices);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 7_500);
+        assert_eq!(p.position(&msft()).unwrap().quantity, -1_250);
+
+        let strict = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_weight_policy(WeightPolicy::Strict { max_gross: 1.5 });
+        assert!(
+            strict
+                .check_targets(&[(aapl(), 1.0), (msft(), -0.5)])
+                .is_ok()
+        );
+        assert_eq!(
+            strict.check_targets(&over),
+            Err(WeightError::ExceedsGross {
+                gross: 1.6,
+                max_gross: 1.5
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_targets_are_rejected_or_summed() {
+        let prices = [(aapl(), 100_00)];
+        let twice = [(aapl(), 0.25), (aapl(), 0.25)];
+
+        let p = Portfolio::new(1_000_000_00, CostModel::zero());
+        assert!(p.check_targets(&twice).is_ok());
+        let p = p.with_duplicate_policy(DuplicatePolicy::Reject);
+        assert_eq!(
+            p.check_targets(&twice),
+            Err(WeightError::DuplicateSymbol(aapl()))
+        );
+
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_duplicate_policy(DuplicatePolicy::Sum)
+            .with_weight_policy(WeightPolicy::Strict { max_gross: 0.5 });
+        assert!(p.check_targets(&twice).is_ok());
+        p.rebalance_simple(&twice, &prices);
+        assert_eq!(p.position(&aapl()).unwrap().quantity, 5_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate target symbol AAPL")]
+    fn duplicate_targets_panic_on_rebalance() {
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_duplicate_policy(DuplicatePolicy::Reject);
+        p.rebalance_simple(&[(aapl(), 0.5), (aapl(), 0.5)], &[(aapl(), 100_00)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the 1 limit")]
+    fn strict_weight_policy_panics_on_rebalance() {
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero())
+            .with_weight_policy(WeightPolicy::Strict { max_gross: 1.0 });
+        p.rebalance_simple(&[(aapl(), 0.75), (msft(), -0.5)], &[(aapl(), 100_00)]);
+    }
+
+    #[test]
+    fn fifo_cost_basis_changes_realized_pnl_only() {
+        let run = |basis| {
+            let mut p = Portfolio::new(1_000_000_00, CostModel::zero()).with_cost_basis(basis);
+            p.rebalance_simple(&[(aapl(), 0.1)], &[(aapl(), 100_00)]);
+            p.rebalance_simple(&[(aapl(), 0.2)], &[(aapl(), 100_00)]);
+            p.execute_fill(aapl(), 100, 120_00);
+            p.execute_fill(aapl(), -1_500, 130_00);
+            p
+        };
+        let avg = run(CostBasis::AverageCost);
+        let fifo = run(CostBasis::Fifo);
+        assert_eq!(fifo.cost_basis(), CostBasis::Fifo);
+        assert_eq!(avg.cash(), fifo.cash());
+
+        // 2_000 @ $100 then 100 @ $120; sell 1_500 @ $130
+        let avg_pos = avg.position(&aapl()).unwrap();
+        let fifo_pos = fifo.position(&aapl()).unwrap();
+        assert_eq!(fifo_pos.realized_pnl, 1_500 * 30_00);
+        assert!(avg_pos.realized_pnl < fifo_pos.realized_pnl);
+        assert_eq!(fifo_pos.quantity, avg_pos.quantity);
+        assert_eq!(fifo_pos.lots.len(), 2);
+    }
+
+    #[test]
+    fn holding_periods_follow_recorded_returns() {
+        let mut p = Portfolio::new(1_000_000_00, CostModel::zero());
+        let prices = [(aapl(), 100_00), (msft(), 200_00)];
+        p.rebalance_simple(&[(aapl(), 0.5), (msft(), 0.25)], &prices);
+        assert_eq!(p.average_holding_period(), None);
+        for _ in 0..3 {
+            p.record_return(&prices);
+        }
+        p.rebalance_simple(&[(aapl(), 0.5)], &prices);
+        p.record_return(&prices);
+        p.rebalance_simple(&[(aapl(), 0.25)], &prices);
+
+        // MSFT closed after 3 periods, half of AAPL after 4
+        let msft_closed = 1_250.0;
+        let aapl_closed = 2_500.0;
+        assert_eq!(
+            p.average_holding_period(),
+            Some((msft_closed * 3.0 + aapl_closed * 4.0) / (msft_closed + aapl_closed))
+        );
+        let snap = p.snapshot(&prices);
+        assert_eq!(snap.positions[0].holding_period, 4.0);
+        assert_eq!(snap.average_holding_period, p.average_holding_period());
+    }
+
     #[test]
     fn current_weights() {
         let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
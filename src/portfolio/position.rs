@@ -1,11 +1,45 @@
 //! Position tracking for a single symbol.
 
+use std::collections::VecDeque;
+
 use crate::types::Symbol;
 
+/// How a [`Position`] measures the cost of quantity it closes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CostBasis {
+    /// Realize PnL against the volume-weighted average entry price.
+    #[default]
+    AverageCost,
+    /// Keep each entry as a [`Lot`] and realize PnL against the oldest
+    /// lots first.
+    Fifo,
+}
+
+/// Quantity opened by a single fill, kept by [`CostBasis::Fifo`]
+/// positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lot {
+    /// Signed open quantity: positive = long, negative = short
+    pub quantity: i64,
+    /// Entry price (cents)
+    pub price: i64,
+}
+
 /// A position in a single instrument.
 ///
 /// Tracks quantity (positive = long, negative = short), average entry price,
 /// and realized PnL. All monetary values are in the smallest currency unit (cents).
+///
+/// Holding periods use average aging: shares added to a position enter at
+/// age 0 and blend into a quantity-weighted average age, and a partial exit
+/// closes shares at that average age, leaving it unchanged for the rest.
+/// Ages are counted in periods advanced by
+/// [`advance_period`](Self::advance_period).
+///
+/// Realized PnL follows the position's [`CostBasis`]. Under FIFO,
+/// `avg_entry_price` and `total_cost` describe the lots still open.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
@@ -19,17 +53,42 @@ pub struct Position {
     pub realized_pnl: i64,
     /// Cumulative cost of entry (quantity * avg_entry_price), used for VWAP tracking
     pub total_cost: i64,
+    /// Average age of the open quantity, in periods
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub age: f64,
+    /// Cumulative quantity closed by reducing or flipping fills
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub closed_quantity: i64,
+    /// Sum of the ages at which closed quantity was closed (share-periods)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub closed_age: f64,
+    /// Accounting method for realized PnL
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cost_basis: CostBasis,
+    /// Open lots, oldest first (empty unless `cost_basis` is FIFO)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lots: VecDeque<Lot>,
 }
 
 impl Position {
     /// Create a new flat position for the given symbol.
     pub fn new(symbol: Symbol) -> Self {
+        Self::with_cost_basis(symbol, CostBasis::AverageCost)
+    }
+
+    /// Create a new flat position that accounts by `cost_basis`.
+    pub fn with_cost_basis(symbol: Symbol, cost_basis: CostBasis) -> Self {
         Self {
             symbol,
             quantity: 0,
             avg_entry_price: 0,
             realized_pnl: 0,
             total_cost: 0,
+            age: 0.0,
+            closed_quantity: 0,
+            closed_age: 0.0,
+            cost_basis,
+            lots: VecDeque::new(),
         }
     }
 
@@ -40,7 +99,8 @@ impl Position {
     ///
     /// If the fill increases the position (same direction), the average entry
     /// price is updated via VWAP. If it reduces or flips the position,
-    /// realized PnL is recorded for the closed portion.
+    /// realized PnL is recorded for the closed portion, against the average
+    /// entry price or, under FIFO, the oldest open lots.
     pub fn apply_fill(&mut self, qty: i64, price: i64) {
         if qty == 0 {
             return;
@@ -53,20 +113,31 @@ impl Position {
             self.quantity = qty;
             self.avg_entry_price = price;
             self.total_cost = qty * price;
+            self.age = 0.0;
+            self.push_lot(qty, price);
         } else if same_direction {
-            // Adding to position — update VWAP
+            // Adding to position — update VWAP; new shares enter at age 0
             self.total_cost += qty * price;
+            self.age *= self.quantity as f64 / (self.quantity + qty) as f64;
             self.quantity += qty;
             self.avg_entry_price = self.total_cost / self.quantity;
+            self.push_lot(qty, price);
         } else {
             // Reducing or flipping
             let close_qty = qty.abs().min(self.quantity.abs());
-            let pnl_per_unit = if self.quantity > 0 {
-                price - self.avg_entry_price // long: sell higher = profit
-            } else {
-                self.avg_entry_price - price // short: buy lower = profit
+            self.realized_pnl += match self.cost_basis {
+                CostBasis::AverageCost => {
+                    let pnl_per_unit = if self.quantity > 0 {
+                        price - self.avg_entry_price // long: sell higher = profit
+                    } else {
+                        self.avg_entry_price - price // short: buy lower = profit
+                    };
+                    pnl_per_unit * close_qty
+                }
+                CostBasis::Fifo => self.close_lots(close_qty, price),
             };
-            self.realized_pnl += pnl_per_unit * close_qty;
+            self.closed_quantity += close_qty;
+            self.closed_age += self.age * close_qty as f64;
 
             let net = self.quantity + qty;
             if net == 0 {
@@ -74,10 +145,14 @@ impl Position {
                 self.quantity = 0;
                 self.avg_entry_price = 0;
                 self.total_cost = 0;
+                self.age = 0.0;
             } else if (net > 0) == (self.quantity > 0) {
                 // Partially closed, same side — subtract closed portion's cost
                 // to preserve any fractional remainder in total_cost
-                self.total_cost -= close_qty * self.avg_entry_price;
+                self.total_cost = match self.cost_basis {
+                    CostBasis::AverageCost => self.total_cost - close_qty * self.avg_entry_price,
+                    CostBasis::Fifo => self.lots.iter().map(|l| l.quantity * l.price).sum(),
+                };
                 self.quantity = net;
                 self.avg_entry_price = self.total_cost / self.quantity;
             } else {
@@ -85,10 +160,37 @@ impl Position {
                 self.quantity = net;
                 self.avg_entry_price = price;
                 self.total_cost = net * price;
+                self.age = 0.0;
+                self.push_lot(net, price);
             }
         }
     }
 
+    fn push_lot(&mut self, quantity: i64, price: i64) {
+        if self.cost_basis == CostBasis::Fifo {
+            self.lots.push_back(Lot { quantity, price });
+        }
+    }
+
+    /// Close `close_qty` units from the oldest lots at `price`, returning
+    /// the realized PnL.
+    fn close_lots(&mut self, mut close_qty: i64, price: i64) -> i64 {
+        let mut pnl = 0;
+        while close_qty > 0 {
+            let Some(lot) = self.lots.front_mut() else {
+                break;
+            };
+            let take = lot.quantity.abs().min(close_qty);
+            pnl += (price - lot.price) * take * lot.quantity.signum();
+            lot.quantity -= take * lot.quantity.signum();
+            close_qty -= take;
+            if lot.quantity == 0 {
+                self.lots.pop_front();
+            }
+        }
+        pnl
+    }
+
     /// Current market value at the given price (cents).
     #[inline]
     pub fn market_value(&self, price: i64) -> i64 {
@@ -104,6 +206,30 @@ impl Position {
         (price - self.avg_entry_price) * self.quantity
     }
 
+    /// Average number of periods the open quantity has been held.
+    ///
+    /// Zero for a flat position.
+    #[inline]
+    pub fn holding_period(&self) -> f64 {
+        self.age
+    }
+
+    /// Average number of periods closed quantity was held before it was
+    /// sold (or bought back), or `None` if nothing has been closed.
+    pub fn closed_holding_period(&self) -> Option<f64> {
+        (self.closed_quantity > 0).then(|| self.closed_age / self.closed_quantity as f64)
+    }
+
+    /// Age the open quantity by one period.
+    ///
+    /// [`Portfolio::record_return`](super::Portfolio::record_return) calls
+    /// this for every position at the end of each period.
+    pub fn advance_period(&mut self) {
+        if self.quantity != 0 {
+            self.age += 1.0;
+        }
+    }
+
     /// Returns true if the position is flat (zero quantity).
     #[inline]
     pub fn is_flat(&self) -> bool {
@@ -203,6 +329,77 @@ mod tests {
         assert_eq!(pos.realized_pnl, 100 * 10_00); // $10 * 100
     }
 
+    #[test]
+    fn holding_period_uses_average_aging() {
+        let mut pos = Position::new(sym());
+        pos.advance_period(); // flat positions do not age
+        pos.apply_fill(100, 50_00);
+        pos.advance_period();
+        pos.advance_period();
+        assert_eq!(pos.holding_period(), 2.0);
+
+        // 100 more shares at age 0 halve the average age
+        pos.apply_fill(100, 50_00);
+        assert_eq!(pos.holding_period(), 1.0);
+        pos.advance_period();
+
+        // A partial exit closes shares at the average age
+        pos.apply_fill(-50, 55_00);
+        assert_eq!(pos.holding_period(), 2.0);
+        assert_eq!(pos.closed_holding_period(), Some(2.0));
+
+        // Flipping closes the rest and restarts the clock for the short
+        pos.advance_period();
+        pos.apply_fill(-200, 55_00);
+        assert_eq!(pos.quantity, -50);
+        assert_eq!(pos.holding_period(), 0.0);
+        assert_eq!(pos.closed_quantity, 200);
+        assert_eq!(
+            pos.closed_holding_period(),
+            Some((50.0 * 2.0 + 150.0 * 3.0) / 200.0)
+        );
+    }
+
+    #[test]
+    fn fifo_realizes_against_oldest_lots() {
+        let mut avg = Position::new(sym());
+        let mut fifo = Position::with_cost_basis(sym(), CostBasis::Fifo);
+        for pos in [&mut avg, &mut fifo] {
+            pos.apply_fill(100, 50_00);
+            pos.apply_fill(100, 60_00);
+            pos.apply_fill(-150, 70_00);
+        }
+        // Average cost: 150 × ($70 − $55)
+        assert_eq!(avg.realized_pnl, 150 * 15_00);
+        // FIFO: 100 × ($70 − $50) + 50 × ($70 − $60)
+        assert_eq!(fifo.realized_pnl, 100 * 20_00 + 50 * 10_00);
+        assert_eq!(fifo.realized_pnl - avg.realized_pnl, 250_00);
+        assert_eq!(
+            fifo.lots,
+            [Lot {
+                quantity: 50,
+                price: 60_00
+            }]
+        );
+        assert_eq!(fifo.avg_entry_price, 60_00);
+        assert_eq!(fifo.unrealized_pnl(70_00), 50 * 10_00);
+
+        // Flipping closes the last lot and opens a short one
+        fifo.apply_fill(-80, 65_00);
+        assert_eq!(fifo.quantity, -30);
+        assert_eq!(
+            fifo.lots,
+            [Lot {
+                quantity: -30,
+                price: 65_00
+            }]
+        );
+        assert_eq!(fifo.realized_pnl, 2_500_00 + 50 * 5_00);
+        fifo.apply_fill(30, 60_00);
+        assert!(fifo.is_flat() && fifo.lots.is_empty());
+        assert_eq!(fifo.realized_pnl, 2_750_00 + 30 * 5_00);
+    }
+
     #[test]
     fn zero_fill_is_noop() {
         let mut pos = Position::new(sym());
@@ -0,0 +1,116 @@
+//! Building per-bar price series from dense price matrices.
+
+use crate::types::Symbol;
+
+/// How [`price_series_from_matrix`] treats a missing (non-finite) price.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MissingPrice {
+    /// Repeat the symbol's last valid price. Bars before its first valid
+    /// price still omit the symbol.
+    #[default]
+    CarryForward,
+    /// Omit the symbol from that bar.
+    Skip,
+}
+
+/// Convert a row-major `bars × symbols` price matrix into the per-bar
+/// series [`run_backtest`](super::run_backtest) takes.
+///
+/// Row `i` holds bar `i`, column `j` the price of `symbols[j]` in the same
+/// integer units as the rest of the portfolio API (cents by default),
+/// rounded to the nearest unit. Non-finite entries (NaN, ±inf) are missing
+/// and handled per `missing`. Within a bar, symbols keep column order.
+///
+/// # Panics
+///
+/// Panics if `symbols` is empty or `prices.len()` is not a multiple of
+/// `symbols.len()`.
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::{MissingPrice, price_series_from_matrix};
+///
+/// let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+/// let prices = [100_00.0, f64::NAN, 101_00.0, 50_00.0, f64::NAN, 51_00.0];
+///
+/// let filled = price_series_from_matrix(&prices, &[a, b], MissingPrice::CarryForward);
+/// assert_eq!(filled[0], vec![(a, 100_00)]);
+/// assert_eq!(filled[2], vec![(a, 101_00), (b, 51_00)]);
+///
+/// let sparse = price_series_from_matrix(&prices, &[a, b], MissingPrice::Skip);
+/// assert_eq!(sparse[2], vec![(b, 51_00)]);
+/// ```
+pub fn price_series_from_matrix(
+    prices: &[f64],
+    symbols: &[Symbol],
+    missing: MissingPrice,
+) -> Vec<Vec<(Symbol, i64)>> {
+    assert!(!symbols.is_empty(), "symbols must not be empty");
+    assert!(
+        prices.len() % symbols.len() == 0,
+        "price matrix has {} values, not a multiple of {} symbols",
+        prices.len(),
+        symbols.len()
+    );
+    let mut last: Vec<Option<i64>> = vec![None; symbols.len()];
+    prices
+        .chunks_exact(symbols.len())
+        .map(|row| {
+            let mut bar = Vec::with_capacity(symbols.len());
+            for ((&sym, &value), last) in symbols.iter().zip(row).zip(&mut last) {
+                let price = if value.is_finite() {
+                    let price = value.round() as i64;
+                    *last = Some(price);
+                    Some(price)
+                } else {
+                    match missing {
+                        MissingPrice::CarryForward => *last,
+                        MissingPrice::Skip => None,
+                    }
+                };
+                if let Some(price) = price {
+                    bar.push((sym, price));
+                }
+            }
+            bar
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_and_fills_per_column() {
+        let syms = [Symbol::new("A"), Symbol::new("B")];
+        let prices = [f64::NAN, 10.4, f64::INFINITY, f64::NAN, 7.6, f64::NAN];
+
+        let filled = price_series_from_matrix(&prices, &syms, MissingPrice::CarryForward);
+        assert_eq!(
+            filled,
+            vec![
+                vec![(syms[1], 10)],
+                vec![(syms[1], 10)],
+                vec![(syms[0], 8), (syms[1], 10)],
+            ]
+        );
+
+        let sparse = price_series_from_matrix(&prices, &syms, MissingPrice::Skip);
+        assert_eq!(
+            sparse,
+            vec![vec![(syms[1], 10)], vec![], vec![(syms[0], 8)]]
+        );
+        assert!(price_series_from_matrix(&[], &syms, MissingPrice::Skip).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "multiple")]
+    fn ragged_matrix_panics() {
+        price_series_from_matrix(
+            &[1.0, 2.0, 3.0],
+            &[Symbol::new("A"), Symbol::new("B")],
+            MissingPrice::Skip,
+        );
+    }
+}
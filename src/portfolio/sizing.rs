@@ -0,0 +1,345 @@
+//! Conversion between target weights and share counts.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::types::Symbol;
+use rustc_hash::FxHashMap;
+
+/// How [`Portfolio`](super::Portfolio) rebalances treat target weights.
+///
+/// Gross exposure is the sum of absolute weights, so shorts count toward
+/// it: `[(A, 0.8), (B, -0.4)]` has gross 1.2.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WeightPolicy {
+    /// Trade toward the weights as given.
+    #[default]
+    AsGiven,
+    /// Rescale the weights so their gross exposure equals `gross`. All-zero
+    /// targets are left unchanged.
+    Normalize {
+        /// Gross exposure to rescale to (1.0 = fully invested)
+        gross: f64,
+    },
+    /// Reject targets whose gross exposure exceeds `max_gross`.
+    Strict {
+        /// Leverage cap on gross exposure
+        max_gross: f64,
+    },
+}
+
+impl WeightPolicy {
+    /// Apply the policy to `targets`, returning the weights to trade
+    /// toward.
+    ///
+    /// Only [`WeightPolicy::Strict`] can fail. A gross exposure within
+    /// `1e-9` of the cap passes, so weights that sum to the cap up to
+    /// rounding are accepted.
+    pub fn apply<'a>(
+        &self,
+        targets: &'a [(Symbol, f64)],
+    ) -> Result<Cow<'a, [(Symbol, f64)]>, WeightError> {
+        match *self {
+            WeightPolicy::AsGiven => Ok(Cow::Borrowed(targets)),
+            WeightPolicy::Normalize { gross } => Ok(Cow::Owned(normalize_weights(targets, gross))),
+            WeightPolicy::Strict { max_gross } => {
+                let gross = gross_weight(targets);
+                if gross > max_gross + 1e-9 {
+                    return Err(WeightError::ExceedsGross { gross, max_gross });
+                }
+                Ok(Cow::Borrowed(targets))
+            }
+        }
+    }
+}
+
+/// How [`Portfolio`](super::Portfolio) rebalances treat a symbol that
+/// appears more than once in the targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicatePolicy {
+    /// Trade each entry in turn, as listed.
+    #[default]
+    Allow,
+    /// Reject the targets.
+    Reject,
+    /// Sum the symbol's entries, keeping its first position in the list.
+    Sum,
+}
+
+/// Order in which [`Portfolio::rebalance_simple`](super::Portfolio::rebalance_simple)
+/// applies its fills.
+///
+/// Requested sizes do not depend on the order: every target is sized from
+/// the pre-trade equity. Buys are capped at the cash on hand, though, so
+/// the order decides which buys are cut when cash is tight.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RebalanceOrder {
+    /// Every sell (reductions, closes, and new or larger shorts) before any
+    /// buy, so sale proceeds fund purchases. Each group keeps the
+    /// [`AsListed`](Self::AsListed) order.
+    #[default]
+    SellsFirst,
+    /// Close untargeted positions, then trade each target as listed.
+    AsListed,
+}
+
+/// Merge repeated symbols in a `(symbol, value)` list by `policy`.
+///
+/// Lists without repeats, and any list under [`DuplicatePolicy::Allow`],
+/// are returned as-is. Under [`DuplicatePolicy::Reject`] the error is the
+/// first symbol seen twice; under [`DuplicatePolicy::Sum`] each symbol
+/// keeps the position of its first entry.
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::{DuplicatePolicy, merge_duplicates};
+///
+/// let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+/// let targets = [(a, 0.25), (b, 0.5), (a, 0.125)];
+/// assert_eq!(merge_duplicates(&targets, DuplicatePolicy::Reject), Err(a));
+/// let merged = merge_duplicates(&targets, DuplicatePolicy::Sum).unwrap();
+/// assert_eq!(&*merged, &[(a, 0.375), (b, 0.5)]);
+/// ```
+pub fn merge_duplicates<T>(
+    entries: &[(Symbol, T)],
+    policy: DuplicatePolicy,
+) -> Result<Cow<'_, [(Symbol, T)]>, Symbol>
+where
+    T: Copy + std::ops::AddAssign,
+{
+    if policy == DuplicatePolicy::Allow {
+        return Ok(Cow::Borrowed(entries));
+    }
+    let mut index: FxHashMap<Symbol, usize> = FxHashMap::default();
+    let mut merged: Vec<(Symbol, T)> = Vec::with_capacity(entries.len());
+    for &(sym, value) in entries {
+        match index.get(&sym) {
+            Some(_) if policy == DuplicatePolicy::Reject => return Err(sym),
+            Some(&i) => merged[i].1 += value,
+            None => {
+                index.insert(sym, merged.len());
+                merged.push((sym, value));
+            }
+        }
+    }
+    if merged.len() == entries.len() {
+        Ok(Cow::Borrowed(entries))
+    } else {
+        Ok(Cow::Owned(merged))
+    }
+}
+
+/// Target weights rejected by a [`WeightPolicy`] or [`DuplicatePolicy`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeightError {
+    /// Gross exposure is above the [`WeightPolicy::Strict`] cap.
+    ExceedsGross {
+        /// Sum of absolute target weights
+        gross: f64,
+        /// The cap
+        max_gross: f64,
+    },
+    /// A symbol appears more than once under [`DuplicatePolicy::Reject`].
+    DuplicateSymbol(Symbol),
+}
+
+impl fmt::Display for WeightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightError::ExceedsGross { gross, max_gross } => write!(
+                f,
+                "target gross exposure {gross} exceeds the {max_gross} limit"
+            ),
+            WeightError::DuplicateSymbol(sym) => write!(f, "duplicate target symbol {sym}"),
+        }
+    }
+}
+
+impl std::error::Error for WeightError {}
+
+/// Sum of absolute weights.
+pub fn gross_weight(weights: &[(Symbol, f64)]) -> f64 {
+    weights.iter().map(|(_, w)| w.abs()).sum()
+}
+
+/// Rescale `weights` so their gross exposure (sum of absolute weights) is
+/// `gross`, keeping each weight's sign and relative size. Weights whose
+/// gross is zero or not finite are returned unchanged.
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::normalize_weights;
+///
+/// let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+/// let w = normalize_weights(&[(a, 1.2), (b, -0.4)], 1.0);
+/// assert_eq!(w, vec![(a, 0.75), (b, -0.25)]);
+/// ```
+pub fn normalize_weights(weights: &[(Symbol, f64)], gross: f64) -> Vec<(Symbol, f64)> {
+    let current = gross_weight(weights);
+    if current == 0.0 || !current.is_finite() {
+        return weights.to_vec();
+    }
+    let scale = gross / current;
+    weights.iter().map(|&(sym, w)| (sym, w * scale)).collect()
+}
+
+/// Convert target weights into share counts at the given prices.
+///
+/// Each target value is `equity × weight` and the share count is that value
+/// divided by the price, truncated toward zero to a multiple of `lot_size`.
+/// With `lot_size = 1` this is exactly the quantity
+/// [`Portfolio::rebalance_simple`](super::Portfolio::rebalance_simple) buys
+/// from an all-cash book. Symbols without a positive price are skipped, and
+/// the output keeps the order of `weights`.
+///
+/// # Panics
+///
+/// Panics if `lot_size` is 0.
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::weights_to_shares;
+///
+/// let aapl = Symbol::new("AAPL");
+/// let shares = weights_to_shares(&[(aapl, 0.5)], 100_000_00, &[(aapl, 150_00)], 100);
+/// // $50_000 / $150 = 333.3 shares, floored to a 100-share lot.
+/// assert_eq!(shares, vec![(aapl, 300)]);
+/// ```
+pub fn weights_to_shares(
+    weights: &[(Symbol, f64)],
+    equity: i64,
+    prices: &[(Symbol, i64)],
+    lot_size: u64,
+) -> Vec<(Symbol, i64)> {
+    assert!(lot_size > 0, "lot_size must be positive");
+    let lot = lot_size as i64;
+    let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+    weights
+        .iter()
+        .filter_map(|&(sym, weight)| {
+            let price = price_map.get(&sym).copied().filter(|&p| p > 0)?;
+            let target_value = (equity as f64 * weight) as i64;
+            Some((sym, target_value / price / lot * lot))
+        })
+        .collect()
+}
+
+/// Convert share counts into weights at the given prices.
+///
+/// Weights are each position's market value over the net market value of
+/// all priced positions, so a fully invested long book sums to 1. Symbols
+/// without a price are skipped. Returns an empty vec when the net value is
+/// not positive (no priced positions, or a book netting to zero or short).
+///
+/// ```
+/// use nanobook::Symbol;
+/// use nanobook::portfolio::shares_to_weights;
+///
+/// let (aapl, msft) = (Symbol::new("AAPL"), Symbol::new("MSFT"));
+/// let weights = shares_to_weights(&[(aapl, 300), (msft, 100)], &[(aapl, 100_00), (msft, 100_00)]);
+/// assert_eq!(weights, vec![(aapl, 0.75), (msft, 0.25)]);
+/// ```
+pub fn shares_to_weights(
+    positions: &[(Symbol, i64)],
+    prices: &[(Symbol, i64)],
+) -> Vec<(Symbol, f64)> {
+    let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+    let values: Vec<(Symbol, i64)> = positions
+        .iter()
+        .filter_map(|&(sym, qty)| Some((sym, qty.saturating_mul(*price_map.get(&sym)?))))
+        .collect();
+    let total: i64 = values.iter().map(|&(_, v)| v).sum();
+    if total <= 0 {
+        return Vec::new();
+    }
+    values
+        .into_iter()
+        .map(|(sym, v)| (sym, v as f64 / total as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_policies() {
+        let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+        let targets = [(a, 0.75), (b, -0.5)];
+        assert_eq!(gross_weight(&targets), 1.25);
+
+        let as_given = WeightPolicy::AsGiven.apply(&targets).unwrap();
+        assert_eq!(&*as_given, &targets);
+        let scaled = WeightPolicy::Normalize { gross: 2.5 }
+            .apply(&targets)
+            .unwrap();
+        assert_eq!(&*scaled, &[(a, 1.5), (b, -1.0)]);
+        assert_eq!(normalize_weights(&[(a, 0.0)], 1.0), vec![(a, 0.0)]);
+
+        assert!(
+            WeightPolicy::Strict { max_gross: 1.25 }
+                .apply(&targets)
+                .is_ok()
+        );
+        let err = WeightPolicy::Strict { max_gross: 1.0 }
+            .apply(&targets)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            WeightError::ExceedsGross {
+                gross: 1.25,
+                max_gross: 1.0
+            }
+        );
+        assert!(err.to_string().contains("exceeds"));
+    }
+
+    #[test]
+    fn merge_duplicates_rejects_or_sums() {
+        let (a, b) = (Symbol::new("A"), Symbol::new("B"));
+        let unique = [(a, 1_i64), (b, 2)];
+        let merged = merge_duplicates(&unique, DuplicatePolicy::Reject).unwrap();
+        assert!(matches!(merged, Cow::Borrowed(_)));
+
+        let repeated = [(b, 1_i64), (a, 2), (b, 3), (b, 4)];
+        assert_eq!(merge_duplicates(&repeated, DuplicatePolicy::Reject), Err(b));
+        let allowed = merge_duplicates(&repeated, DuplicatePolicy::Allow).unwrap();
+        assert_eq!(&*allowed, &repeated);
+        let merged = merge_duplicates(&repeated, DuplicatePolicy::Sum).unwrap();
+        assert_eq!(&*merged, &[(b, 8), (a, 2)]);
+    }
+    use crate::portfolio::{CostModel, Portfolio};
+
+    #[test]
+    fn shares_match_rebalance_simple_and_round_trip() {
+        let syms: Vec<Symbol> = ["A", "B", "C"].iter().map(|s| Symbol::new(s)).collect();
+        let prices = [(syms[0], 37_13), (syms[1], 250_00), (syms[2], 9_99)];
+        let weights = [(syms[0], 0.3), (syms[1], 0.45), (syms[2], -0.1)];
+
+        let shares = weights_to_shares(&weights, 1_000_000_00, &prices, 1);
+        let mut portfolio = Portfolio::new(1_000_000_00, CostModel::zero());
+        portfolio.rebalance_simple(&weights, &prices);
+        for &(sym, qty) in &shares {
+            assert_eq!(portfolio.position(&sym).unwrap().quantity, qty);
+        }
+
+        // Lots truncate toward zero on both sides.
+        let lots = weights_to_shares(&weights, 1_000_000_00, &prices, 100);
+        assert_eq!(
+            lots,
+            vec![(syms[0], 8_000), (syms[1], 1_800), (syms[2], -10_000)]
+        );
+
+        let back = shares_to_weights(&shares, &prices);
+        let net: f64 = weights.iter().map(|&(_, w)| w).sum();
+        for ((_, w), (_, target)) in back.iter().zip(&weights) {
+            assert!((w - target / net).abs() < 1e-4);
+        }
+
+        assert!(weights_to_shares(&weights, 1_000_00, &[], 1).is_empty());
+        assert!(shares_to_weights(&[(syms[0], -5)], &prices).is_empty());
+    }
+}
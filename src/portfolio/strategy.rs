@@ -26,15 +26,23 @@
 //! }
 //! ```
 
-use crate::portfolio::{CostModel, Metrics, Portfolio};
+use crate::portfolio::{
+    CostModel, Metrics, Portfolio, PortfolioSnapshot, PriceHistory, RelativeMetrics,
+};
 use crate::types::Symbol;
 use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
 
 /// A trading strategy that produces target portfolio weights each period.
 ///
 /// Strategies are batch-oriented: given a bar index, current prices, and
 /// portfolio state, they return target weights. The backtest runner handles
 /// rebalancing and return tracking.
+///
+/// Strategies that need lookback override
+/// [`history_len`](Self::history_len) and
+/// [`compute_weights_with_history`](Self::compute_weights_with_history)
+/// instead of keeping their own buffer of past prices.
 pub trait Strategy {
     /// Compute target portfolio weights for the given bar.
     ///
@@ -46,6 +54,41 @@ pub trait Strategy {
         prices: &[(Symbol, i64)],
         portfolio: &Portfolio,
     ) -> Vec<(Symbol, f64)>;
+
+    /// Number of leading bars the strategy needs before it trades.
+    ///
+    /// During warmup the runner still calls
+    /// [`compute_weights`](Self::compute_weights), so the strategy can build
+    /// up state, but ignores the weights: the portfolio stays in cash and
+    /// records flat returns. Defaults to 0.
+    fn warmup(&self) -> usize {
+        0
+    }
+
+    /// Number of bars (including the current one) the runner keeps in the
+    /// [`PriceHistory`] passed to
+    /// [`compute_weights_with_history`](Self::compute_weights_with_history).
+    /// Defaults to 1.
+    fn history_len(&self) -> usize {
+        1
+    }
+
+    /// Compute target weights with access to recent bars.
+    ///
+    /// `history` holds up to [`history_len`](Self::history_len) bars, oldest
+    /// first, and its newest bar is `prices`. The runner calls this method;
+    /// the default forwards to [`compute_weights`](Self::compute_weights), so
+    /// single-bar strategies need not implement it.
+    fn compute_weights_with_history(
+        &self,
+        bar_index: usize,
+        prices: &[(Symbol, i64)],
+        history: &PriceHistory,
+        portfolio: &Portfolio,
+    ) -> Vec<(Symbol, f64)> {
+        let _ = history;
+        self.compute_weights(bar_index, prices, portfolio)
+    }
 }
 
 /// Result of a backtest run.
@@ -55,6 +98,39 @@ pub struct BacktestResult {
     pub portfolio: Portfolio,
     /// Computed performance metrics (None if no returns recorded).
     pub metrics: Option<Metrics>,
+    /// Annualized one-way turnover: [`Portfolio::turnover`] scaled by
+    /// `periods_per_year / periods`. 0 for buy-and-hold or with no returns.
+    pub turnover: f64,
+    /// One snapshot per bar, taken after the bar's return is recorded, when
+    /// [`BacktestOptions::record_history`] is set; empty otherwise.
+    pub history: Vec<PortfolioSnapshot>,
+    /// Beta, alpha, tracking error, and information ratio against
+    /// [`BacktestOptions::benchmark`] (None without a benchmark or returns).
+    pub relative_metrics: Option<RelativeMetrics>,
+    /// Number of leading bars that were not traded (see
+    /// [`Strategy::warmup`]), capped at the number of bars.
+    pub warmup_bars: usize,
+}
+
+/// Optional settings for [`run_backtest_ex`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BacktestOptions<'a> {
+    /// Record a [`PortfolioSnapshot`] (equity, cash, weights, positions) at
+    /// every bar. Off by default to avoid the memory cost on long runs.
+    pub record_history: bool,
+    /// Benchmark return for each bar, used to fill
+    /// [`BacktestResult::relative_metrics`]. Must have one entry per bar.
+    pub benchmark: Option<&'a [f64]>,
+    /// Override [`Strategy::warmup`] with a fixed number of untraded bars.
+    pub warmup: Option<usize>,
+    /// Bars between computing weights and trading them. With 1, weights
+    /// computed from bar `i` execute at bar `i + 1`'s prices, the standard
+    /// signal-to-execution lag; 0 (the default) trades on the same bar.
+    /// Weights from the last `fill_delay` bars are never executed.
+    pub fill_delay: usize,
+    /// Per-period interest credited on uninvested cash (see
+    /// [`Portfolio::with_cash_yield`]). 0 (the default) earns nothing.
+    pub cash_yield: f64,
 }
 
 /// Run a backtest of a strategy over a price series.
@@ -71,6 +147,8 @@ pub struct BacktestResult {
 /// * `cost_model` — Transaction cost model
 /// * `periods_per_year` — For annualizing metrics (12 for monthly, 252 for daily)
 /// * `risk_free` — Risk-free rate per period
+///
+/// Equivalent to [`run_backtest_ex`] with [`BacktestOptions::default`].
 pub fn run_backtest<S: Strategy>(
     strategy: &S,
     price_series: &[Vec<(Symbol, i64)>],
@@ -79,19 +157,97 @@ pub fn run_backtest<S: Strategy>(
     periods_per_year: f64,
     risk_free: f64,
 ) -> BacktestResult {
-    let mut portfolio = Portfolio::new(initial_cash, cost_model);
+    run_backtest_ex(
+        strategy,
+        price_series,
+        initial_cash,
+        cost_model,
+        periods_per_year,
+        risk_free,
+        BacktestOptions::default(),
+    )
+}
+
+/// Run a backtest with non-default [`BacktestOptions`].
+///
+/// # Panics
+///
+/// Panics if `options.benchmark` is set and its length differs from the
+/// number of bars in `price_series`, or if `options.cash_yield` is negative.
+pub fn run_backtest_ex<S: Strategy>(
+    strategy: &S,
+    price_series: &[Vec<(Symbol, i64)>],
+    initial_cash: i64,
+    cost_model: CostModel,
+    periods_per_year: f64,
+    risk_free: f64,
+    options: BacktestOptions<'_>,
+) -> BacktestResult {
+    if let Some(benchmark) = options.benchmark {
+        assert_eq!(
+            benchmark.len(),
+            price_series.len(),
+            "benchmark must have one return per bar"
+        );
+    }
+    let warmup_bars = options
+        .warmup
+        .unwrap_or_else(|| strategy.warmup())
+        .min(price_series.len());
+    let mut portfolio =
+        Portfolio::new(initial_cash, cost_model).with_cash_yield(options.cash_yield);
+    let mut lookback = PriceHistory::new(strategy.history_len());
+    // Weights awaiting execution, oldest first; None for warmup bars.
+    let mut pending: VecDeque<Option<Vec<(Symbol, f64)>>> =
+        VecDeque::with_capacity(options.fill_delay + 1);
+    let mut history = Vec::new();
+    if options.record_history {
+        history.reserve(price_series.len());
+    }
 
     for (i, prices) in price_series.iter().enumerate() {
-        let weights = strategy.compute_weights(i, prices, &portfolio);
+        lookback.push(prices.clone());
+        // The strategy only ever sees bars up to and including `i`.
+        let weights = strategy.compute_weights_with_history(i, prices, &lookback, &portfolio);
+        pending.push_back((i >= warmup_bars).then_some(weights));
         let price_map: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
-        portfolio.rebalance_simple_from_price_map(&weights, &price_map);
+        if pending.len() > options.fill_delay
+            && let Some(Some(weights)) = pending.pop_front()
+        {
+            portfolio.rebalance_simple_from_price_map(&weights, &price_map);
+        }
         portfolio.record_return_from_price_map(&price_map);
+        if options.record_history {
+            history.push(portfolio.snapshot(prices));
+        }
     }
 
     let metrics =
         crate::portfolio::compute_metrics(portfolio.returns(), periods_per_year, risk_free);
+    let periods = portfolio.returns().len();
+    let turnover = if periods > 0 {
+        portfolio.turnover() * periods_per_year / periods as f64
+    } else {
+        0.0
+    };
+
+    let relative_metrics = options.benchmark.and_then(|benchmark| {
+        crate::portfolio::compute_relative_metrics(
+            portfolio.returns(),
+            benchmark,
+            periods_per_year,
+            risk_free,
+        )
+    });
 
-    BacktestResult { portfolio, metrics }
+    BacktestResult {
+        portfolio,
+        metrics,
+        turnover,
+        history,
+        relative_metrics,
+        warmup_bars,
+    }
 }
 
 /// Equal-weight strategy: allocates equally across all symbols.
@@ -112,6 +268,156 @@ impl Strategy for EqualWeight {
     }
 }
 
+/// Precomputed target weights: holds `weights[bar_index]` at each bar.
+///
+/// For signals computed ahead of time. Running it through
+/// [`run_backtest`] gives the same result as a strategy that returns the
+/// same weights from [`compute_weights`](Strategy::compute_weights). Bars
+/// past the end of `weights` hold cash.
+pub struct TargetWeights<'a> {
+    /// Per-bar `(symbol, weight)` targets, aligned with the price series.
+    pub weights: &'a [Vec<(Symbol, f64)>],
+}
+
+impl<'a> TargetWeights<'a> {
+    /// Create a strategy replaying per-bar target weights.
+    pub fn new(weights: &'a [Vec<(Symbol, f64)>]) -> Self {
+        Self { weights }
+    }
+}
+
+impl Strategy for TargetWeights<'_> {
+    fn compute_weights(
+        &self,
+        bar_index: usize,
+        _prices: &[(Symbol, i64)],
+        _portfolio: &Portfolio,
+    ) -> Vec<(Symbol, f64)> {
+        self.weights.get(bar_index).cloned().unwrap_or_default()
+    }
+}
+
+/// Cross-sectional top-k strategy: equal-weights the `k` highest-scored symbols.
+///
+/// `scores[bar_index]` holds that bar's `(symbol, score)` pairs. Only symbols
+/// that are priced on the bar and have a finite score are eligible. Ties in
+/// score break deterministically by symbol (ascending), so the selected set
+/// never depends on input order. Bars without scores hold cash.
+pub struct TopK<'a> {
+    /// Number of symbols to hold each bar.
+    pub k: usize,
+    /// Per-bar `(symbol, score)` pairs, aligned with the price series.
+    pub scores: &'a [Vec<(Symbol, f64)>],
+}
+
+impl<'a> TopK<'a> {
+    /// Create a top-k strategy over per-bar scores.
+    pub fn new(k: usize, scores: &'a [Vec<(Symbol, f64)>]) -> Self {
+        Self { k, scores }
+    }
+}
+
+/// Eligible `(symbol, score)` pairs for a bar, sorted by symbol so that
+/// stable ranking breaks ties deterministically.
+pub(crate) fn ranked_candidates(
+    scores: &[(Symbol, f64)],
+    prices: &[(Symbol, i64)],
+) -> Vec<(Symbol, f64)> {
+    let priced: FxHashMap<Symbol, i64> = prices.iter().copied().collect();
+    let mut candidates: Vec<(Symbol, f64)> = scores
+        .iter()
+        .copied()
+        .filter(|(sym, score)| score.is_finite() && priced.get(sym).is_some_and(|&p| p > 0))
+        .collect();
+    candidates.sort_by_key(|&(sym, _)| sym);
+    candidates.dedup_by_key(|(sym, _)| *sym);
+    candidates
+}
+
+impl Strategy for TopK<'_> {
+    fn compute_weights(
+        &self,
+        bar_index: usize,
+        prices: &[(Symbol, i64)],
+        _portfolio: &Portfolio,
+    ) -> Vec<(Symbol, f64)> {
+        let Some(bar_scores) = self.scores.get(bar_index) else {
+            return Vec::new();
+        };
+        let candidates = ranked_candidates(bar_scores, prices);
+        let n = self.k.min(candidates.len());
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let values: Vec<f64> = candidates.iter().map(|&(_, score)| score).collect();
+        let weight = 1.0 / n as f64;
+        crate::stats::argsort(&values, true)
+            .into_iter()
+            .take(n)
+            .map(|i| (candidates[i].0, weight))
+            .collect()
+    }
+}
+
+/// Long-short quantile strategy: long the top quantile, short the bottom.
+///
+/// Each bar, eligible symbols (priced, finite score) are ranked the same way
+/// as [`crate::stats::quintile_spread`]: group size is
+/// `floor(n / n_quantiles)` and interior observations are ignored. The top
+/// group is held at `+0.5 / group_size` each and the bottom group at
+/// `-0.5 / group_size` each, so the book is dollar-neutral with gross
+/// exposure 1.0. Ties in score break by symbol. Bars with fewer eligible
+/// symbols than `n_quantiles` hold cash.
+pub struct LongShortQuantile<'a> {
+    /// Number of quantile buckets (5 for quintiles, 10 for deciles).
+    pub n_quantiles: usize,
+    /// Per-bar `(symbol, score)` pairs, aligned with the price series.
+    pub scores: &'a [Vec<(Symbol, f64)>],
+}
+
+impl<'a> LongShortQuantile<'a> {
+    /// Create a long-short quantile strategy over per-bar scores.
+    pub fn new(n_quantiles: usize, scores: &'a [Vec<(Symbol, f64)>]) -> Self {
+        Self {
+            n_quantiles,
+            scores,
+        }
+    }
+}
+
+impl Strategy for LongShortQuantile<'_> {
+    fn compute_weights(
+        &self,
+        bar_index: usize,
+        prices: &[(Symbol, i64)],
+        _portfolio: &Portfolio,
+    ) -> Vec<(Symbol, f64)> {
+        let Some(bar_scores) = self.scores.get(bar_index) else {
+            return Vec::new();
+        };
+        if self.n_quantiles < 2 {
+            return Vec::new();
+        }
+        let candidates = ranked_candidates(bar_scores, prices);
+        let group_size = candidates.len() / self.n_quantiles;
+        if group_size == 0 {
+            return Vec::new();
+        }
+
+        let values: Vec<f64> = candidates.iter().map(|&(_, score)| score).collect();
+        let order = crate::stats::argsort(&values, true);
+        let weight = 0.5 / group_size as f64;
+        let longs = order[..group_size]
+            .iter()
+            .map(|&i| (candidates[i].0, weight));
+        let shorts = order[order.len() - group_size..]
+            .iter()
+            .map(|&i| (candidates[i].0, -weight));
+        longs.chain(shorts).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::inconsistent_digit_grouping)]
@@ -226,7 +532,7 @@ mod tests {
         let cost_model = CostModel {
             commission_bps: 10,
             slippage_bps: 5,
-            min_trade_fee: 0,
+            ..CostModel::zero()
         };
 
         let prices = vec![
@@ -248,4 +554,506 @@ mod tests {
         let weights = strat.compute_weights(0, &[], &Portfolio::new(100_00, CostModel::zero()));
         assert!(weights.is_empty());
     }
+
+    #[test]
+    fn top_k_selects_highest_scores() {
+        let prices = vec![(sym("A"), 10_00), (sym("B"), 10_00), (sym("C"), 10_00)];
+        let scores = vec![vec![(sym("A"), 0.1), (sym("B"), 0.9), (sym("C"), 0.5)]];
+        let strat = TopK::new(2, &scores);
+        let weights = strat.compute_weights(0, &prices, &Portfolio::new(100_00, CostModel::zero()));
+        assert_eq!(weights, vec![(sym("B"), 0.5), (sym("C"), 0.5)]);
+    }
+
+    #[test]
+    fn top_k_ties_break_by_symbol() {
+        let prices = vec![(sym("A"), 10_00), (sym("B"), 10_00), (sym("C"), 10_00)];
+        // Same scores, different input orders → same selection.
+        let s1 = vec![vec![(sym("C"), 1.0), (sym("B"), 1.0), (sym("A"), 1.0)]];
+        let s2 = vec![vec![(sym("A"), 1.0), (sym("C"), 1.0), (sym("B"), 1.0)]];
+        let p = Portfolio::new(100_00, CostModel::zero());
+        let w1 = TopK::new(2, &s1).compute_weights(0, &prices, &p);
+        let w2 = TopK::new(2, &s2).compute_weights(0, &prices, &p);
+        assert_eq!(w1, vec![(sym("A"), 0.5), (sym("B"), 0.5)]);
+        assert_eq!(w1, w2);
+    }
+
+    #[test]
+    fn top_k_skips_nan_and_unpriced() {
+        let prices = vec![(sym("A"), 10_00), (sym("B"), 10_00)];
+        let scores = vec![vec![
+            (sym("A"), f64::NAN),
+            (sym("B"), 0.2),
+            (sym("Z"), 9.0), // not priced this bar
+        ]];
+        let strat = TopK::new(2, &scores);
+        let weights = strat.compute_weights(0, &prices, &Portfolio::new(100_00, CostModel::zero()));
+        assert_eq!(weights, vec![(sym("B"), 1.0)]);
+    }
+
+    #[test]
+    fn top_k_backtest_runs() {
+        let prices = vec![
+            vec![(sym("A"), 100_00), (sym("B"), 100_00)],
+            vec![(sym("A"), 110_00), (sym("B"), 90_00)],
+            vec![(sym("A"), 120_00), (sym("B"), 80_00)],
+        ];
+        let scores = vec![vec![(sym("A"), 1.0), (sym("B"), 0.0)]; 3];
+        let result = run_backtest(
+            &TopK::new(1, &scores),
+            &prices,
+            1_000_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+        );
+        let m = result.metrics.unwrap();
+        assert!(m.total_return > 0.0);
+        assert!(
+            result
+                .portfolio
+                .position(&sym("B"))
+                .is_none_or(|p| p.is_flat())
+        );
+    }
+
+    #[test]
+    fn target_weights_replays_schedule() {
+        let series = vec![
+            vec![(sym("A"), 10_00), (sym("B"), 20_00)],
+            vec![(sym("A"), 11_00), (sym("B"), 19_00)],
+            vec![(sym("A"), 12_00), (sym("B"), 21_00)],
+        ];
+        let weights = vec![
+            vec![(sym("A"), 0.5), (sym("B"), 0.5)],
+            vec![(sym("A"), 1.0)],
+        ];
+        let fixed = run_backtest(
+            &TargetWeights::new(&weights),
+            &series,
+            1_000_00,
+            CostModel::zero(),
+            252.0,
+            0.0,
+        );
+        let equal = run_backtest(
+            &EqualWeight,
+            &series[..1],
+            1_000_00,
+            CostModel::zero(),
+            252.0,
+            0.0,
+        );
+        assert_eq!(
+            fixed.portfolio.equity_curve()[0],
+            equal.portfolio.equity_curve()[0]
+        );
+        // The schedule ends after bar 1, so bar 2 goes to cash.
+        assert!(fixed.portfolio.positions().all(|(_, p)| p.quantity == 0));
+        assert_eq!(fixed.portfolio.returns().len(), 3);
+    }
+
+    #[test]
+    fn long_short_is_dollar_neutral() {
+        let prices: Vec<(Symbol, i64)> = ["A", "B", "C", "D", "E"]
+            .iter()
+            .map(|s| (sym(s), 10_00))
+            .collect();
+        let scores = vec![vec![
+            (sym("A"), 5.0),
+            (sym("B"), 4.0),
+            (sym("C"), 3.0),
+            (sym("D"), 2.0),
+            (sym("E"), 1.0),
+        ]];
+        let strat = LongShortQuantile::new(2, &scores);
+        let weights = strat.compute_weights(0, &prices, &Portfolio::new(100_00, CostModel::zero()));
+        // n = 5, group_size = 2; C is in the ignored middle.
+        assert_eq!(
+            weights,
+            vec![
+                (sym("A"), 0.25),
+                (sym("B"), 0.25),
+                (sym("D"), -0.25),
+                (sym("E"), -0.25),
+            ]
+        );
+        let net: f64 = weights.iter().map(|&(_, w)| w).sum();
+        assert!(net.abs() < 1e-12);
+    }
+
+    #[test]
+    fn long_short_too_few_symbols_holds_cash() {
+        let prices = vec![(sym("A"), 10_00)];
+        let scores = vec![vec![(sym("A"), 1.0)]];
+        let strat = LongShortQuantile::new(5, &scores);
+        let weights = strat.compute_weights(0, &prices, &Portfolio::new(100_00, CostModel::zero()));
+        assert!(weights.is_empty());
+    }
+
+    #[test]
+    fn turnover_zero_for_buy_and_hold() {
+        struct BuyAndHold;
+        impl Strategy for BuyAndHold {
+            fn compute_weights(
+                &self,
+                bar_index: usize,
+                prices: &[(Symbol, i64)],
+                portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                if bar_index == 0 {
+                    vec![(prices[0].0, 1.0)]
+                } else {
+                    portfolio.current_weights(prices)
+                }
+            }
+        }
+
+        let prices = vec![
+            vec![(sym("AAPL"), 150_00)],
+            vec![(sym("AAPL"), 155_00)],
+            vec![(sym("AAPL"), 145_00)],
+        ];
+        let result = run_backtest(
+            &BuyAndHold,
+            &prices,
+            1_000_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+        );
+        assert_eq!(result.turnover, 0.0);
+    }
+
+    #[test]
+    fn turnover_annualizes_full_rotations() {
+        // Alternates between A and B every bar: each switch after the
+        // initial buy is one full unit of one-way turnover.
+        struct Rotate;
+        impl Strategy for Rotate {
+            fn compute_weights(
+                &self,
+                bar_index: usize,
+                prices: &[(Symbol, i64)],
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                vec![(prices[bar_index % 2].0, 1.0)]
+            }
+        }
+
+        let prices = vec![vec![(sym("A"), 100_00), (sym("B"), 100_00)]; 4];
+        let result = run_backtest(&Rotate, &prices, 1_000_000_00, CostModel::zero(), 12.0, 0.0);
+        assert!((result.portfolio.turnover() - 3.0).abs() < 1e-12);
+        assert!((result.turnover - 3.0 * 12.0 / 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn record_history_snapshots_every_bar() {
+        let prices = vec![
+            vec![(sym("AAPL"), 100_00)],
+            vec![(sym("AAPL"), 110_00)],
+            vec![(sym("AAPL"), 99_00)],
+        ];
+        let options = BacktestOptions {
+            record_history: true,
+            ..BacktestOptions::default()
+        };
+        let result = run_backtest_ex(
+            &EqualWeight,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+        assert_eq!(result.history.len(), 3);
+        let curve = result.portfolio.equity_curve();
+        for (i, snap) in result.history.iter().enumerate() {
+            assert_eq!(snap.equity, curve[i + 1]);
+            assert_eq!(snap.num_positions, 1);
+        }
+        assert_eq!(result.history[1].weights[0].0, sym("AAPL"));
+
+        let plain = run_backtest(
+            &EqualWeight,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+        );
+        assert!(plain.history.is_empty());
+    }
+
+    #[test]
+    fn benchmark_relative_metrics() {
+        let prices = vec![
+            vec![(sym("AAPL"), 100_00)],
+            vec![(sym("AAPL"), 110_00)],
+            vec![(sym("AAPL"), 99_00)],
+            vec![(sym("AAPL"), 104_00)],
+        ];
+        let plain = run_backtest(
+            &EqualWeight,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+        );
+        assert!(plain.relative_metrics.is_none());
+
+        // Benchmarking against its own returns: beta 1, no active risk.
+        let own = plain.portfolio.returns().to_vec();
+        let options = BacktestOptions {
+            benchmark: Some(&own),
+            ..BacktestOptions::default()
+        };
+        let result = run_backtest_ex(
+            &EqualWeight,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+        let rel = result.relative_metrics.unwrap();
+        assert!((rel.beta - 1.0).abs() < 1e-12);
+        assert!(rel.alpha.abs() < 1e-12);
+        assert_eq!(rel.tracking_error, 0.0);
+        assert_eq!(rel.information_ratio, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "one return per bar")]
+    fn benchmark_length_must_match_bars() {
+        let prices = vec![vec![(sym("AAPL"), 100_00)], vec![(sym("AAPL"), 101_00)]];
+        let options = BacktestOptions {
+            benchmark: Some(&[0.01]),
+            ..BacktestOptions::default()
+        };
+        run_backtest_ex(
+            &EqualWeight,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+    }
+
+    #[test]
+    fn warmup_bars_stay_in_cash() {
+        struct Slow;
+        impl Strategy for Slow {
+            fn compute_weights(
+                &self,
+                _bar_index: usize,
+                prices: &[(Symbol, i64)],
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                prices.iter().map(|&(s, _)| (s, 1.0)).collect()
+            }
+
+            fn warmup(&self) -> usize {
+                2
+            }
+        }
+
+        let prices = vec![
+            vec![(sym("AAPL"), 100_00)],
+            vec![(sym("AAPL"), 120_00)],
+            vec![(sym("AAPL"), 80_00)],
+            vec![(sym("AAPL"), 88_00)],
+        ];
+        let result = run_backtest(&Slow, &prices, 100_000_00, CostModel::zero(), 12.0, 0.0);
+        assert_eq!(result.warmup_bars, 2);
+        let returns = result.portfolio.returns();
+        assert_eq!(returns.len(), 4);
+        assert_eq!(&returns[..3], &[0.0, 0.0, 0.0]);
+        assert!((returns[3] - 0.1).abs() < 1e-10);
+
+        // The options override the strategy's own warmup, capped at the bar count.
+        let options = BacktestOptions {
+            warmup: Some(10),
+            ..BacktestOptions::default()
+        };
+        let result = run_backtest_ex(
+            &Slow,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+        assert_eq!(result.warmup_bars, 4);
+        assert!(result.portfolio.positions().next().is_none());
+        assert_eq!(
+            run_backtest(
+                &EqualWeight,
+                &prices,
+                100_000_00,
+                CostModel::zero(),
+                12.0,
+                0.0
+            )
+            .warmup_bars,
+            0
+        );
+    }
+
+    #[test]
+    fn history_strategy_sees_lookback_window() {
+        // Momentum: hold only while the price is above its value 2 bars ago.
+        struct Momentum;
+        impl Strategy for Momentum {
+            fn compute_weights(
+                &self,
+                _bar_index: usize,
+                _prices: &[(Symbol, i64)],
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                vec![]
+            }
+
+            fn history_len(&self) -> usize {
+                3
+            }
+
+            fn compute_weights_with_history(
+                &self,
+                _bar_index: usize,
+                prices: &[(Symbol, i64)],
+                history: &PriceHistory,
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                assert!(history.len() <= 3);
+                assert_eq!(history.bar(0), Some(prices));
+                let s = sym("AAPL");
+                match (history.price(&s, 0), history.price(&s, 2)) {
+                    (Some(now), Some(then)) if now > then => vec![(s, 1.0)],
+                    _ => vec![],
+                }
+            }
+        }
+
+        let prices: Vec<Vec<(Symbol, i64)>> = [100_00, 90_00, 100_00, 125_00, 100_00]
+            .iter()
+            .map(|&p| vec![(sym("AAPL"), p)])
+            .collect();
+        let result = run_backtest(&Momentum, &prices, 100_000_00, CostModel::zero(), 12.0, 0.0);
+        // Invested only from bar 3 (125 > 90) to bar 4 (100 > 100 is false).
+        let returns = result.portfolio.returns();
+        assert_eq!(&returns[..4], &[0.0, 0.0, 0.0, 0.0]);
+        assert!((returns[4] + 0.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn fill_delay_executes_at_next_bar() {
+        // Hold while the price is below 150: with no delay the strategy
+        // buys at 100 and exits at 150; lagged, it buys at 125 and is still
+        // holding when the exit signal arrives on the last bar.
+        struct BuyDip;
+        impl Strategy for BuyDip {
+            fn compute_weights(
+                &self,
+                _bar_index: usize,
+                prices: &[(Symbol, i64)],
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                let (s, p) = prices[0];
+                if p < 150_00 { vec![(s, 1.0)] } else { vec![] }
+            }
+        }
+
+        let prices: Vec<Vec<(Symbol, i64)>> = [200_00, 100_00, 125_00, 150_00]
+            .iter()
+            .map(|&p| vec![(sym("AAPL"), p)])
+            .collect();
+        let now = run_backtest(&BuyDip, &prices, 100_000_00, CostModel::zero(), 12.0, 0.0);
+        let pos = now.portfolio.position(&sym("AAPL")).unwrap();
+        assert!(pos.is_flat());
+        assert_eq!(pos.realized_pnl, 1_000 * 50_00);
+
+        let options = BacktestOptions {
+            fill_delay: 1,
+            ..BacktestOptions::default()
+        };
+        let lagged = run_backtest_ex(
+            &BuyDip,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+        let pos = lagged.portfolio.position(&sym("AAPL")).unwrap();
+        assert_eq!(pos.avg_entry_price, 125_00);
+        assert_eq!(pos.quantity, 800);
+        let returns = lagged.portfolio.returns();
+        assert_eq!(&returns[..3], &[0.0, 0.0, 0.0]);
+        assert!((returns[3] - 0.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn cash_yield_earns_on_uninvested_weight() {
+        struct HalfInvested;
+        impl Strategy for HalfInvested {
+            fn compute_weights(
+                &self,
+                _bar_index: usize,
+                prices: &[(Symbol, i64)],
+                _portfolio: &Portfolio,
+            ) -> Vec<(Symbol, f64)> {
+                vec![(prices[0].0, 0.5)]
+            }
+        }
+
+        let prices: Vec<Vec<(Symbol, i64)>> = (0..3).map(|_| vec![(sym("AAPL"), 100_00)]).collect();
+        let options = BacktestOptions {
+            cash_yield: 0.01,
+            ..BacktestOptions::default()
+        };
+        let result = run_backtest_ex(
+            &HalfInvested,
+            &prices,
+            100_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+            options,
+        );
+        // 1% on the $50,000 left in cash, then on the slightly larger balance
+        // after rebalancing the interest back to 50/50.
+        let returns = result.portfolio.returns();
+        assert_eq!(returns[0], 0.0);
+        assert!((returns[1] - 0.005).abs() < 1e-9);
+        assert!(returns[2] > 0.0049 && returns[2] < 0.0051);
+        assert_eq!(result.portfolio.cash_yield(), 0.01);
+    }
+
+    #[test]
+    fn long_short_backtest_profits_from_spread() {
+        let prices = vec![
+            vec![(sym("A"), 100_00), (sym("B"), 100_00)],
+            vec![(sym("A"), 110_00), (sym("B"), 90_00)],
+            vec![(sym("A"), 121_00), (sym("B"), 81_00)],
+        ];
+        let scores = vec![vec![(sym("A"), 1.0), (sym("B"), -1.0)]; 3];
+        let result = run_backtest(
+            &LongShortQuantile::new(2, &scores),
+            &prices,
+            1_000_000_00,
+            CostModel::zero(),
+            12.0,
+            0.0,
+        );
+        assert!(result.portfolio.position(&sym("A")).unwrap().quantity > 0);
+        assert!(result.portfolio.position(&sym("B")).unwrap().quantity < 0);
+        assert!(result.metrics.unwrap().total_return > 0.0);
+    }
 }
@@ -5,7 +5,7 @@
 
 use std::collections::BTreeMap;
 
-use crate::{Level, OrderId, Price, Quantity, Side};
+use crate::{Level, OrderId, Price, Quantity, Side, level::PriorityKey};
 
 /// One side of the order book (all bids or all asks).
 ///
@@ -15,6 +15,7 @@ use crate::{Level, OrderId, Price, Quantity, Side};
 /// The `BTreeMap` provides O(log n) insert/remove with sorted iteration.
 /// Best price is cached for O(1) access.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PriceLevels {
     /// Price levels, sorted by price
     levels: BTreeMap<Price, Level>,
@@ -115,6 +116,45 @@ impl PriceLevels {
         actual_index
     }
 
+    /// Add an order at the given price, queued by its priority key.
+    ///
+    /// Creates the level if it doesn't exist. Returns the order's queue
+    /// position (see [`Level::insert`]).
+    pub fn insert_order_with_key(
+        &mut self,
+        price: Price,
+        order_id: OrderId,
+        key: PriorityKey,
+        quantity: Quantity,
+    ) -> u64 {
+        self.get_or_create_level(price)
+            .insert(order_id, key, quantity)
+    }
+
+    /// Mark an order as a tombstone, at its queue `position` if that is
+    /// still current, else by its priority key.
+    ///
+    /// Removes the level if it becomes empty. Returns `false` if the order
+    /// is not queued at `price`.
+    pub fn tombstone_order(
+        &mut self,
+        price: Price,
+        order_id: OrderId,
+        key: PriorityKey,
+        position: u64,
+        quantity: Quantity,
+    ) -> bool {
+        let Some(level) = self.levels.get_mut(&price) else {
+            return false;
+        };
+        let found = level.tombstone_at(position, order_id, quantity)
+            || level.tombstone(order_id, key, quantity);
+        if level.is_empty() {
+            self.remove_level(price);
+        }
+        found
+    }
+
     /// Mark an order as a tombstone.
     pub fn mark_tombstone(&mut self, price: Price, index: usize, quantity: Quantity) {
         if let Some(level) = self.levels.get_mut(&price) {
@@ -189,6 +229,12 @@ impl PriceLevels {
         self.levels.values().map(|l| l.total_quantity()).sum()
     }
 
+    /// Returns the number of active orders across all levels (tombstones
+    /// excluded). O(levels).
+    pub fn order_count(&self) -> usize {
+        self.levels.values().map(|l| l.order_count()).sum()
+    }
+
     /// Returns the total quantity available at prices that would cross with the given price.
     ///
     /// For bids: quantity at prices >= given price
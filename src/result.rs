@@ -1,7 +1,7 @@
 //! Result types for Exchange operations.
 
 use crate::stop::StopStatus;
-use crate::{OrderId, OrderStatus, Quantity, Trade};
+use crate::{OrderId, OrderStatus, Price, Quantity, Trade};
 
 /// Result of submitting an order.
 #[derive(Clone, Debug)]
@@ -19,6 +19,25 @@ pub struct SubmitResult {
     pub resting_quantity: Quantity,
     /// Quantity that was cancelled (IOC remainder, FOK rejection)
     pub cancelled_quantity: Quantity,
+    /// Volume-weighted average price of `trades`, in price units
+    /// (`None` if nothing filled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub average_fill_price: Option<f64>,
+    /// Sum of `price × quantity` over `trades` (0 if nothing filled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub filled_notional: i64,
+    /// Why the exchange rejected the order or cancelled its remainder
+    /// (`None` if it was accepted, or an IOC remainder simply expired)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reject_reason: Option<RejectReason>,
+    /// For a rejected fill-or-kill order, the quantity the book could have
+    /// filled at its limit price when it was checked (`None` otherwise)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_fillable: Option<Quantity>,
+    /// Quantity filled at each price level, in the order the levels were
+    /// swept (empty if nothing filled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fills_by_level: Vec<(Price, Quantity)>,
 }
 
 impl SubmitResult {
@@ -36,6 +55,66 @@ impl SubmitResult {
     pub fn is_fully_filled(&self) -> bool {
         self.status == OrderStatus::Filled
     }
+
+    /// Returns true if the exchange rejected the order or cancelled its
+    /// remainder (see [`reject_reason`](Self::reject_reason)).
+    pub fn is_rejected(&self) -> bool {
+        self.reject_reason.is_some()
+    }
+}
+
+/// Why a submitted order was rejected or had its remainder cancelled.
+///
+/// Carried on [`SubmitResult::reject_reason`]. [`as_str`](Self::as_str)
+/// and [`code`](Self::code) give stable identifiers for callers that
+/// branch on the cause outside Rust. New causes may be added, so matches
+/// need a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum RejectReason {
+    /// A fill-or-kill order could not fill in full, so nothing traded
+    InsufficientLiquidity,
+    /// Self-trade prevention cancelled the incoming order's remainder
+    SelfTrade,
+    /// The order would have taken its owner past its position limit, so it
+    /// was rejected or clipped
+    PositionLimit,
+    /// The exchange was halted
+    Halted,
+    /// The trading session did not accept the order: the exchange was
+    /// closed, or the order was immediate (market, IOC, FOK) pre-open
+    Session,
+}
+
+impl RejectReason {
+    /// Stable snake_case name, e.g. `"self_trade"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::InsufficientLiquidity => "insufficient_liquidity",
+            RejectReason::SelfTrade => "self_trade",
+            RejectReason::PositionLimit => "position_limit",
+            RejectReason::Halted => "halted",
+            RejectReason::Session => "session",
+        }
+    }
+
+    /// Stable numeric code; never reused for a different reason.
+    pub fn code(&self) -> u8 {
+        match self {
+            RejectReason::InsufficientLiquidity => 1,
+            RejectReason::SelfTrade => 2,
+            RejectReason::PositionLimit => 3,
+            RejectReason::Halted => 4,
+            RejectReason::Session => 5,
+        }
+    }
+}
+
+impl std::fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Result of cancelling an order.
@@ -96,6 +175,14 @@ pub struct ModifyResult {
     pub trades: Vec<Trade>,
     /// Error if modification failed
     pub error: Option<ModifyError>,
+    /// Whether the order kept its queue position (amended in place).
+    ///
+    /// When `true`, `new_order_id` equals `old_order_id` and
+    /// `cancelled_quantity` is the size reduction. An in-place reduction
+    /// that a size-time re-rank moved in the queue also keeps its ID but
+    /// reports `false`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub priority_retained: bool,
 }
 
 impl ModifyResult {
@@ -113,6 +200,21 @@ impl ModifyResult {
             cancelled_quantity,
             trades,
             error: None,
+            priority_retained: false,
+        }
+    }
+
+    /// Create a result for an order amended in place (same ID, same queue
+    /// position, reduced by `cancelled_quantity`).
+    pub fn amended(order_id: OrderId, cancelled_quantity: Quantity) -> Self {
+        Self {
+            success: true,
+            old_order_id: order_id,
+            new_order_id: Some(order_id),
+            cancelled_quantity,
+            trades: Vec::new(),
+            error: None,
+            priority_retained: true,
         }
     }
 
@@ -125,6 +227,7 @@ impl ModifyResult {
             cancelled_quantity: 0,
             trades: Vec::new(),
             error: Some(error),
+            priority_retained: false,
         }
     }
 }
@@ -139,6 +242,89 @@ pub enum ModifyError {
     OrderNotActive,
     /// New quantity is zero
     InvalidQuantity,
+    /// The exchange is halted and would reject the replacement
+    Halted,
+    /// The trading session would reject the replacement
+    Session,
+    /// The owner's position limit would reject the replacement
+    PositionLimit,
+}
+
+/// Result of submitting a bracket order.
+///
+/// Returned by [`Exchange::submit_bracket`](crate::Exchange::submit_bracket).
+/// The exit IDs are set if the entry was done on submission; otherwise
+/// see [`Exchange::bracket`](crate::Exchange::bracket) once it fills.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BracketResult {
+    /// Result of the entry order
+    pub entry: SubmitResult,
+    /// Stop-loss order ID, if armed
+    pub stop_order_id: Option<OrderId>,
+    /// Take-profit order ID, if armed
+    pub target_order_id: Option<OrderId>,
+}
+
+/// Errors from [`Exchange::link_oco`](crate::Exchange::link_oco).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OcoError {
+    /// Both IDs name the same order
+    SameOrder,
+    /// An order is not resting or a pending stop
+    OrderNotActive,
+    /// An order is already linked to another
+    AlreadyLinked,
+}
+
+/// Result of submitting a multi-leg spread order.
+///
+/// Returned by [`MultiExchange::submit_spread`](crate::MultiExchange::submit_spread).
+/// Spreads are all-or-nothing: on success every leg filled in full, on
+/// failure no leg traded and `legs` is empty.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpreadResult {
+    /// Whether every leg filled
+    pub success: bool,
+    /// Per-leg submit results, in the order the legs were given
+    pub legs: Vec<SubmitResult>,
+    /// Buy-leg notional minus sell-leg notional across all fills
+    /// (0 if rejected)
+    pub net_notional: i64,
+    /// Error if the spread was rejected
+    pub error: Option<SpreadError>,
+}
+
+impl SpreadResult {
+    /// Create a rejected spread result.
+    pub fn failure(error: SpreadError) -> Self {
+        Self {
+            success: false,
+            legs: Vec::new(),
+            net_notional: 0,
+            error: Some(error),
+        }
+    }
+}
+
+/// Reasons a spread order is rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpreadError {
+    /// No legs, a zero ratio, or a zero quantity
+    InvalidQuantity,
+    /// The same symbol appears in more than one leg
+    DuplicateSymbol,
+    /// A leg's book cannot fill its full quantity
+    InsufficientLiquidity,
+    /// The legs would fill at a net price above the limit
+    LimitExceeded,
+    /// A leg's exchange is halted
+    Halted,
+    /// A leg's exchange is not in its open session
+    Session,
 }
 
 /// Result of submitting a stop order.
@@ -150,3 +336,76 @@ pub struct StopSubmitResult {
     /// Status of the stop order (Pending or Triggered if immediate).
     pub status: StopStatus,
 }
+
+/// Liquidity provided and taken by one owner, from the trade tape.
+///
+/// Returned by [`Exchange::flow_stats`](crate::Exchange::flow_stats).
+/// Notionals are `price × quantity` summed over the counted trades.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlowStats {
+    /// Quantity filled while resting (passive)
+    pub maker_volume: Quantity,
+    /// Quantity filled as the aggressor
+    pub taker_volume: Quantity,
+    /// Notional filled while resting (passive)
+    pub maker_notional: i64,
+    /// Notional filled as the aggressor
+    pub taker_notional: i64,
+}
+
+/// Signed volume and VWAP over a window of the trade tape.
+///
+/// Returned by [`Exchange::trade_stats`](crate::Exchange::trade_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeStats {
+    /// Quantity traded with a buy aggressor
+    pub buy_volume: Quantity,
+    /// Quantity traded with a sell aggressor
+    pub sell_volume: Quantity,
+    /// Number of trades in the window
+    pub trade_count: usize,
+    /// Volume-weighted average price (`None` if no trades)
+    pub vwap: Option<Price>,
+}
+
+/// Result of routing one order across several venues.
+///
+/// Returned by [`route_order`](crate::multi_exchange::route_order).
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouteResult {
+    /// One child order per venue that was sent liquidity, in venue order
+    pub fills: Vec<VenueFill>,
+    /// Quantity filled across all venues
+    pub filled_quantity: Quantity,
+    /// Sum of `price × quantity` over every fill
+    pub filled_notional: i64,
+}
+
+/// The child order a router sent to one venue.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VenueFill {
+    /// Index of the venue in the slice passed to the router
+    pub venue: usize,
+    /// Result of the IOC child order at that venue
+    pub result: SubmitResult,
+}
+
+/// Expected outcome of a market order that was not submitted.
+///
+/// Returned by [`Exchange::sweep_vwap`](crate::Exchange::sweep_vwap).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SweepEstimate {
+    /// Quantity the book can fill (less than requested if it is too thin)
+    pub filled_quantity: Quantity,
+    /// Sum of `price × quantity` over the fills
+    pub notional: i64,
+    /// Volume-weighted average fill price (`None` if nothing fills)
+    pub vwap: Option<Price>,
+    /// Price of the last level reached (`None` if nothing fills)
+    pub worst_price: Option<Price>,
+}
@@ -0,0 +1,280 @@
+//! Trading sessions: pre-open, open, and closed states.
+//!
+//! An exchange is always in one [`SessionState`]. It starts open, and moves
+//! between states either explicitly via [`Exchange::set_session`] or on a
+//! schedule driven by the logical clock ([`Exchange::set_session_schedule`]).
+//!
+//! Pre-open, GTC limit orders are accepted and rest without matching, even
+//! if they cross; immediate orders (market, IOC, FOK) are rejected. There
+//! is no opening auction: when the session opens, the orders queued
+//! pre-open are matched one by one in arrival order, exactly as if they had
+//! been submitted at that moment. Closed, every new order is rejected.
+//! Cancels and in-place reductions work in every state.
+
+#[cfg(feature = "event-log")]
+use crate::event::Event;
+use crate::{Exchange, OrderStatus, Timestamp};
+
+/// Trading session state of an exchange.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SessionState {
+    /// Orders rest but do not match; immediate orders are rejected
+    PreOpen,
+    /// Continuous matching
+    #[default]
+    Open,
+    /// Every new order is rejected
+    Closed,
+}
+
+impl SessionState {
+    /// Stable snake_case name, e.g. `"pre_open"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionState::PreOpen => "pre_open",
+            SessionState::Open => "open",
+            SessionState::Closed => "closed",
+        }
+    }
+}
+
+impl std::fmt::Display for SessionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Exchange {
+    /// Move the exchange to session `state`.
+    ///
+    /// Opening matches the orders queued pre-open in arrival order; their
+    /// trades land in [`Self::trades`] and may trigger stops. Setting the
+    /// current state again does nothing. Rejections carry
+    /// [`RejectReason::Session`](crate::RejectReason::Session).
+    ///
+    /// ```
+    /// use nanobook::{Exchange, Price, SessionState, Side, TimeInForce};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_session(SessionState::PreOpen);
+    /// exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+    /// let bid = exchange.submit_limit(Side::Buy, Price(101_00), 4, TimeInForce::GTC);
+    /// assert!(bid.trades.is_empty());
+    ///
+    /// exchange.set_session(SessionState::Open);
+    /// assert_eq!(exchange.trades()[0].quantity, 4);
+    /// assert_eq!(exchange.trades()[0].price, Price(100_00));
+    /// ```
+    pub fn set_session(&mut self, state: SessionState) {
+        #[cfg(feature = "event-log")]
+        self.events.push(Event::SetSession { state });
+
+        self.set_session_internal(state);
+    }
+
+    /// Current session state.
+    pub fn session(&self) -> SessionState {
+        self.session
+    }
+
+    /// Drive session transitions from the logical clock.
+    ///
+    /// Each `(timestamp, state)` entry takes effect once
+    /// [`Self::set_clock`] reaches its timestamp; entries at or before the
+    /// current clock apply immediately. Entries are sorted by timestamp
+    /// (ties keep their given order) and replace any earlier schedule.
+    /// Transitions are recorded in the event log as explicit
+    /// [`Event::SetSession`](crate::Event::SetSession) events, so a replay
+    /// reproduces them without the schedule.
+    ///
+    /// ```
+    /// use nanobook::{Exchange, SessionState};
+    ///
+    /// let mut exchange = Exchange::new();
+    /// exchange.set_session_schedule([
+    ///     (0, SessionState::PreOpen),
+    ///     (9_30, SessionState::Open),
+    ///     (16_00, SessionState::Closed),
+    /// ]);
+    /// exchange.set_clock(9_00);
+    /// assert_eq!(exchange.session(), SessionState::PreOpen);
+    /// exchange.set_clock(16_05);
+    /// assert_eq!(exchange.session(), SessionState::Closed);
+    /// ```
+    pub fn set_session_schedule(
+        &mut self,
+        schedule: impl IntoIterator<Item = (Timestamp, SessionState)>,
+    ) {
+        let mut schedule: Vec<_> = schedule.into_iter().collect();
+        schedule.sort_by_key(|&(ts, _)| ts);
+        self.session_schedule = schedule;
+        self.next_session = 0;
+        if let Some(now) = self.book.clock() {
+            self.advance_session(now);
+        }
+    }
+
+    /// The session schedule, in time order.
+    pub fn session_schedule(&self) -> &[(Timestamp, SessionState)] {
+        &self.session_schedule
+    }
+
+    /// Apply every scheduled transition at or before `now`.
+    pub(crate) fn advance_session(&mut self, now: Timestamp) {
+        while let Some(&(ts, state)) = self.session_schedule.get(self.next_session) {
+            if ts > now {
+                break;
+            }
+            self.next_session += 1;
+            if state != self.session {
+                #[cfg(feature = "event-log")]
+                self.events.push(Event::SetSession { state });
+                self.set_session_internal(state);
+            }
+        }
+    }
+
+    /// Internal: change session without recording an event.
+    pub(crate) fn set_session_internal(&mut self, state: SessionState) {
+        if state == self.session {
+            return;
+        }
+        self.session = state;
+        if state == SessionState::Open {
+            self.match_queued_orders();
+        }
+    }
+
+    /// Match the orders that rested pre-open, in arrival order.
+    fn match_queued_orders(&mut self) {
+        // Pull every queued order off the book first, so each one only
+        // meets the orders that arrived before it.
+        let mut queued = Vec::with_capacity(self.queued_orders.len());
+        for order_id in std::mem::take(&mut self.queued_orders) {
+            let Some(remaining) = self.book.cancel_order(order_id) else {
+                continue;
+            };
+            let mut order = self
+                .book
+                .orders
+                .remove(&order_id)
+                .expect("cancelled order stays in history");
+            order.remaining_quantity = remaining;
+            order.status = if order.filled_quantity > 0 {
+                OrderStatus::PartiallyFilled
+            } else {
+                OrderStatus::New
+            };
+            queued.push(order);
+        }
+
+        for order in queued {
+            let result = self.execute_order(order, 0);
+            if let Some(last) = result.trades.last() {
+                self.last_trade_price = Some(last.price);
+                self.process_trade_triggers();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "event-log")]
+    use crate::OrderId;
+    use crate::{ModifyError, Price, RejectReason, Side, TimeInForce};
+
+    #[test]
+    fn pre_open_queues_then_matches_in_arrival_order() {
+        let mut exchange = Exchange::new();
+        exchange.set_session(SessionState::PreOpen);
+
+        let ask = exchange.submit_limit(Side::Sell, Price(100_00), 30, TimeInForce::GTC);
+        let bid_a = exchange.submit_limit(Side::Buy, Price(102_00), 20, TimeInForce::GTC);
+        let bid_b = exchange.submit_limit(Side::Buy, Price(101_00), 20, TimeInForce::GTC);
+        assert_eq!(bid_a.status, OrderStatus::New);
+        assert!(exchange.trades().is_empty());
+        assert!(exchange.is_crossed());
+
+        let market = exchange.submit_market(Side::Buy, 5);
+        assert_eq!(market.reject_reason, Some(RejectReason::Session));
+
+        exchange.set_session(SessionState::Open);
+        assert!(!exchange.is_crossed());
+        let trades = exchange.trades();
+        assert_eq!(trades.len(), 2);
+        // Each bid matched the resting ask in arrival order at its price.
+        assert_eq!(trades[0].aggressor_order_id, bid_a.order_id);
+        assert_eq!(trades[0].passive_order_id, ask.order_id);
+        assert_eq!(trades[0].price, Price(100_00));
+        assert_eq!(trades[1].aggressor_order_id, bid_b.order_id);
+        assert_eq!(trades[1].quantity, 10);
+        assert_eq!(
+            exchange
+                .get_order(bid_b.order_id)
+                .unwrap()
+                .remaining_quantity,
+            10
+        );
+        assert_eq!(exchange.best_bid(), Some(Price(101_00)));
+    }
+
+    #[test]
+    fn closed_rejects_new_orders_but_allows_cancels() {
+        let mut exchange = Exchange::new();
+        let resting = exchange.submit_limit(Side::Buy, Price(99_00), 10, TimeInForce::GTC);
+        exchange.set_session(SessionState::Closed);
+
+        let result = exchange.submit_limit(Side::Buy, Price(98_00), 10, TimeInForce::GTC);
+        assert_eq!(result.reject_reason, Some(RejectReason::Session));
+        assert!(exchange.get_order(result.order_id).is_none());
+
+        let modify = exchange.modify(resting.order_id, Price(98_00), 10);
+        assert_eq!(modify.error, Some(ModifyError::Session));
+        assert!(exchange.cancel(resting.order_id).success);
+    }
+
+    #[test]
+    fn schedule_follows_the_logical_clock() {
+        let mut exchange = Exchange::new();
+        exchange.set_clock(5);
+        exchange.set_session_schedule([
+            (20, SessionState::Closed),
+            (0, SessionState::PreOpen),
+            (10, SessionState::Open),
+        ]);
+        assert_eq!(exchange.session(), SessionState::PreOpen);
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+
+        exchange.set_clock(10);
+        assert_eq!(exchange.session(), SessionState::Open);
+        assert_eq!(exchange.trades().len(), 1);
+        assert_eq!(exchange.trades()[0].timestamp, 10);
+
+        exchange.set_clock(25);
+        assert_eq!(exchange.session(), SessionState::Closed);
+
+        exchange.clear();
+        assert_eq!(exchange.session(), SessionState::Open);
+        exchange.set_clock(1);
+        assert_eq!(exchange.session(), SessionState::PreOpen);
+    }
+
+    #[cfg(feature = "event-log")]
+    #[test]
+    fn scheduled_transitions_replay_without_schedule() {
+        let mut exchange = Exchange::new();
+        exchange.set_session_schedule([(0, SessionState::PreOpen), (10, SessionState::Open)]);
+        exchange.set_clock(0);
+        exchange.submit_limit(Side::Sell, Price(100_00), 10, TimeInForce::GTC);
+        exchange.submit_limit(Side::Buy, Price(100_00), 10, TimeInForce::GTC);
+        exchange.set_clock(10);
+
+        let replayed = Exchange::replay(exchange.events());
+        assert_eq!(replayed.trades(), exchange.trades());
+        assert_eq!(replayed.trades()[0].passive_order_id, OrderId(1));
+    }
+}
@@ -1,6 +1,7 @@
 //! Book snapshots for market data.
 
-use crate::{OrderBook, Price, Quantity, Timestamp};
+use crate::{OrderBook, Price, Quantity, Side, Timestamp};
+use std::fmt;
 
 /// A snapshot of the order book at a point in time.
 #[derive(Clone, Debug, Default)]
@@ -14,7 +15,69 @@ pub struct BookSnapshot {
     pub timestamp: Timestamp,
 }
 
+/// Errors returned when building a [`BookSnapshot`] from raw levels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// Bid level at `index` is not strictly below the previous level.
+    UnsortedBids { index: usize },
+    /// Ask level at `index` is not strictly above the previous level.
+    UnsortedAsks { index: usize },
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::UnsortedBids { index } => write!(
+                f,
+                "bid level {index} is not strictly below the previous level"
+            ),
+            SnapshotError::UnsortedAsks { index } => write!(
+                f,
+                "ask level {index} is not strictly above the previous level"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
 impl BookSnapshot {
+    /// Build a snapshot from crafted levels, e.g. to test analytics without
+    /// driving the engine.
+    ///
+    /// Bids must be strictly descending by price and asks strictly
+    /// ascending (best first, no duplicate prices), matching what
+    /// [`OrderBook::snapshot`] produces. Crossed books are accepted.
+    ///
+    /// ```
+    /// use nanobook::{BookSnapshot, LevelSnapshot, Price};
+    ///
+    /// let snap = BookSnapshot::new(
+    ///     vec![LevelSnapshot::new(Price(100_00), 300, 1)],
+    ///     vec![LevelSnapshot::new(Price(102_00), 100, 1)],
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(snap.imbalance(), Some(0.5));
+    /// ```
+    pub fn new(
+        bids: Vec<LevelSnapshot>,
+        asks: Vec<LevelSnapshot>,
+        timestamp: Timestamp,
+    ) -> Result<Self, SnapshotError> {
+        if let Some(index) = (1..bids.len()).find(|&i| bids[i].price >= bids[i - 1].price) {
+            return Err(SnapshotError::UnsortedBids { index });
+        }
+        if let Some(index) = (1..asks.len()).find(|&i| asks[i].price <= asks[i - 1].price) {
+            return Err(SnapshotError::UnsortedAsks { index });
+        }
+        Ok(Self {
+            bids,
+            asks,
+            timestamp,
+        })
+    }
+
     /// Returns the best bid price, if any.
     pub fn best_bid(&self) -> Option<Price> {
         self.bids.first().map(|l| l.price)
@@ -90,6 +153,121 @@ impl BookSnapshot {
                 / total as f64,
         )
     }
+
+    /// Level changes that turn `self` into `other`.
+    ///
+    /// Each price whose quantity or order count differs yields exactly one
+    /// [`LevelChange`]; unchanged levels yield nothing. Changes are listed
+    /// bids first, then asks, each best to worst. A removed level reports
+    /// zero quantity and order count. Snapshots taken with a depth limit
+    /// report levels that moved past the limit as removed.
+    ///
+    /// ```
+    /// use nanobook::{BookSnapshot, LevelChangeKind, LevelSnapshot, Price};
+    ///
+    /// let before = BookSnapshot::new(
+    ///     vec![LevelSnapshot::new(Price(100_00), 300, 1)],
+    ///     vec![LevelSnapshot::new(Price(102_00), 100, 1)],
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// let after = BookSnapshot::new(
+    ///     vec![LevelSnapshot::new(Price(100_00), 200, 1)],
+    ///     vec![],
+    ///     1,
+    /// )
+    /// .unwrap();
+    ///
+    /// let changes = before.diff(&after);
+    /// assert_eq!(changes.len(), 2);
+    /// assert_eq!(changes[0].kind, LevelChangeKind::Changed);
+    /// assert_eq!(changes[0].quantity, 200);
+    /// assert_eq!(changes[1].kind, LevelChangeKind::Removed);
+    /// ```
+    pub fn diff(&self, other: &BookSnapshot) -> Vec<LevelChange> {
+        let mut changes = Vec::new();
+        diff_side(Side::Buy, &self.bids, &other.bids, &mut changes);
+        diff_side(Side::Sell, &self.asks, &other.asks, &mut changes);
+        changes
+    }
+}
+
+/// Merge two best-to-worst level lists into `out`.
+fn diff_side(side: Side, old: &[LevelSnapshot], new: &[LevelSnapshot], out: &mut Vec<LevelChange>) {
+    // Best-to-worst order: descending for bids, ascending for asks
+    let better = |a: Price, b: Price| match side {
+        Side::Buy => a > b,
+        Side::Sell => a < b,
+    };
+    let change = |kind, level: &LevelSnapshot| LevelChange {
+        side,
+        price: level.price,
+        kind,
+        quantity: if kind == LevelChangeKind::Removed {
+            0
+        } else {
+            level.quantity
+        },
+        order_count: if kind == LevelChangeKind::Removed {
+            0
+        } else {
+            level.order_count
+        },
+    };
+
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        match (old.get(i), new.get(j)) {
+            (Some(o), Some(n)) if o.price == n.price => {
+                if o.quantity != n.quantity || o.order_count != n.order_count {
+                    out.push(change(LevelChangeKind::Changed, n));
+                }
+                i += 1;
+                j += 1;
+            }
+            (Some(o), Some(n)) if better(o.price, n.price) => {
+                out.push(change(LevelChangeKind::Removed, o));
+                i += 1;
+            }
+            (Some(o), None) => {
+                out.push(change(LevelChangeKind::Removed, o));
+                i += 1;
+            }
+            (_, Some(n)) => {
+                out.push(change(LevelChangeKind::Added, n));
+                j += 1;
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+/// How a price level differs between two snapshots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LevelChangeKind {
+    /// The level appears only in the newer snapshot.
+    Added,
+    /// The level appears only in the older snapshot.
+    Removed,
+    /// The level is in both, with a different quantity or order count.
+    Changed,
+}
+
+/// One incremental update produced by [`BookSnapshot::diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LevelChange {
+    /// Book side of the level
+    pub side: Side,
+    /// Price of the level
+    pub price: Price,
+    /// Whether the level was added, removed, or changed
+    pub kind: LevelChangeKind,
+    /// New total quantity (0 if removed)
+    pub quantity: Quantity,
+    /// New order count (0 if removed)
+    pub order_count: usize,
 }
 
 /// A snapshot of a single price level.
@@ -104,6 +282,17 @@ pub struct LevelSnapshot {
     pub order_count: usize,
 }
 
+impl LevelSnapshot {
+    /// Create a level snapshot.
+    pub fn new(price: Price, quantity: Quantity, order_count: usize) -> Self {
+        Self {
+            price,
+            quantity,
+            order_count,
+        }
+    }
+}
+
 impl OrderBook {
     /// Take a snapshot of the top N levels on each side.
     pub fn snapshot(&self, depth: usize) -> BookSnapshot {
@@ -298,4 +487,97 @@ mod tests {
         let snap = book.snapshot(10);
         assert!(snap.weighted_mid().is_none());
     }
+
+    // === Construction ===
+
+    #[test]
+    fn new_accepts_sorted_levels() {
+        let snap = BookSnapshot::new(
+            vec![
+                LevelSnapshot::new(Price(100_00), 100, 1),
+                LevelSnapshot::new(Price(99_00), 200, 2),
+            ],
+            vec![LevelSnapshot::new(Price(101_00), 50, 1)],
+            7,
+        )
+        .unwrap();
+        assert_eq!(snap.best_bid(), Some(Price(100_00)));
+        assert_eq!(snap.best_ask(), Some(Price(101_00)));
+        assert_eq!(snap.timestamp, 7);
+        assert_eq!(snap.total_bid_quantity(), 300);
+    }
+
+    #[test]
+    fn new_rejects_unsorted_levels() {
+        let bids = vec![
+            LevelSnapshot::new(Price(99_00), 100, 1),
+            LevelSnapshot::new(Price(100_00), 100, 1),
+        ];
+        assert_eq!(
+            BookSnapshot::new(bids, vec![], 0).unwrap_err(),
+            SnapshotError::UnsortedBids { index: 1 }
+        );
+
+        let asks = vec![
+            LevelSnapshot::new(Price(101_00), 100, 1),
+            LevelSnapshot::new(Price(101_00), 100, 1),
+        ];
+        assert_eq!(
+            BookSnapshot::new(vec![], asks, 0).unwrap_err(),
+            SnapshotError::UnsortedAsks { index: 1 }
+        );
+    }
+
+    #[test]
+    fn new_matches_engine_snapshot() {
+        let mut book = OrderBook::new();
+        let b = book.create_order(Side::Buy, Price(100_00), 300, TimeInForce::GTC);
+        let a = book.create_order(Side::Sell, Price(102_00), 100, TimeInForce::GTC);
+        book.add_order(b);
+        book.add_order(a);
+        let engine = book.snapshot(10);
+
+        let crafted = BookSnapshot::new(engine.bids.clone(), engine.asks.clone(), 0).unwrap();
+        assert_eq!(crafted.weighted_mid(), engine.weighted_mid());
+        assert_eq!(crafted.imbalance(), engine.imbalance());
+    }
+
+    #[test]
+    fn diff_reports_minimal_changes_in_book_order() {
+        let lvl = LevelSnapshot::new;
+        let before = BookSnapshot::new(
+            vec![lvl(Price(100_00), 100, 1), lvl(Price(99_00), 50, 1)],
+            vec![lvl(Price(101_00), 70, 2), lvl(Price(103_00), 10, 1)],
+            0,
+        )
+        .unwrap();
+        let after = BookSnapshot::new(
+            vec![
+                lvl(Price(100_50), 20, 1),
+                lvl(Price(100_00), 100, 1),
+                lvl(Price(99_00), 50, 2),
+            ],
+            vec![lvl(Price(102_00), 30, 1), lvl(Price(103_00), 10, 1)],
+            1,
+        )
+        .unwrap();
+
+        let changes = before.diff(&after);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.side, c.price, c.kind, c.quantity, c.order_count))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Side::Buy, Price(100_50), LevelChangeKind::Added, 20, 1),
+                (Side::Buy, Price(99_00), LevelChangeKind::Changed, 50, 2),
+                (Side::Sell, Price(101_00), LevelChangeKind::Removed, 0, 0),
+                (Side::Sell, Price(102_00), LevelChangeKind::Added, 30, 1),
+            ]
+        );
+
+        assert!(after.diff(&after).is_empty());
+        assert_eq!(BookSnapshot::default().diff(&after).len(), 5);
+    }
 }
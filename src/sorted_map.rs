@@ -0,0 +1,25 @@
+//! Serialize hash maps in key order.
+//!
+//! Hash map iteration order depends on insertion history, so two maps with
+//! equal contents can serialize differently. Sorting the entries makes the
+//! encoding canonical: equal state gives equal checkpoint bytes and an
+//! equal [`state_hash`](crate::MultiExchange::state_hash). The format is
+//! still an ordinary map, so deserialization is unchanged.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Serializer};
+
+pub(crate) fn serialize<K, V, H, S>(
+    map: &HashMap<K, V, H>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    serializer.collect_map(entries)
+}
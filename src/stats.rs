@@ -1,6 +1,8 @@
 //! Statistical functions for quantitative analysis.
 //!
-//! Provides Spearman rank correlation and quintile spread analysis,
+//! Provides Spearman rank correlation, (optionally weighted) quintile
+//! spread analysis, and cross-sectional score preprocessing (demeaning,
+//! z-scoring, winsorizing),
 //! replacing direct scipy/numpy calls in qtrade.
 //!
 //! # References
@@ -398,6 +400,57 @@ pub fn spearman(x: &[f64], y: &[f64]) -> (f64, f64) {
     (r_clamped, p_value)
 }
 
+/// Permutation test for a factor's information coefficient (IC).
+///
+/// The IC is the Spearman correlation of `scores` and `returns`. Each of
+/// `n_perms` permutations shuffles `returns` with an [`Rng`](crate::rng::Rng)
+/// seeded by `seed` and recomputes the IC, so the result is reproducible.
+/// The test is one-sided: the p-value is the share of permutations whose IC
+/// is at least the observed one (within `1e-12`, so ties from float
+/// rounding count), using the `(1 + hits) / (1 + n_perms)` estimate, which
+/// is never zero. Negate `scores` to test a factor with negative IC.
+///
+/// # Returns
+///
+/// `(observed_ic, p_value)`, or `(NaN, NaN)` if `n < 3`, the lengths
+/// differ, `n_perms` is 0, any input is NaN, or either input is constant.
+///
+/// ```
+/// use nanobook::stats::permutation_test;
+///
+/// let scores: Vec<f64> = (0..30).map(f64::from).collect();
+/// let returns: Vec<f64> = scores.iter().map(|s| s * 0.001).collect();
+/// let (ic, p) = permutation_test(&scores, &returns, 199, 7);
+/// assert_eq!(ic, 1.0);
+/// assert_eq!(p, 1.0 / 200.0);
+/// ```
+pub fn permutation_test(scores: &[f64], returns: &[f64], n_perms: usize, seed: u64) -> (f64, f64) {
+    let n = scores.len();
+    if n != returns.len() || n < 3 || n_perms == 0 {
+        return (f64::NAN, f64::NAN);
+    }
+
+    // Shuffling returns permutes their ranks, so rank once and correlate
+    // ranks directly.
+    let rank_x = rankdata(scores);
+    let mut rank_y = rankdata(returns);
+    let observed = pearson(&rank_x, &rank_y);
+    if observed.is_nan() {
+        return (f64::NAN, f64::NAN);
+    }
+
+    let mut rng = crate::rng::Rng::from_seed(seed);
+    let mut hits = 0_usize;
+    for _ in 0..n_perms {
+        rng.shuffle(&mut rank_y);
+        if pearson(&rank_x, &rank_y) >= observed - 1e-12 {
+            hits += 1;
+        }
+    }
+    let p_value = (1 + hits) as f64 / (1 + n_perms) as f64;
+    (observed.clamp(-1.0, 1.0), p_value)
+}
+
 /// Deflated Sharpe Ratio after Lopez de Prado (2018).
 ///
 /// Returns the probability that an observed Sharpe ratio remains positive
@@ -494,29 +547,11 @@ pub fn deflated_sharpe(sharpe: f64, n_trials: usize, skewness: f64, kurtosis: f6
 /// meaningless `top_mean - bottom_mean`. Callers who need to include
 /// NaN-valued positions must substitute a sentinel first.
 pub fn quintile_spread(scores: &[f64], returns: &[f64], n_quantiles: usize) -> f64 {
-    let n = scores.len();
-    if n != returns.len() || n < n_quantiles || n_quantiles == 0 {
+    let Some(indices) = score_order(scores, returns, n_quantiles) else {
         return f64::NAN;
-    }
-
-    // NaN propagation: any NaN in either input produces NaN output.
-    if scores.iter().any(|v| v.is_nan()) || returns.iter().any(|v| v.is_nan()) {
-        return f64::NAN;
-    }
-
-    // Sort indices by score (ascending). With NaN ruled out above the
-    // Equal fallback is unreachable.
-    let mut indices: Vec<usize> = (0..n).collect();
-    indices.sort_by(|&a, &b| {
-        scores[a]
-            .partial_cmp(&scores[b])
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-
+    };
+    let n = indices.len();
     let group_size = n / n_quantiles;
-    if group_size == 0 {
-        return f64::NAN;
-    }
 
     // Bottom group (lowest scores)
     let bottom_mean: f64 = indices[..group_size]
@@ -536,6 +571,210 @@ pub fn quintile_spread(scores: &[f64], returns: &[f64], n_quantiles: usize) -> f
     top_mean - bottom_mean
 }
 
+/// Indices of `scores` in ascending order, or `None` if the inputs cannot
+/// be split into `n_quantiles` groups: mismatched lengths, fewer
+/// observations than groups, no groups, or a NaN in either input.
+fn score_order(scores: &[f64], returns: &[f64], n_quantiles: usize) -> Option<Vec<usize>> {
+    let n = scores.len();
+    if n != returns.len() || n < n_quantiles || n_quantiles == 0 {
+        return None;
+    }
+
+    // NaN propagation: any NaN in either input produces NaN output.
+    if scores.iter().any(|v| v.is_nan()) || returns.iter().any(|v| v.is_nan()) {
+        return None;
+    }
+
+    // With NaN ruled out above, argsort's Equal fallback is unreachable.
+    Some(argsort(scores, false))
+}
+
+/// Mean return of every score quantile, in ascending score order.
+///
+/// Unlike [`quintile_spread`], every observation lands in a bucket: the
+/// observation with sorted rank `i` (0-based) goes to bucket
+/// `i * n_quantiles / n`, so bucket sizes differ by at most one. When `n`
+/// is a multiple of `n_quantiles` the buckets are exactly the
+/// `quintile_spread` groups, and the last mean minus the first equals the
+/// spread. Plotting the means shows whether a factor is monotone across
+/// buckets rather than only at the extremes.
+///
+/// Returns `n_quantiles` NaNs if inputs are invalid (mismatched lengths,
+/// fewer observations than buckets, or any NaN), and an empty vector if
+/// `n_quantiles` is 0.
+///
+/// ```
+/// use nanobook::stats::quantile_means;
+///
+/// let scores = [5.0, 1.0, 4.0, 2.0, 3.0, 6.0];
+/// let returns = [0.05, 0.01, 0.04, 0.02, 0.03, 0.06];
+/// let means = quantile_means(&scores, &returns, 3);
+/// assert!((means[0] - 0.015).abs() < 1e-12);
+/// assert!((means[2] - 0.055).abs() < 1e-12);
+/// ```
+pub fn quantile_means(scores: &[f64], returns: &[f64], n_quantiles: usize) -> Vec<f64> {
+    let Some(indices) = score_order(scores, returns, n_quantiles) else {
+        return vec![f64::NAN; n_quantiles];
+    };
+    let n = indices.len();
+    let mut sums = vec![0.0; n_quantiles];
+    let mut counts = vec![0_usize; n_quantiles];
+    for (rank, &i) in indices.iter().enumerate() {
+        let bucket = rank * n_quantiles / n;
+        sums[bucket] += returns[i];
+        counts[bucket] += 1;
+    }
+    sums.iter()
+        .zip(&counts)
+        .map(|(&sum, &count)| sum / count as f64)
+        .collect()
+}
+
+/// Weighted quintile spread: the `weights`-weighted mean return of the top
+/// group minus that of the bottom group.
+///
+/// Groups are formed exactly as in [`quintile_spread`] (sort by `scores`,
+/// `floor(n / n_quantiles)` observations at each extreme); only the
+/// within-group average changes, from equal weights to
+/// `sum(w·r) / sum(w)`. Use market caps as `weights` for a cap-weighted
+/// factor test. Equal weights reproduce [`quintile_spread`].
+///
+/// # Returns
+///
+/// `top_mean - bottom_mean`, or NaN if inputs are invalid: mismatched
+/// lengths, too few observations, any NaN input, a negative weight, or a
+/// top or bottom group whose weights sum to zero.
+///
+/// ```
+/// use nanobook::stats::quintile_spread_weighted;
+///
+/// let scores = [1.0, 2.0, 3.0, 4.0];
+/// let returns = [0.01, 0.03, 0.02, 0.06];
+/// let weights = [3.0, 1.0, 1.0, 1.0];
+/// // Bottom half: (3·0.01 + 0.03) / 4 = 0.015; top half: 0.04.
+/// let spread = quintile_spread_weighted(&scores, &returns, &weights, 2);
+/// assert!((spread - 0.025).abs() < 1e-12);
+/// ```
+pub fn quintile_spread_weighted(
+    scores: &[f64],
+    returns: &[f64],
+    weights: &[f64],
+    n_quantiles: usize,
+) -> f64 {
+    if weights.len() != scores.len() || weights.iter().any(|&w| w.is_nan() || w < 0.0) {
+        return f64::NAN;
+    }
+    let Some(indices) = score_order(scores, returns, n_quantiles) else {
+        return f64::NAN;
+    };
+    let n = indices.len();
+    let group_size = n / n_quantiles;
+    let weighted_mean = |group: &[usize]| {
+        let total: f64 = group.iter().map(|&i| weights[i]).sum();
+        if total == 0.0 {
+            return f64::NAN;
+        }
+        group.iter().map(|&i| weights[i] * returns[i]).sum::<f64>() / total
+    };
+
+    weighted_mean(&indices[n - group_size..]) - weighted_mean(&indices[..group_size])
+}
+
+/// Indices that sort `values` ascending (or descending), breaking ties by
+/// original position.
+///
+/// The sort is stable, so equal scores keep their input order. Callers that
+/// need a deterministic cross-sectional ranking should pre-order their input
+/// (e.g. by symbol) before calling this. NaN compares as equal to everything
+/// and should be filtered out beforehand.
+pub(crate) fn argsort(values: &[f64], descending: bool) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by(|&a, &b| {
+        let ord = values[a]
+            .partial_cmp(&values[b])
+            .unwrap_or(std::cmp::Ordering::Equal);
+        if descending { ord.reverse() } else { ord }
+    });
+    indices
+}
+
+// ---------------------------------------------------------------------------
+// Cross-sectional preprocessing
+// ---------------------------------------------------------------------------
+
+/// Subtract the mean from every value.
+///
+/// Returns all NaN if any value is NaN, matching the NaN propagation of
+/// the rest of this module.
+pub fn demean(values: &[f64]) -> Vec<f64> {
+    if values.iter().any(|v| v.is_nan()) {
+        return vec![f64::NAN; values.len()];
+    }
+    let (mean, _) = welford_mean_m2(values);
+    values.iter().map(|v| v - mean).collect()
+}
+
+/// Standardize values to zero mean and unit population standard deviation.
+///
+/// Matches `scipy.stats.zscore(values)` (ddof=0). Returns all NaN if any
+/// value is NaN or the values have zero variance.
+///
+/// ```
+/// use nanobook::stats::zscore;
+///
+/// let z = zscore(&[1.0, 2.0, 3.0]);
+/// let expected = 1.5_f64.sqrt();
+/// assert!((z[2] - expected).abs() < 1e-12 && z[1].abs() < 1e-12);
+/// ```
+pub fn zscore(values: &[f64]) -> Vec<f64> {
+    let n = values.len();
+    if values.iter().any(|v| v.is_nan()) {
+        return vec![f64::NAN; n];
+    }
+    let (mean, m2) = welford_mean_m2(values);
+    let std = (m2 / n as f64).sqrt();
+    if std == 0.0 || !std.is_finite() {
+        return vec![f64::NAN; n];
+    }
+    values.iter().map(|v| (v - mean) / std).collect()
+}
+
+/// Clip values to their `lower_pct` and `upper_pct` percentiles.
+///
+/// Percentiles are in `[0, 100]` and interpolate linearly between sorted
+/// values, as `np.percentile` does, so the result equals
+/// `np.clip(values, np.percentile(values, lower_pct), np.percentile(values,
+/// upper_pct))`. Returns all NaN if any value is NaN or the percentiles are
+/// out of range or reversed.
+///
+/// ```
+/// use nanobook::stats::winsorize;
+///
+/// let values: Vec<f64> = (0..=100).map(f64::from).collect();
+/// let clipped = winsorize(&values, 5.0, 95.0);
+/// assert_eq!((clipped[0], clipped[50], clipped[100]), (5.0, 50.0, 95.0));
+/// ```
+pub fn winsorize(values: &[f64], lower_pct: f64, upper_pct: f64) -> Vec<f64> {
+    let valid = (0.0..=100.0).contains(&lower_pct)
+        && (0.0..=100.0).contains(&upper_pct)
+        && lower_pct <= upper_pct;
+    if !valid || values.iter().any(|v| v.is_nan()) {
+        return vec![f64::NAN; values.len()];
+    }
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let percentile = |pct: f64| {
+        let pos = pct / 100.0 * (sorted.len() - 1) as f64;
+        let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+        sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+    };
+    let (lo, hi) = (percentile(lower_pct), percentile(upper_pct));
+    values.iter().map(|v| v.clamp(lo, hi)).collect()
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -622,6 +861,161 @@ mod tests {
         assert!(spread < 0.0, "expected negative spread, got {spread}");
     }
 
+    #[test]
+    fn quintile_spread_weighted_matches_equal_weights() {
+        let scores: Vec<f64> = (0..23).map(|i| ((i * 7) % 23) as f64).collect();
+        let returns: Vec<f64> = (0..23).map(|i| (i as f64 * 0.37).sin() * 0.02).collect();
+        let ones = vec![2.5; 23];
+        let equal = quintile_spread(&scores, &returns, 5);
+        let weighted = quintile_spread_weighted(&scores, &returns, &ones, 5);
+        assert!((equal - weighted).abs() < 1e-15);
+    }
+
+    #[test]
+    fn quintile_spread_weighted_tilts_toward_heavy_names() {
+        let scores = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let returns = [0.0, 0.03, 0.0, 0.0, 0.01, 0.05];
+        let weights = [1.0, 3.0, 1.0, 1.0, 1.0, 1.0];
+        // Bottom: (0 + 3·0.03) / 4; top: (0.01 + 0.05) / 2.
+        let spread = quintile_spread_weighted(&scores, &returns, &weights, 3);
+        assert!((spread - (0.03 - 0.0225)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn quintile_spread_weighted_invalid() {
+        let scores = [1.0, 2.0, 3.0, 4.0];
+        let returns = [0.01, 0.02, 0.03, 0.04];
+        let spread = |weights: &[f64]| quintile_spread_weighted(&scores, &returns, weights, 2);
+        // Zero weight in the top group, negative and NaN weights, bad length.
+        assert!(spread(&[1.0, 1.0, 0.0, 0.0]).is_nan());
+        assert!(spread(&[1.0, -1.0, 1.0, 1.0]).is_nan());
+        assert!(spread(&[1.0, f64::NAN, 1.0, 1.0]).is_nan());
+        assert!(spread(&[1.0, 1.0, 1.0]).is_nan());
+        // A zero weight is fine while the group total stays positive.
+        assert!((spread(&[0.0, 1.0, 1.0, 0.0]) - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn quantile_means_cover_every_observation() {
+        // 7 observations in 3 buckets: ranks 0-2, 3-4, 5-6.
+        let scores = [7.0, 1.0, 6.0, 2.0, 5.0, 3.0, 4.0];
+        let returns = [0.7, 0.1, 0.6, 0.2, 0.5, 0.3, 0.4];
+        let means = quantile_means(&scores, &returns, 3);
+        let expected = [0.2, 0.45, 0.65];
+        for (m, e) in means.iter().zip(expected) {
+            assert!((m - e).abs() < 1e-12, "{means:?}");
+        }
+
+        let scores: Vec<f64> = (0..20).map(|i| ((i * 3) % 20) as f64).collect();
+        let returns: Vec<f64> = (0..20).map(|i| (i as f64).cos() * 0.01).collect();
+        let means = quantile_means(&scores, &returns, 5);
+        let spread = quintile_spread(&scores, &returns, 5);
+        assert!((means[4] - means[0] - spread).abs() < 1e-15);
+    }
+
+    #[test]
+    fn quantile_means_invalid() {
+        let means = quantile_means(&[1.0, f64::NAN, 3.0], &[0.1, 0.2, 0.3], 2);
+        assert_eq!(means.len(), 2);
+        assert!(means.iter().all(|m| m.is_nan()));
+        assert!(quantile_means(&[1.0], &[0.1], 2).iter().all(|m| m.is_nan()));
+        assert!(quantile_means(&[1.0], &[0.1], 0).is_empty());
+    }
+
+    #[test]
+    fn demean_and_zscore() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        let close = |got: Vec<f64>, expected: [f64; 8]| {
+            assert!(
+                got.iter().zip(expected).all(|(g, e)| (g - e).abs() < 1e-12),
+                "{got:?}"
+            );
+        };
+        close(
+            demean(&values),
+            [-3.0, -1.0, -1.0, -1.0, 0.0, 0.0, 2.0, 4.0],
+        );
+        // Population std of this classic example is exactly 2.
+        close(
+            zscore(&values),
+            [-1.5, -0.5, -0.5, -0.5, 0.0, 0.0, 1.0, 2.0],
+        );
+
+        assert!(zscore(&[3.0, 3.0]).iter().all(|v| v.is_nan()));
+        assert!(zscore(&[1.0, f64::NAN]).iter().all(|v| v.is_nan()));
+        assert!(demean(&[1.0, f64::NAN]).iter().all(|v| v.is_nan()));
+        assert!(zscore(&[]).is_empty());
+    }
+
+    #[test]
+    fn winsorize_clips_to_interpolated_percentiles() {
+        let values = [10.0, -50.0, 1.0, 2.0, 3.0];
+        // Sorted: -50, 1, 2, 3, 10. The 10th percentile sits 0.4 of the way
+        // from -50 to 1; the 90th 0.6 of the way from 3 to 10.
+        let clipped = winsorize(&values, 10.0, 90.0);
+        let expected = [7.2, -29.6, 1.0, 2.0, 3.0];
+        for (c, e) in clipped.iter().zip(expected) {
+            assert!((c - e).abs() < 1e-12, "{clipped:?}");
+        }
+        assert_eq!(winsorize(&values, 0.0, 100.0), values.to_vec());
+
+        assert!(winsorize(&values, 90.0, 10.0).iter().all(|v| v.is_nan()));
+        assert!(winsorize(&values, -1.0, 50.0).iter().all(|v| v.is_nan()));
+        assert!(
+            winsorize(&[f64::NAN, 1.0], 5.0, 95.0)
+                .iter()
+                .all(|v| v.is_nan())
+        );
+        assert!(winsorize(&[], 5.0, 95.0).is_empty());
+    }
+
+    #[test]
+    fn permutation_test_is_seeded_and_one_sided() {
+        let scores: Vec<f64> = (0..40).map(|i| ((i * 17) % 40) as f64).collect();
+        let noise: Vec<f64> = (0..40).map(|i| (i as f64 * 1.7).sin()).collect();
+        let returns: Vec<f64> = scores
+            .iter()
+            .zip(&noise)
+            .map(|(s, e)| 0.05 * s + e)
+            .collect();
+
+        let (ic, p) = permutation_test(&scores, &returns, 500, 42);
+        assert_eq!(ic, spearman(&scores, &returns).0);
+        assert!(ic > 0.0 && p < 0.05, "ic {ic}, p {p}");
+        assert_eq!(permutation_test(&scores, &returns, 500, 42), (ic, p));
+
+        // The opposite sign is insignificant under a one-sided test.
+        let negated: Vec<f64> = scores.iter().map(|s| -s).collect();
+        let (neg_ic, neg_p) = permutation_test(&negated, &returns, 500, 42);
+        assert_eq!(neg_ic, -ic);
+        assert!(neg_p > 0.95, "p {neg_p}");
+
+        // Pure noise: p is spread across the unit interval.
+        let (_, noise_p) = permutation_test(&scores, &noise, 500, 1);
+        assert!(noise_p > 0.05 && noise_p <= 1.0);
+    }
+
+    #[test]
+    fn permutation_test_invalid() {
+        let x = [1.0, 2.0, 3.0, 4.0];
+        assert!(permutation_test(&x, &x[..3], 10, 0).0.is_nan());
+        assert!(permutation_test(&x[..2], &x[..2], 10, 0).0.is_nan());
+        assert!(permutation_test(&x, &x, 0, 0).1.is_nan());
+        assert!(permutation_test(&x, &[1.0; 4], 10, 0).0.is_nan());
+        assert!(
+            permutation_test(&x, &[1.0, f64::NAN, 3.0, 4.0], 10, 0)
+                .1
+                .is_nan()
+        );
+    }
+
+    #[test]
+    fn argsort_is_stable_on_ties() {
+        let values = [2.0, 1.0, 2.0, 3.0, 1.0];
+        assert_eq!(argsort(&values, false), vec![1, 4, 0, 2, 3]);
+        assert_eq!(argsort(&values, true), vec![3, 0, 2, 1, 4]);
+    }
+
     #[test]
     fn quintile_spread_invalid() {
         let scores = [1.0, 2.0];
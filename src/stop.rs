@@ -83,12 +83,17 @@ pub struct StopOrder {
 ///
 /// Also maintains a rolling window of trade price changes for ATR computation.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StopBook {
     /// Buy stop orders indexed by stop price.
     buy_stops: BTreeMap<Price, Vec<OrderId>>,
     /// Sell stop orders indexed by stop price.
     sell_stops: BTreeMap<Price, Vec<OrderId>>,
     /// All stop orders by ID.
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
     orders: FxHashMap<OrderId, StopOrder>,
     /// IDs of trailing stop orders (for efficient update iteration).
     trailing_ids: Vec<OrderId>,
@@ -156,6 +161,21 @@ impl StopBook {
         true
     }
 
+    /// Reduce a pending stop order's quantity by `quantity`, cancelling it
+    /// if nothing would remain. Returns true if the order was pending.
+    pub fn reduce(&mut self, order_id: OrderId, quantity: Quantity) -> bool {
+        let order = match self.orders.get_mut(&order_id) {
+            Some(o) if o.status == StopStatus::Pending => o,
+            _ => return false,
+        };
+        if quantity < order.quantity {
+            order.quantity -= quantity;
+            true
+        } else {
+            self.cancel(order_id)
+        }
+    }
+
     /// Collect all stop orders triggered by a trade at the given price.
     ///
     /// Triggered orders are removed from the pending book and returned
@@ -862,4 +882,18 @@ mod tests {
         // Only pending trailing stops remain in trailing_ids
         assert_eq!(book.trailing_ids.len(), 1);
     }
+
+    #[test]
+    fn reduce_shrinks_then_cancels() {
+        let mut book = StopBook::new();
+        book.insert(make_stop(1, Side::Sell, 95_00, 100, 1));
+
+        assert!(book.reduce(OrderId(1), 40));
+        assert_eq!(book.get(OrderId(1)).unwrap().quantity, 60);
+
+        assert!(book.reduce(OrderId(1), 60));
+        assert_eq!(book.get(OrderId(1)).unwrap().status, StopStatus::Cancelled);
+        assert!(book.is_empty());
+        assert!(!book.reduce(OrderId(1), 1));
+    }
 }
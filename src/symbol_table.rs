@@ -0,0 +1,183 @@
+//! Interning of arbitrary-length instrument names to compact symbols.
+
+use crate::Symbol;
+use rustc_hash::FxHashMap;
+
+/// Prefix reserved for generated symbols.
+const GENERATED_PREFIX: char = '~';
+
+/// Maps human-readable names of any length to compact [`Symbol`]s and back.
+///
+/// Names that fit in a `Symbol` (≤ 8 bytes) intern to themselves, so
+/// `"AAPL"` stays `Symbol::new("AAPL")`. Longer names are assigned a
+/// generated symbol of the form `~0`, `~1`, … `~Z`, `~10`, … (base 36,
+/// in interning order). Names that themselves start with `~` are treated
+/// like long names so they can never collide with a generated symbol;
+/// avoid constructing `~`-prefixed symbols by hand.
+///
+/// ```
+/// use nanobook::{Symbol, SymbolTable};
+///
+/// let mut table = SymbolTable::new();
+/// let short = table.intern("AAPL");
+/// let long = table.intern("BRK.B-US-EQUITY");
+///
+/// assert_eq!(short, Symbol::new("AAPL"));
+/// assert_eq!(long.as_str(), "~0");
+/// assert_eq!(table.name(&long), Some("BRK.B-US-EQUITY"));
+/// assert_eq!(table.intern("BRK.B-US-EQUITY"), long);
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolTable {
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
+    by_name: FxHashMap<String, Symbol>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::sorted_map::serialize")
+    )]
+    names: FxHashMap<Symbol, String>,
+    next_generated: u64,
+}
+
+impl SymbolTable {
+    /// Create an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Intern `name`, returning its symbol. Interning the same name twice
+    /// returns the same symbol.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&sym) = self.by_name.get(name) {
+            return sym;
+        }
+
+        let sym = match Symbol::try_new(name) {
+            Some(sym) if !name.starts_with(GENERATED_PREFIX) => sym,
+            _ => self.generate(),
+        };
+        self.by_name.insert(name.to_string(), sym);
+        self.names.insert(sym, name.to_string());
+        sym
+    }
+
+    /// Intern `name` using [`Symbol::from_hashed`] instead of a generated id,
+    /// keeping the reverse mapping.
+    ///
+    /// The resulting symbol depends only on the name, so it is the same in
+    /// every table and every run. On a hash collision both names share the
+    /// symbol and [`name`](Self::name) reports the first one interned.
+    pub fn intern_hashed(&mut self, name: &str) -> Symbol {
+        if let Some(&sym) = self.by_name.get(name) {
+            return sym;
+        }
+
+        let sym = Symbol::from_hashed(name);
+        self.by_name.insert(name.to_string(), sym);
+        self.names.entry(sym).or_insert_with(|| name.to_string());
+        sym
+    }
+
+    /// Look up the symbol for an already-interned name.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.by_name.get(name).copied()
+    }
+
+    /// Original name for an interned symbol.
+    pub fn name(&self, symbol: &Symbol) -> Option<&str> {
+        self.names.get(symbol).map(String::as_str)
+    }
+
+    /// Number of interned names.
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    /// Returns true if no names are interned.
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    fn generate(&mut self) -> Symbol {
+        const DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+        let mut n = self.next_generated;
+        self.next_generated += 1;
+
+        // 7 base-36 digits cover ~7.8e10 generated names.
+        let mut digits = Vec::with_capacity(7);
+        loop {
+            digits.push(DIGITS[(n % 36) as usize]);
+            n /= 36;
+            if n == 0 {
+                break;
+            }
+        }
+        digits.reverse();
+
+        let mut s = String::with_capacity(8);
+        s.push(GENERATED_PREFIX);
+        s.push_str(std::str::from_utf8(&digits).expect("base-36 digits are ASCII"));
+        Symbol::new(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_names_intern_to_themselves() {
+        let mut table = SymbolTable::new();
+        assert_eq!(table.intern("AAPL"), Symbol::new("AAPL"));
+        assert_eq!(table.name(&Symbol::new("AAPL")), Some("AAPL"));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn long_names_get_generated_symbols() {
+        let mut table = SymbolTable::new();
+        let a = table.intern("VERY_LONG_NAME_A");
+        let b = table.intern("VERY_LONG_NAME_B");
+        assert_eq!(a.as_str(), "~0");
+        assert_eq!(b.as_str(), "~1");
+        assert_eq!(table.intern("VERY_LONG_NAME_A"), a);
+        assert_eq!(table.name(&b), Some("VERY_LONG_NAME_B"));
+        assert_eq!(table.get("VERY_LONG_NAME_B"), Some(b));
+        assert_eq!(table.get("MISSING"), None);
+    }
+
+    #[test]
+    fn reserved_prefix_never_collides() {
+        let mut table = SymbolTable::new();
+        let long = table.intern("SOME_LONG_TICKER");
+        let literal = table.intern("~0");
+        assert_ne!(long, literal);
+        assert_eq!(table.name(&literal), Some("~0"));
+        assert_eq!(table.name(&long), Some("SOME_LONG_TICKER"));
+    }
+
+    #[test]
+    fn hashed_interning_keeps_reverse_mapping() {
+        let mut table = SymbolTable::new();
+        let sym = table.intern_hashed("BERKSHIRE_HATHAWAY_B");
+        assert_eq!(sym, Symbol::from_hashed("BERKSHIRE_HATHAWAY_B"));
+        assert_eq!(table.name(&sym), Some("BERKSHIRE_HATHAWAY_B"));
+        assert_eq!(table.intern_hashed("AAPL"), Symbol::new("AAPL"));
+    }
+
+    #[test]
+    fn generated_symbols_use_base36() {
+        let mut table = SymbolTable::new();
+        let syms: Vec<Symbol> = (0..37)
+            .map(|i| table.intern(&format!("LONG_NAME_{i:04}")))
+            .collect();
+        assert_eq!(syms[10].as_str(), "~A");
+        assert_eq!(syms[35].as_str(), "~Z");
+        assert_eq!(syms[36].as_str(), "~10");
+    }
+}
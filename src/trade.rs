@@ -3,6 +3,16 @@
 use crate::{OrderId, Price, Quantity, Side, Timestamp, TradeId, error::ValidationError};
 use std::fmt;
 
+/// Whether a fill added liquidity to the book or removed it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Liquidity {
+    /// Passive fill: the order was resting on the book.
+    Maker,
+    /// Aggressive fill: the order crossed the spread.
+    Taker,
+}
+
 /// A completed trade between two orders.
 ///
 /// Trades are created when an incoming (aggressor) order matches
@@ -39,7 +49,9 @@ pub struct Trade {
     pub passive_order_id: OrderId,
     /// Side of the aggressor order
     pub aggressor_side: Side,
-    /// When the trade occurred
+    /// When the trade occurred: the book's logical clock if one is set
+    /// (shared by every trade until the clock advances), otherwise the next
+    /// value of its timestamp counter. See [`OrderBook::set_clock`](crate::OrderBook::set_clock).
     pub timestamp: Timestamp,
 }
 
@@ -71,6 +83,38 @@ impl Trade {
         self.aggressor_side.opposite()
     }
 
+    /// The resting order that provided liquidity (same as `passive_order_id`).
+    #[inline]
+    pub fn maker_order_id(&self) -> OrderId {
+        self.passive_order_id
+    }
+
+    /// The incoming order that took liquidity (same as `aggressor_order_id`).
+    #[inline]
+    pub fn taker_order_id(&self) -> OrderId {
+        self.aggressor_order_id
+    }
+
+    /// Whether `order_id` was the maker or the taker in this trade, or
+    /// `None` if it was neither.
+    pub fn liquidity(&self, order_id: OrderId) -> Option<Liquidity> {
+        if order_id == self.passive_order_id {
+            Some(Liquidity::Maker)
+        } else if order_id == self.aggressor_order_id {
+            Some(Liquidity::Taker)
+        } else {
+            None
+        }
+    }
+
+    /// The side `order_id` traded on, or `None` if it was not in this trade.
+    pub fn side_of(&self, order_id: OrderId) -> Option<Side> {
+        self.liquidity(order_id).map(|l| match l {
+            Liquidity::Maker => self.passive_side(),
+            Liquidity::Taker => self.aggressor_side,
+        })
+    }
+
     /// Returns the notional value (price × quantity).
     ///
     /// The product is the raw `price.0 * quantity`; interpretation
@@ -186,6 +230,19 @@ mod tests {
         assert_eq!(trade.timestamp, 1000);
     }
 
+    #[test]
+    fn maker_taker_roles() {
+        let trade = make_trade();
+
+        assert_eq!(trade.maker_order_id(), OrderId(5));
+        assert_eq!(trade.taker_order_id(), OrderId(10));
+        assert_eq!(trade.liquidity(OrderId(5)), Some(Liquidity::Maker));
+        assert_eq!(trade.liquidity(OrderId(10)), Some(Liquidity::Taker));
+        assert_eq!(trade.liquidity(OrderId(7)), None);
+        assert_eq!(trade.side_of(OrderId(5)), Some(Side::Sell));
+        assert_eq!(trade.side_of(OrderId(10)), Some(Side::Buy));
+    }
+
     #[test]
     fn passive_side() {
         let buy_aggressor = make_trade();
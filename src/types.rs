@@ -14,6 +14,22 @@ impl Price {
     pub const ZERO: Price = Price(0);
     pub const MAX: Price = Price(i64::MAX);
     pub const MIN: Price = Price(i64::MIN);
+    /// Units per whole currency unit assumed by default (cents).
+    pub const DEFAULT_SCALE: i64 = 100;
+
+    /// Convert to a decimal value given the number of price units per whole
+    /// currency unit (`100` for cents, `10_000` for four decimals).
+    ///
+    /// ```
+    /// use nanobook::Price;
+    ///
+    /// assert_eq!(Price(100_50).to_f64(Price::DEFAULT_SCALE), 100.5);
+    /// assert_eq!(Price(1_0050).to_f64(10_000), 1.005);
+    /// ```
+    #[inline]
+    pub fn to_f64(self, scale: i64) -> f64 {
+        self.0 as f64 / scale as f64
+    }
 }
 
 impl fmt::Display for Price {
@@ -37,7 +53,7 @@ pub type Quantity = u64;
 pub type Timestamp = u64;
 
 /// Unique order identifier assigned by exchange.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderId(pub u64);
 
@@ -58,6 +74,9 @@ impl fmt::Display for TradeId {
     }
 }
 
+/// First byte of symbols produced by [`Symbol::from_hashed`].
+const HASHED_PREFIX: u8 = b'#';
+
 /// A fixed-size symbol identifier (e.g., "AAPL", "MSFT").
 ///
 /// Stored inline as `[u8; 8]` with a length byte — no heap allocation, `Copy`,
@@ -118,6 +137,62 @@ impl Symbol {
         }
     }
 
+    /// Create a symbol from a name of any length, hashing names that don't fit.
+    ///
+    /// Names of at most 8 bytes that don't start with `#` are stored as-is.
+    /// Anything else becomes `#` followed by 7 base-62 characters derived
+    /// from a 64-bit FNV-1a hash of the name, so the result is stable across
+    /// runs, platforms, and releases. The name cannot be recovered from the
+    /// symbol; use [`SymbolTable::intern_hashed`](crate::SymbolTable::intern_hashed)
+    /// to keep the reverse mapping.
+    ///
+    /// # Collisions
+    ///
+    /// There are 62^7 ≈ 3.5e12 hashed symbols, so the chance of any
+    /// collision reaches ~50% around 2.2 million distinct long names and is
+    /// negligible for typical universes. Colliding names silently share a
+    /// symbol (and therefore a book or position).
+    ///
+    /// ```
+    /// use nanobook::Symbol;
+    ///
+    /// assert_eq!(Symbol::from_hashed("AAPL"), Symbol::new("AAPL"));
+    /// let long = Symbol::from_hashed("BERKSHIRE_HATHAWAY_B");
+    /// assert!(long.is_hashed());
+    /// assert_eq!(long, Symbol::from_hashed("BERKSHIRE_HATHAWAY_B"));
+    /// ```
+    pub fn from_hashed(s: &str) -> Self {
+        const DIGITS: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        if !s.starts_with(HASHED_PREFIX as char)
+            && let Some(sym) = Self::try_new(s)
+        {
+            return sym;
+        }
+
+        let mut hash = FNV_OFFSET;
+        for &b in s.as_bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+
+        let mut buf = [0u8; 8];
+        buf[0] = HASHED_PREFIX;
+        for slot in buf[1..].iter_mut().rev() {
+            *slot = DIGITS[(hash % 62) as usize];
+            hash /= 62;
+        }
+        Self { buf, len: 8 }
+    }
+
+    /// Returns true if this symbol was produced by hashing a name
+    /// (see [`Symbol::from_hashed`]).
+    pub fn is_hashed(&self) -> bool {
+        self.len == 8 && self.buf[0] == HASHED_PREFIX
+    }
+
     /// Returns the symbol as a string slice.
     #[inline]
     pub fn as_str(&self) -> &str {
@@ -251,4 +326,29 @@ mod tests {
     fn symbol_new_panics_too_long() {
         Symbol::new("TOOLONGNAME");
     }
+
+    #[test]
+    fn symbol_from_hashed_short_is_plain() {
+        assert_eq!(Symbol::from_hashed("AAPL"), Symbol::new("AAPL"));
+        assert!(!Symbol::from_hashed("AAPL").is_hashed());
+    }
+
+    #[test]
+    fn symbol_from_hashed_is_stable() {
+        let a = Symbol::from_hashed("TOOLONGNAME");
+        assert!(a.is_hashed());
+        assert_eq!(a.as_str().len(), 8);
+        assert!(a.as_str().starts_with('#'));
+        assert_eq!(a, Symbol::from_hashed("TOOLONGNAME"));
+        assert_ne!(a, Symbol::from_hashed("TOOLONGNAMF"));
+        // Pinned value: the encoding must not change between releases.
+        assert_eq!(a.as_str(), "#sOfNEJo");
+    }
+
+    #[test]
+    fn symbol_from_hashed_reserves_prefix() {
+        // A short name that looks like a hashed symbol is hashed itself.
+        let h = Symbol::from_hashed("TOOLONGNAME");
+        assert_ne!(Symbol::from_hashed(h.as_str()), h);
+    }
 }
@@ -37,11 +37,9 @@ fn equity_conserved_simple_rebalance() {
 
 #[test]
 fn equity_decreases_with_costs() {
-    let model = CostModel {
-        commission_bps: 10,
-        slippage_bps: 5,
-        min_trade_fee: 0,
-    };
+    let model = CostModel::zero()
+        .with_commission_bps(10)
+        .with_slippage_bps(5);
     let mut portfolio = Portfolio::new(1_000_000_00, model);
     let prices = [(aapl(), 150_00)];
     let targets = [(aapl(), 1.0)];
@@ -188,11 +186,10 @@ fn metrics_risk_free_rate() {
 
 #[test]
 fn cost_model_non_negative() {
-    let model = CostModel {
-        commission_bps: 100,
-        slippage_bps: 50,
-        min_trade_fee: 5_00,
-    };
+    let model = CostModel::zero()
+        .with_commission_bps(100)
+        .with_slippage_bps(50)
+        .with_min_trade_fee(5_00);
 
     for notional in &[0, 100, 1_000, 1_000_000, -500_000] {
         assert!(
@@ -204,11 +201,9 @@ fn cost_model_non_negative() {
 
 #[test]
 fn cost_model_min_fee_floor() {
-    let model = CostModel {
-        commission_bps: 1,
-        slippage_bps: 0,
-        min_trade_fee: 10_00, // $10 minimum
-    };
+    let model = CostModel::zero()
+        .with_commission_bps(1)
+        .with_min_trade_fee(10_00); // $10 minimum
 
     // Small trade: bps cost < min fee → min fee wins
     let cost = model.compute_cost(1_000); // $10 notional, 0.01% = $0.001
@@ -708,11 +708,10 @@ mod portfolio_props {
             min_fee in 0i64..1_000_000,
             notional in -1_000_000_000_000i64..1_000_000_000_000i64,
         ) {
-            let model = CostModel {
-                commission_bps,
-                slippage_bps,
-                min_trade_fee: min_fee,
-            };
+            let model = CostModel::zero()
+                .with_commission_bps(commission_bps)
+                .with_slippage_bps(slippage_bps)
+                .with_min_trade_fee(min_fee);
             let cost = model.compute_cost(notional);
             prop_assert!(cost >= 0, "negative cost: {}", cost);
         }